@@ -0,0 +1,535 @@
+//! CSS transitions and `@keyframes` animations
+//!
+//! The animation state is updated once per frame, after the CSS has been matched
+//! to the DOM: Transitions are started for all properties whose value changed since the
+//! last frame, then the cascaded values of all animated properties are replaced with the
+//! interpolated values for the current frame.
+//!
+//! Nodes are identified by their `NodeId`, so a transition is only continued if the
+//! node is still at the same position in the DOM in the next frame.
+
+use std::{
+    f32, mem,
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+use azul_css::{
+    Css, CssDeclaration, CssProperty, CssPropertyType, CssPropertyValue, CssKeyframes,
+    StyleTransitions, StyleAnimations, StyleAnimation, AnimationTimingFunction,
+    AnimationIterationCount, AnimationDirection,
+};
+use {
+    FastHashMap,
+    dom::{DomId, DomString},
+    id_tree::NodeId,
+    ui_description::UiDescription,
+};
+
+/// Stores the running transitions and animations of all nodes in a window
+#[derive(Debug, Default, Clone)]
+pub struct AnimationState {
+    /// Values of the properties with a `transition` in the last frame, necessary
+    /// to detect which properties changed
+    previous_values: BTreeMap<(DomId, NodeId), BTreeMap<CssPropertyType, CssProperty>>,
+    /// Currently running transitions of each node
+    transitions: BTreeMap<(DomId, NodeId), BTreeMap<CssPropertyType, RunningTransition>>,
+    /// When the `animation`s of each node were started, by name of the `@keyframes` rule
+    animation_starts: BTreeMap<(DomId, NodeId), BTreeMap<String, Instant>>,
+}
+
+/// A transition of a single property from one value to another
+#[derive(Debug, Clone, PartialEq)]
+struct RunningTransition {
+    from: CssProperty,
+    to: CssProperty,
+    /// When the transition starts (after the `transition-delay`)
+    start: Instant,
+    duration: Duration,
+    timing_function: AnimationTimingFunction,
+}
+
+impl RunningTransition {
+
+    fn is_finished(&self, now: Instant) -> bool {
+        now >= self.start + self.duration
+    }
+
+    /// Returns the interpolated value at the given time
+    fn get_value(&self, now: Instant) -> CssProperty {
+        let progress = if now <= self.start {
+            0.0
+        } else {
+            duration_as_secs(now - self.start) / duration_as_secs(self.duration)
+        };
+        let t = self.timing_function.evaluate(progress);
+        self.from.interpolate(&self.to, t).unwrap_or_else(|| self.to.clone())
+    }
+}
+
+impl AnimationState {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts transitions for all properties whose value has changed since the last frame
+    /// and overrides the cascaded properties of all animated nodes with the values at `now`.
+    ///
+    /// Returns whether any transition or animation in this DOM is still running,
+    /// i.e. whether the next frame has to be redrawn.
+    pub fn update<T>(&mut self, ui_description: &mut UiDescription<T>, css: &Css, now: Instant) -> bool {
+
+        let dom_id = ui_description.dom_id.clone();
+        let css_overrides = &ui_description.dynamic_css_overrides;
+
+        let mut previous_values = BTreeMap::new();
+        let mut transitions = BTreeMap::new();
+        let mut animation_starts = BTreeMap::new();
+        let mut animations_running = false;
+
+        for (node_idx, styled_node) in ui_description.styled_nodes.internal.iter_mut().enumerate() {
+
+            let node_id = NodeId::new(node_idx);
+            let key = (dom_id.clone(), node_id);
+            let overrides = css_overrides.get(&node_id);
+            let css_constraints = &mut styled_node.css_constraints;
+
+            // Transitions
+            let node_transitions = match get_declared_property(css_constraints, CssPropertyType::Transition, overrides) {
+                Some(CssProperty::Transition(CssPropertyValue::Exact(t))) => Some(t),
+                _ => None,
+            };
+
+            if let Some(node_transitions) = node_transitions {
+                let (node_values, node_running) = update_node_transitions(
+                    css_constraints,
+                    overrides,
+                    &node_transitions,
+                    self.previous_values.remove(&key).unwrap_or_default(),
+                    self.transitions.remove(&key).unwrap_or_default(),
+                    now,
+                );
+                if !node_running.is_empty() {
+                    transitions.insert(key.clone(), node_running);
+                }
+                previous_values.insert(key.clone(), node_values);
+            }
+
+            // @keyframes animations
+            let node_animations = match get_declared_property(css_constraints, CssPropertyType::Animation, overrides) {
+                Some(CssProperty::Animation(CssPropertyValue::Exact(a))) => Some(a),
+                _ => None,
+            };
+
+            if let Some(node_animations) = node_animations {
+                let mut old_starts = self.animation_starts.remove(&key).unwrap_or_default();
+                let mut node_starts = BTreeMap::new();
+                let running = apply_node_animations(css_constraints, overrides, &node_animations, css, &mut old_starts, &mut node_starts, now);
+                animations_running = animations_running || running;
+                animation_starts.insert(key, node_starts);
+            }
+        }
+
+        // Replace the state of this DOM, so that nodes without transitions / animations are removed
+        self.previous_values.retain(|(d, _), _| *d != dom_id);
+        self.transitions.retain(|(d, _), _| *d != dom_id);
+        self.animation_starts.retain(|(d, _), _| *d != dom_id);
+        let transitions_running = !transitions.is_empty();
+        self.previous_values.extend(previous_values);
+        self.transitions.extend(transitions);
+        self.animation_starts.extend(animation_starts);
+
+        transitions_running || animations_running
+    }
+}
+
+/// Returns the value of a property after the cascading step, with the `var()` overrides applied
+fn get_declared_property(
+    css_constraints: &BTreeMap<CssPropertyType, CssDeclaration>,
+    property_type: CssPropertyType,
+    overrides: Option<&FastHashMap<DomString, CssProperty>>,
+) -> Option<CssProperty> {
+    css_constraints.get(&property_type).map(|declaration| get_declared_value(declaration, overrides))
+}
+
+fn get_declared_value(declaration: &CssDeclaration, overrides: Option<&FastHashMap<DomString, CssProperty>>) -> CssProperty {
+    match declaration {
+        CssDeclaration::Static(property) => property.clone(),
+        CssDeclaration::Dynamic(dynamic) => {
+            // Same as in the display list: the override is only used if the type matches
+            overrides
+                .and_then(|o| o.get(&dynamic.dynamic_id.clone().into()))
+                .filter(|o| mem::discriminant(*o) == mem::discriminant(&dynamic.default_value))
+                .unwrap_or(&dynamic.default_value)
+                .clone()
+        },
+    }
+}
+
+/// Starts / continues the transitions of a single node and replaces the animated
+/// properties with their current values. Returns the values of all transitioned
+/// properties (for the next frame) and the transitions that are still running.
+fn update_node_transitions(
+    css_constraints: &mut BTreeMap<CssPropertyType, CssDeclaration>,
+    overrides: Option<&FastHashMap<DomString, CssProperty>>,
+    node_transitions: &StyleTransitions,
+    previous_values: BTreeMap<CssPropertyType, CssProperty>,
+    mut running: BTreeMap<CssPropertyType, RunningTransition>,
+    now: Instant,
+) -> (BTreeMap<CssPropertyType, CssProperty>, BTreeMap<CssPropertyType, RunningTransition>) {
+
+    let mut current_values = BTreeMap::new();
+
+    for (property_type, declaration) in css_constraints.iter() {
+
+        let transition = match node_transitions.get(*property_type) {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let target = get_declared_value(declaration, overrides);
+
+        // Properties that are set for the first time (i.e. when the node is created) are not transitioned
+        if let Some(previous_value) = previous_values.get(property_type) {
+            if *previous_value != target {
+                // Start from the currently displayed value, so that interrupting a transition doesn't jump
+                let from = running.get(property_type).map(|r| r.get_value(now)).unwrap_or_else(|| previous_value.clone());
+                if transition.duration > Duration::from_millis(0) && from.interpolate(&target, 0.0).is_some() {
+                    running.insert(*property_type, RunningTransition {
+                        from,
+                        to: target.clone(),
+                        start: now + transition.delay,
+                        duration: transition.duration,
+                        timing_function: transition.timing_function,
+                    });
+                } else {
+                    running.remove(property_type);
+                }
+            }
+        }
+
+        current_values.insert(*property_type, target);
+    }
+
+    // Stop transitions of properties that aren't set or transitioned anymore
+    running.retain(|property_type, r| current_values.get(property_type) == Some(&r.to) && !r.is_finished(now));
+
+    for (property_type, r) in running.iter() {
+        css_constraints.insert(*property_type, CssDeclaration::Static(r.get_value(now)));
+    }
+
+    (current_values, running)
+}
+
+/// Applies the current keyframes of all `animation`s of a single node, returns whether
+/// any of the animations is still running
+fn apply_node_animations(
+    css_constraints: &mut BTreeMap<CssPropertyType, CssDeclaration>,
+    overrides: Option<&FastHashMap<DomString, CssProperty>>,
+    node_animations: &StyleAnimations,
+    css: &Css,
+    old_starts: &mut BTreeMap<String, Instant>,
+    node_starts: &mut BTreeMap<String, Instant>,
+    now: Instant,
+) -> bool {
+
+    let mut is_running = false;
+
+    // Later animations override the properties of earlier ones
+    for animation in &node_animations.0 {
+
+        let start = old_starts.remove(&animation.name).unwrap_or(now);
+        node_starts.insert(animation.name.clone(), start);
+
+        let keyframes = match css.get_keyframes(&animation.name) {
+            Some(k) => k,
+            None => continue,
+        };
+
+        let (progress, running) = get_animation_progress(animation, now - start);
+        is_running = is_running || running;
+
+        if let Some(progress) = progress {
+            apply_keyframes(css_constraints, overrides, keyframes, animation.timing_function, progress);
+        }
+    }
+
+    is_running
+}
+
+/// Returns the progress of the current iteration (`0.0` - `1.0`, already reversed
+/// depending on the `animation-direction`) - or `None` if no keyframe should
+/// be applied - and whether the animation is still running.
+fn get_animation_progress(animation: &StyleAnimation, elapsed: Duration) -> (Option<f32>, bool) {
+
+    let is_reversed = |iteration: f32| {
+        let is_odd = iteration % 2.0 >= 1.0;
+        match animation.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => is_odd,
+            AnimationDirection::AlternateReverse => !is_odd,
+        }
+    };
+
+    let directed = |iteration: f32, progress: f32| if is_reversed(iteration) { 1.0 - progress } else { progress };
+
+    if elapsed < animation.delay {
+        let progress = if animation.fill_mode.fills_backwards() { Some(directed(0.0, 0.0)) } else { None };
+        return (progress, true);
+    }
+
+    let iterations = match animation.iteration_count {
+        AnimationIterationCount::Count(c) => c.get(),
+        AnimationIterationCount::Infinite => ::std::f32::INFINITY,
+    };
+
+    let duration = duration_as_secs(animation.duration);
+    let elapsed_iterations = if duration > 0.0 {
+        duration_as_secs(elapsed - animation.delay) / duration
+    } else {
+        iterations
+    };
+
+    if elapsed_iterations < iterations {
+        return (Some(directed(elapsed_iterations.floor(), elapsed_iterations.fract())), true);
+    }
+
+    if !animation.fill_mode.fills_forwards() || !iterations.is_finite() {
+        return (None, false);
+    }
+
+    // An animation with 2 iterations ends at the end of the second iteration, not at the start of the third one
+    let last_iteration = if iterations > 0.0 && iterations.fract() == 0.0 { iterations - 1.0 } else { iterations.floor() };
+    (Some(directed(last_iteration, iterations - last_iteration)), false)
+}
+
+/// Replaces the properties of the `@keyframes` rule with the values at the given progress.
+/// If there is no keyframe at `0%` or `100%`, the cascaded value of the property is used instead.
+fn apply_keyframes(
+    css_constraints: &mut BTreeMap<CssPropertyType, CssDeclaration>,
+    overrides: Option<&FastHashMap<DomString, CssProperty>>,
+    keyframes: &CssKeyframes,
+    timing_function: AnimationTimingFunction,
+    progress: f32,
+) {
+
+    let mut property_types = keyframes.keyframes.iter()
+        .flat_map(|keyframe| keyframe.declarations.iter().map(|d| d.get_type()))
+        .collect::<Vec<_>>();
+    property_types.sort();
+    property_types.dedup();
+
+    for property_type in property_types {
+
+        let cascaded_value = get_declared_property(css_constraints, property_type, overrides);
+
+        // (offset, value) of all keyframes that contain the property, including the implicit first / last keyframes
+        let mut frames = keyframes.keyframes.iter().filter_map(|keyframe| {
+            let value = keyframe.declarations.iter().rev().find(|d| d.get_type() == property_type)?;
+            Some((keyframe.offset.get() / 100.0, value.clone()))
+        }).collect::<Vec<_>>();
+
+        if let Some(cascaded_value) = cascaded_value {
+            if frames.first().map(|(offset, _)| *offset > 0.0) == Some(true) {
+                frames.insert(0, (0.0, cascaded_value.clone()));
+            }
+            if frames.last().map(|(offset, _)| *offset < 1.0) == Some(true) {
+                frames.push((1.0, cascaded_value));
+            }
+        }
+
+        let value = match frames.iter().position(|(offset, _)| *offset >= progress) {
+            // before the first or exactly on a keyframe
+            Some(0) => frames[0].1.clone(),
+            Some(next_idx) => {
+                let (from_offset, ref from) = frames[next_idx - 1];
+                let (to_offset, ref to) = frames[next_idx];
+                let t = timing_function.evaluate((progress - from_offset) / (to_offset - from_offset));
+                // Properties that can't be interpolated switch in the middle of the keyframes
+                from.interpolate(to, t).unwrap_or_else(|| if t < 0.5 { from.clone() } else { to.clone() })
+            },
+            // after the last keyframe
+            None => match frames.last() {
+                Some((_, last)) => last.clone(),
+                None => continue,
+            },
+        };
+
+        css_constraints.insert(property_type, CssDeclaration::Static(value));
+    }
+}
+
+fn duration_as_secs(duration: Duration) -> f32 {
+    duration.as_secs() as f32 + duration.subsec_nanos() as f32 / 1_000_000_000.0
+}
+
+#[test]
+fn test_interpolate_css_properties() {
+
+    use azul_css::*;
+
+    let from = CssProperty::from(StyleTextColor(ColorU { r: 0, g: 100, b: 200, a: 255 }));
+    let to = CssProperty::from(StyleTextColor(ColorU { r: 100, g: 200, b: 0, a: 255 }));
+    assert_eq!(from.interpolate(&to, 0.5), Some(StyleTextColor(ColorU { r: 50, g: 150, b: 100, a: 255 }).into()));
+
+    // Mixed units result in a calc() expression
+    let from = CssProperty::from(LayoutWidth::px(10.0));
+    let to = CssProperty::from(LayoutWidth(PixelValue::percent(50.0)));
+    let expected = LayoutWidth(PixelValue::calc(&[(SizeMetric::Px, 5.0), (SizeMetric::Percent, 25.0)]).unwrap());
+    assert_eq!(from.interpolate(&to, 0.5), Some(expected.into()));
+    assert_eq!(from.interpolate(&to, 1.0), Some(to.clone()));
+
+    let from = CssProperty::from(LayoutFlexGrow(FloatValue::new(1.0)));
+    let to = CssProperty::from(LayoutFlexGrow(FloatValue::new(2.0)));
+    assert_eq!(from.interpolate(&to, 0.25), Some(LayoutFlexGrow(FloatValue::new(1.25)).into()));
+
+    // Discrete properties and keywords can't be interpolated
+    assert_eq!(CssProperty::from(LayoutDisplay::Flex).interpolate(&LayoutDisplay::Inline.into(), 0.5), None);
+    assert_eq!(CssProperty::auto(CssPropertyType::Width).interpolate(&LayoutWidth::px(5.0).into(), 0.5), None);
+
+    assert_eq!(AnimationTimingFunction::Linear.evaluate(0.3), 0.3);
+    assert!((AnimationTimingFunction::EaseInOut.evaluate(0.5) - 0.5).abs() < 0.001);
+    assert!(AnimationTimingFunction::EaseIn.evaluate(0.25) < 0.25);
+    assert_eq!(AnimationTimingFunction::Steps(4, StepPosition::End).evaluate(0.3), 0.25);
+    assert_eq!(AnimationTimingFunction::Steps(4, StepPosition::Start).evaluate(0.3), 0.5);
+}
+
+#[cfg(test)]
+fn styled_test_dom(css: &Css, hovered: bool) -> UiDescription<()> {
+
+    use std::collections::BTreeMap;
+    use azul_css::MediaQueryEnvironment;
+    use {
+        dom::Dom,
+        ui_state::ui_state_from_dom,
+        callbacks::HitTestItem,
+    };
+
+    // Same as the root DOM of a window, which keeps its DomId across frames
+    let mut ui_state = ui_state_from_dom(Dom::div().with_child(Dom::div().with_class("button")), None);
+    ui_state.dom_id = DomId::ROOT_ID;
+
+    let mut hovered_nodes = BTreeMap::new();
+    if hovered {
+        hovered_nodes.insert(NodeId::new(1), HitTestItem {
+            pipeline: ::callbacks::PipelineId(0, 0),
+            point_in_viewport: ::azul_css::LayoutPoint::new(0.0, 0.0),
+            point_relative_to_item: ::azul_css::LayoutPoint::new(0.0, 0.0),
+            tag: (0, 0),
+        });
+    }
+
+    UiDescription::match_css_to_dom(&mut ui_state, css, &mut None, &mut None, &hovered_nodes, false, &MediaQueryEnvironment::default())
+}
+
+#[test]
+fn test_hover_transition() {
+
+    use azul_css::*;
+
+    let transition = StyleTransitions(vec![StyleTransition {
+        property: Some(CssPropertyType::Width),
+        duration: Duration::from_millis(100),
+        timing_function: AnimationTimingFunction::Linear,
+        delay: Duration::from_millis(0),
+    }]);
+
+    let rule = |path: Vec<CssPathSelector>, width: f32| CssRuleBlock {
+        path: CssPath { selectors: path },
+        declarations: vec![
+            CssDeclaration::Static(transition.clone().into()),
+            CssDeclaration::Static(LayoutWidth::px(width).into()),
+        ],
+        custom_properties: Vec::new(),
+        media_queries: Vec::new(),
+        source: None,
+    };
+
+    let css = Css { stylesheets: vec![vec![
+        rule(vec![CssPathSelector::Class("button".into())], 100.0),
+        rule(vec![CssPathSelector::Class("button".into()), CssPathSelector::PseudoSelector(CssPathPseudoSelector::Hover)], 200.0),
+    ].into()] };
+
+    let get_width = |ui_description: &UiDescription<()>| ui_description.styled_nodes.internal[1].css_constraints[&CssPropertyType::Width].clone();
+
+    let mut animation_state = AnimationState::new();
+    let start = Instant::now();
+
+    let mut frame = styled_test_dom(&css, false);
+    assert_eq!(animation_state.update(&mut frame, &css, start), false);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(100.0).into()));
+
+    // hovering starts the transition
+    let mut frame = styled_test_dom(&css, true);
+    assert_eq!(animation_state.update(&mut frame, &css, start), true);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(100.0).into()));
+
+    let mut frame = styled_test_dom(&css, true);
+    assert_eq!(animation_state.update(&mut frame, &css, start + Duration::from_millis(50)), true);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(150.0).into()));
+
+    // un-hovering in the middle of the transition transitions back from the current value
+    let mut frame = styled_test_dom(&css, false);
+    assert_eq!(animation_state.update(&mut frame, &css, start + Duration::from_millis(50)), true);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(150.0).into()));
+
+    let mut frame = styled_test_dom(&css, false);
+    assert_eq!(animation_state.update(&mut frame, &css, start + Duration::from_millis(100)), true);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(125.0).into()));
+
+    let mut frame = styled_test_dom(&css, false);
+    assert_eq!(animation_state.update(&mut frame, &css, start + Duration::from_millis(150)), false);
+    assert_eq!(get_width(&frame), CssDeclaration::Static(LayoutWidth::px(100.0).into()));
+}
+
+#[test]
+fn test_keyframe_animation() {
+
+    use azul_css::*;
+
+    let animation = StyleAnimations(vec![StyleAnimation {
+        name: "grow".into(),
+        duration: Duration::from_millis(100),
+        timing_function: AnimationTimingFunction::Linear,
+        delay: Duration::from_millis(0),
+        iteration_count: AnimationIterationCount::Count(FloatValue::new(2.0)),
+        direction: AnimationDirection::Alternate,
+        fill_mode: AnimationFillMode::Forwards,
+    }]);
+
+    let mut stylesheet: Stylesheet = vec![CssRuleBlock {
+        path: CssPath { selectors: vec![CssPathSelector::Class("button".into())] },
+        declarations: vec![
+            CssDeclaration::Static(animation.into()),
+            CssDeclaration::Static(LayoutWidth::px(0.0).into()),
+        ],
+        custom_properties: Vec::new(),
+        media_queries: Vec::new(),
+        source: None,
+    }].into();
+
+    // no "from" keyframe: the animation starts at the cascaded width
+    stylesheet.keyframes.push(CssKeyframes {
+        name: "grow".into(),
+        keyframes: vec![CssKeyframe { offset: PercentageValue::new(100.0), declarations: vec![LayoutWidth::px(100.0).into()] }],
+    });
+
+    let css = Css { stylesheets: vec![stylesheet] };
+    let get_width = |ui_description: &UiDescription<()>| ui_description.styled_nodes.internal[1].css_constraints[&CssPropertyType::Width].clone();
+
+    let mut animation_state = AnimationState::new();
+    let start = Instant::now();
+    let mut width_at = |ms: u64| {
+        let mut frame = styled_test_dom(&css, false);
+        let is_running = animation_state.update(&mut frame, &css, start + Duration::from_millis(ms));
+        (get_width(&frame), is_running)
+    };
+
+    assert_eq!(width_at(0), (CssDeclaration::Static(LayoutWidth::px(0.0).into()), true));
+    assert_eq!(width_at(25), (CssDeclaration::Static(LayoutWidth::px(25.0).into()), true));
+    // the second iteration runs backwards
+    assert_eq!(width_at(125), (CssDeclaration::Static(LayoutWidth::px(75.0).into()), true));
+    // fill-mode: forwards keeps the end of the last (reversed) iteration
+    assert_eq!(width_at(300), (CssDeclaration::Static(LayoutWidth::px(0.0).into()), false));
+}
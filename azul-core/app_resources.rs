@@ -1,5 +1,5 @@
 use std::{fmt, path::PathBuf};
-use azul_css::{LayoutPoint, LayoutSize};
+use azul_css::{LayoutPoint, LayoutSize, StyleFontWeight, StyleFontStyle, ConicGradient};
 use {
     FastHashMap, FastHashSet,
     ui_solver::{ResolvedTextLayoutOptions},
@@ -139,8 +139,17 @@ pub struct AppResources {
     pub css_ids_to_image_ids: FastHashMap<CssImageId, ImageId>,
     /// Same as CssImageId -> ImageId, but for fonts, i.e. "Roboto" -> FontId(9)
     pub css_ids_to_font_ids: FastHashMap<CssFontId, FontId>,
+    /// Additional faces of a font family, i.e. "Roboto" (bold, italic) -> FontId(10).
+    /// The regular face (400, normal) of a family is stored in `css_ids_to_font_ids`.
+    pub css_font_faces: FastHashMap<CssFontId, Vec<(StyleFontWeight, StyleFontStyle, FontId)>>,
+    /// Font faces that were registered from the `@font-face` rules of the stylesheets,
+    /// so that they can be removed again once their rule is removed from all stylesheets
+    pub css_declared_font_faces: FastHashSet<(CssFontId, StyleFontWeight, StyleFontStyle)>,
     /// Stores where the images were loaded from
     pub image_sources: FastHashMap<ImageId, ImageSource>,
+    /// Images that `conic-gradient` backgrounds were rendered into, by gradient and size
+    /// of the image. The images are deleted when they weren't used in the last frame.
+    pub conic_gradient_images: FastHashMap<(ConicGradient, (u32, u32)), ImageId>,
     /// Stores where the fonts were loaded from
     pub font_sources: FastHashMap<FontId, FontSource>,
     /// All image keys currently active in the RenderApi
@@ -163,6 +172,42 @@ pub struct AppResources {
     pub text_cache: TextCache,
 }
 
+/// Order of preference for the available styles when a certain style is requested
+fn font_style_distance(desired: StyleFontStyle, available: StyleFontStyle) -> usize {
+    use azul_css::StyleFontStyle::*;
+    let preference = match desired {
+        Normal => [Normal, Oblique, Italic],
+        Italic => [Italic, Oblique, Normal],
+        Oblique => [Oblique, Italic, Normal],
+    };
+    preference.iter().position(|s| *s == available).unwrap_or(preference.len())
+}
+
+/// Order of preference for the available weights when a certain weight is requested: for weights
+/// between 400 and 500, heavier weights up to 500 are tried first, then lighter weights, then heavier
+/// weights above 500. Lighter weights prefer lighter faces, heavier weights prefer heavier faces.
+#[allow(clippy::manual_range_contains)]
+fn font_weight_distance(desired: StyleFontWeight, available: StyleFontWeight) -> (usize, u16) {
+    let (desired, available) = (desired.0, available.0);
+    let lighter = desired.saturating_sub(available);
+    let heavier = available.saturating_sub(desired);
+    if desired >= 400 && desired <= 500 {
+        if available >= desired && available <= 500 {
+            (0, heavier)
+        } else if available < desired {
+            (1, lighter)
+        } else {
+            (2, heavier)
+        }
+    } else if desired < 400 {
+        if available <= desired { (0, lighter) } else { (1, heavier) }
+    } else if available >= desired {
+        (0, heavier)
+    } else {
+        (1, lighter)
+    }
+}
+
 macro_rules! unique_id {($struct_name:ident, $counter_name:ident) => {
 
     static $counter_name: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
@@ -587,6 +632,61 @@ impl AppResources {
         self.css_ids_to_font_ids.remove(css_id)
     }
 
+    /// Same as `add_css_font_id`, but registers a specific face of the font family,
+    /// i.e. the bold italic face of "Roboto". Adding the regular face (`400`, `normal`)
+    /// is the same as calling `add_css_font_id`.
+    pub fn add_css_font_face<S: Into<String>>(&mut self, css_id: S, weight: StyleFontWeight, style: StyleFontStyle) -> FontId {
+        if weight == StyleFontWeight::NORMAL && style == StyleFontStyle::Normal {
+            return self.add_css_font_id(css_id);
+        }
+        let faces = self.css_font_faces.entry(css_id.into()).or_default();
+        match faces.iter().find(|(w, s, _)| *w == weight && *s == style) {
+            Some((_, _, font_id)) => *font_id,
+            None => {
+                let font_id = FontId::new();
+                faces.push((weight, style, font_id));
+                font_id
+            }
+        }
+    }
+
+    /// Returns the `FontId` of the face with exactly this weight and style
+    pub fn get_css_font_face_id(&self, css_id: &str, weight: StyleFontWeight, style: StyleFontStyle) -> Option<FontId> {
+        if weight == StyleFontWeight::NORMAL && style == StyleFontStyle::Normal {
+            return self.get_css_font_id(css_id).cloned();
+        }
+        self.css_font_faces.get(css_id)?
+            .iter()
+            .find(|(w, s, _)| *w == weight && *s == style)
+            .map(|(_, _, font_id)| *font_id)
+    }
+
+    /// Returns the face of the font family that matches the weight and style best, following the
+    /// [CSS font matching algorithm](https://www.w3.org/TR/css-fonts-3/#font-style-matching): first
+    /// the closest style is selected (i.e. `oblique` if no `italic` face exists), then the closest weight.
+    pub fn select_css_font_face_id(&self, css_id: &str, weight: StyleFontWeight, style: StyleFontStyle) -> Option<FontId> {
+        let regular = self.get_css_font_id(css_id).map(|font_id| (StyleFontWeight::NORMAL, StyleFontStyle::Normal, *font_id));
+        let faces = self.css_font_faces.get(css_id).into_iter().flat_map(|faces| faces.iter().cloned());
+        regular.into_iter()
+            .chain(faces)
+            .min_by_key(|(w, s, _)| (font_style_distance(style, *s), font_weight_distance(weight, *w)))
+            .map(|(_, _, font_id)| font_id)
+    }
+
+    /// Deletes a face that was added with `add_css_font_face`
+    pub fn delete_css_font_face_id(&mut self, css_id: &str, weight: StyleFontWeight, style: StyleFontStyle) -> Option<FontId> {
+        if weight == StyleFontWeight::NORMAL && style == StyleFontStyle::Normal {
+            return self.delete_css_font_id(css_id);
+        }
+        let faces = self.css_font_faces.get_mut(css_id)?;
+        let position = faces.iter().position(|(w, s, _)| *w == weight && *s == style)?;
+        let (_, _, font_id) = faces.remove(position);
+        if faces.is_empty() {
+            self.css_font_faces.remove(css_id);
+        }
+        Some(font_id)
+    }
+
     pub fn add_font_source(&mut self, font_id: FontId, font_source: FontSource) {
         self.font_sources.insert(font_id, font_source);
     }
@@ -630,4 +730,31 @@ impl AppResources {
     pub fn get_loaded_font(&self, font_id: &ImmediateFontId) -> Option<&LoadedFont> {
         self.currently_registered_fonts.get(font_id)
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_select_css_font_face_id() {
+
+    let mut app_resources = AppResources::new();
+    let regular = app_resources.add_css_font_id("Roboto");
+    let light = app_resources.add_css_font_face("Roboto", StyleFontWeight(300), StyleFontStyle::Normal);
+    let bold = app_resources.add_css_font_face("Roboto", StyleFontWeight::BOLD, StyleFontStyle::Normal);
+    let oblique = app_resources.add_css_font_face("Roboto", StyleFontWeight::NORMAL, StyleFontStyle::Oblique);
+
+    let select = |weight, style| app_resources.select_css_font_face_id("Roboto", StyleFontWeight(weight), style);
+
+    assert_eq!(select(400, StyleFontStyle::Normal), Some(regular));
+    assert_eq!(select(450, StyleFontStyle::Normal), Some(regular));
+    assert_eq!(select(600, StyleFontStyle::Normal), Some(bold));
+    assert_eq!(select(900, StyleFontStyle::Normal), Some(bold));
+    assert_eq!(select(200, StyleFontStyle::Normal), Some(light));
+    assert_eq!(select(350, StyleFontStyle::Normal), Some(light));
+    // No italic face is registered, oblique is the closest match
+    assert_eq!(select(700, StyleFontStyle::Italic), Some(oblique));
+    assert_eq!(app_resources.select_css_font_face_id("Arial", StyleFontWeight::NORMAL, StyleFontStyle::Normal), None);
+
+    assert_eq!(app_resources.add_css_font_face("Roboto", StyleFontWeight::BOLD, StyleFontStyle::Normal), bold);
+    assert_eq!(app_resources.get_css_font_face_id("Roboto", StyleFontWeight::NORMAL, StyleFontStyle::Normal), Some(regular));
+    assert_eq!(app_resources.delete_css_font_face_id("Roboto", StyleFontWeight::NORMAL, StyleFontStyle::Oblique), Some(oblique));
+    assert_eq!(app_resources.get_css_font_face_id("Roboto", StyleFontWeight::NORMAL, StyleFontStyle::Oblique), None);
+}
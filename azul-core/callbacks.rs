@@ -455,11 +455,18 @@ macro_rules! impl_callback_info_api {() => (
     }
 
     /// Returns the bounds (width / height / position / margins / border) for any given NodeId,
-    /// useful for calculating scroll positions / offsets
+    /// useful for calculating scroll positions / offsets. The bounds are not affected by the
+    /// CSS `transform` of the node, use `get_transformed_bounds` for the area on the screen.
     pub fn get_bounds(&self, (dom_id, node_id): &(DomId, NodeId)) -> Option<&PositionedRectangle> {
         self.window().layout_result.get(&dom_id)?.rects.get(*node_id)
     }
 
+    /// Returns the bounding box of the node after applying the CSS `transform` of the node
+    /// and all of its parents, i.e. the area of the window that the node covers
+    pub fn get_transformed_bounds(&self, (dom_id, node_id): &(DomId, NodeId)) -> Option<LayoutRect> {
+        self.window().layout_result.get(&dom_id)?.get_transformed_bounds(*node_id)
+    }
+
     /// If the node is a text node, return the text of the node
     pub fn get_words(&self, (dom_id, node_id): &(DomId, NodeId)) -> Option<&Words> {
         self.window().layout_result.get(&dom_id)?.word_cache.get(&node_id)
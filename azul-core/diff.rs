@@ -1,10 +1,9 @@
-#![allow(unused_variables)]
 #![allow(dead_code)]
 
-use std::{collections::BTreeMap, marker::PhantomData};
+use std::marker::PhantomData;
 use {
     id_tree::{NodeId, NodeHierarchy},
-    dom::{Dom, NodeData},
+    dom::{Dom, NodeData, NodeType},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -40,19 +39,18 @@ pub(crate) enum DomChange {
 pub struct DomDiff {
     /// What the actual changes nodes (not trees / subtrees) were in this diff, in order of appearance
     pub(crate) changed_nodes: Vec<DomChange>,
-    /// Which items simply need updating in terms of image source?
+    /// Image nodes where only the image source changed (they still need a re-layout,
+    /// since the new image can have a different size)
     pub(crate) only_replace_images: Vec<NodeId>,
     /// Which nodes / subtrees need re-styling?
     pub(crate) need_restyling: Vec<DomRange<NewState>>,
-    /// Which nodes need a re-layout?
-    /// For example A would be:
+    /// Which nodes need a re-layout? Only contains the nodes that changed themselves (or
+    /// whose children were added / removed), not the parents that are affected by the change.
     pub(crate) need_relayout: Vec<DomRange<NewState>>,
+    /// The ID of each node of the new DOM in the old DOM, `None` if the node was added
+    pub(crate) old_node_ids: Vec<Option<NodeId>>,
 }
 
-type TreeDepth = usize;
-type ParentNodeId = NodeId;
-type LeafNodeId = NodeId;
-
 impl<F: FrameMarker + PartialEq> DomRange<F> {
 
     /// Is `other` a subtree of `self`? - Assumes that the DOM was
@@ -70,78 +68,292 @@ impl<F: FrameMarker + PartialEq> DomRange<F> {
     }
 }
 
+impl<F: FrameMarker> DomRange<F> {
+
+    /// Range that only contains the node itself
+    fn single(node_id: NodeId) -> Self {
+        DomRange {
+            start: DomNode { id: node_id, marker: PhantomData },
+            end: DomNode { id: node_id, marker: PhantomData },
+        }
+    }
+
+    /// Range that contains the node and all of its descendants
+    fn subtree(node_id: NodeId, hierarchy: &NodeHierarchy) -> Self {
+        let mut last_descendant = node_id;
+        while let Some(last_child) = hierarchy[last_descendant].last_child {
+            last_descendant = last_child;
+        }
+        DomRange {
+            start: DomNode { id: node_id, marker: PhantomData },
+            end: DomNode { id: last_descendant, marker: PhantomData },
+        }
+    }
+}
+
+impl DomDiff {
+
+    /// Returns whether the old and the new DOM are the same
+    pub fn is_empty(&self) -> bool {
+        self.changed_nodes.is_empty() &&
+        self.need_restyling.is_empty() &&
+        self.need_relayout.is_empty()
+    }
+
+    /// Returns the ID that the node of the new DOM had in the old DOM, `None` if the node was added
+    pub fn get_old_node_id(&self, new_node_id: NodeId) -> Option<NodeId> {
+        self.old_node_ids.get(new_node_id.index()).and_then(|old_node_id| *old_node_id)
+    }
+
+    /// Returns the IDs that the nodes of the new DOM had in the old DOM, indexed by the new node ID
+    pub fn get_old_node_ids(&self) -> &[Option<NodeId>] {
+        &self.old_node_ids
+    }
+
+    /// Returns the nodes of the new DOM that have to be laid out again, because they were added,
+    /// their content changed or children were added to or removed from them
+    pub fn get_nodes_that_need_relayout(&self) -> Vec<NodeId> {
+        self.need_relayout.iter()
+            .flat_map(|range| (range.start.id.index()..=range.end.id.index()).map(NodeId::new))
+            .collect()
+    }
+}
+
 // In order to test two DOM nodes for "equality", you'd need to
 // test if the node type, the classes and the ids are the same.
 // The rest of the attributes can be ignored, since they are not
 // used by the CSS engine.
 //
-// Right now the CSS doesn't support adjacent modifiers ("+" selectors),
-// which will make this algorithm a bit more complex (but should be
-// solvable with adjacency lists).
-//
-// for each leaf node (sorted by depth):
-//     - if the node has only changed its position:
-//         - node doesn't need restyle (but may need re-layout when "+" selectors are implemented)
-//     - else insert it it
-//     - add the end added / removed nodes
-//
-// for each parent node (sorted by depth, bubble up):
-//     - if the parent has changed:
-//
-//     - ask if that child has affected that parents layout
-//          - if yes, set the parent to be restyled
-//     - ask if that child affects its siblings layout
-//          - if yes, add the siblings to the changeset
+// The two trees are walked in parallel, starting at the root:
 //
+// - if the node type, the classes or the ids changed, the node and its
+//   descendants need a restyle (selectors can match on the parents)
+// - if the content of the node changed (i.e. the text of a label), the
+//   node only needs a re-layout
+// - the children are matched in order: if a child doesn't match, but
+//   one of the lists is longer and the next child matches, the child is
+//   considered to be added / removed, otherwise the two children are
+//   compared as if they were the same node
+// - added / removed children require a re-layout of the parent and a
+//   restyle of the parent and all of its descendants: sibling selectors
+//   ("+", "~") and structural pseudo-classes (":nth-child", ":nth-last-child",
+//   ":only-child", ":empty", ...) can match differently on the parent, on the
+//   siblings before and after the change and on their descendants
 
-const NODE_CHANGED_NOTHING: u8  = 0x01;
-const NODE_CHANGED_TYPE: u8     = 0x02;
-const NODE_CHANGED_CLASSES: u8  = 0x04;
-const NODE_CHANGED_IDS: u8      = 0x08;
+const NODE_CHANGED_NOTHING: u8  = 0x00;
+const NODE_CHANGED_TYPE: u8     = 0x01;
+const NODE_CHANGED_CLASSES: u8  = 0x02;
+const NODE_CHANGED_IDS: u8      = 0x04;
+const NODE_CHANGED_CONTENT: u8  = 0x08;
 
-fn node_needs_restyle<T>(old: &NodeData<T>, new: &NodeData<T>) -> u8 {
+const NODE_NEEDS_RESTYLE: u8 = NODE_CHANGED_TYPE | NODE_CHANGED_CLASSES | NODE_CHANGED_IDS;
+
+fn get_node_changes<T>(old: &NodeData<T>, new: &NodeData<T>) -> u8 {
     let mut result = NODE_CHANGED_NOTHING;
 
-    if old.get_node_type() != new.get_node_type() {
-        result &= NODE_CHANGED_TYPE;
+    if old.get_node_type().get_path() != new.get_node_type().get_path() {
+        result |= NODE_CHANGED_TYPE;
+    } else if old.get_node_type() != new.get_node_type() {
+        result |= NODE_CHANGED_CONTENT;
     }
 
     if old.get_classes() != new.get_classes() {
-        result &= NODE_CHANGED_CLASSES;
+        result |= NODE_CHANGED_CLASSES;
     }
 
     if old.get_ids() != new.get_ids() {
-        result &= NODE_CHANGED_CLASSES;
+        result |= NODE_CHANGED_IDS;
     }
 
     result
 }
 
-fn get_leaf_nodes_by_depth<T: FrameMarker>(hierarchy: &NodeHierarchy)
--> BTreeMap<TreeDepth, BTreeMap<ParentNodeId, Vec<DomNode<T>>>>
-{
-    let mut map = BTreeMap::new();
-    let parent_nodes = hierarchy.get_parents_sorted_by_depth();
+/// Nodes that only differ in their content are considered to be the same node
+fn is_same_node<T>(old: &NodeData<T>, new: &NodeData<T>) -> bool {
+    get_node_changes(old, new) & NODE_NEEDS_RESTYLE == NODE_CHANGED_NOTHING
+}
+
+/// Compares the old and the new DOM and returns which nodes have to be restyled and laid out again
+pub fn diff_dom_tree<T>(old: &Dom<T>, new: &Dom<T>) -> DomDiff {
+
+    let mut diff = DomDiff {
+        old_node_ids: vec![None; new.arena.len()],
+        .. Default::default()
+    };
 
-    for (parent_depth, parent_id) in parent_nodes {
-        for child_id in parent_id.children(hierarchy).filter(|child| hierarchy[*child].first_child.is_some()) {
-            map.entry(parent_depth + 1).or_insert_with(|| BTreeMap::new())
-               .entry(parent_id).or_insert_with(|| Vec::new())
-               .push(DomNode { id: child_id, marker: PhantomData });
+    match (old.arena.is_empty(), new.arena.is_empty()) {
+        (true, true) => { },
+        (true, false) => {
+            add_subtree(new.root, &new.arena.node_layout, &mut diff);
+            diff.need_restyling.push(DomRange::subtree(new.root, &new.arena.node_layout));
+        },
+        (false, true) => diff.changed_nodes.push(DomChange::Removed(DomRange::subtree(old.root, &old.arena.node_layout))),
+        (false, false) => diff_node(old, old.root, new, new.root, &mut diff),
+    }
+
+    diff
+}
+
+fn diff_node<T>(old: &Dom<T>, old_id: NodeId, new: &Dom<T>, new_id: NodeId, diff: &mut DomDiff) {
+
+    let old_hierarchy = &old.arena.node_layout;
+    let new_hierarchy = &new.arena.node_layout;
+
+    diff.old_node_ids[new_id.index()] = Some(old_id);
+
+    let changes = get_node_changes(&old.arena.node_data[old_id], &new.arena.node_data[new_id]);
+
+    if changes & NODE_NEEDS_RESTYLE != NODE_CHANGED_NOTHING {
+        diff.need_restyling.push(DomRange::subtree(new_id, new_hierarchy));
+    }
+
+    if changes == NODE_CHANGED_CONTENT {
+        if let NodeType::Image(_) = new.arena.node_data[new_id].get_node_type() {
+            diff.only_replace_images.push(new_id);
         }
     }
 
-    map
+    let old_children = old_id.children(old_hierarchy).collect::<Vec<_>>();
+    let new_children = new_id.children(new_hierarchy).collect::<Vec<_>>();
+
+    let mut children_changed = false;
+    let (mut old_idx, mut new_idx) = (0, 0);
+
+    while old_idx < old_children.len() || new_idx < new_children.len() {
+
+        let old_remaining = old_children.len() - old_idx;
+        let new_remaining = new_children.len() - new_idx;
+
+        if old_remaining == 0 {
+            add_subtree(new_children[new_idx], new_hierarchy, diff);
+            new_idx += 1;
+            children_changed = true;
+            continue;
+        }
+
+        if new_remaining == 0 {
+            diff.changed_nodes.push(DomChange::Removed(DomRange::subtree(old_children[old_idx], old_hierarchy)));
+            old_idx += 1;
+            children_changed = true;
+            continue;
+        }
+
+        let old_child = old_children[old_idx];
+        let new_child = new_children[new_idx];
+
+        if !is_same_node(&old.arena.node_data[old_child], &new.arena.node_data[new_child]) {
+            if new_remaining > old_remaining && is_same_node(&old.arena.node_data[old_child], &new.arena.node_data[new_children[new_idx + 1]]) {
+                add_subtree(new_child, new_hierarchy, diff);
+                new_idx += 1;
+                children_changed = true;
+                continue;
+            }
+            if old_remaining > new_remaining && is_same_node(&old.arena.node_data[old_children[old_idx + 1]], &new.arena.node_data[new_child]) {
+                diff.changed_nodes.push(DomChange::Removed(DomRange::subtree(old_child, old_hierarchy)));
+                old_idx += 1;
+                children_changed = true;
+                continue;
+            }
+        }
+
+        diff_node(old, old_child, new, new_child, diff);
+        old_idx += 1;
+        new_idx += 1;
+    }
+
+    // If the node itself needs a restyle, its whole subtree was already pushed above
+    if children_changed && changes & NODE_NEEDS_RESTYLE == NODE_CHANGED_NOTHING {
+        diff.need_restyling.push(DomRange::subtree(new_id, new_hierarchy));
+    }
+
+    if changes != NODE_CHANGED_NOTHING || children_changed {
+        diff.need_relayout.push(DomRange::single(new_id));
+    }
+}
+
+fn add_subtree(node_id: NodeId, hierarchy: &NodeHierarchy, diff: &mut DomDiff) {
+    let range = DomRange::subtree(node_id, hierarchy);
+    diff.changed_nodes.push(DomChange::Added(range));
+    diff.need_relayout.push(range);
+}
+
+#[test]
+fn test_diff_dom_tree_unchanged() {
+
+    struct TestLayout;
+
+    let dom = || -> Dom<TestLayout> {
+        Dom::div()
+            .with_child(Dom::label("a").with_class("row"))
+            .with_child(Dom::label("b").with_class("row"))
+    };
+
+    let diff = diff_dom_tree(&dom(), &dom());
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.get_old_node_ids(), &[Some(NodeId::new(0)), Some(NodeId::new(1)), Some(NodeId::new(2))]);
+}
+
+#[test]
+fn test_diff_dom_tree_insert_child() {
+
+    struct TestLayout;
+
+    let old: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::label("a").with_class("row"))
+        .with_child(Dom::label("b").with_class("row"));
+
+    let new: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::label("a").with_class("row"))
+        .with_child(Dom::div().with_class("separator").with_child(Dom::label("-")))
+        .with_child(Dom::label("b").with_class("row"));
+
+    let diff = diff_dom_tree(&old, &new);
+
+    assert_eq!(diff.get_old_node_ids(), &[Some(NodeId::new(0)), Some(NodeId::new(1)), None, None, Some(NodeId::new(2))]);
+    assert_eq!(diff.changed_nodes, vec![DomChange::Added(DomRange::subtree(NodeId::new(2), &new.arena.node_layout))]);
+    // "b" is now the third child, so the whole subtree of the parent needs a restyle
+    assert_eq!(diff.need_restyling, vec![DomRange::subtree(NodeId::new(0), &new.arena.node_layout)]);
+    assert_eq!(diff.get_nodes_that_need_relayout(), vec![NodeId::new(2), NodeId::new(3), NodeId::new(0)]);
 }
 
-pub(crate) fn diff_dom_tree<T>(old: &Dom<T>, new:Dom<T>) -> DomDiff {
+#[test]
+fn test_diff_dom_tree_remove_nested_child() {
 
-    // TODO!
+    struct TestLayout;
 
-    // let old_leaf_nodes = get_leaf_nodes_by_depth(&old.arena.node_layout, OldState { });
-    // let new_leaf_nodes = get_leaf_nodes_by_depth(&new.arena.node_layout, NewState { });
+    let old: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::div().with_class("list").with_child(Dom::label("a")).with_child(Dom::label("b")))
+        .with_child(Dom::label("c").with_class("footer"));
 
-    // depth -> parents (in order) -> [leaf children]
+    let new: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::div().with_class("list").with_child(Dom::label("a")))
+        .with_child(Dom::label("c").with_class("footer"));
 
-    DomDiff::default()
-}
\ No newline at end of file
+    let diff = diff_dom_tree(&old, &new);
+
+    assert_eq!(diff.changed_nodes, vec![DomChange::Removed(DomRange::single(NodeId::new(3)))]);
+    // Only the list and its remaining children are restyled, not the footer
+    assert_eq!(diff.need_restyling, vec![DomRange::subtree(NodeId::new(1), &new.arena.node_layout)]);
+    assert_eq!(diff.get_nodes_that_need_relayout(), vec![NodeId::new(1)]);
+}
+
+#[test]
+fn test_diff_dom_tree_changed_content() {
+
+    struct TestLayout;
+
+    let old: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::label("a").with_class("row"))
+        .with_child(Dom::label("b").with_class("row"));
+
+    let new: Dom<TestLayout> = Dom::div()
+        .with_child(Dom::label("a").with_class("row"))
+        .with_child(Dom::label("c").with_class("row").with_class("selected"));
+
+    let diff = diff_dom_tree(&old, &new);
+
+    assert!(diff.changed_nodes.is_empty());
+    assert_eq!(diff.need_restyling, vec![DomRange::single(NodeId::new(2))]);
+    assert_eq!(diff.get_nodes_that_need_relayout(), vec![NodeId::new(2)]);
+}
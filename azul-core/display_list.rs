@@ -1,9 +1,9 @@
 use std::fmt;
 use azul_css::{
-    LayoutPoint, LayoutSize, LayoutRect,
+    LayoutPoint, LayoutSize, LayoutRect, LayoutTransform, StyleFilterFunction, StyleMixBlendMode,
     StyleBackgroundRepeat, StyleBackgroundPosition, ColorU, BoxShadowClipMode,
-    LinearGradient, RadialGradient, BoxShadowPreDisplayItem, StyleBackgroundSize,
-    CssPropertyValue,
+    LinearGradient, RadialGradient, StyleBoxShadowList, TextShadowPreDisplayItem,
+    StyleBackgroundSize, CssPropertyValue, NinePatchBorder,
 
     StyleBorderTopWidth, StyleBorderRightWidth, StyleBorderBottomWidth, StyleBorderLeftWidth,
     StyleBorderTopColor, StyleBorderRightColor, StyleBorderBottomColor, StyleBorderLeftColor,
@@ -130,8 +130,19 @@ pub struct DisplayListFrame {
     pub rect: LayoutRect,
     /// Border radius, set to none only if overflow: visible is set!
     pub border_radius: StyleBorderRadius,
+    /// Padding box of the frame that the children are clipped to (using the inner border radius),
+    /// `None` if the children are allowed to overflow the frame (`overflow: visible`)
     pub clip_rect: Option<LayoutRect>,
     pub tag: Option<ItemTag>,
+    /// CSS `transform` of this frame (already including the `transform-origin`), in the same
+    /// coordinate space as the `rect`. Applies to the content and all children of the frame.
+    pub transform: Option<LayoutTransform>,
+    /// CSS `filter`s and `opacity` of this frame, applied to the content and all children
+    pub filters: Vec<StyleFilterFunction>,
+    /// CSS `mix-blend-mode` of this frame, `None` if the frame is blended normally
+    pub mix_blend_mode: Option<StyleMixBlendMode>,
+    /// Whether the frame is scrolled by the scroll frames of its ancestors
+    pub position: FramePosition,
     pub content: Vec<LayoutRectContent>,
     pub children: Vec<DisplayListMsg>,
 }
@@ -146,6 +157,18 @@ impl fmt::Debug for DisplayListFrame {
         if let Some(tag) = &self.tag {
             write!(f, "\r\ntag: ({}, {}),", tag.0, tag.1)?;
         }
+        if let Some(transform) = &self.transform {
+            write!(f, "\r\ntransform: {:?},", transform)?;
+        }
+        if !self.filters.is_empty() {
+            write!(f, "\r\nfilters: {:?},", self.filters)?;
+        }
+        if let Some(mix_blend_mode) = &self.mix_blend_mode {
+            write!(f, "\r\nmix_blend_mode: {:?},", mix_blend_mode)?;
+        }
+        if self.position != FramePosition::Scrolled {
+            write!(f, "\r\nposition: {:?},", self.position)?;
+        }
         if !self.content.is_empty() {
             write!(f, "\r\ncontent: {:#?}", self.content)?;
         }
@@ -161,6 +184,10 @@ impl DisplayListFrame {
         DisplayListFrame {
             tag: None,
             clip_rect: None,
+            transform: None,
+            filters: Vec::new(),
+            mix_blend_mode: None,
+            position: FramePosition::Scrolled,
             rect: LayoutRect {
                 origin: LayoutPoint { x: 0.0, y: 0.0 },
                 size: dimensions,
@@ -172,6 +199,33 @@ impl DisplayListFrame {
     }
 }
 
+/// How a frame moves when the scroll frames of its ancestors are scrolled
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum FramePosition {
+    /// The frame scrolls with its scroll frame
+    Scrolled,
+    /// The frame is positioned relative to the window (`position: fixed`), it
+    /// is neither scrolled nor clipped by the scroll frames of its ancestors
+    Fixed,
+    /// The frame scrolls with its scroll frame, but sticks to its edges (`position: sticky`)
+    Sticky(StickyFrame),
+}
+
+/// Offsets of a `position: sticky` frame
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct StickyFrame {
+    /// Distance to the top / right / bottom / left edge of the scroll frame at which
+    /// the frame starts to stick, `None` if the frame doesn't stick to that edge
+    pub top: Option<f32>,
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+    pub left: Option<f32>,
+    /// How far (up, down) the frame can be moved, so that it stays inside of the content box of its parent
+    pub vertical_offset_bounds: (f32, f32),
+    /// How far (left, right) the frame can be moved, so that it stays inside of the content box of its parent
+    pub horizontal_offset_bounds: (f32, f32),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ImageRendering {
     Auto,
@@ -263,12 +317,12 @@ pub struct StyleBorderStyles {
 
 tlbr_debug!(StyleBorderStyles);
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StyleBoxShadow {
-    pub top: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub right: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub bottom: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub left: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
+    pub top: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub right: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub bottom: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub left: Option<CssPropertyValue<StyleBoxShadowList>>,
 }
 
 tlbr_debug!(StyleBoxShadow);
@@ -282,6 +336,23 @@ pub enum LayoutRectContent {
         glyph_options: Option<GlyphOptions>,
         clip: Option<LayoutRect>,
     },
+    /// Shadows of a `text-shadow`, painted below the glyphs of the text
+    TextShadow {
+        shadows: Vec<TextShadowPreDisplayItem>,
+        glyphs: Vec<GlyphInstance>,
+        font_instance_key: FontInstanceKey,
+        /// Color of the text, used for shadows that don't have a color
+        color: ColorU,
+        glyph_options: Option<GlyphOptions>,
+        clip: Option<LayoutRect>,
+    },
+    /// Lines of a `text-decoration` (underlines, overlines, strike-throughs),
+    /// in the same coordinate space as the glyphs of the text
+    TextDecoration {
+        lines: Vec<LayoutRect>,
+        color: ColorU,
+        clip: Option<LayoutRect>,
+    },
     Background {
         content: RectBackground,
         size: Option<StyleBackgroundSize>,
@@ -301,6 +372,13 @@ pub enum LayoutRectContent {
         colors: StyleBorderColors,
         styles: StyleBorderStyles,
     },
+    /// `border-image`, drawn instead of the normal border
+    NinePatchBorder {
+        /// Image of the `border-image-source`, resolved via the `AppResources`
+        image: ImageInfo,
+        widths: StyleBorderWidths,
+        border: Box<NinePatchBorder>,
+    },
     BoxShadow {
         shadow: StyleBoxShadow,
         clip_mode: BoxShadowClipMode,
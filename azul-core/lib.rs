@@ -19,6 +19,7 @@ pub mod display_list;
 pub mod ui_solver;
 pub mod style;
 pub mod ui_description;
+pub mod animation;
 
 mod stack_checked_pointer;
 
@@ -2,8 +2,8 @@
 
 use std::{fmt, collections::BTreeMap};
 use azul_css::{
-    Css, CssContentGroup, CssPath,
-    CssPathSelector, CssPathPseudoSelector, CssNthChildSelector::*,
+    Css, CssContentGroup, CssPath, MediaQueryEnvironment,
+    CssPathSelector, CssPathPseudoSelector,
 };
 use {
     dom::{DomId, NodeData},
@@ -17,9 +17,15 @@ use {
 pub struct HtmlCascadeInfo<'a, T: 'a> {
     pub node_data: &'a NodeData<T>,
     pub index_in_parent: usize,
+    /// Same as `index_in_parent`, but counted from the last child (necessary for :nth-last-child)
+    pub index_from_end: usize,
     pub is_last_child: bool,
+    /// Whether the node has no children and no text content (necessary for :empty)
+    pub is_empty: bool,
     pub is_hovered_over: bool,
     pub is_focused: bool,
+    /// Whether the node itself or any of its children is focused (necessary for :focus-within)
+    pub is_focus_within: bool,
     pub is_active: bool,
 }
 
@@ -28,16 +34,22 @@ impl<'a, T: 'a> fmt::Debug for HtmlCascadeInfo<'a, T> {
         write!(f, "HtmlCascadeInfo {{ \
             node_data: {:?}, \
             index_in_parent: {}, \
+            index_from_end: {}, \
             is_last_child: {:?}, \
+            is_empty: {:?}, \
             is_hovered_over: {:?}, \
             is_focused: {:?}, \
+            is_focus_within: {:?}, \
             is_active: {:?}, \
          }}",
             self.node_data,
             self.index_in_parent,
+            self.index_from_end,
             self.is_last_child,
+            self.is_empty,
             self.is_hovered_over,
             self.is_focused,
+            self.is_focus_within,
             self.is_active,
          )
     }
@@ -51,48 +63,68 @@ pub fn matches_html_element<'a, T>(
     html_node_tree: &NodeDataContainer<HtmlCascadeInfo<'a, T>>)
 -> bool
 {
-    use self::CssGroupSplitReason::*;
-
     if css_path.selectors.is_empty() {
         return false;
     }
 
-    let mut current_node = Some(node_id);
-    let mut direct_parent_has_to_match = false;
-    let mut last_selector_matched = true;
-
-    for (content_group, reason) in CssGroupIterator::new(&css_path.selectors) {
-        let cur_node_id = match current_node {
-            Some(c) => c,
-            None => {
-                // The node has no parent, but the CSS path
-                // still has an extra limitation - only valid if the
-                // next content group is a "*" element
-                return *content_group == [&CssPathSelector::Global];
-            },
-        };
-        let current_selector_matches = selector_group_matches(&content_group, &html_node_tree[cur_node_id]);
+    let content_groups = CssGroupIterator::new(&css_path.selectors).collect::<Vec<_>>();
+    matches_content_groups(&content_groups, node_id, node_hierarchy, html_node_tree)
+}
 
-        if direct_parent_has_to_match && !current_selector_matches {
-            // If the element was a ">" element and the current,
-            // direct parent does not match, return false
-            return false; // not executed (maybe this is the bug)
-        }
+/// Matches the content groups (ordered from the last to the first group of the CSS path)
+/// against the node and - depending on the combinator between the groups - against its
+/// parents or previous siblings. Backtracks if a descendant / general sibling combinator
+/// could match more than one node.
+fn matches_content_groups<'a, T>(
+    content_groups: &[(CssContentGroup, CssGroupSplitReason)],
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    html_node_tree: &NodeDataContainer<HtmlCascadeInfo<'a, T>>)
+-> bool
+{
+    use self::CssGroupSplitReason::*;
 
-        // If the current selector matches, but the previous one didn't,
-        // that means that the CSS path chain is broken and therefore doesn't match the element
-        if current_selector_matches && !last_selector_matched {
-            return false;
-        }
+    let (content_group, reason) = match content_groups.first() {
+        Some(s) => s,
+        None => return true,
+    };
 
-        // Important: Set if the current selector has matched the element
-        last_selector_matched = current_selector_matches;
-        // Select if the next content group has to exactly match or if it can potentially be skipped
-        direct_parent_has_to_match = reason == DirectChildren;
-        current_node = node_hierarchy[cur_node_id].parent;
+    if !selector_group_matches(content_group, &html_node_tree[node_id]) {
+        return false;
     }
 
-    last_selector_matched
+    let remaining_groups = &content_groups[1..];
+    let next_group = match remaining_groups.first() {
+        Some((next_group, _)) => next_group,
+        None => return true,
+    };
+
+    let node = &node_hierarchy[node_id];
+
+    match reason {
+        DirectChildren | Children if node.parent.is_none() => {
+            // The node has no parent, but the CSS path
+            // still has an extra limitation - only valid if the
+            // next content group is a "*" element
+            *next_group == [&CssPathSelector::Global]
+        },
+        DirectChildren => {
+            matches_content_groups(remaining_groups, node.parent.unwrap(), node_hierarchy, html_node_tree)
+        },
+        Children => {
+            node_id.ancestors(node_hierarchy).skip(1)
+            .any(|parent_id| matches_content_groups(remaining_groups, parent_id, node_hierarchy, html_node_tree))
+        },
+        AdjacentSibling => {
+            node.previous_sibling
+            .map(|sibling_id| matches_content_groups(remaining_groups, sibling_id, node_hierarchy, html_node_tree))
+            .unwrap_or(false)
+        },
+        GeneralSibling => {
+            node_id.preceding_siblings(node_hierarchy).skip(1)
+            .any(|sibling_id| matches_content_groups(remaining_groups, sibling_id, node_hierarchy, html_node_tree))
+        },
+    }
 }
 
 pub fn match_dom_selectors<T>(
@@ -102,9 +134,10 @@ pub fn match_dom_selectors<T>(
     pending_focus_target: &mut Option<FocusTarget>,
     hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
     is_mouse_down: bool,
+    media_environment: &MediaQueryEnvironment,
 ) -> UiDescription<T> {
 
-    use azul_css::CssDeclaration;
+    use azul_css::{CssDeclaration, CssSourceLocation, CustomCssProperty};
 
     let non_leaf_nodes = ui_state.dom.arena.node_layout.get_parents_sorted_by_depth();
 
@@ -131,28 +164,91 @@ pub fn match_dom_selectors<T>(
 
     // First, apply all rules normally (no inheritance) of CSS values
     // This is an O(n^2) operation, but it can be parallelized in the future
-    let mut styled_nodes = ui_state.dom.arena.node_data.transform(|_, node_id| StyledNode {
-        css_constraints: css
+    let mut custom_properties = ui_state.dom.arena.node_data.transform(|_, _| BTreeMap::<String, CustomCssProperty>::new());
+    let mut styled_nodes = ui_state.dom.arena.node_data.transform(|_, node_id| {
+        let matched_rules = css
             .rules()
+            .filter(|rule| rule.matches_media(media_environment))
             .filter(|rule| matches_html_element(&rule.path, node_id, &ui_state.dom.arena.node_layout, &html_tree))
-            .flat_map(|matched_rule| matched_rule.declarations.iter().map(|declaration| (declaration.get_type(), declaration.clone())))
-            .collect(),
+            .collect::<Vec<_>>();
+        custom_properties[node_id] = matched_rules.iter()
+            .flat_map(|matched_rule| matched_rule.custom_properties.iter().map(|custom| (custom.name.clone(), custom.clone())))
+            .collect();
+        let mut styled_node = StyledNode::default();
+        for matched_rule in &matched_rules {
+            for declaration in &matched_rule.declarations {
+                let property_type = declaration.get_type();
+                styled_node.css_constraints.insert(property_type, declaration.clone());
+                match matched_rule.get_declaration_source(property_type) {
+                    Some(source) => { styled_node.css_constraint_sources.insert(property_type, source.clone()); },
+                    None => { styled_node.css_constraint_sources.remove(&property_type); },
+                }
+            }
+        }
+        styled_node
     });
 
+    // Custom properties (`--main-bg-color: red`) are always inherited,
+    // unless the child declares a custom property with the same name
+    for (_depth, parent_id) in &non_leaf_nodes {
+        if custom_properties[*parent_id].is_empty() {
+            continue;
+        }
+        let inherited_custom_properties = custom_properties[*parent_id].clone();
+        for child_id in parent_id.children(&ui_state.dom.arena.node_layout) {
+            for (name, custom_property) in &inherited_custom_properties {
+                custom_properties[child_id].entry(name.clone()).or_insert_with(|| custom_property.clone());
+            }
+        }
+    }
+
+    // Substitute `var(--main-bg-color)` with the value of the custom property that is visible
+    // on the node. This has to happen before the inheritance step, so that children inherit
+    // the value as computed on the parent. Variables that aren't defined in the CSS stay
+    // dynamic properties, so that they can still be overridden from Rust.
+    for node_id in custom_properties.linear_iter() {
+        let node_custom_properties = &custom_properties[node_id];
+        if node_custom_properties.is_empty() {
+            continue;
+        }
+        for declaration in styled_nodes[node_id].css_constraints.values_mut() {
+            let substituted_value = match declaration {
+                CssDeclaration::Dynamic(dynamic) => node_custom_properties
+                    .get(&dynamic.dynamic_id)
+                    .and_then(|custom_property| custom_property.get_value(dynamic.default_value.get_type()))
+                    .cloned(),
+                CssDeclaration::Static(_) => None,
+            };
+            if let Some(substituted_value) = substituted_value {
+                *declaration = CssDeclaration::Static(substituted_value);
+            }
+        }
+    }
+
     // Then, inherit all values of the parent to the children, but only if the property is
     // inheritable and isn't yet set. NOTE: This step can't be parallelized!
-    for (_depth, parent_id) in non_leaf_nodes {
+    for (_depth, parent_id) in &non_leaf_nodes {
 
-        let inherited_rules: Vec<CssDeclaration> = styled_nodes[parent_id].css_constraints.values().filter(|prop| prop.is_inheritable()).cloned().collect();
+        let inherited_rules: Vec<(CssDeclaration, Option<CssSourceLocation>)> = styled_nodes[*parent_id].css_constraints.values()
+            .filter(|prop| prop.is_inheritable())
+            .map(|prop| (prop.clone(), styled_nodes[*parent_id].get_property_source(prop.get_type()).cloned()))
+            .collect();
         if inherited_rules.is_empty() {
             continue;
         }
 
         for child_id in parent_id.children(&ui_state.dom.arena.node_layout) {
-            for inherited_rule in &inherited_rules {
+            for (inherited_rule, inherited_source) in &inherited_rules {
                 // Only override the rule if the child already has an inherited rule, don't override it
                 let inherited_rule_type = inherited_rule.get_type();
-                styled_nodes[child_id].css_constraints.entry(inherited_rule_type).or_insert_with(|| inherited_rule.clone());
+                let child = &mut styled_nodes[child_id];
+                if child.css_constraints.contains_key(&inherited_rule_type) {
+                    continue;
+                }
+                child.css_constraints.insert(inherited_rule_type, inherited_rule.clone());
+                if let Some(inherited_source) = inherited_source {
+                    child.css_constraint_sources.insert(inherited_rule_type, inherited_source.clone());
+                }
             }
         }
     }
@@ -160,7 +256,7 @@ pub fn match_dom_selectors<T>(
     // In order to hit-test :hover and :active nodes, need to select them
     // first (to insert their TagId later)
     let selected_hover_nodes = match_hover_selectors(
-        collect_hover_groups(css),
+        collect_hover_groups(css, media_environment),
         &ui_state.dom.arena.node_layout,
         &html_tree,
     );
@@ -195,6 +291,8 @@ pub struct CssGroupIterator<'a> {
 pub enum CssGroupSplitReason {
     Children,
     DirectChildren,
+    AdjacentSibling,
+    GeneralSibling,
 }
 
 impl<'a> CssGroupIterator<'a> {
@@ -232,6 +330,14 @@ impl<'a> Iterator for CssGroupIterator<'a> {
                     self.last_reason = CssGroupSplitReason::DirectChildren;
                     break;
                 },
+                AdjacentSibling => {
+                    self.last_reason = CssGroupSplitReason::AdjacentSibling;
+                    break;
+                },
+                GeneralSibling => {
+                    self.last_reason = CssGroupSplitReason::GeneralSibling;
+                    break;
+                },
                 other => current_path.push(other),
             }
             new_idx -= 1;
@@ -251,7 +357,7 @@ impl<'a> Iterator for CssGroupIterator<'a> {
                 Some((current_path, self.last_reason))
             }
         } else {
-            // skip the "Children | DirectChildren" element itself
+            // skip the "Children | DirectChildren | AdjacentSibling | GeneralSibling" element itself
             self.current_idx = new_idx - 1;
             Some((current_path, self.last_reason))
         }
@@ -270,50 +376,91 @@ pub fn construct_html_cascade_tree<'a, T>(
     let mut nodes = (0..node_hierarchy.len()).map(|_| HtmlCascadeInfo {
         node_data: &input[NodeId::new(0)],
         index_in_parent: 0,
+        index_from_end: 0,
         is_last_child: false,
+        is_empty: false,
         is_hovered_over: false,
         is_active: false,
         is_focused: false,
+        is_focus_within: false,
     }).collect::<Vec<_>>();
 
     for (_depth, parent_id) in node_depths_sorted {
 
         // Note: :nth-child() starts at 1 instead of 0
         let index_in_parent = parent_id.preceding_siblings(node_hierarchy).count();
+        let index_from_end = parent_id.following_siblings(node_hierarchy).count();
 
         let is_parent_hovered_over = hovered_items.contains_key(parent_id);
         let parent_html_matcher = HtmlCascadeInfo {
             node_data: &input[*parent_id],
             index_in_parent: index_in_parent, // necessary for nth-child
+            index_from_end: index_from_end, // necessary for nth-last-child
             is_last_child: node_hierarchy[*parent_id].next_sibling.is_none(), // Necessary for :last selectors
+            is_empty: false, // parents always have children
             is_hovered_over: is_parent_hovered_over,
             is_active: is_parent_hovered_over && is_mouse_down,
             is_focused: focused_item == Some(*parent_id),
+            is_focus_within: false,
         };
 
         nodes[parent_id.index()] = parent_html_matcher;
 
+        let num_children = parent_id.children(node_hierarchy).count();
+
         for (child_idx, child_id) in parent_id.children(node_hierarchy).enumerate() {
             let is_child_hovered_over = hovered_items.contains_key(&child_id);
             let child_html_matcher = HtmlCascadeInfo {
                 node_data: &input[child_id],
                 index_in_parent: child_idx + 1, // necessary for nth-child
+                index_from_end: num_children - child_idx,
                 is_last_child: node_hierarchy[child_id].next_sibling.is_none(),
+                is_empty: node_hierarchy[child_id].first_child.is_none() && !has_text_content(&input[child_id]),
                 is_hovered_over: is_child_hovered_over,
                 is_active: is_child_hovered_over && is_mouse_down,
                 is_focused: focused_item == Some(child_id),
+                is_focus_within: false,
             };
 
             nodes[child_id.index()] = child_html_matcher;
         }
     }
 
-    NodeDataContainer { internal: nodes }
+    let mut nodes = NodeDataContainer { internal: nodes };
+    set_focus_within(&mut nodes, node_hierarchy, focused_item);
+    nodes
+}
+
+/// Whether the node contains text, i.e. a non-empty label (necessary for :empty)
+fn has_text_content<T>(node_data: &NodeData<T>) -> bool {
+    use dom::NodeType::*;
+    match node_data.get_node_type() {
+        Label(text) => !text.as_str().is_empty(),
+        Text(_) => true,
+        _ => false,
+    }
+}
+
+/// Marks the focused node and all of its parents as `:focus-within`
+fn set_focus_within<'a, T>(
+    html_node_tree: &mut NodeDataContainer<HtmlCascadeInfo<'a, T>>,
+    node_hierarchy: &NodeHierarchy,
+    focused_item: Option<NodeId>,
+) {
+    for html_node in &mut html_node_tree.internal {
+        html_node.is_focus_within = false;
+    }
+
+    if let Some(focused_item) = focused_item {
+        for node_id in focused_item.ancestors(node_hierarchy) {
+            html_node_tree[node_id].is_focus_within = true;
+        }
+    }
 }
 
 /// Returns all CSS paths that have a `:hover` or `:active` in their path
 /// (since they need to have tags for hit-testing)
-pub fn collect_hover_groups(css: &Css) -> BTreeMap<CssPath, HoverGroup> {
+pub fn collect_hover_groups(css: &Css, media_environment: &MediaQueryEnvironment) -> BTreeMap<CssPath, HoverGroup> {
     use azul_css::{CssPathSelector::*, CssPathPseudoSelector::*};
 
     let hover_rule = PseudoSelector(Hover);
@@ -321,9 +468,9 @@ pub fn collect_hover_groups(css: &Css) -> BTreeMap<CssPath, HoverGroup> {
 
     // Filter out all :hover and :active rules, since we need to create tags
     // for them after the main CSS styling has been done
-    css.rules().filter_map(|rule_block| {
+    css.rules().filter(|rule_block| rule_block.matches_media(media_environment)).filter_map(|rule_block| {
         let pos = rule_block.path.selectors.iter().position(|x| *x == hover_rule || *x == active_rule)?;
-        if rule_block.declarations.is_empty() {
+        if rule_block.declarations.is_empty() && rule_block.custom_properties.is_empty() {
             return None;
         }
 
@@ -333,9 +480,17 @@ pub fn collect_hover_groups(css: &Css) -> BTreeMap<CssPath, HoverGroup> {
             _ => return None,
         };
 
-        let css_path = CssPath { selectors: rule_block.path.selectors.iter().cloned().take(pos).collect() };
+        // The hit-testing tags have to be independent of which node is currently focused:
+        // for ".form:focus-within .button:hover", the button needs a tag before the form
+        // receives focus, otherwise the hover would only be picked up after the next restyle.
+        let css_path = CssPath { selectors: rule_block.path.selectors.iter().take(pos).map(|selector| match selector {
+            PseudoSelector(Focus) | PseudoSelector(FocusWithin) => Global,
+            other => other.clone(),
+        }).collect() };
         let hover_group = HoverGroup {
-            affects_layout: rule_block.declarations.iter().any(|hover_rule| hover_rule.can_trigger_relayout()),
+            // Changing a custom property can change any property that references it
+            affects_layout: !rule_block.custom_properties.is_empty() ||
+                rule_block.declarations.iter().any(|hover_rule| hover_rule.can_trigger_relayout()),
             active_or_hover,
         };
         Some((css_path, hover_group))
@@ -364,7 +519,7 @@ fn match_hover_selectors<'a, T>(
     btree_map
 }
 
-/// Matches a single group of items, panics on Children or DirectChildren selectors
+/// Matches a single group of items, panics on Children, DirectChildren or sibling selectors
 ///
 /// The intent is to "split" the CSS path into groups by selectors, then store and cache
 /// whether the direct or any parent has matched the path correctly
@@ -398,13 +553,20 @@ pub fn selector_group_matches<'a, T>(selectors: &[&CssPathSelector], html_node:
                 if !html_node.is_last_child { return false; }
             },
             PseudoSelector(CssPathPseudoSelector::NthChild(x)) => {
-                match *x {
-                    Number(value) => if html_node.index_in_parent != value { return false; },
-                    Even => if html_node.index_in_parent % 2 == 0 { return false; },
-                    Odd => if html_node.index_in_parent % 2 == 1 { return false; },
-                    Pattern { repeat, offset } => if html_node.index_in_parent >= offset &&
-                        ((html_node.index_in_parent - offset) % repeat != 0) { return false; },
-                }
+                if !x.matches(html_node.index_in_parent) { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::NthLastChild(x)) => {
+                if !x.matches(html_node.index_from_end) { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::OnlyChild) => {
+                if html_node.index_in_parent != 1 || !html_node.is_last_child { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::Empty) => {
+                if !html_node.is_empty { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::Not(not_selectors)) => {
+                let not_selectors = not_selectors.iter().collect::<Vec<_>>();
+                if selector_group_matches(&not_selectors, html_node) { return false; }
             },
             PseudoSelector(CssPathPseudoSelector::Hover) => {
                 if !html_node.is_hovered_over { return false; }
@@ -415,8 +577,11 @@ pub fn selector_group_matches<'a, T>(selectors: &[&CssPathSelector], html_node:
             PseudoSelector(CssPathPseudoSelector::Focus) => {
                 if !html_node.is_focused { return false; }
             },
-            DirectChildren | Children => {
-                panic!("Unreachable: DirectChildren or Children in CSS path!");
+            PseudoSelector(CssPathPseudoSelector::FocusWithin) => {
+                if !html_node.is_focus_within { return false; }
+            },
+            DirectChildren | Children | AdjacentSibling | GeneralSibling => {
+                panic!("Unreachable: DirectChildren, Children or sibling combinator in CSS path!");
             },
         }
     }
@@ -489,6 +654,8 @@ pub fn update_focus_from_callbacks<'a, T: 'a>(
         html_node_tree[*focused_node].is_focused = true;
     }
 
+    set_focus_within(html_node_tree, node_hierarchy, focused_node.as_ref().map(|(_dom_id, node_id)| *node_id));
+
     *pending_focus_target = None;
 
     warning
@@ -569,6 +736,132 @@ fn test_case_issue_93() {
     assert_eq!(matches_html_element(&tab_active_close, NodeId::new(4), &node_hierarchy, &html_node_tree), true);
 }
 
+#[test]
+fn test_sibling_combinators() {
+    use azul_css::CssPathSelector::*;
+    use azul_css::*;
+    use dom::*;
+
+    struct DataModel;
+
+    //  0: [div .form]
+    //   |-- 1: [p .label]
+    //   |-- 2: [div .input]
+    //   |-- 3: [p .hint]
+    //   |-- 4: [div .input]
+    let dom: Dom<DataModel> = Dom::div().with_class("form")
+        .with_child(Dom::label("").with_class("label"))
+        .with_child(Dom::div().with_class("input"))
+        .with_child(Dom::label("").with_class("hint"))
+        .with_child(Dom::div().with_class("input"));
+
+    let node_hierarchy = &dom.arena.node_layout;
+    let nodes_sorted: Vec<_> = node_hierarchy.get_parents_sorted_by_depth();
+    let html_node_tree = construct_html_cascade_tree(
+        &dom.arena.node_data,
+        &node_hierarchy,
+        &nodes_sorted,
+        None,
+        &BTreeMap::new(),
+        false,
+    );
+
+    // ".label + .input"
+    let adjacent = CssPath { selectors: vec![Class("label".into()), AdjacentSibling, Class("input".into())] };
+    // ".label ~ .input"
+    let general = CssPath { selectors: vec![Class("label".into()), GeneralSibling, Class("input".into())] };
+    // ".form > .label ~ .hint + .input"
+    let chained = CssPath { selectors: vec![
+        Class("form".into()),
+        DirectChildren,
+        Class("label".into()),
+        GeneralSibling,
+        Class("hint".into()),
+        AdjacentSibling,
+        Class("input".into()),
+    ] };
+
+    assert_eq!(matches_html_element(&adjacent, NodeId::new(2), &node_hierarchy, &html_node_tree), true);
+    assert_eq!(matches_html_element(&adjacent, NodeId::new(4), &node_hierarchy, &html_node_tree), false);
+    assert_eq!(matches_html_element(&general, NodeId::new(2), &node_hierarchy, &html_node_tree), true);
+    assert_eq!(matches_html_element(&general, NodeId::new(4), &node_hierarchy, &html_node_tree), true);
+    assert_eq!(matches_html_element(&general, NodeId::new(1), &node_hierarchy, &html_node_tree), false);
+    assert_eq!(matches_html_element(&chained, NodeId::new(2), &node_hierarchy, &html_node_tree), false);
+    assert_eq!(matches_html_element(&chained, NodeId::new(4), &node_hierarchy, &html_node_tree), true);
+}
+
+#[test]
+fn test_structural_and_focus_pseudo_selectors() {
+    use azul_css::CssPathSelector::*;
+    use azul_css::CssPathPseudoSelector::*;
+    use azul_css::*;
+    use dom::*;
+
+    struct DataModel;
+
+    //  0: [div .form]
+    //   |-- 1: [div .row]
+    //   |    |-- 2: [p .label]
+    //   |    |-- 3: [div .input]
+    //   |-- 4: [div .row]
+    //   |    |-- 5: [p .label]
+    //   |-- 6: [div .row]
+    let dom: Dom<DataModel> = Dom::div().with_class("form")
+        .with_child(Dom::div().with_class("row")
+            .with_child(Dom::label("Name").with_class("label"))
+            .with_child(Dom::div().with_class("input")))
+        .with_child(Dom::div().with_class("row")
+            .with_child(Dom::label("").with_class("label")))
+        .with_child(Dom::div().with_class("row"));
+
+    let node_hierarchy = &dom.arena.node_layout;
+    let nodes_sorted: Vec<_> = node_hierarchy.get_parents_sorted_by_depth();
+    let html_node_tree = construct_html_cascade_tree(
+        &dom.arena.node_data,
+        &node_hierarchy,
+        &nodes_sorted,
+        Some(NodeId::new(3)),
+        &BTreeMap::new(),
+        false,
+    );
+
+    let matches = |selectors: Vec<CssPathSelector>, node_id: usize| {
+        matches_html_element(&CssPath { selectors }, NodeId::new(node_id), &node_hierarchy, &html_node_tree)
+    };
+
+    assert!(matches(vec![Class("row".into()), PseudoSelector(NthLastChild(CssNthChildSelector::Number(1)))], 6));
+    assert!(matches(vec![Class("row".into()), PseudoSelector(NthLastChild(CssNthChildSelector::Number(3)))], 1));
+    assert!(!matches(vec![Class("row".into()), PseudoSelector(NthLastChild(CssNthChildSelector::Number(3)))], 4));
+    assert!(matches(vec![Class("row".into()), PseudoSelector(NthChild(CssNthChildSelector::Even))], 4));
+    assert!(!matches(vec![Class("row".into()), PseudoSelector(NthChild(CssNthChildSelector::Even))], 6));
+    assert!(matches(vec![Class("label".into()), PseudoSelector(OnlyChild)], 5));
+    assert!(!matches(vec![Class("label".into()), PseudoSelector(OnlyChild)], 2));
+    assert!(matches(vec![PseudoSelector(Empty)], 5));
+    assert!(matches(vec![PseudoSelector(Empty)], 6));
+    assert!(!matches(vec![PseudoSelector(Empty)], 2));
+    assert!(!matches(vec![PseudoSelector(Empty)], 1));
+    assert!(matches(vec![Class("row".into()), PseudoSelector(Not(vec![PseudoSelector(Empty)]))], 4));
+    assert!(!matches(vec![Class("row".into()), PseudoSelector(Not(vec![PseudoSelector(Empty)]))], 6));
+    assert!(matches(vec![Class("row".into()), PseudoSelector(FocusWithin)], 1));
+    assert!(matches(vec![Class("form".into()), PseudoSelector(FocusWithin)], 0));
+    assert!(matches(vec![Class("input".into()), PseudoSelector(FocusWithin)], 3));
+    assert!(!matches(vec![Class("row".into()), PseudoSelector(FocusWithin)], 4));
+
+    // ".row:focus-within .label:hover" has to create hit-testing tags independent of the focus
+    let css = Css { stylesheets: vec![Stylesheet { rules: vec![CssRuleBlock {
+        path: CssPath { selectors: vec![
+            Class("row".into()), PseudoSelector(FocusWithin), Children, Class("label".into()), PseudoSelector(Hover),
+        ] },
+        declarations: vec![CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU::RED).into()))],
+        custom_properties: Vec::new(),
+        media_queries: Vec::new(),
+        source: None,
+    }], font_faces: Vec::new(), keyframes: Vec::new() }] };
+    let hover_groups = collect_hover_groups(&css, &MediaQueryEnvironment::default());
+    let hover_nodes = match_hover_selectors(hover_groups, &node_hierarchy, &html_node_tree);
+    assert_eq!(hover_nodes.keys().cloned().collect::<Vec<_>>(), vec![NodeId::new(2), NodeId::new(5)]);
+}
+
 #[test]
 fn test_css_group_iterator() {
     use self::CssPathSelector::*;
@@ -616,4 +909,77 @@ fn test_css_group_iterator() {
     ], CssGroupSplitReason::Children)));
 
     assert_eq!(it.next(), None);
-}
\ No newline at end of file
+}
+#[test]
+fn test_css_custom_properties_cascade() {
+    use azul_css::*;
+    use dom::*;
+    use ui_state::ui_state_from_dom;
+
+    struct DataModel;
+
+    let red = CssProperty::TextColor(CssPropertyValue::Exact(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 })));
+    let black = CssProperty::TextColor(CssPropertyValue::Exact(StyleTextColor(ColorU { r: 0, g: 0, b: 0, a: 255 })));
+    let color_source = CssSourceLocation { file: Some("app.css".into()), line: 2, column: 10 };
+
+    //  0: [div]
+    //   |-- 1: [div .theme]
+    //   |    |-- 2: [div .child]
+    //   |    |    |-- 3: [div]
+    //   |-- 4: [div .child]
+    let dom: Dom<DataModel> = Dom::div()
+        .with_child(Dom::div().with_class("theme")
+            .with_child(Dom::div().with_class("child")
+                .with_child(Dom::div())))
+        .with_child(Dom::div().with_class("child"));
+
+    // .theme { --fg: red; }
+    // .child { color: var(--fg, black); }
+    let css = Css { stylesheets: vec![Stylesheet { rules: vec![
+        CssRuleBlock {
+            path: CssPath { selectors: vec![CssPathSelector::Class("theme".into())] },
+            declarations: Vec::new(),
+            custom_properties: vec![CustomCssProperty {
+                name: "fg".into(),
+                value: "red".into(),
+                parsed_values: vec![red.clone()],
+            }],
+            media_queries: Vec::new(),
+            source: None,
+        },
+        CssRuleBlock {
+            path: CssPath { selectors: vec![CssPathSelector::Class("child".into())] },
+            declarations: vec![CssDeclaration::Dynamic(DynamicCssProperty {
+                dynamic_id: "fg".into(),
+                default_value: black.clone(),
+            })],
+            custom_properties: Vec::new(),
+            media_queries: Vec::new(),
+            source: Some(CssRuleSource {
+                block: CssSourceLocation { file: Some("app.css".into()), line: 2, column: 1 },
+                declarations: vec![(CssPropertyType::TextColor, color_source.clone())].into_iter().collect(),
+                custom_properties: BTreeMap::new(),
+            }),
+        },
+    ], font_faces: Vec::new(), keyframes: Vec::new() }]};
+
+    let ui_state = ui_state_from_dom(dom, None);
+    let ui_description = match_dom_selectors(&ui_state, &css, &mut None, &mut None, &BTreeMap::new(), false, &MediaQueryEnvironment::default());
+    let text_color = |node_id| ui_description.styled_nodes[NodeId::new(node_id)].css_constraints.get(&CssPropertyType::TextColor).cloned();
+
+    // The variable is defined on a parent, so it gets substituted and inherited like a normal property
+    assert_eq!(text_color(2), Some(CssDeclaration::Static(red.clone())));
+    assert_eq!(text_color(3), Some(CssDeclaration::Static(red.clone())));
+
+    // Outside of ".theme", the variable is undefined, so it stays overridable from Rust
+    assert_eq!(text_color(4), Some(CssDeclaration::Dynamic(DynamicCssProperty {
+        dynamic_id: "fg".into(),
+        default_value: black.clone(),
+    })));
+
+    // Inherited properties report the location of the declaration on the parent
+    let color_source_of = |node_id| ui_description.styled_nodes[NodeId::new(node_id)].get_property_source(CssPropertyType::TextColor).map(|source| source.to_string());
+    assert_eq!(color_source_of(1), None);
+    assert_eq!(color_source_of(2), Some("app.css:2:10".to_string()));
+    assert_eq!(color_source_of(3), Some("app.css:2:10".to_string()));
+}
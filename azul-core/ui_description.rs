@@ -2,7 +2,7 @@ use std::{
     fmt,
     collections::BTreeMap,
 };
-use azul_css::{ Css, CssDeclaration, CssProperty, CssPropertyType };
+use azul_css::{ Css, CssDeclaration, CssProperty, CssPropertyType, CssSourceLocation, MediaQueryEnvironment };
 use {
     FastHashMap,
     id_tree::{Arena, NodeId, NodeDataContainer},
@@ -72,6 +72,7 @@ impl<T> Default for UiDescription<T> {
         let default_dom = Dom::div();
         let hovered_nodes = BTreeMap::new();
         let is_mouse_down = false;
+        let media_environment = MediaQueryEnvironment::default();
 
         let mut focused_node = None;
         let mut focus_target = None;
@@ -84,6 +85,7 @@ impl<T> Default for UiDescription<T> {
             &mut focus_target,
             &hovered_nodes,
             is_mouse_down,
+            &media_environment,
         )
     }
 }
@@ -92,6 +94,9 @@ impl<T> UiDescription<T> {
     /// Applies the styles to the nodes calculated from the `layout_screen`
     /// function and calculates the final display list that is submitted to the
     /// renderer.
+    ///
+    /// The `@media` rules of the CSS are evaluated against the `media_environment`
+    /// (i.e. the current size of the window) on every call.
     pub fn match_css_to_dom(
         ui_state: &mut UiState<T>,
         style: &Css,
@@ -99,6 +104,7 @@ impl<T> UiDescription<T> {
         pending_focus_target: &mut Option<FocusTarget>,
         hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
         is_mouse_down: bool,
+        media_environment: &MediaQueryEnvironment,
     ) -> Self {
 
         use ui_state::ui_state_create_tags_for_hover_nodes;
@@ -110,6 +116,7 @@ impl<T> UiDescription<T> {
             pending_focus_target,
             hovered_nodes,
             is_mouse_down,
+            media_environment,
         );
 
         // Important: Create all the tags for the :hover and :active selectors
@@ -123,4 +130,15 @@ impl<T> UiDescription<T> {
 pub struct StyledNode {
     /// The CSS constraints, after the cascading step
     pub css_constraints: BTreeMap<CssPropertyType, CssDeclaration>,
+    /// Where the `css_constraints` were declared in the stylesheet - inherited properties point
+    /// to the declaration of the parent. Missing if the rule wasn't parsed from a stylesheet.
+    pub css_constraint_sources: BTreeMap<CssPropertyType, CssSourceLocation>,
+}
+
+impl StyledNode {
+    /// Returns where the value of the property was declared, for debugging why a style isn't
+    /// applied - for example `width` => `app.css:42:5`
+    pub fn get_property_source(&self, property_type: CssPropertyType) -> Option<&CssSourceLocation> {
+        self.css_constraint_sources.get(&property_type)
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 use azul_css::{
-    LayoutRect, PixelValue, LayoutSize, StyleFontSize,
+    LayoutRect, LayoutPoint, LayoutTransform, PixelValue, LayoutSize, StyleFontSize,
     StyleTextColor, ColorU as StyleColorU, Overflow,
     StyleTextAlignmentHorz, StyleTextAlignmentVert,
 };
@@ -135,6 +135,35 @@ pub struct LayoutResult {
     pub positioned_word_cache: BTreeMap<NodeId, (WordPositions, FontInstanceKey)>,
     pub layouted_glyph_cache: BTreeMap<NodeId, LayoutedGlyphs>,
     pub node_depths: Vec<(usize, NodeId)>,
+    /// CSS `transform` of all nodes that are transformed (by themselves or by one of their
+    /// parents), combined with the transforms of the parents: Maps the layout coordinates
+    /// of the node to the coordinates in the window.
+    pub transforms: BTreeMap<NodeId, LayoutTransform>,
+}
+
+impl LayoutResult {
+
+    /// Returns the bounding box of the node after applying the CSS `transform`s of the node and
+    /// its parents, i.e. the area that the node covers in the window.
+    pub fn get_transformed_bounds(&self, node_id: NodeId) -> Option<LayoutRect> {
+        let bounds = self.rects.get(node_id)?.bounds;
+        Some(match self.transforms.get(&node_id) {
+            Some(transform) => transform.transform_rect(&bounds),
+            None => bounds,
+        })
+    }
+
+    /// Maps a point in the window (i.e. the cursor position) into the untransformed layout
+    /// space of the node, relative to the top left corner of the node. Returns `None` if the
+    /// transform can't be inverted (i.e. `scale(0)`).
+    pub fn get_point_relative_to_node(&self, node_id: NodeId, point_in_window: LayoutPoint) -> Option<LayoutPoint> {
+        let bounds = self.rects.get(node_id)?.bounds;
+        let point = match self.transforms.get(&node_id) {
+            Some(transform) => transform.inverse()?.transform_point(&point_in_window),
+            None => point_in_window,
+        };
+        Some(LayoutPoint::new(point.x - bounds.origin.x, point.y - bounds.origin.y))
+    }
 }
 
 /// Layout options that can impact the flow of word positions
@@ -157,10 +186,15 @@ pub struct TextLayoutOptions {
     /// so for effects like `:first-letter`, use a hole instead of a leading.
     pub leading: Option<f32>,
     /// This is more important for inline text layout where items can punch "holes"
-    /// into the text flow, for example an image that floats to the right.
-    ///
-    /// TODO: Currently unused!
+    /// into the text flow, for example an image that floats to the right. The holes are
+    /// relative to the top left corner of the text, lines whose baseline is inside of a
+    /// hole continue behind it (or on the next line if the hole reaches the max width).
     pub holes: Vec<LayoutRect>,
+    /// Whether the text is only one part of a paragraph, i.e. a `<span>` in a label that
+    /// also contains other inline boxes. Trailing whitespace is kept (since it separates
+    /// the text from the next inline box) and the lines are positioned by the paragraph,
+    /// so the text must not be aligned again.
+    pub is_inline_fragment: bool,
 }
 
 /// Same as `TextLayoutOptions`, but with the widths / heights of the `PixelValue`s
@@ -184,10 +218,15 @@ pub struct ResolvedTextLayoutOptions {
     /// so for effects like `:first-letter`, use a hole instead of a leading.
     pub leading: Option<f32>,
     /// This is more important for inline text layout where items can punch "holes"
-    /// into the text flow, for example an image that floats to the right.
-    ///
-    /// TODO: Currently unused!
+    /// into the text flow, for example an image that floats to the right. The holes are
+    /// relative to the top left corner of the text, lines whose baseline is inside of a
+    /// hole continue behind it (or on the next line if the hole reaches the max width).
     pub holes: Vec<LayoutRect>,
+    /// Whether the text is only one part of a paragraph, i.e. a `<span>` in a label that
+    /// also contains other inline boxes. Trailing whitespace is kept (since it separates
+    /// the text from the next inline box) and the lines are positioned by the paragraph,
+    /// so the text must not be aligned again.
+    pub is_inline_fragment: bool,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
@@ -4,6 +4,7 @@ use std::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 use gleam::gl::Gl;
+use azul_css::{ColorScheme, MediaQueryEnvironment};
 use {
     callbacks::{DefaultCallbackId, DefaultCallback, DefaultCallbackTypeUnchecked},
     stack_checked_pointer::StackCheckedPointer,
@@ -307,6 +308,9 @@ pub struct WindowState {
     pub request_user_attention: bool,
     /// Set the windows Wayland theme. Irrelevant on other platforms, set to `None`
     pub wayland_theme: Option<WaylandTheme>,
+    /// Color scheme that `@media (prefers-color-scheme: ...)` queries are evaluated against.
+    /// Azul can't query the theme of the OS (yet), so this has to be set manually.
+    pub color_scheme: ColorScheme,
 }
 
 impl WindowState{
@@ -387,6 +391,16 @@ impl WindowSize {
         )
     }
 
+    /// Returns the environment that `@media` queries in the CSS are evaluated against
+    pub fn get_media_query_environment(&self, color_scheme: ColorScheme) -> MediaQueryEnvironment {
+        MediaQueryEnvironment {
+            width: self.dimensions.width,
+            height: self.dimensions.height,
+            hidpi_factor: self.hidpi_factor,
+            color_scheme,
+        }
+    }
+
     /// Get a size that is usually smaller than the logical one, so that the winit DPI factor is compensated for.
     pub fn get_reverse_logical_size(&self) -> LogicalSize {
         LogicalSize::new(
@@ -426,6 +440,7 @@ impl Default for WindowState {
             ime_position: None,
             request_user_attention: false,
             wayland_theme: None,
+            color_scheme: ColorScheme::default(),
         }
     }
 }
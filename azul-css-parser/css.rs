@@ -2,18 +2,22 @@
 use std::{
     num::ParseIntError,
     fmt,
-    collections::HashMap,
+    io,
+    collections::{HashMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 pub use azul_simplecss::Error as CssSyntaxError;
 use azul_simplecss::Tokenizer;
 
 use crate::css_parser;
-pub use crate::css_parser::CssParsingError;
+pub use crate::css_parser::{CssParsingError, CssMediaQueryParseError, CssFontFaceParseError, CssKeyframesParseError};
 use azul_css::{
-    Css, CssDeclaration, Stylesheet, DynamicCssProperty,
-    CssPropertyType, CssRuleBlock, CssPath, CssPathSelector,
+    Css, CssDeclaration, Stylesheet, DynamicCssProperty, CustomCssProperty,
+    CssProperty, CssPropertyType, CssRuleBlock, CssPath, CssPathSelector,
     CssNthChildSelector, CssPathPseudoSelector, CssNthChildSelector::*,
     NodeTypePath, NodeTypePathParseError, CombinedCssPropertyType, CssKeyMap,
+    MediaQueryList, FontFace, FontFaceSource, CssKeyframes, CssSourceLocation, CssRuleSource,
 };
 
 /// Error that can happen during the parsing of a CSS value
@@ -55,6 +59,14 @@ pub enum CssParseErrorInner<'a> {
     /// when setting the variable, whether all sides should be set, instead, you have to use `margin-top: var(--blah)`,
     /// `margin-bottom: var(--baz)` in order to work around this limitation.
     VarOnShorthandProperty { key: CombinedCssPropertyType, value: &'a str },
+    /// Invalid condition of a `@media` block, i.e. `@media (min-width: abc)`
+    MediaQueryParseError(CssMediaQueryParseError<'a>),
+    /// `@import` without a valid file name, i.e. `@import other.css;` (missing quotes)
+    MalformedImport(&'a str),
+    /// Invalid `@font-face` rule, i.e. `@font-face { src: url(a.ttf); }` (missing `font-family`)
+    FontFaceParseError(CssFontFaceParseError<'a>),
+    /// Invalid `@keyframes` rule, i.e. `@keyframes grow { half { width: 50%; } }`
+    KeyframesParseError(CssKeyframesParseError<'a>),
 }
 
 impl_display!{ CssParseErrorInner<'a>, {
@@ -69,6 +81,10 @@ impl_display!{ CssParseErrorInner<'a>, {
         "Error while parsing: \"{}: {};\": var() cannot be used on shorthand properties - use `{}-top` or `{}-x` as the key instead: ",
         key, value, key, key
     ),
+    MediaQueryParseError(e) => format!("Failed to parse @media query: {}", e),
+    MalformedImport(e) => format!("Malformed @import: \"{}\" - expected \"@import \"file.css\";\" or \"@import url(file.css);\"", e),
+    FontFaceParseError(e) => format!("Failed to parse @font-face: {}", e),
+    KeyframesParseError(e) => format!("Failed to parse @keyframes: {}", e),
 }}
 
 impl<'a> From<CssSyntaxError> for CssParseErrorInner<'a> {
@@ -80,6 +96,9 @@ impl<'a> From<CssSyntaxError> for CssParseErrorInner<'a> {
 impl_from! { DynamicCssParseError<'a>, CssParseErrorInner::DynamicCssParseError }
 impl_from! { NodeTypePathParseError<'a>, CssParseErrorInner::NodeTypePath }
 impl_from! { CssPseudoSelectorParseError<'a>, CssParseErrorInner::PseudoSelectorParseError }
+impl_from! { CssMediaQueryParseError<'a>, CssParseErrorInner::MediaQueryParseError }
+impl_from! { CssFontFaceParseError<'a>, CssParseErrorInner::FontFaceParseError }
+impl_from! { CssKeyframesParseError<'a>, CssParseErrorInner::KeyframesParseError }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CssPseudoSelectorParseError<'a> {
@@ -87,6 +106,8 @@ pub enum CssPseudoSelectorParseError<'a> {
     UnknownSelector(&'a str, Option<&'a str>),
     InvalidNthChildPattern(&'a str),
     InvalidNthChild(ParseIntError),
+    /// `:not()` without a selector or with a selector that contains combinators
+    InvalidNotSelector(&'a str),
 }
 
 impl<'a> From<ParseIntError> for CssPseudoSelectorParseError<'a> {
@@ -110,6 +131,10 @@ impl_display! { CssPseudoSelectorParseError<'a>, {
         number, \"even\" or \"odd\" or a pattern such as \"2n+3\"", selector
     ),
     InvalidNthChild(e) => format!("Invalid :nth-child pseudo-selector: ':{}'", e),
+    InvalidNotSelector(selector) => format!(
+        "Invalid pseudo-selector :not({}) - value has to be a selector \
+        without combinators, such as \".class\" or \"div#id\"", selector
+    ),
 }}
 
 /// Error that can happen during `css_parser::parse_key_value_pair`
@@ -138,22 +163,56 @@ fn pseudo_selector_from_str<'a>(selector: &'a str, value: Option<&'a str>)
 -> Result<CssPathPseudoSelector, CssPseudoSelectorParseError<'a>>
 {
     match selector {
-        "first" => Ok(CssPathPseudoSelector::First),
-        "last" => Ok(CssPathPseudoSelector::Last),
+        "first" | "first-child" => Ok(CssPathPseudoSelector::First),
+        "last" | "last-child" => Ok(CssPathPseudoSelector::Last),
+        "only-child" => Ok(CssPathPseudoSelector::OnlyChild),
+        "empty" => Ok(CssPathPseudoSelector::Empty),
         "hover" => Ok(CssPathPseudoSelector::Hover),
         "active" => Ok(CssPathPseudoSelector::Active),
         "focus" => Ok(CssPathPseudoSelector::Focus),
+        "focus-within" => Ok(CssPathPseudoSelector::FocusWithin),
         "nth-child" => {
             let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
             let parsed = parse_nth_child_selector(value)?;
             Ok(CssPathPseudoSelector::NthChild(parsed))
         },
+        "nth-last-child" => {
+            let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
+            let parsed = parse_nth_child_selector(value)?;
+            Ok(CssPathPseudoSelector::NthLastChild(parsed))
+        },
+        "not" => {
+            let value = value.ok_or(CssPseudoSelectorParseError::InvalidNotSelector(""))?;
+            Ok(CssPathPseudoSelector::Not(parse_not_selector(value)?))
+        },
         _ => {
             Err(CssPseudoSelectorParseError::UnknownSelector(selector, value))
         },
     }
 }
 
+/// Parses the inner value of the `:not` selector, i.e. `".class#id"`. Combinators are not
+/// allowed, the value has to match a single element.
+fn parse_not_selector<'a>(value: &'a str) -> Result<Vec<CssPathSelector>, CssPseudoSelectorParseError<'a>> {
+
+    use self::CssPathSelector::*;
+
+    let selectors = parse_css_path(value)
+        .map_err(|_| CssPseudoSelectorParseError::InvalidNotSelector(value))?
+        .selectors;
+
+    let has_combinators = selectors.iter().any(|selector| match selector {
+        Children | DirectChildren | AdjacentSibling | GeneralSibling => true,
+        _ => false,
+    });
+
+    if has_combinators {
+        Err(CssPseudoSelectorParseError::InvalidNotSelector(value))
+    } else {
+        Ok(selectors)
+    }
+}
+
 /// Parses the inner value of the `:nth-child` selector, including numbers and patterns.
 ///
 /// I.e.: `"2n+3"` -> `Pattern { repeat: 2, offset: 3 }`
@@ -222,9 +281,17 @@ fn test_css_pseudo_selector_parse() {
     let ok_res = [
         (("first", None), First),
         (("last", None), Last),
+        (("first-child", None), First),
+        (("last-child", None), Last),
+        (("only-child", None), OnlyChild),
+        (("empty", None), Empty),
         (("hover", None), Hover),
         (("active", None), Active),
         (("focus", None), Focus),
+        (("focus-within", None), FocusWithin),
+        (("nth-last-child", Some("2")), NthLastChild(Number(2))),
+        (("not", Some(".a#b")), Not(vec![CssPathSelector::Class("a".into()), CssPathSelector::Id("b".into())])),
+        (("not", Some(":hover")), Not(vec![CssPathSelector::PseudoSelector(Hover)])),
         (("nth-child", Some("4")), NthChild(Number(4))),
         (("nth-child", Some("even")), NthChild(Even)),
         (("nth-child", Some("odd")), NthChild(Odd)),
@@ -236,12 +303,14 @@ fn test_css_pseudo_selector_parse() {
         (("asdf", None), UnknownSelector("asdf", None)),
         (("", None), UnknownSelector("", None)),
         (("nth-child", Some("2n+")), InvalidNthChildPattern("2n+")),
+        (("not", None), InvalidNotSelector("")),
+        (("not", Some(".a > .b")), InvalidNotSelector(".a > .b")),
         // Can't test for ParseIntError because the fields are private.
         // This is an example on why you shouldn't use std::error::Error!
     ];
 
     for ((selector, val), a) in &ok_res {
-        assert_eq!(pseudo_selector_from_str(selector, *val), Ok(*a));
+        assert_eq!(pseudo_selector_from_str(selector, *val), Ok(a.clone()));
     }
 
     for ((selector, val), e) in &err {
@@ -255,22 +324,59 @@ pub struct ErrorLocation {
 }
 
 impl ErrorLocation {
-    /// Given an error location, returns the (line, column)
+    /// Given an error location, returns the (line, column), both starting at 1
     pub fn get_line_column_from_error(&self, css_string: &str) -> (usize, usize) {
 
-        let error_location = self.original_pos.saturating_sub(1);
-        let (mut line_number, mut total_characters) = (0, 0);
+        let text_before_error = &css_string[0..self.original_pos.min(css_string.len())];
+
+        let line_number = text_before_error.matches('\n').count() + 1;
+        let line_start = text_before_error.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+        let column_pos = text_before_error[line_start..].chars().count() + 1;
+
+        (line_number, column_pos)
+    }
+}
+
+/// Converts positions in a CSS string into the `CssSourceLocation`s stored in the parsed rules
+struct SourceMap<'a> {
+    css_string: &'a str,
+    file: Option<Arc<str>>,
+    /// Position of the first character of each line
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+
+    fn new(css_string: &'a str, file: Option<Arc<str>>) -> Self {
+        let line_starts = Some(0).into_iter()
+            .chain(css_string.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+        Self { css_string, file, line_starts }
+    }
+
+    /// Returns the location of the first character at or after `pos` that isn't whitespace
+    /// or a comment, since the tokenizer positions point to the end of the previous token
+    fn get_location(&self, pos: usize) -> CssSourceLocation {
+
+        let mut pos = pos.min(self.css_string.len());
 
-        for line in css_string[0..error_location].lines() {
-            line_number += 1;
-            total_characters += line.chars().count();
+        loop {
+            let rest = &self.css_string[pos..];
+            let trimmed = rest.trim_start();
+            pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with("/*") {
+                break;
+            }
+            pos = trimmed.find("*/").map(|comment_end| pos + comment_end + 2).unwrap_or(self.css_string.len());
         }
 
-        // Rust doesn't count "\n" as a character, so we have to add the line number count on top
-        let total_characters = total_characters + line_number;
-        let column_pos = error_location - total_characters.saturating_sub(2);
+        let line = match self.line_starts.binary_search(&pos) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let column = self.css_string[self.line_starts[line]..pos].chars().count() + 1;
 
-        (line_number, column_pos)
+        CssSourceLocation { file: self.file.clone(), line: line + 1, column }
     }
 }
 
@@ -287,10 +393,144 @@ impl<'a> fmt::Display for CssParseError<'a> {
     }
 }
 
+/// Parses a CSS string into a `Css` with a single stylesheet
+///
+/// `@import` rules are ignored, since there is no file that the path could be resolved
+/// against - use `new_from_file` to load a stylesheet including its imports.
 pub fn new_from_str<'a>(css_string: &'a str) -> Result<Css, CssParseError<'a>> {
-    let mut tokenizer = Tokenizer::new(css_string);
-    let (stylesheet, _warnings) = new_from_str_inner(css_string, &mut tokenizer)?;
-    Ok(Css { stylesheets: vec![stylesheet] })
+    let (stylesheet, _imports, mut errors, _warnings) = new_from_str_inner(css_string, None);
+    if errors.is_empty() {
+        Ok(Css { stylesheets: vec![stylesheet] })
+    } else {
+        Err(errors.remove(0))
+    }
+}
+
+/// Same as `new_from_str`, but doesn't stop at the first error: like in a browser, invalid
+/// declarations and rule blocks are skipped and the rest of the stylesheet is still parsed.
+///
+/// Returns the valid part of the stylesheet together with all errors and warnings,
+/// in the order in which they appear in the source string.
+pub fn new_from_str_lenient<'a>(css_string: &'a str) -> (Css, Vec<CssParseError<'a>>, Vec<CssParseWarnMsg<'a>>) {
+    let (stylesheet, _imports, errors, warnings) = new_from_str_inner(css_string, None);
+    (Css { stylesheets: vec![stylesheet] }, errors, warnings)
+}
+
+/// Error that can happen while loading a CSS file and its imports from disk
+#[derive(Debug)]
+pub enum CssImportError {
+    /// The file (or one of its imports) could not be read
+    Io(PathBuf, io::Error),
+    /// The file (or one of its imports) contains invalid CSS. Since the `CssParseError`
+    /// borrows the contents of the file, it is stored as an already formatted string.
+    Parse(PathBuf, String),
+}
+
+impl fmt::Display for CssImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::CssImportError::*;
+        match self {
+            Io(path, e) => write!(f, "Error loading CSS file \"{}\":\r\nIO Error: {}", path.display(), e),
+            Parse(path, e) => write!(f, "Error parsing CSS file \"{}\":\r\n{}", path.display(), e),
+        }
+    }
+}
+
+/// Loads and parses a CSS file, including all files that it (transitively) imports via
+/// `@import "other.css";`. Import paths are resolved relative to the importing file.
+///
+/// Each file ends up as its own `Stylesheet` in the returned `Css`, where the imported
+/// stylesheets come before the stylesheet that imports them. Files that are imported
+/// more than once (or recursively) are only loaded the first time.
+pub fn new_from_file<P: AsRef<Path>>(file_path: P) -> Result<Css, CssImportError> {
+    let mut css = Css::new();
+    let mut loaded_files = Vec::new();
+    load_css_file(file_path.as_ref(), &[], &mut css, &mut loaded_files, None)?;
+    Ok(css)
+}
+
+/// Same as `new_from_file`, but skips invalid declarations, rule blocks and unreadable imports
+/// (see `new_from_str_lenient`). Only fails if the file itself can't be read.
+///
+/// Returns the valid part of the stylesheets together with a human-readable message
+/// for every error and warning, prefixed with the file it occurred in.
+pub fn new_from_file_lenient<P: AsRef<Path>>(file_path: P) -> Result<(Css, Vec<String>), CssImportError> {
+    let mut css = Css::new();
+    let mut loaded_files = Vec::new();
+    let mut messages = Vec::new();
+    load_css_file(file_path.as_ref(), &[], &mut css, &mut loaded_files, Some(&mut messages))?;
+    Ok((css, messages))
+}
+
+/// If `messages` is `Some`, the file is loaded leniently and all errors and warnings are pushed
+/// to the messages, otherwise the first error is returned
+fn load_css_file(
+    file_path: &Path,
+    media_queries: &[MediaQueryList],
+    css: &mut Css,
+    loaded_files: &mut Vec<PathBuf>,
+    mut messages: Option<&mut Vec<String>>,
+) -> Result<(), CssImportError> {
+
+    use std::fs;
+
+    let file_path = fs::canonicalize(file_path).map_err(|e| CssImportError::Io(file_path.to_path_buf(), e))?;
+    if loaded_files.contains(&file_path) {
+        return Ok(());
+    }
+    loaded_files.push(file_path.clone());
+
+    let css_string = fs::read_to_string(&file_path).map_err(|e| CssImportError::Io(file_path.clone(), e))?;
+    let file_name = file_path.display().to_string();
+    let (mut stylesheet, imports, errors, warnings) = new_from_str_inner(&css_string, Some(file_name.into()));
+
+    match messages.as_mut() {
+        Some(messages) => {
+            messages.extend(errors.iter().map(|e| format!("{}", CssImportError::Parse(file_path.clone(), format!("{}", e)))));
+            messages.extend(warnings.iter().map(|w| format!("Warning while parsing CSS file \"{}\":\r\n{}", file_path.display(), w)));
+        },
+        None => {
+            if let Some(e) = errors.first() {
+                return Err(CssImportError::Parse(file_path.clone(), format!("{}", e)));
+            }
+        },
+    }
+
+    let base_directory = file_path.parent().unwrap_or(Path::new(""));
+
+    // @font-face { src: url("font.ttf"); } - font files are relative to the stylesheet
+    for font_face in &mut stylesheet.font_faces {
+        for source in &mut font_face.sources {
+            if let FontFaceSource::Url(url) = source {
+                *url = base_directory.join(url.as_str()).to_string_lossy().into_owned();
+            }
+        }
+    }
+
+    for import in imports {
+        let mut import_media_queries = media_queries.to_vec();
+        import_media_queries.extend(import.media_queries);
+        let import_path = base_directory.join(import.file_path);
+        let import_result = load_css_file(&import_path, &import_media_queries, css, loaded_files, messages.as_mut().map(|m| &mut **m));
+        match (import_result, messages.as_mut()) {
+            (Err(e), Some(messages)) => messages.push(format!("{}", e)),
+            (Err(e), None) => return Err(e),
+            (Ok(()), _) => { },
+        }
+    }
+
+    // @import "print.css" print; - all rules of the imported file are nested in the media query
+    if !media_queries.is_empty() {
+        for rule in &mut stylesheet.rules {
+            let mut rule_media_queries = media_queries.to_vec();
+            rule_media_queries.append(&mut rule.media_queries);
+            rule.media_queries = rule_media_queries;
+        }
+    }
+
+    css.append_stylesheet(stylesheet);
+
+    Ok(())
 }
 
 /// Returns the location of where the parser is currently in the document
@@ -379,6 +619,12 @@ pub fn parse_css_path<'a>(input: &'a str) -> Result<CssPath, CssPathParseError<'
             Token::Combinator(Combinator::Space) => {
                 selectors.push(CssPathSelector::Children);
             },
+            Token::Combinator(Combinator::Plus) => {
+                selectors.push(CssPathSelector::AdjacentSibling);
+            },
+            Token::Combinator(Combinator::Tilde) => {
+                selectors.push(CssPathSelector::GeneralSibling);
+            },
             Token::PseudoClass { selector, value } => {
                 selectors.push(CssPathSelector::PseudoSelector(pseudo_selector_from_str(selector, value)?));
             },
@@ -404,12 +650,27 @@ pub struct UnparsedCssRuleBlock<'a> {
     pub path: CssPath,
     /// `"justify-content" => "center"`
     pub declarations: HashMap<&'a str, (&'a str, (ErrorLocation, ErrorLocation))>,
+    /// Conditions of the `@media` blocks the rule is nested in
+    pub media_queries: Vec<MediaQueryList>,
+    /// Position where the css path starts
+    pub location: ErrorLocation,
+}
+
+/// Unresolved `@import "other.css";` rule
+#[derive(Debug, Clone, PartialEq)]
+struct CssImport<'a> {
+    /// `"other.css"`, relative to the importing file
+    file_path: &'a str,
+    /// Conditions of the `@media` blocks the import is nested in + the media query
+    /// of the import itself, i.e. `@import "print.css" print;`
+    media_queries: Vec<MediaQueryList>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CssParseWarnMsg<'a> {
-    warning: CssParseWarnMsgInner<'a>,
-    location: (ErrorLocation, ErrorLocation),
+    pub css_string: &'a str,
+    pub warning: CssParseWarnMsgInner<'a>,
+    pub location: (ErrorLocation, ErrorLocation),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -418,17 +679,375 @@ pub enum CssParseWarnMsgInner<'a> {
     UnsupportedKeyValuePair { key: &'a str, value: &'a str },
 }
 
+impl_display!{ CssParseWarnMsgInner<'a>, {
+    UnsupportedKeyValuePair { key, value } => format!("Unsupported CSS key: \"{}: {}\"", key, value),
+}}
+
+impl<'a> fmt::Display for CssParseWarnMsg<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start_location = self.location.0.get_line_column_from_error(self.css_string);
+        let end_location = self.location.1.get_line_column_from_error(self.css_string);
+        write!(f, "    start: line {}:{}\r\n    end: line {}:{}\r\n    reason: {}",
+            start_location.0, start_location.1,
+            end_location.0, end_location.1,
+            self.warning,
+        )
+    }
+}
+
 /// Parses a CSS string (single-threaded) and returns the parsed rules in blocks
 ///
 /// May return "warning" messages, i.e. messages that just serve as a warning,
 /// instead of being actual errors. These warnings may be ignored by the caller,
 /// but can be useful for debugging.
-fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
--> Result<(Stylesheet, Vec<CssParseWarnMsg<'a>>), CssParseError<'a>> {
+///
+/// Invalid rule blocks and declarations are skipped and returned as errors (sorted
+/// by their position in the source), so the returned stylesheet only contains the valid rules.
+///
+/// The `file` is stored in the source locations of the parsed rules.
+fn new_from_str_inner<'a>(css_string: &'a str, file: Option<Arc<str>>)
+-> (Stylesheet, Vec<CssImport<'a>>, Vec<CssParseError<'a>>, Vec<CssParseWarnMsg<'a>>) {
+    let mut css_blocks = Vec::new();
+    let mut at_rules = CssAtRules::default();
+    let mut errors = Vec::new();
+    parse_css_blocks(css_string, 0, css_string.len(), &[], &mut css_blocks, &mut at_rules, &mut errors);
+    let source_map = SourceMap::new(css_string, file);
+    let (mut stylesheet, warnings) = unparsed_css_blocks_to_stylesheet(css_blocks, css_string, &source_map, &mut errors);
+    // Errors in the declarations are only found after all blocks have been parsed
+    errors.sort_by_key(|e| e.location.0.original_pos);
+    stylesheet.font_faces = at_rules.font_faces;
+    stylesheet.keyframes = at_rules.keyframes;
+    (stylesheet, at_rules.imports, errors, warnings)
+}
 
-    use azul_simplecss::{Token, Combinator};
+/// At-rules that are collected while parsing the rule blocks
+#[derive(Debug, Default, Clone, PartialEq)]
+struct CssAtRules<'a> {
+    /// `@import "other.css";` rules, not yet resolved
+    imports: Vec<CssImport<'a>>,
+    /// `@font-face { ... }` rules
+    font_faces: Vec<FontFace>,
+    /// `@keyframes name { ... }` rules
+    keyframes: Vec<CssKeyframes>,
+}
 
-    let mut css_blocks = Vec::new();
+/// An at-rule such as `@media screen { ... }` or `@charset "utf-8";`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct CssAtRule<'a> {
+    /// `"media"`
+    name: &'a str,
+    /// Everything between the name and the block / semicolon, i.e. `"screen"`
+    prelude: &'a str,
+    /// Start and end position of the block contents (without the braces), if the rule has a block
+    block: Option<(usize, usize)>,
+    /// Position of the `@`
+    start: usize,
+    /// Position after the closing brace or semicolon
+    end: usize,
+}
+
+/// Parses the rule blocks between `start` and `end` of the `css_string`.
+///
+/// The tokenizer silently skips over at-rules, so `@media` blocks have to be split
+/// off before tokenizing - the rules inside of them are parsed recursively.
+/// `@import`, `@font-face` and `@keyframes` rules are collected, but not resolved.
+fn parse_css_blocks<'a>(
+    css_string: &'a str,
+    start: usize,
+    end: usize,
+    media_queries: &[MediaQueryList],
+    css_blocks: &mut Vec<UnparsedCssRuleBlock<'a>>,
+    at_rules: &mut CssAtRules<'a>,
+    errors: &mut Vec<CssParseError<'a>>,
+) {
+
+    let mut current_pos = start;
+
+    loop {
+
+        let at_rule = match find_next_at_rule(css_string, current_pos, end) {
+            Ok(Some(at_rule)) => at_rule,
+            Ok(None) => break,
+            Err(e) => {
+                // Unclosed at-rule block: parse everything up to the at-rule, skip the rest
+                tokenize_css_blocks(css_string, current_pos, e.location.0.original_pos, media_queries, css_blocks, errors);
+                errors.push(e);
+                return;
+            },
+        };
+
+        tokenize_css_blocks(css_string, current_pos, at_rule.start, media_queries, css_blocks, errors);
+
+        match (at_rule.name, at_rule.block) {
+            ("media", Some((block_start, block_end))) => {
+                match css_parser::parse_media_query_list(at_rule.prelude) {
+                    Ok(media_query) => {
+                        let mut nested_media_queries = media_queries.to_vec();
+                        nested_media_queries.push(media_query);
+                        parse_css_blocks(css_string, block_start, block_end, &nested_media_queries, css_blocks, at_rules, errors);
+                    },
+                    Err(e) => errors.push(CssParseError {
+                        css_string,
+                        error: e.into(),
+                        location: (ErrorLocation { original_pos: at_rule.start }, ErrorLocation { original_pos: block_start }),
+                    }),
+                }
+            },
+            ("import", None) => {
+                match parse_import_prelude(at_rule.prelude) {
+                    Ok((file_path, media_query)) => {
+                        let mut import_media_queries = media_queries.to_vec();
+                        import_media_queries.extend(media_query);
+                        at_rules.imports.push(CssImport { file_path, media_queries: import_media_queries });
+                    },
+                    Err(e) => errors.push(CssParseError {
+                        css_string,
+                        error: e,
+                        location: (ErrorLocation { original_pos: at_rule.start }, ErrorLocation { original_pos: at_rule.end }),
+                    }),
+                }
+            },
+            ("font-face", Some((block_start, block_end))) => {
+                // Fonts are registered regardless of the @media blocks the rule is nested in
+                match css_parser::parse_font_face(&css_string[block_start..block_end]) {
+                    Ok(font_face) => at_rules.font_faces.push(font_face),
+                    Err(e) => errors.push(CssParseError {
+                        css_string,
+                        error: e.into(),
+                        location: (ErrorLocation { original_pos: at_rule.start }, ErrorLocation { original_pos: at_rule.end }),
+                    }),
+                }
+            },
+            ("keyframes", Some((block_start, block_end))) => {
+                match css_parser::parse_keyframes(at_rule.prelude, &css_string[block_start..block_end]) {
+                    Ok(keyframes) => at_rules.keyframes.push(keyframes),
+                    Err(e) => errors.push(CssParseError {
+                        css_string,
+                        error: e.into(),
+                        location: (ErrorLocation { original_pos: at_rule.start }, ErrorLocation { original_pos: at_rule.end }),
+                    }),
+                }
+            },
+            _ => {
+                // other at-rules (@charset, @supports, etc.) are not supported
+            }
+        }
+
+        current_pos = at_rule.end;
+    }
+
+    tokenize_css_blocks(css_string, current_pos, end, media_queries, css_blocks, errors);
+}
+
+/// Parses the `"other.css" screen` part of an `@import "other.css" screen;` rule
+fn parse_import_prelude<'a>(prelude: &'a str) -> Result<(&'a str, Option<MediaQueryList>), CssParseErrorInner<'a>> {
+
+    let prelude = prelude.trim();
+
+    // Split into the file name and the (optional) media query following it
+    let (file_path, rest) = if prelude.starts_with("url(") {
+        let closing_brace = prelude.find(')').ok_or(CssParseErrorInner::MalformedImport(prelude))?;
+        let url = prelude[4..closing_brace].trim();
+        let url = css_parser::strip_quotes(url).map(|q| q.0).unwrap_or(url);
+        (url, &prelude[(closing_brace + 1)..])
+    } else if prelude.starts_with('"') || prelude.starts_with('\'') {
+        let quote = &prelude[..1];
+        let closing_quote = prelude[1..].find(quote).ok_or(CssParseErrorInner::MalformedImport(prelude))? + 1;
+        (&prelude[1..closing_quote], &prelude[(closing_quote + 1)..])
+    } else {
+        return Err(CssParseErrorInner::MalformedImport(prelude));
+    };
+
+    if file_path.is_empty() {
+        return Err(CssParseErrorInner::MalformedImport(prelude));
+    }
+
+    let rest = rest.trim();
+    let media_query = if rest.is_empty() {
+        None
+    } else {
+        Some(css_parser::parse_media_query_list(rest)?)
+    };
+
+    Ok((file_path, media_query))
+}
+
+/// Returns the position after the comment or string starting at `pos`, if there is one
+fn skip_comment_or_string(css_string: &str, pos: usize, end: usize) -> Option<usize> {
+    let bytes = css_string.as_bytes();
+    match bytes[pos] {
+        b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+            Some(css_string[(pos + 2)..end].find("*/").map(|p| pos + 2 + p + 2).unwrap_or(end))
+        },
+        quote @ b'"' | quote @ b'\'' => {
+            Some(bytes[(pos + 1)..end].iter().position(|c| *c == quote).map(|p| pos + 1 + p + 1).unwrap_or(end))
+        },
+        _ => None,
+    }
+}
+
+/// Finds the next at-rule between `start` and `end` that is not inside of a rule block,
+/// skipping over comments and strings
+fn find_next_at_rule<'a>(css_string: &'a str, start: usize, end: usize)
+-> Result<Option<CssAtRule<'a>>, CssParseError<'a>> {
+
+    let bytes = css_string.as_bytes();
+    let mut block_nesting = 0_usize;
+    let mut pos = start;
+    let mut at_rule_start = None;
+    let skip_comment_or_string = |pos: usize| skip_comment_or_string(css_string, pos, end);
+
+    // Find the "@" of the at-rule
+    while pos < end {
+        if let Some(next_pos) = skip_comment_or_string(pos) {
+            pos = next_pos;
+            continue;
+        }
+        match bytes[pos] {
+            b'{' => block_nesting += 1,
+            b'}' => block_nesting = block_nesting.saturating_sub(1),
+            b'@' if block_nesting == 0 => { at_rule_start = Some(pos); break; },
+            _ => { },
+        }
+        pos += 1;
+    }
+
+    let at_rule_start = match at_rule_start {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    let name_end = bytes[(at_rule_start + 1)..end].iter()
+        .position(|c| !(c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_'))
+        .map(|p| at_rule_start + 1 + p)
+        .unwrap_or(end);
+
+    let name = &css_string[(at_rule_start + 1)..name_end];
+
+    // Find the end of the prelude, i.e. the "{" or ";"
+    pos = name_end;
+    while pos < end {
+        if let Some(next_pos) = skip_comment_or_string(pos) {
+            pos = next_pos;
+            continue;
+        }
+        match bytes[pos] {
+            b';' => {
+                return Ok(Some(CssAtRule {
+                    name,
+                    prelude: css_string[name_end..pos].trim(),
+                    block: None,
+                    start: at_rule_start,
+                    end: pos + 1,
+                }));
+            },
+            b'{' => break,
+            _ => { },
+        }
+        pos += 1;
+    }
+
+    let prelude_end = pos;
+    let block_start = pos + 1;
+
+    // Find the matching closing brace of the block
+    while pos < end {
+        if let Some(next_pos) = skip_comment_or_string(pos) {
+            pos = next_pos;
+            continue;
+        }
+        match bytes[pos] {
+            b'{' => block_nesting += 1,
+            b'}' => {
+                block_nesting -= 1;
+                if block_nesting == 0 {
+                    return Ok(Some(CssAtRule {
+                        name,
+                        prelude: css_string[name_end..prelude_end].trim(),
+                        block: Some((block_start, pos)),
+                        start: at_rule_start,
+                        end: pos + 1,
+                    }));
+                }
+            },
+            _ => { },
+        }
+        pos += 1;
+    }
+
+    Err(CssParseError {
+        css_string,
+        error: CssParseErrorInner::UnclosedBlock,
+        location: (ErrorLocation { original_pos: at_rule_start }, ErrorLocation { original_pos: end }),
+    })
+}
+
+/// Tokenizes the part of a CSS string between `start` and `end` that doesn't contain any
+/// at-rules into blocks of unparsed rules.
+///
+/// Every rule block is tokenized on its own, so that a syntax error only
+/// skips the rule block it occurs in, not the rest of the stylesheet.
+fn tokenize_css_blocks<'a>(
+    css_string: &'a str,
+    start: usize,
+    end: usize,
+    media_queries: &[MediaQueryList],
+    css_blocks: &mut Vec<UnparsedCssRuleBlock<'a>>,
+    errors: &mut Vec<CssParseError<'a>>,
+) {
+
+    let bytes = css_string.as_bytes();
+    let mut block_nesting = 0_usize;
+    let mut block_start = start;
+    let mut pos = start;
+
+    while pos < end {
+        if let Some(next_pos) = skip_comment_or_string(css_string, pos, end) {
+            pos = next_pos;
+            continue;
+        }
+        match bytes[pos] {
+            b'{' => block_nesting += 1,
+            b'}' if block_nesting == 0 => {
+                // "}" without a matching "{", skip everything up to and including the brace
+                errors.push(CssParseError {
+                    css_string,
+                    error: CssParseErrorInner::MalformedCss,
+                    location: (ErrorLocation { original_pos: pos }, ErrorLocation { original_pos: pos + 1 }),
+                });
+                block_start = pos + 1;
+            },
+            b'}' => {
+                block_nesting -= 1;
+                if block_nesting == 0 {
+                    let mut tokenizer = Tokenizer::new_bound(css_string, block_start, pos + 1);
+                    if let Err(e) = tokenize_css_rule_block(css_string, &mut tokenizer, media_queries, css_blocks) {
+                        errors.push(e);
+                    }
+                    block_start = pos + 1;
+                }
+            },
+            _ => { },
+        }
+        pos += 1;
+    }
+
+    // Unclosed rule block or trailing selector without a block
+    let mut tokenizer = Tokenizer::new_bound(css_string, block_start, end);
+    if let Err(e) = tokenize_css_rule_block(css_string, &mut tokenizer, media_queries, css_blocks) {
+        errors.push(e);
+    }
+}
+
+/// Tokenizes a single rule block (`div, .class { ... }`) into blocks of unparsed rules
+fn tokenize_css_rule_block<'a>(
+    css_string: &'a str,
+    tokenizer: &mut Tokenizer<'a>,
+    media_queries: &[MediaQueryList],
+    css_blocks: &mut Vec<UnparsedCssRuleBlock<'a>>,
+) -> Result<(), CssParseError<'a>> {
+
+    use azul_simplecss::{Token, Combinator};
 
     // Used for error checking / checking for closed braces
     let mut parser_in_block = false;
@@ -443,10 +1062,16 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
     // Keep track of the current path during parsing
     let mut last_path = Vec::new();
 
-    let mut last_error_location = ErrorLocation { original_pos: 0 };
+    let mut last_error_location = get_error_location(tokenizer);
+    let mut last_path_location = last_error_location;
 
     loop {
 
+        // The current path starts with the next token
+        if last_path.is_empty() {
+            last_path_location = last_error_location;
+        }
+
         let token = tokenizer.parse_next().map_err(|e| CssParseError {
             css_string,
             error: e.into(),
@@ -478,12 +1103,12 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
                 check_parser_is_outside_block!();
                 parser_in_block = true;
                 block_nesting += 1;
-                current_paths.push(last_path.clone());
+                current_paths.push((last_path.clone(), last_path_location));
                 last_path.clear();
             },
             Token::Comma => {
                 check_parser_is_outside_block!();
-                current_paths.push(last_path.clone());
+                current_paths.push((last_path.clone(), last_path_location));
                 last_path.clear();
             },
             Token::BlockEnd => {
@@ -492,10 +1117,12 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
                 check_parser_is_inside_block!();
                 parser_in_block = false;
 
-                css_blocks.extend(current_paths.drain(..).map(|path| {
+                css_blocks.extend(current_paths.drain(..).map(|(path, location)| {
                     UnparsedCssRuleBlock {
                         path: CssPath { selectors: path },
                         declarations: current_rules.clone(),
+                        media_queries: media_queries.to_vec(),
+                        location,
                     }
                 }));
 
@@ -534,6 +1161,14 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
                 check_parser_is_outside_block!();
                 last_path.push(CssPathSelector::Children);
             },
+            Token::Combinator(Combinator::Plus) => {
+                check_parser_is_outside_block!();
+                last_path.push(CssPathSelector::AdjacentSibling);
+            },
+            Token::Combinator(Combinator::Tilde) => {
+                check_parser_is_outside_block!();
+                last_path.push(CssPathSelector::GeneralSibling);
+            },
             Token::PseudoClass { selector, value } => {
                 check_parser_is_outside_block!();
                 last_path.push(CssPathSelector::PseudoSelector(pseudo_selector_from_str(selector, value).map_err(|e| {
@@ -562,18 +1197,23 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>)
                 break;
             },
             _ => {
-                // attributes, lang-attributes and @keyframes are not supported
+                // attributes and lang-attributes are not supported
             }
         }
 
         last_error_location = get_error_location(tokenizer);
     }
 
-    unparsed_css_blocks_to_stylesheet(css_blocks, css_string)
+    Ok(())
 }
 
-fn unparsed_css_blocks_to_stylesheet<'a>(css_blocks: Vec<UnparsedCssRuleBlock<'a>>, css_string: &'a str)
--> Result<(Stylesheet, Vec<CssParseWarnMsg<'a>>), CssParseError<'a>> {
+/// Invalid declarations are skipped and pushed to the `errors`
+fn unparsed_css_blocks_to_stylesheet<'a>(
+    css_blocks: Vec<UnparsedCssRuleBlock<'a>>,
+    css_string: &'a str,
+    source_map: &SourceMap,
+    errors: &mut Vec<CssParseError<'a>>,
+) -> (Stylesheet, Vec<CssParseWarnMsg<'a>>) {
 
     // Actually parse the properties (TODO: this could be done in parallel and in a separate function)
     let css_key_map = azul_css::get_css_key_map();
@@ -583,44 +1223,85 @@ fn unparsed_css_blocks_to_stylesheet<'a>(css_blocks: Vec<UnparsedCssRuleBlock<'a
     let parsed_css_blocks = css_blocks.into_iter().map(|unparsed_css_block| {
 
         let mut declarations = Vec::<CssDeclaration>::new();
+        let mut custom_properties = Vec::<CustomCssProperty>::new();
+        let mut source = CssRuleSource {
+            block: source_map.get_location(unparsed_css_block.location.original_pos),
+            .. Default::default()
+        };
+
+        // Parse the declarations in the order of the source string, so that the errors are ordered, too
+        let mut unparsed_declarations = unparsed_css_block.declarations.into_iter().collect::<Vec<_>>();
+        unparsed_declarations.sort_by_key(|(_, (_, location))| *location);
 
-        for (unparsed_css_key, (unparsed_css_value, location)) in unparsed_css_block.declarations {
-            parse_css_declaration(
+        for (unparsed_css_key, (unparsed_css_value, location)) in unparsed_declarations {
+
+            let previous_declarations = declarations.len();
+            let previous_custom_properties = custom_properties.len();
+
+            if let Err(e) = parse_css_declaration(
                 unparsed_css_key,
                 unparsed_css_value,
                 location,
+                css_string,
                 &css_key_map,
                 &mut warnings,
                 &mut declarations,
-            ).map_err(|e| CssParseError {
-                css_string,
-                error: e.into(),
-                location,
-            })?;
+                &mut custom_properties,
+            ) {
+                errors.push(CssParseError {
+                    css_string,
+                    error: e,
+                    location,
+                });
+            }
+
+            // A later declaration of the same property overrides the location, too
+            let declaration_location = source_map.get_location(location.0.original_pos);
+            for declaration in &declarations[previous_declarations..] {
+                source.declarations.insert(declaration.get_type(), declaration_location.clone());
+            }
+            for custom_property in &custom_properties[previous_custom_properties..] {
+                source.custom_properties.insert(custom_property.name.clone(), declaration_location.clone());
+            }
         }
 
-        Ok(CssRuleBlock {
+        // A declaration overrides all previous declarations of the same property, i.e.
+        // "border-top-style: none" overrides the style set by a previous "border: 1px solid red"
+        let mut declared_types = BTreeSet::new();
+        declarations.reverse();
+        declarations.retain(|declaration| declared_types.insert(declaration.get_type()));
+        declarations.reverse();
+
+        CssRuleBlock {
             path: unparsed_css_block.path,
             declarations,
-        })
-    }).collect::<Result<Vec<CssRuleBlock>, CssParseError>>()?;
+            custom_properties,
+            media_queries: unparsed_css_block.media_queries,
+            source: Some(source),
+        }
+    }).collect::<Vec<CssRuleBlock>>();
 
-    Ok((parsed_css_blocks.into(), warnings))
+    (parsed_css_blocks.into(), warnings)
 }
 
 fn parse_css_declaration<'a>(
     unparsed_css_key: &'a str,
     unparsed_css_value: &'a str,
     location: (ErrorLocation, ErrorLocation),
+    css_string: &'a str,
     css_key_map: &CssKeyMap,
     warnings: &mut Vec<CssParseWarnMsg<'a>>,
     declarations: &mut Vec<CssDeclaration>,
+    custom_properties: &mut Vec<CustomCssProperty>,
 ) -> Result<(), CssParseErrorInner<'a>> {
 
     use self::CssParseErrorInner::*;
     use self::CssParseWarnMsgInner::*;
 
-    if let Some(combined_key) = CombinedCssPropertyType::from_str(unparsed_css_key, &css_key_map) {
+    if unparsed_css_key.starts_with("--") {
+        // --main-bg-color: #e3e3e3;
+        custom_properties.push(parse_custom_css_property(&unparsed_css_key[2..], unparsed_css_value, css_key_map));
+    } else if let Some(combined_key) = CombinedCssPropertyType::from_str(unparsed_css_key, &css_key_map) {
         if let Some(css_var) = check_if_value_is_css_var(unparsed_css_value) {
             // margin: var(--my-variable);
             return Err(VarOnShorthandProperty { key: combined_key, value: unparsed_css_value });
@@ -655,6 +1336,7 @@ fn parse_css_declaration<'a>(
     } else {
         // asldfkjasdf: 10px;
         warnings.push(CssParseWarnMsg {
+            css_string,
             warning: UnsupportedKeyValuePair { key: unparsed_css_key, value: unparsed_css_value },
             location,
         });
@@ -663,6 +1345,29 @@ fn parse_css_declaration<'a>(
     Ok(())
 }
 
+/// Parses the value of a custom property (`--main-bg-color: #e3e3e3`) as every property
+/// type that accepts it - whether it is actually valid for a property is only known once
+/// the property gets referenced via `var(--main-bg-color)` during the cascade.
+fn parse_custom_css_property(name: &str, unparsed_css_value: &str, css_key_map: &CssKeyMap) -> CustomCssProperty {
+
+    let mut parsed_values = Vec::<CssProperty>::new();
+
+    for property_type in css_key_map.non_shorthands.values() {
+        if let Ok(parsed_value) = css_parser::parse_css_property(*property_type, unparsed_css_value) {
+            // Some keys parse to the same property (i.e. "background" and "background-color")
+            if !parsed_values.iter().any(|p| p.get_type() == parsed_value.get_type()) {
+                parsed_values.push(parsed_value);
+            }
+        }
+    }
+
+    CustomCssProperty {
+        name: name.to_string(),
+        value: unparsed_css_value.trim().to_string(),
+        parsed_values,
+    }
+}
+
 fn check_if_value_is_css_var<'a>(unparsed_css_value: &'a str) -> Option<Result<(&'a str, &'a str), CssParseErrorInner<'a>>> {
 
     const DEFAULT_VARIABLE_DEFAULT: &str = "none";
@@ -727,8 +1432,11 @@ fn test_css_parse_1() {
                 g: 0,
                 b: 0,
                 a: 255,
-            })),
+            }).into()),
         ))],
+        custom_properties: Vec::new(),
+        media_queries: Vec::new(),
+        source: None,
     }];
 
     assert_eq!(
@@ -758,11 +1466,31 @@ fn test_css_simple_selector_parse() {
             rules: vec![CssRuleBlock {
                 path: CssPath { selectors: parsed },
                 declarations: Vec::new(),
+                custom_properties: Vec::new(),
+                media_queries: Vec::new(),
+                source: None,
             }],
+            font_faces: Vec::new(),
+            keyframes: Vec::new(),
         }],
     });
 }
 
+#[test]
+fn test_css_sibling_selector_parse() {
+    use self::CssPathSelector::*;
+    use azul_css::NodeTypePath;
+    let parsed = vec![
+        Class("label".into()),
+        AdjacentSibling,
+        Type(NodeTypePath::P),
+        GeneralSibling,
+        Class("hint".into())
+    ];
+    assert_eq!(parse_css_path(".label + p ~ .hint"), Ok(CssPath { selectors: parsed.clone() }));
+    assert_eq!(new_from_str(".label+p~.hint { }").unwrap().stylesheets[0].rules[0].path, CssPath { selectors: parsed });
+}
+
 #[cfg(test)]
 mod stylesheet_parse {
 
@@ -783,7 +1511,7 @@ mod stylesheet_parse {
                 g: 0,
                 b: 0,
                 a: 255,
-            }),
+            }).into(),
         ));
         let blue = CssProperty::BackgroundContent(CssPropertyValue::Exact(
             StyleBackgroundContent::Color(ColorU {
@@ -791,7 +1519,7 @@ mod stylesheet_parse {
                 g: 0,
                 b: 255,
                 a: 255,
-            }),
+            }).into(),
         ));
         let black = CssProperty::BackgroundContent(CssPropertyValue::Exact(
             StyleBackgroundContent::Color(ColorU {
@@ -799,7 +1527,7 @@ mod stylesheet_parse {
                 g: 0,
                 b: 0,
                 a: 255,
-            }),
+            }).into(),
         ));
 
         // Simple example
@@ -811,6 +1539,9 @@ mod stylesheet_parse {
                     declarations: vec![
                         CssDeclaration::Static(red.clone())
                     ],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
             ];
             test_css(css_1, expected_rules);
@@ -822,11 +1553,17 @@ mod stylesheet_parse {
             let expected_rules = vec![
                 CssRuleBlock {
                     path: CssPath { selectors: vec![CssPathSelector::Id("my_id".into())] },
-                    declarations: vec![CssDeclaration::Static(red.clone())]
+                    declarations: vec![CssDeclaration::Static(red.clone())],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
                 CssRuleBlock {
                     path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
-                    declarations: vec![CssDeclaration::Static(blue.clone())]
+                    declarations: vec![CssDeclaration::Static(blue.clone())],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
             ];
             test_css(css_2, expected_rules);
@@ -838,15 +1575,24 @@ mod stylesheet_parse {
             let expected_rules = vec![
                 CssRuleBlock {
                     path: CssPath { selectors: vec![CssPathSelector::Global] },
-                    declarations: vec![CssDeclaration::Static(black.clone())]
+                    declarations: vec![CssDeclaration::Static(black.clone())],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
                 CssRuleBlock {
                     path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into()), CssPathSelector::Id("my_id".into())] },
-                    declarations: vec![CssDeclaration::Static(red.clone())]
+                    declarations: vec![CssDeclaration::Static(red.clone())],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
                 CssRuleBlock {
                     path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
-                    declarations: vec![CssDeclaration::Static(blue.clone())]
+                    declarations: vec![CssDeclaration::Static(blue.clone())],
+                    custom_properties: Vec::new(),
+                    media_queries: Vec::new(),
+                    source: None,
                 },
             ];
             test_css(css_3, expected_rules);
@@ -870,11 +1616,11 @@ fn test_multiple_rules() {
 
     let expected_rules = vec![
         // Rules are sorted by order of appearance in source string
-        CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Type(NodeTypePath::Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+        CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+        CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+        CssRuleBlock { path: CssPath { selectors: vec![Type(NodeTypePath::Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
@@ -908,6 +1654,9 @@ fn test_case_issue_93() {
             declarations: vec![CssDeclaration::Static(CssProperty::TextColor(
                 CssPropertyValue::Exact(StyleTextColor(color)),
             ))],
+            custom_properties: Vec::new(),
+            media_queries: Vec::new(),
+            source: None,
         }
     }
 
@@ -918,4 +1667,483 @@ fn test_case_issue_93() {
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
-}
\ No newline at end of file
+}
+#[test]
+fn test_css_custom_properties() {
+
+    use azul_css::*;
+
+    let parsed_css = new_from_str("
+        .theme {
+            --main-fg: #ff0000;
+        }
+        .label {
+            color: var(--main-fg, black);
+        }
+    ").unwrap();
+
+    let rules = &parsed_css.stylesheets[0].rules;
+    let red = CssProperty::TextColor(CssPropertyValue::Exact(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 })));
+
+    assert_eq!(rules[0].declarations, Vec::new());
+    assert_eq!(rules[0].custom_properties.len(), 1);
+    assert_eq!(rules[0].custom_properties[0].name, "main-fg");
+    assert_eq!(rules[0].custom_properties[0].value, "#ff0000");
+    assert_eq!(rules[0].custom_properties[0].get_value(CssPropertyType::TextColor), Some(&red));
+    assert_eq!(rules[0].custom_properties[0].get_value(CssPropertyType::Width), None);
+
+    assert_eq!(rules[1].declarations, vec![CssDeclaration::Dynamic(DynamicCssProperty {
+        dynamic_id: "main-fg".into(),
+        default_value: CssProperty::TextColor(CssPropertyValue::Exact(StyleTextColor(ColorU { r: 0, g: 0, b: 0, a: 255 }))),
+    })]);
+}
+
+#[test]
+fn test_css_media_queries() {
+
+    use azul_css::*;
+
+    let parsed_css = new_from_str("
+        .sidebar { width: 300px; }
+        @media (max-width: 600px) {
+            .sidebar { width: 100px; }
+            @media (orientation: portrait) {
+                .sidebar { width: 50px; }
+            }
+        }
+        @charset \"utf-8\";
+        .content { width: 500px; }
+    ").unwrap();
+
+    let rules = &parsed_css.stylesheets[0].rules;
+    let max_width = MediaQueryList { queries: vec![MediaQuery {
+        negated: false,
+        media_type: MediaType::All,
+        features: vec![MediaFeature::MaxWidth(PixelValue::px(600.0))],
+    }]};
+    let portrait = MediaQueryList { queries: vec![MediaQuery {
+        negated: false,
+        media_type: MediaType::All,
+        features: vec![MediaFeature::Orientation(MediaOrientation::Portrait)],
+    }]};
+
+    // Rules stay in the order of the source string
+    assert_eq!(rules.len(), 4);
+    assert_eq!(rules[0].media_queries, Vec::new());
+    assert_eq!(rules[1].media_queries, vec![max_width.clone()]);
+    assert_eq!(rules[2].media_queries, vec![max_width.clone(), portrait.clone()]);
+    assert_eq!(rules[3].path, CssPath { selectors: vec![CssPathSelector::Class("content".into())] });
+    assert_eq!(rules[3].media_queries, Vec::new());
+
+    assert!(new_from_str("@media (max-width: 600px) { .a { width: 5px; }").is_err());
+    assert!(new_from_str("@media (max-width: abc) { .a { width: 5px; } }").is_err());
+}
+
+#[test]
+fn test_error_location_line_column() {
+    let css = ".a {\n    width: 5px;\n}";
+    assert_eq!(ErrorLocation { original_pos: 0 }.get_line_column_from_error(css), (1, 1));
+    assert_eq!(ErrorLocation { original_pos: 3 }.get_line_column_from_error(css), (1, 4));
+    assert_eq!(ErrorLocation { original_pos: 9 }.get_line_column_from_error(css), (2, 5));
+    assert_eq!(ErrorLocation { original_pos: 21 }.get_line_column_from_error(css), (3, 1));
+}
+
+#[test]
+fn test_css_parse_lenient() {
+
+    use azul_css::*;
+
+    let css_string = "
+        .a { width: 5px; height: abc; }
+        .b { width: 10px; color: red; foo: bar; }
+        .c:unknown { width: 15px; }
+        @media (max-width: abc) { .d { width: 20px; } }
+        } .e { width: 25px; }
+    ";
+
+    let (css, errors, warnings) = new_from_str_lenient(css_string);
+
+    let width = |px: f32| CssDeclaration::Static(CssProperty::Width(LayoutWidth::px(px).into()));
+    let rules = &css.stylesheets[0].rules;
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[0].path, CssPath { selectors: vec![CssPathSelector::Class("a".into())] });
+    assert_eq!(rules[0].declarations, vec![width(5.0)]);
+    assert_eq!(rules[1].declarations.len(), 2);
+    assert_eq!(rules[2].path, CssPath { selectors: vec![CssPathSelector::Class("e".into())] });
+    assert_eq!(rules[2].declarations, vec![width(25.0)]);
+
+    // "height: abc", ":unknown", "(max-width: abc)" and the unmatched "}"
+    let error_lines = errors.iter().map(|e| e.location.0.get_line_column_from_error(css_string).0).collect::<Vec<_>>();
+    assert_eq!(error_lines, vec![2, 4, 5, 6]);
+    match errors[0].error {
+        CssParseErrorInner::DynamicCssParseError(_) => { },
+        ref other => panic!("expected invalid value error, got {:?}", other),
+    }
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].warning, CssParseWarnMsgInner::UnsupportedKeyValuePair { key: "foo", value: "bar" });
+    assert_eq!(warnings[0].location.0.get_line_column_from_error(css_string).0, 3);
+
+    // The strict parser stops at the first error
+    assert_eq!(new_from_str(css_string).err(), Some(errors[0].clone()));
+}
+
+#[test]
+fn test_css_to_string_round_trip() {
+
+    let css_string = r#"
+        @font-face { font-family: Open Sans; src: local('Open Sans'), url(fonts/OpenSans.ttf) format("truetype"); }
+        @font-face { font-family: Open Sans; font-style: italic; src: url(fonts/OpenSans-BoldItalic.ttf); font-weight: bold; }
+        * { box-sizing: border-box; cursor: default; }
+        div > p.a + .b ~ #c:nth-child(2n+1):not(.d) {
+            display: flex;
+            float: right;
+            position: relative;
+            z-index: -2;
+            top: 5px; right: 2.5em; left: 10%; bottom: 3pt;
+            width: 100.5px; height: auto; min-width: 0px; max-height: none;
+            min-height: calc(100vh - 2rem); max-width: 50vmin;
+            flex-wrap: nowrap; flex-direction: column-reverse;
+            flex-grow: 1.5; flex-shrink: 0;
+            justify-content: space-evenly; align-items: center; align-content: space-between;
+            overflow-x: scroll; overflow-y: hidden;
+            padding: 1px 2px 3px 4px;
+            margin: 0.001px 2px;
+        }
+        .text:hover {
+            color: rgba(10, 20, 30, 0.5);
+            font-size: 14pt;
+            font-family: "Helvetica Neue", monospace;
+            font-weight: 600;
+            font-style: oblique;
+            text-decoration: underline line-through rgb(255, 0, 0) 0.1em;
+            text-shadow: 1px 1px 2px rgba(0, 0, 0, 0.5), 0px 0px 5px;
+            text-align: center;
+            letter-spacing: -1px;
+            line-height: 120%;
+            word-spacing: 2px;
+            tab-width: 4;
+        }
+        .background:focus-within {
+            background: linear-gradient(to top right, red, #00ff0080 40%, blue);
+            background-position: center 10px;
+            background-size: contain;
+            background-repeat: repeat-x;
+        }
+        .layers {
+            background: image("pattern"), conic-gradient(from 45deg at 25% bottom, red, blue 50%, red), rgba(0, 0, 0, 0.5);
+            background-position: left top, center;
+            background-size: 16px 16px, cover;
+            background-repeat: repeat, no-repeat;
+        }
+        .conic {
+            background: repeating-conic-gradient(black, white 12.5%, black 25%);
+        }
+        .skinned {
+            border-image: image("button") 10 20% fill / 2 auto 5px / 1px round stretch;
+        }
+        .dashboard {
+            display: grid;
+            grid-template-columns: 200px repeat(2, minmax(100px, 1fr));
+            grid-template-rows: auto 1fr;
+            grid-auto-flow: column;
+            gap: 10px 5%;
+        }
+        .dashboard .header {
+            grid-column: 1 / -1;
+            grid-area: 2 / 1 / span 2;
+        }
+        .skinned:active {
+            border-image-source: image("button-pressed");
+            border-image-slice: 10;
+            border-image-width: 8px;
+            border-image-outset: 0 1;
+            border-image-repeat: space;
+        }
+        .gradients:first > .image:last {
+            background: repeating-radial-gradient(circle, red, blue 25%);
+        }
+        .image:only-child:empty { background: image("my-image"); }
+        .angle:nth-last-child(odd) { background: linear-gradient(45deg, red, blue); }
+        .border {
+            border: 2px dashed #123456;
+            border-radius: 5px;
+            border-top-style: none;
+        }
+        .border.rounded {
+            border-radius: 10px 20% / 5px;
+            border-top-left-radius: 50% 10px;
+            box-shadow: 1px 2px 3px 4px #00000040 inset, inset red -2px 0px;
+            box-shadow-top: 0px 0px 5px black;
+        }
+        @keyframes pulse { from { background-color: white; } 50% { background-color: red; width: 10px; } to { background-color: white; } }
+        .animated {
+            transition: background-color 200ms ease-in, padding 1s cubic-bezier(0.1, 0.7, 1.0, 0.1) 0.5s;
+            animation: pulse 2s steps(4) infinite alternate-reverse both, pulse 1s;
+        }
+        .badge {
+            transform: translate(-50%, 10px) rotate(-0.125turn) scaleY(1.5) skewX(10deg) matrix(1, 0, 0, 1, 5, 5);
+            transform-origin: left 25%;
+            opacity: 50%;
+            filter: blur(2px) grayscale(1) drop-shadow(1px 2px 3px rgba(0, 0, 0, 0.5));
+            mix-blend-mode: multiply;
+        }
+        .badge:hover { transform: none; filter: none; text-decoration: none; }
+        .dynamic {
+            --main-color: #abcdef;
+            width: var(--dynamic-width, 500px);
+            height: var(--dynamic-height);
+            color: var(--main-color, red);
+        }
+        @media screen and (min-width: 600px), not print and (orientation: landscape) {
+            .media { width: 50%; }
+            @media (max-resolution: 2dppx) and (prefers-color-scheme: dark) {
+                .nested-media { background-color: red; }
+            }
+        }
+    "#;
+
+    let css = new_from_str(css_string).unwrap();
+    let printed = css.to_string();
+    let reparsed = new_from_str(&printed).unwrap_or_else(|e| panic!("{}\n\n{}", e, printed));
+    assert_eq!(css, reparsed, "\n{}", printed);
+
+    // Printing the re-parsed stylesheet results in the exact same string
+    assert_eq!(printed, reparsed.to_string());
+}
+
+#[test]
+fn test_css_font_face() {
+
+    use azul_css::*;
+
+    let css = new_from_str(r#"
+        @font-face {
+            font-family: "My Font";
+            font-weight: bold;
+            src: local("My Font Bold"), url('fonts/my-font.woff') format("woff");
+        }
+        @media print {
+            @font-face { font-family: Print Font; src: local(Times New Roman); }
+        }
+        .text { font-family: "My Font"; }
+    "#).unwrap();
+
+    assert_eq!(css.stylesheets[0].font_faces, vec![
+        FontFace {
+            font_family: "My Font".into(),
+            sources: vec![
+                FontFaceSource::Local("My Font Bold".into()),
+                FontFaceSource::Url("fonts/my-font.woff".into()),
+            ],
+            font_weight: StyleFontWeight::BOLD,
+            font_style: StyleFontStyle::Normal,
+        },
+        FontFace {
+            font_family: "Print Font".into(),
+            sources: vec![FontFaceSource::Local("Times New Roman".into())],
+            font_weight: StyleFontWeight::NORMAL,
+            font_style: StyleFontStyle::Normal,
+        },
+    ]);
+    assert_eq!(css.stylesheets[0].rules.len(), 1);
+
+    let missing_family = "@font-face { src: url(a.ttf); } .a { width: 5px; }";
+    let (css, errors, _) = new_from_str_lenient(missing_family);
+    assert_eq!(errors[0].error, CssParseErrorInner::FontFaceParseError(CssFontFaceParseError::MissingFontFamily));
+    assert_eq!(errors[0].get_error_string(), "@font-face { src: url(a.ttf); }");
+    assert_eq!(css.stylesheets[0].font_faces, Vec::new());
+    assert_eq!(css.stylesheets[0].rules.len(), 1);
+
+    assert_eq!(
+        new_from_str("@font-face { font-family: A; src: a.ttf; }").err().map(|e| e.error),
+        Some(CssParseErrorInner::FontFaceParseError(CssFontFaceParseError::InvalidSrc("a.ttf")))
+    );
+    assert_eq!(
+        new_from_str("@font-face { font-family: A; src: local(A); font-weight: bolder; }").err().map(|e| e.error),
+        Some(CssParseErrorInner::FontFaceParseError(CssFontFaceParseError::InvalidDescriptorValue("bolder")))
+    );
+}
+
+#[test]
+fn test_css_keyframes_and_animations() {
+
+    use azul_css::*;
+    use crate::css_parser::CssAnimationParseError;
+    use std::time::Duration;
+
+    let css = new_from_str(r#"
+        @keyframes "fade" {
+            to { color: #ffffff; }
+            from { color: #000000; margin: 5px; }
+        }
+        .button { transition: color 250ms ease-out, margin 1s; }
+        .button:hover { animation: fade 1s ease-in 200ms 3 forwards; }
+    "#).unwrap();
+
+    let keyframes = css.get_keyframes("fade").unwrap();
+    assert_eq!(keyframes.keyframes.len(), 2);
+    assert_eq!(keyframes.keyframes[0].offset, PercentageValue::new(0.0));
+    assert_eq!(keyframes.keyframes[0].declarations.len(), 5);
+    assert_eq!(keyframes.keyframes[1].declarations, vec![StyleTextColor(ColorU { r: 255, g: 255, b: 255, a: 255 }).into()]);
+    assert_eq!(css.get_keyframes("pulse"), None);
+
+    let transitions = match &css.stylesheets[0].rules[0].declarations[0] {
+        CssDeclaration::Static(CssProperty::Transition(CssPropertyValue::Exact(t))) => t.clone(),
+        other => panic!("expected transition, got {:?}", other),
+    };
+    // "margin" is expanded to margin-top, margin-bottom, margin-left and margin-right
+    assert_eq!(transitions.0.len(), 5);
+    assert_eq!(transitions.get(CssPropertyType::TextColor).map(|t| t.timing_function), Some(AnimationTimingFunction::EaseOut));
+    assert_eq!(transitions.get(CssPropertyType::MarginLeft).map(|t| t.duration), Some(Duration::from_secs(1)));
+    assert_eq!(transitions.get(CssPropertyType::Width), None);
+
+    assert_eq!(css.stylesheets[0].rules[1].declarations, vec![CssDeclaration::Static(StyleAnimations(vec![StyleAnimation {
+        name: "fade".into(),
+        duration: Duration::from_secs(1),
+        timing_function: AnimationTimingFunction::EaseIn,
+        delay: Duration::from_millis(200),
+        iteration_count: AnimationIterationCount::Count(FloatValue::new(3.0)),
+        direction: AnimationDirection::Normal,
+        fill_mode: AnimationFillMode::Forwards,
+    }]).into())]);
+
+    let (css, errors, _) = new_from_str_lenient("@keyframes grow { half { width: 50%; } } .a { transition: colour 1s; width: 5px; }");
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].error, CssParseErrorInner::KeyframesParseError(CssKeyframesParseError::InvalidSelector("half")));
+    assert_eq!(css.stylesheets[0].keyframes, Vec::new());
+    assert_eq!(css.stylesheets[0].rules[0].declarations.len(), 1);
+
+    assert_eq!(
+        new_from_str(".a { animation: 1s ease; }").err().map(|e| e.error),
+        Some(CssParseErrorInner::DynamicCssParseError(CssParsingError::AnimationParseError(CssAnimationParseError::MissingAnimationName("1s ease")).into()))
+    );
+}
+
+#[test]
+fn test_parse_import_prelude() {
+
+    use azul_css::*;
+
+    assert_eq!(parse_import_prelude("\"widgets.css\""), Ok(("widgets.css", None)));
+    assert_eq!(parse_import_prelude("'widgets.css'"), Ok(("widgets.css", None)));
+    assert_eq!(parse_import_prelude("url(\"widgets.css\")"), Ok(("widgets.css", None)));
+    assert_eq!(parse_import_prelude("url(../widgets.css)"), Ok(("../widgets.css", None)));
+    assert_eq!(parse_import_prelude("\"print.css\" print"), Ok(("print.css", Some(MediaQueryList { queries: vec![MediaQuery {
+        negated: false,
+        media_type: MediaType::Print,
+        features: Vec::new(),
+    }]}))));
+
+    assert_eq!(parse_import_prelude("widgets.css"), Err(CssParseErrorInner::MalformedImport("widgets.css")));
+    assert_eq!(parse_import_prelude("\"widgets.css"), Err(CssParseErrorInner::MalformedImport("\"widgets.css")));
+    assert_eq!(parse_import_prelude("url()"), Err(CssParseErrorInner::MalformedImport("url()")));
+}
+
+#[test]
+fn test_css_import_from_file() {
+
+    use std::fs;
+    use azul_css::*;
+
+    let dir = ::std::env::temp_dir().join(format!("azul_css_import_test_{}", ::std::process::id()));
+    fs::create_dir_all(dir.join("widgets")).unwrap();
+
+    fs::write(dir.join("main.css"), "
+        @import \"widgets/button.css\";
+        @import url(widgets/print.css) print;
+        .main { width: 100px; }
+    ").unwrap();
+    // Imports are resolved relative to the importing file, cyclic imports are only loaded once
+    fs::write(dir.join("widgets").join("button.css"), "
+        @import '../main.css';
+        @font-face { font-family: Icons; src: url(fonts/icons.ttf); }
+        .button { width: 10px; }
+    ").unwrap();
+    fs::write(dir.join("widgets").join("print.css"), ".print { width: 20px; }").unwrap();
+
+    let css = new_from_file(dir.join("main.css"));
+    let missing = new_from_file(dir.join("missing.css"));
+    let widgets_dir = fs::canonicalize(dir.join("widgets")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let css = css.unwrap();
+    assert_eq!(css.stylesheets.len(), 3);
+    assert_eq!(css.stylesheets[0].rules[0].path, CssPath { selectors: vec![CssPathSelector::Class("button".into())] });
+    assert_eq!(css.stylesheets[0].rules[0].media_queries, Vec::new());
+    assert_eq!(css.stylesheets[1].rules[0].path, CssPath { selectors: vec![CssPathSelector::Class("print".into())] });
+    assert_eq!(css.stylesheets[1].rules[0].media_queries, vec![MediaQueryList { queries: vec![MediaQuery {
+        negated: false,
+        media_type: MediaType::Print,
+        features: Vec::new(),
+    }]}]);
+    assert_eq!(css.stylesheets[2].rules[0].path, CssPath { selectors: vec![CssPathSelector::Class("main".into())] });
+
+    // Source locations refer to the file that the rule was loaded from
+    assert_eq!(
+        css.stylesheets[0].rules[0].get_declaration_source(CssPropertyType::Width).map(|source| source.to_string()),
+        Some(format!("{}:4:19", widgets_dir.join("button.css").display()))
+    );
+
+    // Font files are relative to the stylesheet that declares the @font-face
+    assert_eq!(css.font_faces().collect::<Vec<_>>(), vec![&FontFace {
+        font_family: "Icons".into(),
+        sources: vec![FontFaceSource::Url(widgets_dir.join("fonts/icons.ttf").to_string_lossy().into_owned())],
+        font_weight: StyleFontWeight::NORMAL,
+        font_style: StyleFontStyle::Normal,
+    }]);
+
+    match missing {
+        Err(CssImportError::Io(_, _)) => { },
+        other => panic!("expected IO error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_css_source_locations() {
+
+    use azul_css::*;
+
+    let css = new_from_str(".a,\n  .b:hover {\n    width: 5px; /* comment */ border: 1px solid red;\n    --fg: blue;\n}\n\
+        @media print {\n    .c { height: 5px; }\n}").unwrap();
+    let rules = &css.stylesheets[0].rules;
+    let location = |line, column| CssSourceLocation { file: None, line, column };
+
+    assert_eq!(rules[0].source.as_ref().map(|source| &source.block), Some(&location(1, 1)));
+    assert_eq!(rules[1].source.as_ref().map(|source| &source.block), Some(&location(2, 3)));
+    assert_eq!(rules[1].get_declaration_source(CssPropertyType::Width), Some(&location(3, 5)));
+    assert_eq!(rules[1].get_declaration_source(CssPropertyType::BorderLeftStyle), Some(&location(3, 31)));
+    assert_eq!(rules[1].source.as_ref().and_then(|source| source.custom_properties.get("fg")), Some(&location(4, 5)));
+    assert_eq!(rules[2].source.as_ref().map(|source| &source.block), Some(&location(7, 5)));
+    assert_eq!(rules[2].get_declaration_source(CssPropertyType::Height).map(|source| source.to_string()), Some("7:10".to_string()));
+    assert_eq!(rules[2].get_declaration_source(CssPropertyType::Width), None);
+}
+
+#[test]
+fn test_css_optimize() {
+
+    let mut css = new_from_str(r#"
+        .a { width: 10px; color: red; width: 20px; }
+        .b { color: blue; }
+        .a { height: 5px; }
+        .a { color: green; }
+        .a:not(.a) { color: black; }
+        p:first:nth-child(2) { color: black; }
+        .c:nth-child(0) > .d { color: black; }
+        .d:hover { }
+        @media (min-width: 600px) { .a { height: 10px; } }
+    "#).unwrap();
+    css.sort_by_specificity();
+    css.optimize();
+
+    // The second ".a" can't be merged into the first one, since ".b" could override its color
+    let mut expected = new_from_str(r#"
+        .a { width: 20px; height: 5px; }
+        .b { color: blue; }
+        .a { color: green; }
+        @media (min-width: 600px) { .a { height: 10px; } }
+    "#).unwrap();
+    expected.sort_by_specificity();
+
+    assert_eq!(css, expected, "\n{}", css);
+}
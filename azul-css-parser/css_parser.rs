@@ -1,23 +1,28 @@
 //! Contains utilities to convert strings (CSS strings) to servo types
 
 use std::num::{ParseIntError, ParseFloatError};
+use std::time::Duration;
 use azul_css::{
     CssPropertyType, CssProperty, CombinedCssPropertyType, CssPropertyValue,
-    Overflow, Shape, PixelValue, PixelValueNoPercent, PercentageValue, FloatValue, ColorU,
+    Overflow, Shape, PixelValue, PixelSize, PixelValueNoPercent, PercentageValue, FloatValue, ColorU,
     GradientStopPre, RadialGradient, DirectionCorner, Direction, CssImageId,
-    LinearGradient, BoxShadowPreDisplayItem, StyleBorderSide, BorderStyle,
-    SizeMetric, BoxShadowClipMode, ExtendMode, FontId, GradientType,
+    LinearGradient, BoxShadowPreDisplayItem, StyleBoxShadowList, StyleTextShadow,
+    TextShadowPreDisplayItem, StyleBorderSide, BorderStyle,
+    SizeMetric, MAX_CALC_TERMS, BoxShadowClipMode, ExtendMode, FontId, GradientType,
     BackgroundPositionHorizontal, BackgroundPositionVertical,
 
     StyleTextColor, StyleFontSize, StyleFontFamily, StyleTextAlignmentHorz,
     StyleLetterSpacing, StyleLineHeight, StyleWordSpacing, StyleTabWidth,
     StyleCursor, StyleBackgroundContent, StyleBackgroundPosition, StyleBackgroundSize,
-    StyleBackgroundRepeat, StyleBorderTopLeftRadius, StyleBorderTopRightRadius,
+    StyleBackgroundRepeat, StyleBackgroundContentList, StyleBackgroundPositionList,
+    StyleBackgroundSizeList, StyleBackgroundRepeatList, ConicGradient, StyleBorderTopLeftRadius, StyleBorderTopRightRadius,
     StyleBorderBottomLeftRadius, StyleBorderBottomRightRadius, StyleBorderTopColor,
     StyleBorderRightColor, StyleBorderLeftColor, StyleBorderBottomColor,
     StyleBorderTopStyle, StyleBorderRightStyle, StyleBorderLeftStyle,
     StyleBorderBottomStyle, StyleBorderTopWidth, StyleBorderRightWidth,
-    StyleBorderLeftWidth, StyleBorderBottomWidth,
+    StyleBorderLeftWidth, StyleBorderBottomWidth, StyleBorderImageSource, StyleBorderImageSlice,
+    StyleBorderImageWidth, StyleBorderImageOutset, StyleBorderImageRepeat, BorderImageLength,
+    BorderImageRepeat, NinePatchBorder,
 
     LayoutDisplay, LayoutFloat, LayoutWidth, LayoutHeight, LayoutBoxSizing,
     LayoutMinWidth, LayoutMinHeight, LayoutMaxWidth, LayoutMaxHeight,
@@ -25,7 +30,17 @@ use azul_css::{
     LayoutDirection, LayoutFlexGrow, LayoutFlexShrink, LayoutJustifyContent,
     LayoutAlignItems, LayoutAlignContent, LayoutPaddingRight, LayoutPaddingBottom,
     LayoutMarginTop, LayoutMarginLeft, LayoutMarginRight, LayoutMarginBottom,
-    LayoutPaddingTop, LayoutPaddingLeft,
+    LayoutPaddingTop, LayoutPaddingLeft, LayoutGridTemplateColumns, LayoutGridTemplateRows,
+    GridTrackSize, GridTrackBreadth, GridLine, LayoutGridColumnStart, LayoutGridColumnEnd,
+    LayoutGridRowStart, LayoutGridRowEnd, LayoutGridAutoFlow, LayoutRowGap, LayoutColumnGap,
+
+    MediaQueryList, MediaQuery, MediaType, MediaFeature, MediaOrientation, ColorScheme,
+    FontFace, FontFaceSource, CssKeyframes, CssKeyframe, CssKeyMap, get_css_key_map,
+    StyleTransitions, StyleTransition, StyleAnimations, StyleAnimation, AnimationTimingFunction,
+    StepPosition, AnimationIterationCount, AnimationDirection, AnimationFillMode,
+    StyleTransform, StyleTransformFunction, StyleTransformOrigin,
+    StyleOpacity, StyleFilter, StyleFilterFunction, StyleMixBlendMode,
+    StyleFontWeight, StyleFontStyle, StyleTextDecoration, LayoutZIndex,
 };
 
 /// A parser that can accept a list of items and mappings
@@ -126,6 +141,10 @@ pub fn parse_css_property<'a>(key: CssPropertyType, value: &'a str) -> Result<Cs
             TextColor                   => parse_style_text_color(value)?.into(),
             FontSize                    => parse_style_font_size(value)?.into(),
             FontFamily                  => parse_style_font_family(value)?.into(),
+            FontWeight                  => parse_style_font_weight(value)?.into(),
+            FontStyle                   => parse_style_font_style(value)?.into(),
+            TextDecoration              => parse_style_text_decoration(value)?.into(),
+            TextShadow                  => parse_style_text_shadow(value)?.into(),
             TextAlign                   => parse_layout_text_align(value)?.into(),
             LetterSpacing               => parse_style_letter_spacing(value)?.into(),
             LineHeight                  => parse_style_line_height(value)?.into(),
@@ -147,6 +166,7 @@ pub fn parse_css_property<'a>(key: CssPropertyType, value: &'a str) -> Result<Cs
             Right                       => parse_layout_right(value)?.into(),
             Left                        => parse_layout_left(value)?.into(),
             Bottom                      => parse_layout_bottom(value)?.into(),
+            ZIndex                      => parse_layout_z_index(value)?.into(),
             FlexWrap                    => parse_layout_wrap(value)?.into(),
             FlexDirection               => parse_layout_direction(value)?.into(),
             FlexGrow                    => parse_layout_flex_grow(value)?.into(),
@@ -155,12 +175,22 @@ pub fn parse_css_property<'a>(key: CssPropertyType, value: &'a str) -> Result<Cs
             AlignItems                  => parse_layout_align_items(value)?.into(),
             AlignContent                => parse_layout_align_content(value)?.into(),
 
-            Background                  => parse_style_background_content(value)?.into(),
-            BackgroundImage             => StyleBackgroundContent::Image(parse_image(value)?).into(),
-            BackgroundColor             => StyleBackgroundContent::Color(parse_css_color(value)?).into(),
-            BackgroundPosition          => parse_style_background_position(value)?.into(),
-            BackgroundSize              => parse_style_background_size(value)?.into(),
-            BackgroundRepeat            => parse_style_background_repeat(value)?.into(),
+            GridTemplateColumns         => parse_layout_grid_template_columns(value)?.into(),
+            GridTemplateRows            => parse_layout_grid_template_rows(value)?.into(),
+            GridColumnStart             => parse_layout_grid_column_start(value)?.into(),
+            GridColumnEnd               => parse_layout_grid_column_end(value)?.into(),
+            GridRowStart                => parse_layout_grid_row_start(value)?.into(),
+            GridRowEnd                  => parse_layout_grid_row_end(value)?.into(),
+            GridAutoFlow                => parse_layout_grid_auto_flow(value)?.into(),
+            RowGap                      => parse_layout_row_gap(value)?.into(),
+            ColumnGap                   => parse_layout_column_gap(value)?.into(),
+
+            Background                  => parse_style_background_content_list(value)?.into(),
+            BackgroundImage             => parse_style_background_image_list(value)?.into(),
+            BackgroundColor             => StyleBackgroundContentList::from(StyleBackgroundContent::Color(parse_css_color(value)?)).into(),
+            BackgroundPosition          => parse_style_background_position_list(value)?.into(),
+            BackgroundSize              => parse_style_background_size_list(value)?.into(),
+            BackgroundRepeat            => parse_style_background_repeat_list(value)?.into(),
 
             OverflowX                   => CssProperty::OverflowX(CssPropertyValue::Exact(parse_layout_overflow(value)?)).into(),
             OverflowY                   => CssProperty::OverflowY(CssPropertyValue::Exact(parse_layout_overflow(value)?)).into(),
@@ -195,10 +225,26 @@ pub fn parse_css_property<'a>(key: CssPropertyType, value: &'a str) -> Result<Cs
             BorderLeftWidth             => parse_style_border_left_width(value)?.into(),
             BorderBottomWidth           => parse_style_border_bottom_width(value)?.into(),
 
-            BoxShadowLeft               => CssProperty::BoxShadowLeft(CssPropertyValue::Exact(parse_style_box_shadow(value)?)).into(),
-            BoxShadowRight              => CssProperty::BoxShadowRight(CssPropertyValue::Exact(parse_style_box_shadow(value)?)).into(),
-            BoxShadowTop                => CssProperty::BoxShadowTop(CssPropertyValue::Exact(parse_style_box_shadow(value)?)).into(),
-            BoxShadowBottom             => CssProperty::BoxShadowBottom(CssPropertyValue::Exact(parse_style_box_shadow(value)?)).into(),
+            BorderImageSource           => parse_style_border_image_source(value)?.into(),
+            BorderImageSlice            => parse_style_border_image_slice(value)?.into(),
+            BorderImageWidth            => parse_style_border_image_width(value)?.into(),
+            BorderImageOutset           => parse_style_border_image_outset(value)?.into(),
+            BorderImageRepeat           => parse_style_border_image_repeat(value)?.into(),
+
+            BoxShadowLeft               => CssProperty::BoxShadowLeft(CssPropertyValue::Exact(parse_style_box_shadow_list(value)?)).into(),
+            BoxShadowRight              => CssProperty::BoxShadowRight(CssPropertyValue::Exact(parse_style_box_shadow_list(value)?)).into(),
+            BoxShadowTop                => CssProperty::BoxShadowTop(CssPropertyValue::Exact(parse_style_box_shadow_list(value)?)).into(),
+            BoxShadowBottom             => CssProperty::BoxShadowBottom(CssPropertyValue::Exact(parse_style_box_shadow_list(value)?)).into(),
+
+            Transition                  => parse_style_transitions(value)?.into(),
+            Animation                   => parse_style_animations(value)?.into(),
+
+            Transform                   => parse_style_transform(value)?.into(),
+            TransformOrigin             => parse_style_transform_origin(value)?.into(),
+
+            Opacity                     => parse_style_opacity(value)?.into(),
+            Filter                      => parse_style_filter(value)?.into(),
+            MixBlendMode                => parse_style_mix_blend_mode(value)?.into(),
         }
     })
 }
@@ -227,90 +273,7 @@ pub fn parse_combined_css_property<'a>(key: CombinedCssPropertyType, value: &'a
 {
     use self::CombinedCssPropertyType::*;
 
-    let keys = match key {
-        BorderRadius => {
-            vec![
-                CssPropertyType::BorderTopLeftRadius,
-                CssPropertyType::BorderTopRightRadius,
-                CssPropertyType::BorderBottomLeftRadius,
-                CssPropertyType::BorderBottomRightRadius,
-            ]
-        },
-        Overflow => {
-            vec![
-                CssPropertyType::OverflowX,
-                CssPropertyType::OverflowY,
-            ]
-        },
-        Padding => {
-            vec![
-                CssPropertyType::PaddingTop,
-                CssPropertyType::PaddingBottom,
-                CssPropertyType::PaddingLeft,
-                CssPropertyType::PaddingRight,
-            ]
-        },
-        Margin => {
-            vec![
-                CssPropertyType::MarginTop,
-                CssPropertyType::MarginBottom,
-                CssPropertyType::MarginLeft,
-                CssPropertyType::MarginRight,
-            ]
-        },
-        Border => {
-            vec![
-                CssPropertyType::BorderTopColor,
-                CssPropertyType::BorderRightColor,
-                CssPropertyType::BorderLeftColor,
-                CssPropertyType::BorderBottomColor,
-                CssPropertyType::BorderTopStyle,
-                CssPropertyType::BorderRightStyle,
-                CssPropertyType::BorderLeftStyle,
-                CssPropertyType::BorderBottomStyle,
-                CssPropertyType::BorderTopWidth,
-                CssPropertyType::BorderRightWidth,
-                CssPropertyType::BorderLeftWidth,
-                CssPropertyType::BorderBottomWidth,
-            ]
-        },
-        BorderLeft => {
-            vec![
-                CssPropertyType::BorderLeftColor,
-                CssPropertyType::BorderLeftStyle,
-                CssPropertyType::BorderLeftWidth,
-            ]
-        },
-        BorderRight => {
-            vec![
-                CssPropertyType::BorderRightColor,
-                CssPropertyType::BorderRightStyle,
-                CssPropertyType::BorderRightWidth,
-            ]
-        },
-        BorderTop => {
-            vec![
-                CssPropertyType::BorderTopColor,
-                CssPropertyType::BorderTopStyle,
-                CssPropertyType::BorderTopWidth,
-            ]
-        },
-        BorderBottom => {
-            vec![
-                CssPropertyType::BorderBottomColor,
-                CssPropertyType::BorderBottomStyle,
-                CssPropertyType::BorderBottomWidth,
-            ]
-        },
-        BoxShadow => {
-            vec![
-                CssPropertyType::BoxShadowLeft,
-                CssPropertyType::BoxShadowRight,
-                CssPropertyType::BoxShadowTop,
-                CssPropertyType::BoxShadowBottom,
-            ]
-        },
-    };
+    let keys = get_combined_css_property_keys(key);
 
     match value {
         "auto" => return Ok(keys.into_iter().map(|ty| CssProperty::auto(ty)).collect()),
@@ -406,15 +369,182 @@ pub fn parse_combined_css_property<'a>(key: CombinedCssPropertyType, value: &'a
                CssProperty::BorderBottomWidth(StyleBorderBottomWidth(border.border_width).into()),
             ])
         },
+        BorderImage => {
+            let border_image = parse_style_border_image(value)?;
+            Ok(vec![
+               CssProperty::BorderImageSource(StyleBorderImageSource(border_image.source).into()),
+               CssProperty::BorderImageSlice(border_image.slice.into()),
+               CssProperty::BorderImageWidth(border_image.width.into()),
+               CssProperty::BorderImageOutset(border_image.outset.into()),
+               CssProperty::BorderImageRepeat(border_image.repeat.into()),
+            ])
+        },
         BoxShadow => {
-            let box_shadow = parse_style_box_shadow(value)?;
+            let box_shadow = parse_style_box_shadow_list(value)?;
             Ok(vec![
-               CssProperty::BoxShadowLeft(CssPropertyValue::Exact(box_shadow)),
-               CssProperty::BoxShadowRight(CssPropertyValue::Exact(box_shadow)),
-               CssProperty::BoxShadowTop(CssPropertyValue::Exact(box_shadow)),
+               CssProperty::BoxShadowLeft(CssPropertyValue::Exact(box_shadow.clone())),
+               CssProperty::BoxShadowRight(CssPropertyValue::Exact(box_shadow.clone())),
+               CssProperty::BoxShadowTop(CssPropertyValue::Exact(box_shadow.clone())),
                CssProperty::BoxShadowBottom(CssPropertyValue::Exact(box_shadow)),
             ])
         },
+        GridColumn => {
+            let column = parse_layout_grid_placement(value)?;
+            Ok(vec![
+               CssProperty::GridColumnStart(grid_line_to_property(column.start, LayoutGridColumnStart)),
+               CssProperty::GridColumnEnd(grid_line_to_property(column.end, LayoutGridColumnEnd)),
+            ])
+        },
+        GridRow => {
+            let row = parse_layout_grid_placement(value)?;
+            Ok(vec![
+               CssProperty::GridRowStart(grid_line_to_property(row.start, LayoutGridRowStart)),
+               CssProperty::GridRowEnd(grid_line_to_property(row.end, LayoutGridRowEnd)),
+            ])
+        },
+        GridArea => {
+            let area = parse_layout_grid_area(value)?;
+            Ok(vec![
+               CssProperty::GridRowStart(grid_line_to_property(area.row.start, LayoutGridRowStart)),
+               CssProperty::GridColumnStart(grid_line_to_property(area.column.start, LayoutGridColumnStart)),
+               CssProperty::GridRowEnd(grid_line_to_property(area.row.end, LayoutGridRowEnd)),
+               CssProperty::GridColumnEnd(grid_line_to_property(area.column.end, LayoutGridColumnEnd)),
+            ])
+        },
+        Gap => {
+            let gap = parse_layout_gap(value)?;
+            Ok(vec![
+               CssProperty::RowGap(LayoutRowGap(gap.row).into()),
+               CssProperty::ColumnGap(LayoutColumnGap(gap.column).into()),
+            ])
+        },
+    }
+}
+
+/// Returns the properties that a combined CSS property is a shorthand for,
+/// i.e. `padding` => `padding-top`, `padding-bottom`, `padding-left`, `padding-right`
+fn get_combined_css_property_keys(key: CombinedCssPropertyType) -> Vec<CssPropertyType> {
+
+    use self::CombinedCssPropertyType::*;
+
+    match key {
+        BorderRadius => {
+            vec![
+                CssPropertyType::BorderTopLeftRadius,
+                CssPropertyType::BorderTopRightRadius,
+                CssPropertyType::BorderBottomLeftRadius,
+                CssPropertyType::BorderBottomRightRadius,
+            ]
+        },
+        Overflow => {
+            vec![
+                CssPropertyType::OverflowX,
+                CssPropertyType::OverflowY,
+            ]
+        },
+        Padding => {
+            vec![
+                CssPropertyType::PaddingTop,
+                CssPropertyType::PaddingBottom,
+                CssPropertyType::PaddingLeft,
+                CssPropertyType::PaddingRight,
+            ]
+        },
+        Margin => {
+            vec![
+                CssPropertyType::MarginTop,
+                CssPropertyType::MarginBottom,
+                CssPropertyType::MarginLeft,
+                CssPropertyType::MarginRight,
+            ]
+        },
+        Border => {
+            vec![
+                CssPropertyType::BorderTopColor,
+                CssPropertyType::BorderRightColor,
+                CssPropertyType::BorderLeftColor,
+                CssPropertyType::BorderBottomColor,
+                CssPropertyType::BorderTopStyle,
+                CssPropertyType::BorderRightStyle,
+                CssPropertyType::BorderLeftStyle,
+                CssPropertyType::BorderBottomStyle,
+                CssPropertyType::BorderTopWidth,
+                CssPropertyType::BorderRightWidth,
+                CssPropertyType::BorderLeftWidth,
+                CssPropertyType::BorderBottomWidth,
+            ]
+        },
+        BorderLeft => {
+            vec![
+                CssPropertyType::BorderLeftColor,
+                CssPropertyType::BorderLeftStyle,
+                CssPropertyType::BorderLeftWidth,
+            ]
+        },
+        BorderRight => {
+            vec![
+                CssPropertyType::BorderRightColor,
+                CssPropertyType::BorderRightStyle,
+                CssPropertyType::BorderRightWidth,
+            ]
+        },
+        BorderTop => {
+            vec![
+                CssPropertyType::BorderTopColor,
+                CssPropertyType::BorderTopStyle,
+                CssPropertyType::BorderTopWidth,
+            ]
+        },
+        BorderBottom => {
+            vec![
+                CssPropertyType::BorderBottomColor,
+                CssPropertyType::BorderBottomStyle,
+                CssPropertyType::BorderBottomWidth,
+            ]
+        },
+        BorderImage => {
+            vec![
+                CssPropertyType::BorderImageSource,
+                CssPropertyType::BorderImageSlice,
+                CssPropertyType::BorderImageWidth,
+                CssPropertyType::BorderImageOutset,
+                CssPropertyType::BorderImageRepeat,
+            ]
+        },
+        BoxShadow => {
+            vec![
+                CssPropertyType::BoxShadowLeft,
+                CssPropertyType::BoxShadowRight,
+                CssPropertyType::BoxShadowTop,
+                CssPropertyType::BoxShadowBottom,
+            ]
+        },
+        GridColumn => {
+            vec![
+                CssPropertyType::GridColumnStart,
+                CssPropertyType::GridColumnEnd,
+            ]
+        },
+        GridRow => {
+            vec![
+                CssPropertyType::GridRowStart,
+                CssPropertyType::GridRowEnd,
+            ]
+        },
+        GridArea => {
+            vec![
+                CssPropertyType::GridRowStart,
+                CssPropertyType::GridColumnStart,
+                CssPropertyType::GridRowEnd,
+                CssPropertyType::GridColumnEnd,
+            ]
+        },
+        Gap => {
+            vec![
+                CssPropertyType::RowGap,
+                CssPropertyType::ColumnGap,
+            ]
+        },
     }
 }
 
@@ -438,6 +568,12 @@ pub enum CssParsingError<'a> {
     FlexShrinkParseError(FlexShrinkParseError<'a>),
     FlexGrowParseError(FlexGrowParseError<'a>),
     BackgroundPositionParseError(CssBackgroundPositionParseError<'a>),
+    AnimationParseError(CssAnimationParseError<'a>),
+    TransformParseError(CssStyleTransformParseError<'a>),
+    FilterParseError(CssStyleFilterParseError<'a>),
+    TextDecorationParseError(CssStyleTextDecorationParseError<'a>),
+    BorderImageParseError(CssBorderImageParseError<'a>),
+    GridParseError(CssGridParseError<'a>),
 }
 
 impl_debug_as_display!(CssParsingError<'a>);
@@ -457,6 +593,12 @@ impl_display!{ CssParsingError<'a>, {
     FlexShrinkParseError(e) => format!("{}", e),
     FlexGrowParseError(e) => format!("{}", e),
     BackgroundPositionParseError(e) => format!("{}", e),
+    AnimationParseError(e) => format!("{}", e),
+    TransformParseError(e) => format!("{}", e),
+    FilterParseError(e) => format!("{}", e),
+    TextDecorationParseError(e) => format!("{}", e),
+    BorderImageParseError(e) => format!("Invalid border-image: {}", e),
+    GridParseError(e) => format!("Invalid grid property: {}", e),
 }}
 
 impl_from!(CssBorderParseError<'a>, CssParsingError::CssBorderParseError);
@@ -473,6 +615,12 @@ impl_from!(LayoutMarginParseError<'a>, CssParsingError::MarginParseError);
 impl_from!(FlexShrinkParseError<'a>, CssParsingError::FlexShrinkParseError);
 impl_from!(FlexGrowParseError<'a>, CssParsingError::FlexGrowParseError);
 impl_from!(CssBackgroundPositionParseError<'a>, CssParsingError::BackgroundPositionParseError);
+impl_from!(CssAnimationParseError<'a>, CssParsingError::AnimationParseError);
+impl_from!(CssStyleTransformParseError<'a>, CssParsingError::TransformParseError);
+impl_from!(CssStyleFilterParseError<'a>, CssParsingError::FilterParseError);
+impl_from!(CssStyleTextDecorationParseError<'a>, CssParsingError::TextDecorationParseError);
+impl_from!(CssBorderImageParseError<'a>, CssParsingError::BorderImageParseError);
+impl_from!(CssGridParseError<'a>, CssParsingError::GridParseError);
 
 impl<'a> From<PercentageParseError> for CssParsingError<'a> {
     fn from(e: PercentageParseError) -> Self {
@@ -487,12 +635,14 @@ pub struct InvalidValueErr<'a>(pub &'a str);
 #[derive(Clone, PartialEq)]
 pub enum CssStyleBorderRadiusParseError<'a> {
     TooManyValues(&'a str),
+    MissingValue(&'a str),
     PixelParseError(PixelParseError<'a>),
 }
 
 impl_debug_as_display!(CssStyleBorderRadiusParseError<'a>);
 impl_display!{ CssStyleBorderRadiusParseError<'a>, {
     TooManyValues(val) => format!("Too many values: \"{}\"", val),
+    MissingValue(val) => format!("Missing value: \"{}\"", val),
     PixelParseError(e) => format!("{}", e),
 }}
 
@@ -598,6 +748,8 @@ impl_display!{ CssBorderParseError<'a>, {
 pub enum CssShadowParseError<'a> {
     InvalidSingleStatement(&'a str),
     TooManyComponents(&'a str),
+    /// Color or `inset` keyword that was specified twice, i.e. `5px 5px red blue`
+    DuplicateValue(&'a str),
     ValueParseErr(PixelParseError<'a>),
     ColorParseError(CssColorParseError<'a>),
 }
@@ -605,6 +757,7 @@ impl_debug_as_display!(CssShadowParseError<'a>);
 impl_display!{ CssShadowParseError<'a>, {
     InvalidSingleStatement(e) => format!("Invalid single statement: \"{}\"", e),
     TooManyComponents(e) => format!("Too many components: \"{}\"", e),
+    DuplicateValue(e) => format!("Duplicate value: \"{}\"", e),
     ValueParseErr(e) => format!("Invalid value: {}", e),
     ColorParseError(e) => format!("Invalid color-value: {}", e),
 }}
@@ -612,16 +765,13 @@ impl_display!{ CssShadowParseError<'a>, {
 impl_from!(PixelParseError<'a>, CssShadowParseError::ValueParseErr);
 impl_from!(CssColorParseError<'a>, CssShadowParseError::ColorParseError);
 
+/// Parsed `border-radius`, each corner has a horizontal (`width`) and a vertical (`height`) radius
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
 pub struct StyleBorderRadius {
-
-    // TODO: Should technically be PixelSize because the border radius doesn't have to be uniform
-    // but the parsing for that is complicated...
-
-    pub top_left: PixelValue,
-    pub top_right: PixelValue,
-    pub bottom_left: PixelValue,
-    pub bottom_right: PixelValue,
+    pub top_left: PixelSize,
+    pub top_right: PixelSize,
+    pub bottom_left: PixelSize,
+    pub bottom_right: PixelSize,
 }
 
 impl Default for StyleBorderRadius {
@@ -637,85 +787,124 @@ impl StyleBorderRadius {
     }
 
     pub const fn uniform(value: PixelValue) -> Self {
+        let corner = PixelSize::new(value, value);
         Self {
-            top_left: value,
-            top_right: value,
-            bottom_left: value,
-            bottom_right: value,
+            top_left: corner,
+            top_right: corner,
+            bottom_left: corner,
+            bottom_right: corner,
         }
     }
 }
 
-/// parse the border-radius like "5px 10px" or "5px 10px 6px 10px"
+/// parse the border-radius like "5px 10px", "5px 10px 6px 10px" or "10px 5% / 20px 30px"
+///
+/// The values before the `/` are the horizontal radii, the values after it the vertical
+/// radii. Without a `/`, the corners are circular (vertical radius = horizontal radius).
 pub fn parse_style_border_radius<'a>(input: &'a str)
 -> Result<StyleBorderRadius, CssStyleBorderRadiusParseError<'a>>
 {
-    let mut components = input.split_whitespace();
-    let len = components.clone().count();
-
-    match len {
-        1 => {
-            // One value - border-radius: 15px;
-            // (the value applies to all four corners, which are rounded equally:
+    let (horizontal, vertical) = split_border_radius(input)?;
 
-            let uniform_radius = parse_pixel_value(components.next().unwrap())?;
-            Ok(StyleBorderRadius::uniform(uniform_radius))
-        },
-        2 => {
-            // Two values - border-radius: 15px 50px;
-            // (first value applies to top-left and bottom-right corners,
-            // and the second value applies to top-right and bottom-left corners):
+    // [top_left, top_right, bottom_right, bottom_left]
+    let horizontal = parse_border_radius_values(horizontal)?;
+    let vertical = match vertical {
+        Some(vertical) => parse_border_radius_values(vertical)?,
+        None => horizontal,
+    };
 
-            let top_left_bottom_right = parse_pixel_value(components.next().unwrap())?;
-            let top_right_bottom_left = parse_pixel_value(components.next().unwrap())?;
+    Ok(StyleBorderRadius {
+        top_left: PixelSize::new(horizontal[0], vertical[0]),
+        top_right: PixelSize::new(horizontal[1], vertical[1]),
+        bottom_right: PixelSize::new(horizontal[2], vertical[2]),
+        bottom_left: PixelSize::new(horizontal[3], vertical[3]),
+    })
+}
 
-            Ok(StyleBorderRadius {
-                top_left:       top_left_bottom_right,
-                bottom_right:   top_left_bottom_right,
-                top_right:      top_right_bottom_left,
-                bottom_left:    top_right_bottom_left,
-            })
-        },
-        3 => {
-            // Three values - border-radius: 15px 50px 30px;
-            // (first value applies to top-left corner,
-            // second value applies to top-right and bottom-left corners,
-            // and third value applies to bottom-right corner):
-            let top_left = parse_pixel_value(components.next().unwrap())?;
-            let top_right_bottom_left = parse_pixel_value(components.next().unwrap())?;
-            let bottom_right = parse_pixel_value(components.next().unwrap())?;
+/// Splits a `border-radius` at the `/` (outside of any `calc()`) into the horizontal and vertical radii
+fn split_border_radius<'a>(input: &'a str)
+-> Result<(&'a str, Option<&'a str>), CssStyleBorderRadiusParseError<'a>>
+{
+    let mut depth = 0_usize;
+    let mut slash = None;
 
-            Ok(StyleBorderRadius {
-                top_left,
-                bottom_right,
-                top_right:  top_right_bottom_left,
-                bottom_left: top_right_bottom_left,
-            })
+    for (idx, ch) in input.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '/' if depth == 0 => {
+                if slash.is_some() {
+                    return Err(CssStyleBorderRadiusParseError::TooManyValues(input));
+                }
+                slash = Some(idx);
+            },
+            _ => { },
         }
-        4 => {
+    }
 
-            // Four values - border-radius: 15px 50px 30px 5px;
-            //
-            // first value applies to top-left corner,
-            // second value applies to top-right corner,
-            // third value applies to bottom-right corner,
-            // fourth value applies to bottom-left corner
+    match slash {
+        Some(idx) => Ok((&input[..idx], Some(&input[(idx + 1)..]))),
+        None => Ok((input, None)),
+    }
+}
 
-            let top_left = parse_pixel_value(components.next().unwrap())?;
-            let top_right = parse_pixel_value(components.next().unwrap())?;
-            let bottom_right = parse_pixel_value(components.next().unwrap())?;
-            let bottom_left = parse_pixel_value(components.next().unwrap())?;
+/// Parses one to four radii and expands them to all four corners,
+/// in the order `[top_left, top_right, bottom_right, bottom_left]`
+fn parse_border_radius_values<'a>(input: &'a str)
+-> Result<[PixelValue;4], CssStyleBorderRadiusParseError<'a>>
+{
+    let components = split_outside_parentheses(input, char::is_whitespace);
 
-            Ok(StyleBorderRadius {
-                top_left,
-                bottom_right,
-                top_right,
-                bottom_left,
-            })
+    match components.len() {
+        // One value - border-radius: 15px;
+        // (the value applies to all four corners, which are rounded equally)
+        1 => {
+            let uniform_radius = parse_pixel_value(components[0])?;
+            Ok([uniform_radius; 4])
         },
-        _ => {
-            Err(CssStyleBorderRadiusParseError::TooManyValues(input))
-        }
+        // Two values - border-radius: 15px 50px;
+        // (first value applies to top-left and bottom-right corners,
+        // and the second value applies to top-right and bottom-left corners)
+        2 => {
+            let top_left_bottom_right = parse_pixel_value(components[0])?;
+            let top_right_bottom_left = parse_pixel_value(components[1])?;
+            Ok([top_left_bottom_right, top_right_bottom_left, top_left_bottom_right, top_right_bottom_left])
+        },
+        // Three values - border-radius: 15px 50px 30px;
+        // (first value applies to top-left corner,
+        // second value applies to top-right and bottom-left corners,
+        // and third value applies to bottom-right corner)
+        3 => {
+            let top_left = parse_pixel_value(components[0])?;
+            let top_right_bottom_left = parse_pixel_value(components[1])?;
+            let bottom_right = parse_pixel_value(components[2])?;
+            Ok([top_left, top_right_bottom_left, bottom_right, top_right_bottom_left])
+        },
+        // Four values - border-radius: 15px 50px 30px 5px;
+        // (top-left, top-right, bottom-right, bottom-left)
+        4 => {
+            let top_left = parse_pixel_value(components[0])?;
+            let top_right = parse_pixel_value(components[1])?;
+            let bottom_right = parse_pixel_value(components[2])?;
+            let bottom_left = parse_pixel_value(components[3])?;
+            Ok([top_left, top_right, bottom_right, bottom_left])
+        },
+        0 => Err(CssStyleBorderRadiusParseError::MissingValue(input)),
+        _ => Err(CssStyleBorderRadiusParseError::TooManyValues(input)),
+    }
+}
+
+/// Parses the radius of a single corner, i.e. `"10px"` (circular) or `"10px 20%"` (elliptical)
+fn parse_corner_radius<'a>(input: &'a str) -> Result<PixelSize, CssStyleBorderRadiusParseError<'a>> {
+    let components = split_outside_parentheses(input, char::is_whitespace);
+    match components.len() {
+        1 => {
+            let radius = parse_pixel_value(components[0])?;
+            Ok(PixelSize::new(radius, radius))
+        },
+        2 => Ok(PixelSize::new(parse_pixel_value(components[0])?, parse_pixel_value(components[1])?)),
+        0 => Err(CssStyleBorderRadiusParseError::MissingValue(input)),
+        _ => Err(CssStyleBorderRadiusParseError::TooManyValues(input)),
     }
 }
 
@@ -725,6 +914,8 @@ pub enum PixelParseError<'a> {
     NoValueGiven(&'a str),
     UnsupportedMetric(f32, String, &'a str),
     ValueParseErr(ParseFloatError, String),
+    InvalidCalcExpression(&'a str),
+    TooManyCalcTerms(&'a str),
 }
 
 impl_debug_as_display!(PixelParseError<'a>);
@@ -734,16 +925,32 @@ impl_display!{ PixelParseError<'a>, {
     NoValueGiven(input) => format!("Expected floating-point pixel value, got: \"{}\"", input),
     UnsupportedMetric(_, metric, input) => format!("Could not parse \"{}\": Metric \"{}\" is not (yet) implemented.", input, metric),
     ValueParseErr(err, number_str) => format!("Could not parse \"{}\" as floating-point value: \"{}\"", number_str, err),
+    InvalidCalcExpression(input) => format!("Invalid calc() expression: \"{}\"", input),
+    TooManyCalcTerms(input) => format!("calc() expression \"{}\" mixes more than {} different units", input, MAX_CALC_TERMS),
 }}
 
+const PIXEL_VALUE_METRICS: &[(&str, SizeMetric)] = &[
+    ("px", SizeMetric::Px),
+    ("em", SizeMetric::Em),
+    ("rem", SizeMetric::Rem),
+    ("pt", SizeMetric::Pt),
+    ("%", SizeMetric::Percent),
+    ("vw", SizeMetric::Vw),
+    ("vh", SizeMetric::Vh),
+    ("vmin", SizeMetric::Vmin),
+    ("vmax", SizeMetric::Vmax),
+];
+
+/// Parses a single value such as "15px", "2rem" or "50vw", or a
+/// `calc()` expression such as "calc(100% - 2em - 12px)"
 pub fn parse_pixel_value<'a>(input: &'a str)
 -> Result<PixelValue, PixelParseError<'a>> {
-    parse_pixel_value_inner(input, &[
-        ("px", SizeMetric::Px),
-        ("em", SizeMetric::Em),
-        ("pt", SizeMetric::Pt),
-        ("%", SizeMetric::Percent),
-    ])
+    let input = input.trim();
+    if input.starts_with("calc(") {
+        parse_calc_expression(input)
+    } else {
+        parse_pixel_value_inner(input, PIXEL_VALUE_METRICS)
+    }
 }
 
 pub fn parse_pixel_value_no_percent<'a>(input: &'a str)
@@ -752,6 +959,7 @@ pub fn parse_pixel_value_no_percent<'a>(input: &'a str)
         parse_pixel_value_inner(input, &[
             ("px", SizeMetric::Px),
             ("em", SizeMetric::Em),
+            ("rem", SizeMetric::Rem),
             ("pt", SizeMetric::Pt),
         ])?
     ))
@@ -786,10 +994,178 @@ fn parse_pixel_value_inner<'a>(input: &'a str, match_values: &[(&'static str, Si
     Ok(PixelValue::from_metric(unit, number))
 }
 
-#[derive(Clone, PartialEq, Eq)]
-pub enum PercentageParseError {
-    ValueParseErr(ParseFloatError),
-    NoPercentSign
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum CalcToken<'a> {
+    Value(&'a str),
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    OpenParen,
+    CloseParen,
+}
+
+/// Intermediate result of a `calc()` expression: either a plain
+/// number (only valid as a factor or divisor) or a sum of units
+#[derive(Debug, Clone, PartialEq)]
+enum CalcOperand {
+    Number(f32),
+    Length(Vec<(SizeMetric, f32)>),
+}
+
+impl CalcOperand {
+    fn scale(self, factor: f32) -> Self {
+        match self {
+            CalcOperand::Number(n) => CalcOperand::Number(n * factor),
+            CalcOperand::Length(terms) => CalcOperand::Length(terms.into_iter().map(|(m, n)| (m, n * factor)).collect()),
+        }
+    }
+}
+
+/// Parses "calc(100% - 2em - 12px)", supports `+` / `-` between values with units
+/// and `*` / `/` by unitless numbers, as well as nested parentheses
+fn parse_calc_expression<'a>(input: &'a str)
+-> Result<PixelValue, PixelParseError<'a>>
+{
+    let tokens = tokenize_calc_expression(input)?;
+    let mut position = 0;
+    let result = parse_calc_sum(input, &tokens, &mut position)?;
+
+    if position != tokens.len() {
+        return Err(PixelParseError::InvalidCalcExpression(input));
+    }
+
+    match result {
+        CalcOperand::Length(terms) => PixelValue::calc(&terms).ok_or(PixelParseError::TooManyCalcTerms(input)),
+        CalcOperand::Number(_) => Err(PixelParseError::InvalidCalcExpression(input)),
+    }
+}
+
+fn tokenize_calc_expression<'a>(input: &'a str)
+-> Result<Vec<CalcToken<'a>>, PixelParseError<'a>>
+{
+    let mut tokens = Vec::new();
+    let mut remaining = input;
+
+    loop {
+        remaining = remaining.trim_start();
+
+        let mut chars = remaining.chars();
+        let current = match chars.next() {
+            Some(c) => c,
+            None => break,
+        };
+        let next_is_number = chars.next().map(|c| c.is_numeric() || c == '.').unwrap_or(false);
+
+        // A "+" or "-" is only an operator if it follows a value or a closing paren,
+        // otherwise it is the sign of the following number, as in "calc(-5px + 10%)"
+        let previous_is_operand = match tokens.last() {
+            Some(CalcToken::Value(_)) | Some(CalcToken::CloseParen) => true,
+            _ => false,
+        };
+
+        let (token, len) = match current {
+            _ if remaining.starts_with("calc(") => (CalcToken::OpenParen, "calc(".len()),
+            '(' => (CalcToken::OpenParen, 1),
+            ')' => (CalcToken::CloseParen, 1),
+            '*' => (CalcToken::Multiply, 1),
+            '/' => (CalcToken::Divide, 1),
+            '+' if previous_is_operand || !next_is_number => (CalcToken::Plus, 1),
+            '-' if previous_is_operand || !next_is_number => (CalcToken::Minus, 1),
+            _ => {
+                let len = remaining
+                    .find(|c: char| c.is_whitespace() || "()*/".contains(c))
+                    .unwrap_or(remaining.len());
+                (CalcToken::Value(&remaining[..len]), len)
+            },
+        };
+
+        tokens.push(token);
+        remaining = &remaining[len..];
+    }
+
+    if tokens.is_empty() {
+        return Err(PixelParseError::EmptyString);
+    }
+
+    Ok(tokens)
+}
+
+fn parse_calc_sum<'a>(input: &'a str, tokens: &[CalcToken<'a>], position: &mut usize)
+-> Result<CalcOperand, PixelParseError<'a>>
+{
+    let mut result = parse_calc_product(input, tokens, position)?;
+
+    loop {
+        let sign = match tokens.get(*position) {
+            Some(CalcToken::Plus) => 1.0,
+            Some(CalcToken::Minus) => -1.0,
+            _ => return Ok(result),
+        };
+        *position += 1;
+
+        let rhs = parse_calc_product(input, tokens, position)?.scale(sign);
+        result = match (result, rhs) {
+            (CalcOperand::Number(a), CalcOperand::Number(b)) => CalcOperand::Number(a + b),
+            (CalcOperand::Length(mut a), CalcOperand::Length(b)) => { a.extend(b); CalcOperand::Length(a) },
+            _ => return Err(PixelParseError::InvalidCalcExpression(input)),
+        };
+    }
+}
+
+fn parse_calc_product<'a>(input: &'a str, tokens: &[CalcToken<'a>], position: &mut usize)
+-> Result<CalcOperand, PixelParseError<'a>>
+{
+    let mut result = parse_calc_factor(input, tokens, position)?;
+
+    loop {
+        let is_division = match tokens.get(*position) {
+            Some(CalcToken::Multiply) => false,
+            Some(CalcToken::Divide) => true,
+            _ => return Ok(result),
+        };
+        *position += 1;
+
+        let rhs = parse_calc_factor(input, tokens, position)?;
+        result = match (result, rhs, is_division) {
+            (lhs, CalcOperand::Number(n), true) if n != 0.0 => lhs.scale(1.0 / n),
+            (lhs, CalcOperand::Number(n), false) => lhs.scale(n),
+            (CalcOperand::Number(n), rhs, false) => rhs.scale(n),
+            _ => return Err(PixelParseError::InvalidCalcExpression(input)),
+        };
+    }
+}
+
+fn parse_calc_factor<'a>(input: &'a str, tokens: &[CalcToken<'a>], position: &mut usize)
+-> Result<CalcOperand, PixelParseError<'a>>
+{
+    let token = *tokens.get(*position).ok_or(PixelParseError::InvalidCalcExpression(input))?;
+    *position += 1;
+
+    match token {
+        CalcToken::OpenParen => {
+            let result = parse_calc_sum(input, tokens, position)?;
+            if tokens.get(*position) != Some(&CalcToken::CloseParen) {
+                return Err(PixelParseError::InvalidCalcExpression(input));
+            }
+            *position += 1;
+            Ok(result)
+        },
+        CalcToken::Value(value) => match value.parse::<f32>() {
+            Ok(number) => Ok(CalcOperand::Number(number)),
+            Err(_) => {
+                let pixel_value = parse_pixel_value_inner(value, PIXEL_VALUE_METRICS)?;
+                Ok(CalcOperand::Length(vec![(pixel_value.metric, pixel_value.number.get())]))
+            },
+        },
+        _ => Err(PixelParseError::InvalidCalcExpression(input)),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum PercentageParseError {
+    ValueParseErr(ParseFloatError),
+    NoPercentSign
 }
 
 impl_debug_as_display!(PercentageParseError);
@@ -1446,109 +1822,139 @@ pub fn parse_style_border<'a>(input: &'a str)
     })
 }
 
-/// Parses a CSS box-shadow, such as "5px 10px inset"
+/// Parses a single CSS box-shadow, such as "5px 10px inset" or "inset 5px 10px 5px 10px #888888".
+///
+/// The offsets, blur radius and spread radius have to be written next to each other,
+/// the color and the `inset` keyword can be written before or after them.
 pub fn parse_style_box_shadow<'a>(input: &'a str)
 -> Result<BoxShadowPreDisplayItem, CssShadowParseError<'a>>
 {
-    let mut input_iter = input.split_whitespace();
-    let count = input_iter.clone().count();
+    let components = parse_shadow_components(input, 6)?;
+    let lengths = &components.lengths;
+    let zero = PixelValueNoPercent(PixelValue::const_px(0));
+
+    Ok(BoxShadowPreDisplayItem {
+        offset: [lengths[0], lengths[1]],
+        color: components.color.unwrap_or(ColorU { r: 0, g: 0, b: 0, a: 255 }),
+        blur_radius: lengths.get(2).cloned().unwrap_or(zero),
+        spread_radius: lengths.get(3).cloned().unwrap_or(zero),
+        clip_mode: components.clip_mode.unwrap_or(BoxShadowClipMode::Outset),
+    })
+}
 
-    let mut box_shadow = BoxShadowPreDisplayItem {
-        offset: [PixelValueNoPercent(PixelValue::const_px(0)), PixelValueNoPercent(PixelValue::const_px(0))],
-        color: ColorU { r: 0, g: 0, b: 0, a: 255 },
-        blur_radius: PixelValueNoPercent(PixelValue::const_px(0)),
-        spread_radius: PixelValueNoPercent(PixelValue::const_px(0)),
-        clip_mode: BoxShadowClipMode::Outset,
-    };
+/// Parses a comma-separated list of box-shadows, such as "0px 0px 5px red, 2px 2px 10px 5px black inset"
+pub fn parse_style_box_shadow_list<'a>(input: &'a str)
+-> Result<StyleBoxShadowList, CssShadowParseError<'a>>
+{
+    let shadows = split_outside_parentheses(input, |c| c == ',');
+    if shadows.is_empty() {
+        return Err(CssShadowParseError::InvalidSingleStatement(input));
+    }
+    shadows.into_iter().map(parse_style_box_shadow).collect::<Result<Vec<_>, _>>().map(StyleBoxShadowList)
+}
+
+/// Parses a comma-separated `text-shadow`, such as "1px 1px 2px black, 0px 0px 5px blue".
+/// Text shadows have no spread radius and can't be inset.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_text_shadow;
+/// # use azul_css::{StyleTextShadow, TextShadowPreDisplayItem, ColorU, PixelValue, PixelValueNoPercent};
+/// assert_eq!(parse_style_text_shadow("2px 4px #ff0000"), Ok(StyleTextShadow(vec![TextShadowPreDisplayItem {
+///     offset: [PixelValueNoPercent(PixelValue::px(2.0)), PixelValueNoPercent(PixelValue::px(4.0))],
+///     color: Some(ColorU { r: 255, g: 0, b: 0, a: 255 }),
+///     blur_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+/// }])));
+/// ```
+pub fn parse_style_text_shadow<'a>(input: &'a str)
+-> Result<StyleTextShadow, CssShadowParseError<'a>>
+{
+    let shadows = split_outside_parentheses(input, |c| c == ',');
+    if shadows.is_empty() {
+        return Err(CssShadowParseError::InvalidSingleStatement(input));
+    }
 
-    let last_val = input_iter.clone().rev().next();
-    let is_inset = last_val == Some("inset") || last_val == Some("outset");
+    let mut text_shadows = Vec::with_capacity(shadows.len());
 
-    if count > 2 && is_inset {
-        let l_val = last_val.unwrap();
-        if l_val == "outset" {
-            box_shadow.clip_mode = BoxShadowClipMode::Outset;
-        } else if l_val == "inset" {
-            box_shadow.clip_mode = BoxShadowClipMode::Inset;
+    for shadow in shadows {
+        let components = parse_shadow_components(shadow, 4)?;
+        if components.clip_mode.is_some() || components.lengths.len() > 3 {
+            return Err(CssShadowParseError::InvalidSingleStatement(shadow));
         }
+        let lengths = &components.lengths;
+        text_shadows.push(TextShadowPreDisplayItem {
+            offset: [lengths[0], lengths[1]],
+            color: components.color,
+            blur_radius: lengths.get(2).cloned().unwrap_or(PixelValueNoPercent(PixelValue::const_px(0))),
+        });
     }
 
-    match count {
-        2 => {
-            // box-shadow: 5px 10px; (h_offset, v_offset)
-            let h_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-        },
-        3 => {
-            // box-shadow: 5px 10px inset; (h_offset, v_offset, inset)
-            let h_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            if !is_inset {
-                // box-shadow: 5px 10px #888888; (h_offset, v_offset, color)
-                let color = parse_css_color(input_iter.next().unwrap())?;
-                box_shadow.color = color;
-            }
-        },
-        4 => {
-            let h_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            if !is_inset {
-                let blur = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-                box_shadow.blur_radius = blur.into();
-            }
+    Ok(StyleTextShadow(text_shadows))
+}
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
-        },
-        5 => {
-            // box-shadow: 5px 10px 5px 10px #888888; (h_offset, v_offset, blur, spread, color)
-            // box-shadow: 5px 10px 5px #888888 inset; (h_offset, v_offset, blur, color, inset)
-            let h_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            let blur = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.blur_radius = blur.into();
-
-            if !is_inset {
-                let spread = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-                box_shadow.spread_radius = spread.into();
-            }
+/// Whitespace-separated components of a single box- or text-shadow
+struct ShadowComponents {
+    /// Offsets, blur radius and spread radius (in that order), at least two
+    lengths: Vec<PixelValueNoPercent>,
+    color: Option<ColorU>,
+    clip_mode: Option<BoxShadowClipMode>,
+}
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
-        },
-        6 => {
-            // box-shadow: 5px 10px 5px 10px #888888 inset; (h_offset, v_offset, blur, spread, color, inset)
-            let h_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
+fn parse_shadow_components<'a>(input: &'a str, max_components: usize)
+-> Result<ShadowComponents, CssShadowParseError<'a>>
+{
+    use self::CssShadowParseError::*;
+
+    let values = split_outside_parentheses(input, char::is_whitespace);
+    if values.len() < 2 || values.len() > max_components {
+        return Err(TooManyComponents(input));
+    }
 
-            let blur = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.blur_radius = blur.into();
+    let mut components = ShadowComponents {
+        lengths: Vec::with_capacity(4),
+        color: None,
+        clip_mode: None,
+    };
 
-            let spread = parse_pixel_value_no_percent(input_iter.next().unwrap())?;
-            box_shadow.spread_radius = spread.into();
+    // Set as soon as a color or keyword follows the lengths,
+    // since the lengths have to be written next to each other
+    let mut lengths_finished = false;
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
-        }
-        _ => {
-            return Err(CssShadowParseError::TooManyComponents(input));
+    for value in values {
+        let clip_mode = match value {
+            "inset" => Some(BoxShadowClipMode::Inset),
+            "outset" => Some(BoxShadowClipMode::Outset),
+            _ => None,
+        };
+
+        if let Some(clip_mode) = clip_mode {
+            if components.clip_mode.is_some() {
+                return Err(DuplicateValue(value));
+            }
+            components.clip_mode = Some(clip_mode);
+            lengths_finished |= !components.lengths.is_empty();
+        } else if value.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') {
+            if lengths_finished || components.lengths.len() == 4 {
+                return Err(InvalidSingleStatement(input));
+            }
+            components.lengths.push(parse_pixel_value_no_percent(value)?);
+        } else {
+            if components.color.is_some() {
+                return Err(DuplicateValue(value));
+            }
+            components.color = Some(parse_css_color(value)?);
+            lengths_finished |= !components.lengths.is_empty();
         }
     }
 
-    Ok(box_shadow)
+    if components.lengths.len() < 2 {
+        return Err(InvalidSingleStatement(input));
+    }
+
+    Ok(components)
 }
 
 #[derive(Clone, PartialEq)]
@@ -1563,6 +1969,10 @@ pub enum CssBackgroundParseError<'a> {
     ShapeParseError(CssShapeParseError<'a>),
     ImageParseError(CssImageParseError<'a>),
     ColorParseError(CssColorParseError<'a>),
+    /// Invalid `from` angle of a conic gradient, i.e. `conic-gradient(from to right, ...)`
+    ConicAngleParseError(&'a str),
+    /// Invalid `at` position of a conic gradient
+    PositionParseError(CssBackgroundPositionParseError<'a>),
 }
 
 impl_debug_as_display!(CssBackgroundParseError<'a>);
@@ -1577,9 +1987,12 @@ impl_display!{ CssBackgroundParseError<'a>, {
     ShapeParseError(e) => format!("Failed to parse shape of radial gradient: {}", e),
     ImageParseError(e) => format!("Failed to parse image() value: {}", e),
     ColorParseError(e) => format!("Failed to parse color value: {}", e),
+    ConicAngleParseError(e) => format!("Failed to parse angle of conic gradient: \"{}\"", e),
+    PositionParseError(e) => format!("Failed to parse center of conic gradient: {}", e),
 }}
 
 impl_from!(ParenthesisParseError<'a>, CssBackgroundParseError::InvalidBackground);
+impl_from!(CssBackgroundPositionParseError<'a>, CssBackgroundParseError::PositionParseError);
 impl_from!(CssDirectionParseError<'a>, CssBackgroundParseError::DirectionParseError);
 impl_from!(CssGradientStopParseError<'a>, CssBackgroundParseError::GradientParseError);
 impl_from!(CssShapeParseError<'a>, CssBackgroundParseError::ShapeParseError);
@@ -1593,6 +2006,7 @@ pub fn parse_style_background_content<'a>(input: &'a str)
     match parse_parentheses(input, &[
         "linear-gradient", "repeating-linear-gradient",
         "radial-gradient", "repeating-radial-gradient",
+        "conic-gradient", "repeating-conic-gradient",
         "image",
     ]) {
         Ok((background_type, brace_contents)) => {
@@ -1601,6 +2015,8 @@ pub fn parse_style_background_content<'a>(input: &'a str)
                 "repeating-linear-gradient" => GradientType::RepeatingLinearGradient,
                 "radial-gradient" => GradientType::RadialGradient,
                 "repeating-radial-gradient" => GradientType::RepeatingRadialGradient,
+                "conic-gradient" => GradientType::ConicGradient,
+                "repeating-conic-gradient" => GradientType::RepeatingConicGradient,
                 "image" => { return Ok(StyleBackgroundContent::Image(parse_image(brace_contents)?)); },
                 other => { return Err(CssBackgroundParseError::Error(other)); /* unreachable */ },
             };
@@ -1613,6 +2029,85 @@ pub fn parse_style_background_content<'a>(input: &'a str)
     }
 }
 
+/// Parses the comma-separated layers of a `background`, such as
+/// `"image(\"pattern\"), linear-gradient(red, blue)"` - the first layer is painted on top
+pub fn parse_style_background_content_list<'a>(input: &'a str)
+-> Result<StyleBackgroundContentList, CssBackgroundParseError<'a>>
+{
+    parse_comma_separated_list(input, parse_style_background_content, CssBackgroundParseError::Error(input))
+    .map(StyleBackgroundContentList)
+}
+
+/// Parses the comma-separated layers of a `background-image`, such as `"\"pattern\", \"photo\""`
+pub fn parse_style_background_image_list<'a>(input: &'a str)
+-> Result<StyleBackgroundContentList, CssImageParseError<'a>>
+{
+    parse_comma_separated_list(input, |layer| parse_image(layer).map(StyleBackgroundContent::Image), CssImageParseError::UnclosedQuotes(input))
+    .map(StyleBackgroundContentList)
+}
+
+/// Parses a `background-position` with one position per background layer, such as `"center, left 10px"`
+pub fn parse_style_background_position_list<'a>(input: &'a str)
+-> Result<StyleBackgroundPositionList, CssBackgroundPositionParseError<'a>>
+{
+    parse_comma_separated_list(input, parse_style_background_position, CssBackgroundPositionParseError::NoPosition(input))
+    .map(StyleBackgroundPositionList)
+}
+
+/// Parses a `background-size` with one size per background layer, such as `"20px 20px, cover"`
+pub fn parse_style_background_size_list<'a>(input: &'a str)
+-> Result<StyleBackgroundSizeList, InvalidValueErr<'a>>
+{
+    parse_comma_separated_list(input, parse_style_background_size, InvalidValueErr(input))
+    .map(StyleBackgroundSizeList)
+}
+
+/// Parses a `background-repeat` with one repeat mode per background layer, such as `"repeat, no-repeat"`
+pub fn parse_style_background_repeat_list<'a>(input: &'a str)
+-> Result<StyleBackgroundRepeatList, InvalidValueErr<'a>>
+{
+    parse_comma_separated_list(input, parse_style_background_repeat, InvalidValueErr(input))
+    .map(StyleBackgroundRepeatList)
+}
+
+/// Parses every comma-separated item (commas inside of parentheses are ignored),
+/// returns `empty_error` if the list contains no items at all
+fn parse_comma_separated_list<'a, T, E, F>(input: &'a str, parse_item: F, empty_error: E)
+-> Result<Vec<T>, E> where F: Fn(&'a str) -> Result<T, E>
+{
+    let items = split_outside_parentheses(input, |c| c == ',');
+    if items.is_empty() {
+        return Err(empty_error);
+    }
+    items.into_iter().map(parse_item).collect()
+}
+
+/// Parses a `background-size` attribute, such as `"cover"`, `"contain"`,
+/// `"20px 10px"` or `"50%"` (the same value is used for the width and the height)
+pub fn parse_style_background_size<'a>(input: &'a str)
+-> Result<StyleBackgroundSize, InvalidValueErr<'a>>
+{
+    let input = input.trim();
+    match input {
+        "contain" => Ok(StyleBackgroundSize::Contain),
+        "cover" => Ok(StyleBackgroundSize::Cover),
+        other => {
+            let mut values = other.split_whitespace();
+            let width = values.next().ok_or(InvalidValueErr(input))?;
+            let height = values.next();
+            if values.next().is_some() {
+                return Err(InvalidValueErr(input));
+            }
+            let width = parse_pixel_value(width).map_err(|_| InvalidValueErr(input))?;
+            let height = match height {
+                Some(h) => parse_pixel_value(h).map_err(|_| InvalidValueErr(input))?,
+                None => width,
+            };
+            Ok(StyleBackgroundSize::ExactSize(width, height))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CssBackgroundPositionParseError<'a> {
     NoPosition(&'a str),
@@ -1756,6 +2251,14 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: GradientType)
     let is_radial_gradient = background_type == GradientType::RadialGradient ||
                              background_type == GradientType::RepeatingRadialGradient;
 
+    let is_conic_gradient = background_type == GradientType::ConicGradient ||
+                            background_type == GradientType::RepeatingConicGradient;
+
+    // default conic gradient: starting at the top, around the center
+    let mut conic_angle = FloatValue::new(0.0);
+    let mut conic_center = ConicGradient::DEFAULT_CENTER;
+    let mut first_is_conic_header = false;
+
     if is_linear_gradient {
         if let Ok(dir) = parse_direction(first_brace_item) {
             direction = dir;
@@ -1770,8 +2273,16 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: GradientType)
         }
     }
 
+    if is_conic_gradient {
+        if let Some((angle, center)) = parse_conic_gradient_header(first_brace_item)? {
+            conic_angle = angle;
+            conic_center = center;
+            first_is_conic_header = true;
+        }
+    }
+
     let mut first_item_doesnt_count = false;
-    if (is_linear_gradient && first_is_direction) || (is_radial_gradient && first_is_shape) {
+    if (is_linear_gradient && first_is_direction) || (is_radial_gradient && first_is_shape) || (is_conic_gradient && first_is_conic_header) {
         gradient_stop_count -= 1; // first item is not a gradient stop
         first_item_doesnt_count = true;
     }
@@ -1820,9 +2331,70 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: GradientType)
                 stops: color_stops,
             }))
         },
+        GradientType::ConicGradient => {
+            Ok(StyleBackgroundContent::ConicGradient(ConicGradient {
+                angle: conic_angle,
+                center: conic_center,
+                extend_mode: ExtendMode::Clamp,
+                stops: color_stops,
+            }))
+        },
+        GradientType::RepeatingConicGradient => {
+            Ok(StyleBackgroundContent::ConicGradient(ConicGradient {
+                angle: conic_angle,
+                center: conic_center,
+                extend_mode: ExtendMode::Repeat,
+                stops: color_stops,
+            }))
+        },
     }
 }
 
+/// Parses the optional first item of a conic gradient, such as `"from 45deg at 25% 75%"`,
+/// `"from 0.5turn"` or `"at center top"`. Returns `Ok(None)` if the item is a color stop.
+fn parse_conic_gradient_header<'a>(input: &'a str)
+-> Result<Option<(FloatValue, StyleBackgroundPosition)>, CssBackgroundParseError<'a>>
+{
+    let input = input.trim();
+
+    let (angle_str, center_str) = if input.starts_with("from ") {
+        let rest = &input["from ".len()..];
+        match rest.find(" at ") {
+            Some(idx) => (Some(rest[..idx].trim()), Some(rest[(idx + " at ".len())..].trim())),
+            None => (Some(rest.trim()), None),
+        }
+    } else if input.starts_with("at ") {
+        (None, Some(input["at ".len()..].trim()))
+    } else {
+        return Ok(None);
+    };
+
+    let angle = match angle_str {
+        Some(angle_str) => {
+            // "from 0.5turn" - parse_direction doesn't know about turns
+            let angle = if angle_str.ends_with("turn") {
+                let turns = angle_str[..angle_str.len() - "turn".len()].parse::<f32>()
+                    .map_err(|_| CssBackgroundParseError::ConicAngleParseError(angle_str))?;
+                Direction::Angle(FloatValue::new(((turns * 360.0) % 360.0 + 360.0) % 360.0))
+            } else {
+                parse_direction(angle_str).map_err(|_| CssBackgroundParseError::ConicAngleParseError(angle_str))?
+            };
+            match angle {
+                Direction::Angle(deg) => deg,
+                Direction::FromTo(_, _) => return Err(CssBackgroundParseError::ConicAngleParseError(angle_str)),
+            }
+        },
+        None => FloatValue::new(0.0),
+    };
+
+    let center = match center_str {
+        Some(center_str) => parse_style_background_position(center_str)?,
+        None => ConicGradient::DEFAULT_CENTER,
+    };
+
+    Ok(Some((angle, center)))
+}
+
 // Normalize the percentages of the parsed color stops
 pub fn normalize_color_stops(color_stops: &mut Vec<GradientStopPre>) {
 
@@ -2159,10 +2731,20 @@ typed_pixel_value_parser!(parse_layout_padding_bottom, LayoutPaddingBottom);
 typed_pixel_value_parser!(parse_layout_padding_right, LayoutPaddingRight);
 typed_pixel_value_parser!(parse_layout_padding_left, LayoutPaddingLeft);
 
-typed_pixel_value_parser!(parse_style_border_top_left_radius, StyleBorderTopLeftRadius);
-typed_pixel_value_parser!(parse_style_border_bottom_left_radius, StyleBorderBottomLeftRadius);
-typed_pixel_value_parser!(parse_style_border_top_right_radius, StyleBorderTopRightRadius);
-typed_pixel_value_parser!(parse_style_border_bottom_right_radius, StyleBorderBottomRightRadius);
+typed_pixel_value_parser!(parse_layout_row_gap, LayoutRowGap);
+typed_pixel_value_parser!(parse_layout_column_gap, LayoutColumnGap);
+
+macro_rules! typed_corner_radius_parser {($fn:ident, $return:ident) => (
+    /// Parses the radius of a single corner, i.e. `"10px"` or `"10px 20%"` (horizontal and vertical radius)
+    pub fn $fn<'a>(input: &'a str) -> Result<$return, CssStyleBorderRadiusParseError<'a>> {
+        parse_corner_radius(input).map($return)
+    }
+)}
+
+typed_corner_radius_parser!(parse_style_border_top_left_radius, StyleBorderTopLeftRadius);
+typed_corner_radius_parser!(parse_style_border_bottom_left_radius, StyleBorderBottomLeftRadius);
+typed_corner_radius_parser!(parse_style_border_top_right_radius, StyleBorderTopRightRadius);
+typed_corner_radius_parser!(parse_style_border_bottom_right_radius, StyleBorderBottomRightRadius);
 
 typed_pixel_value_parser!(parse_style_border_top_width, StyleBorderTopWidth);
 typed_pixel_value_parser!(parse_style_border_bottom_width, StyleBorderBottomWidth);
@@ -2201,69 +2783,1593 @@ pub fn parse_layout_flex_shrink<'a>(input: &'a str) -> Result<LayoutFlexShrink,
     }
 }
 
-pub fn parse_style_tab_width(input: &str)
--> Result<StyleTabWidth, PercentageParseError>
-{
-    parse_percentage_value(input).and_then(|e| Ok(StyleTabWidth(e)))
-}
+/// Parses a `z-index` attribute, i.e. `"5"` or `"-1"` (`auto` is handled by `parse_css_property`)
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_layout_z_index;
+/// # use azul_css::LayoutZIndex;
+/// assert_eq!(parse_layout_z_index("-1"), Ok(LayoutZIndex(-1)));
+/// ```
+pub fn parse_layout_z_index<'a>(input: &'a str) -> Result<LayoutZIndex, InvalidValueErr<'a>> {
+    let input = input.trim();
+    input.parse::<i32>().map(LayoutZIndex).map_err(|_| InvalidValueErr(input))
+}
+
+pub fn parse_style_tab_width(input: &str)
+-> Result<StyleTabWidth, PercentageParseError>
+{
+    parse_percentage_value(input).and_then(|e| Ok(StyleTabWidth(e)))
+}
+
+pub fn parse_style_line_height(input: &str)
+-> Result<StyleLineHeight, PercentageParseError>
+{
+    parse_percentage_value(input).and_then(|e| Ok(StyleLineHeight(e)))
+}
+
+typed_pixel_value_parser!(parse_style_font_size, StyleFontSize);
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CssStyleFontFamilyParseError<'a> {
+    InvalidStyleFontFamily(&'a str),
+    UnclosedQuotes(&'a str),
+}
+
+impl_display!{CssStyleFontFamilyParseError<'a>, {
+    InvalidStyleFontFamily(val) => format!("Invalid font-family: \"{}\"", val),
+    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+}}
+
+impl<'a> From<UnclosedQuotesError<'a>> for CssStyleFontFamilyParseError<'a> {
+    fn from(err: UnclosedQuotesError<'a>) -> Self {
+        CssStyleFontFamilyParseError::UnclosedQuotes(err.0)
+    }
+}
+
+/// Parses a `StyleFontFamily` declaration from a `&str`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_family;
+/// # use azul_css::{StyleFontFamily, FontId};
+/// let input = "\"Helvetica\", 'Arial', Times New Roman";
+/// let fonts = vec![
+///     FontId("Helvetica".into()),
+///     FontId("Arial".into()),
+///     FontId("Times New Roman".into())
+/// ];
+///
+/// assert_eq!(parse_style_font_family(input), Ok(StyleFontFamily { fonts }));
+/// ```
+pub fn parse_style_font_family<'a>(input: &'a str) -> Result<StyleFontFamily, CssStyleFontFamilyParseError<'a>> {
+    let multiple_fonts = input.split(',');
+    let mut fonts = Vec::with_capacity(1);
+
+    for font in multiple_fonts {
+        let font = font.trim();
+        let font = font.trim_matches('\'');
+        let font = font.trim_matches('\"');
+        let font = font.trim();
+        fonts.push(FontId(font.into()));
+    }
+
+    Ok(StyleFontFamily {
+        fonts: fonts,
+    })
+}
+
+/// Parses a `font-weight` attribute: either `normal`, `bold` or a number between 1 and 1000.
+/// The relative weights `bolder` and `lighter` are not supported.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_weight;
+/// # use azul_css::StyleFontWeight;
+/// assert_eq!(parse_style_font_weight("bold"), Ok(StyleFontWeight::BOLD));
+/// assert_eq!(parse_style_font_weight("300"), Ok(StyleFontWeight(300)));
+/// ```
+#[allow(clippy::manual_range_contains)]
+pub fn parse_style_font_weight<'a>(input: &'a str) -> Result<StyleFontWeight, InvalidValueErr<'a>> {
+    let input = input.trim();
+    match input {
+        "normal" => Ok(StyleFontWeight::NORMAL),
+        "bold" => Ok(StyleFontWeight::BOLD),
+        number => match number.parse::<u16>() {
+            Ok(weight) if weight >= 1 && weight <= 1000 => Ok(StyleFontWeight(weight)),
+            _ => Err(InvalidValueErr(input)),
+        },
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CssStyleTextDecorationParseError<'a> {
+    /// Value that is neither a line, a color nor a thickness, i.e. `blink`
+    InvalidValue(&'a str),
+    /// Line, color or thickness that was specified twice, i.e. `red blue`
+    DuplicateValue(&'a str),
+}
+
+impl_display!{ CssStyleTextDecorationParseError<'a>, {
+    InvalidValue(e) => format!("Invalid text-decoration value: \"{}\"", e),
+    DuplicateValue(e) => format!("Duplicate text-decoration value: \"{}\"", e),
+}}
+
+/// Parses a `text-decoration` attribute, i.e. `"underline"` or `"underline overline red 2px"`.
+/// The values can be written in any order, the color and thickness are optional.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_text_decoration;
+/// # use azul_css::{StyleTextDecoration, ColorU, PixelValue};
+/// assert_eq!(parse_style_text_decoration("line-through #ff0000 2px"), Ok(StyleTextDecoration {
+///     line_through: true,
+///     color: Some(ColorU { r: 255, g: 0, b: 0, a: 255 }),
+///     thickness: Some(PixelValue::px(2.0)),
+///     .. Default::default()
+/// }));
+/// ```
+pub fn parse_style_text_decoration<'a>(input: &'a str) -> Result<StyleTextDecoration, CssStyleTextDecorationParseError<'a>> {
+
+    use self::CssStyleTextDecorationParseError::*;
+
+    let mut decoration = StyleTextDecoration::default();
+    let mut has_none = false;
+
+    for value in split_outside_parentheses(input, char::is_whitespace) {
+        let line = match value {
+            "underline" => Some(&mut decoration.underline),
+            "overline" => Some(&mut decoration.overline),
+            "line-through" => Some(&mut decoration.line_through),
+            _ => None,
+        };
+
+        if let Some(line) = line {
+            if *line {
+                return Err(DuplicateValue(value));
+            }
+            *line = true;
+        } else if value == "none" {
+            if has_none {
+                return Err(DuplicateValue(value));
+            }
+            has_none = true;
+        } else if let Ok(thickness) = parse_pixel_value(value) {
+            if decoration.thickness.is_some() {
+                return Err(DuplicateValue(value));
+            }
+            decoration.thickness = Some(thickness);
+        } else if let Ok(color) = parse_css_color(value) {
+            if decoration.color.is_some() {
+                return Err(DuplicateValue(value));
+            }
+            decoration.color = Some(color);
+        } else {
+            return Err(InvalidValue(value));
+        }
+    }
+
+    // "none" can only be combined with a color or thickness, not with a line
+    if has_none && (decoration.underline || decoration.overline || decoration.line_through) {
+        return Err(InvalidValue(input));
+    }
+
+    Ok(decoration)
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum CssBorderImageParseError<'a> {
+    /// Value that is not valid for the property, i.e. `border-image-slice: 10px`
+    InvalidValue(&'a str),
+    /// More than four sides or more than two repeat modes, i.e. `1 2 3 4 5`
+    TooManyValues(&'a str),
+    /// Source, `fill` or repeat mode that was specified twice
+    DuplicateValue(&'a str),
+    /// `border-image` shorthand without an `image("...")`
+    MissingSource(&'a str),
+    ImageParseError(CssImageParseError<'a>),
+}
+
+impl_debug_as_display!(CssBorderImageParseError<'a>);
+impl_display!{ CssBorderImageParseError<'a>, {
+    InvalidValue(e) => format!("Invalid value: \"{}\"", e),
+    TooManyValues(e) => format!("Too many values: \"{}\"", e),
+    DuplicateValue(e) => format!("Duplicate value: \"{}\"", e),
+    MissingSource(e) => format!("Missing image source: \"{}\"", e),
+    ImageParseError(e) => format!("{}", e),
+}}
+
+impl_from!(CssImageParseError<'a>, CssBorderImageParseError::ImageParseError);
+
+impl<'a> From<InvalidValueErr<'a>> for CssBorderImageParseError<'a> {
+    fn from(e: InvalidValueErr<'a>) -> Self {
+        CssBorderImageParseError::InvalidValue(e.0)
+    }
+}
+
+/// Parses a `border-image-source` attribute, i.e. `image("button")`
+pub fn parse_style_border_image_source<'a>(input: &'a str)
+-> Result<StyleBorderImageSource, CssBorderImageParseError<'a>>
+{
+    match parse_parentheses(input.trim(), &["image"]) {
+        Ok((_, image)) => Ok(StyleBorderImageSource(parse_image(image)?)),
+        Err(_) => Err(CssBorderImageParseError::InvalidValue(input)),
+    }
+}
+
+/// Parses a `border-image-slice` attribute, i.e. `"30"`, `"10% 20"` or `"10 20 30 40 fill"`.
+/// Numbers are pixels of the image and are returned as `px` values.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_border_image_slice;
+/// # use azul_css::{StyleBorderImageSlice, PixelValue};
+/// assert_eq!(parse_style_border_image_slice("10 25% fill"), Ok(StyleBorderImageSlice {
+///     top: PixelValue::px(10.0),
+///     right: PixelValue::percent(25.0),
+///     bottom: PixelValue::px(10.0),
+///     left: PixelValue::percent(25.0),
+///     fill: true,
+/// }));
+/// ```
+pub fn parse_style_border_image_slice<'a>(input: &'a str)
+-> Result<StyleBorderImageSlice, CssBorderImageParseError<'a>>
+{
+    let values = input.split_whitespace().collect::<Vec<_>>();
+    parse_border_image_slice_values(input, &values)
+}
+
+/// Parses a `border-image-width` attribute, i.e. `"10px"`, `"2 auto"` or `"1 10% 2 5px"`
+pub fn parse_style_border_image_width<'a>(input: &'a str)
+-> Result<StyleBorderImageWidth, CssBorderImageParseError<'a>>
+{
+    let values = input.split_whitespace().collect::<Vec<_>>();
+    parse_border_image_width_values(input, &values)
+}
+
+/// Parses a `border-image-outset` attribute, i.e. `"5px"` or `"1 0"`
+pub fn parse_style_border_image_outset<'a>(input: &'a str)
+-> Result<StyleBorderImageOutset, CssBorderImageParseError<'a>>
+{
+    let values = input.split_whitespace().collect::<Vec<_>>();
+    parse_border_image_outset_values(input, &values)
+}
+
+/// Parses a `border-image-repeat` attribute, i.e. `"round"` or `"repeat stretch"`
+pub fn parse_style_border_image_repeat<'a>(input: &'a str)
+-> Result<StyleBorderImageRepeat, CssBorderImageParseError<'a>>
+{
+    let values = input.split_whitespace().collect::<Vec<_>>();
+    parse_border_image_repeat_values(input, &values)
+}
+
+/// Parses a `border-image` shorthand, i.e. `image("button") 10 fill / 5px / 2px round`.
+///
+/// The slice, width and outset are separated by slashes and have to be written
+/// next to each other, the source and the repeat modes can be written before or after them.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_border_image;
+/// # use azul_css::*;
+/// let border_image = parse_style_border_image("image(\"button\") 10 / 5px round").unwrap();
+/// assert_eq!(border_image.source, CssImageId(String::from("button")));
+/// assert_eq!(border_image.slice.top, PixelValue::px(10.0));
+/// assert_eq!(border_image.width.top, BorderImageLength::Length(PixelValue::px(5.0)));
+/// assert_eq!(border_image.repeat.horizontal, BorderImageRepeat::Round);
+/// ```
+pub fn parse_style_border_image<'a>(input: &'a str)
+-> Result<NinePatchBorder, CssBorderImageParseError<'a>>
+{
+    use self::CssBorderImageParseError::*;
+
+    let mut source = None;
+    let mut repeat_values = Vec::new();
+    // values of the slice, width and outset (in that order)
+    let mut sections = [Vec::new(), Vec::new(), Vec::new()];
+    let mut current_section = 0;
+
+    for value in split_outside_parentheses(input, char::is_whitespace) {
+        if value.starts_with("image(") {
+            if source.is_some() {
+                return Err(DuplicateValue(value));
+            }
+            source = Some(parse_style_border_image_source(value)?.0);
+        } else if parse_border_image_repeat(value).is_ok() {
+            repeat_values.push(value);
+        } else {
+            // "10/5px" is the same as "10 / 5px"
+            for (idx, part) in value.split('/').enumerate() {
+                if idx != 0 {
+                    current_section += 1;
+                    if current_section >= sections.len() {
+                        return Err(TooManyValues(input));
+                    }
+                }
+                if !part.is_empty() {
+                    sections[current_section].push(part);
+                }
+            }
+        }
+    }
+
+    let [slice, width, outset] = sections;
+
+    Ok(NinePatchBorder {
+        source: source.ok_or(MissingSource(input))?,
+        slice: if slice.is_empty() { StyleBorderImageSlice::default() } else { parse_border_image_slice_values(input, &slice)? },
+        width: if width.is_empty() { StyleBorderImageWidth::default() } else { parse_border_image_width_values(input, &width)? },
+        outset: if outset.is_empty() { StyleBorderImageOutset::default() } else { parse_border_image_outset_values(input, &outset)? },
+        repeat: if repeat_values.is_empty() { StyleBorderImageRepeat::default() } else { parse_border_image_repeat_values(input, &repeat_values)? },
+    })
+}
+
+/// Expands one to four values to the `[top, right, bottom, left]` sides,
+/// the same way as the values of a `padding` or `margin`
+fn parse_border_image_sides<'a, T: Copy>(
+    input: &'a str,
+    values: &[&'a str],
+    parse_side: fn(&str) -> Option<T>,
+) -> Result<[T;4], CssBorderImageParseError<'a>>
+{
+    use self::CssBorderImageParseError::*;
+
+    let sides = values.iter()
+        .map(|value| parse_side(value).ok_or(InvalidValue(value)))
+        .collect::<Result<Vec<T>, _>>()?;
+
+    match sides.as_slice() {
+        [all] => Ok([*all, *all, *all, *all]),
+        [vertical, horizontal] => Ok([*vertical, *horizontal, *vertical, *horizontal]),
+        [top, horizontal, bottom] => Ok([*top, *horizontal, *bottom, *horizontal]),
+        [top, right, bottom, left] => Ok([*top, *right, *bottom, *left]),
+        [] => Err(InvalidValue(input)),
+        _ => Err(TooManyValues(input)),
+    }
+}
+
+fn parse_border_image_slice_values<'a>(input: &'a str, values: &[&'a str])
+-> Result<StyleBorderImageSlice, CssBorderImageParseError<'a>>
+{
+    // "fill" can be written before or after the numbers
+    let fill_count = values.iter().filter(|value| **value == "fill").count();
+    if fill_count > 1 {
+        return Err(CssBorderImageParseError::DuplicateValue("fill"));
+    }
+
+    let numbers = values.iter().cloned().filter(|value| *value != "fill").collect::<Vec<_>>();
+    let [top, right, bottom, left] = parse_border_image_sides(input, &numbers, |value| {
+        let is_percent = value.ends_with('%');
+        let number = if is_percent { &value[..value.len() - 1] } else { value };
+        let number = number.parse::<f32>().ok().filter(|n| *n >= 0.0)?;
+        Some(if is_percent { PixelValue::percent(number) } else { PixelValue::px(number) })
+    })?;
+
+    Ok(StyleBorderImageSlice { top, right, bottom, left, fill: fill_count == 1 })
+}
+
+fn parse_border_image_width_values<'a>(input: &'a str, values: &[&'a str])
+-> Result<StyleBorderImageWidth, CssBorderImageParseError<'a>>
+{
+    let [top, right, bottom, left] = parse_border_image_sides(input, values, parse_border_image_length)?;
+    Ok(StyleBorderImageWidth { top, right, bottom, left })
+}
+
+fn parse_border_image_outset_values<'a>(input: &'a str, values: &[&'a str])
+-> Result<StyleBorderImageOutset, CssBorderImageParseError<'a>>
+{
+    // The outset can't be "auto" and can't be relative to the size of the rectangle
+    let [top, right, bottom, left] = parse_border_image_sides(input, values, |value| {
+        match parse_border_image_length(value)? {
+            BorderImageLength::Auto => None,
+            BorderImageLength::Length(l) if l.metric == SizeMetric::Percent => None,
+            other => Some(other),
+        }
+    })?;
+    Ok(StyleBorderImageOutset { top, right, bottom, left })
+}
+
+fn parse_border_image_repeat_values<'a>(input: &'a str, values: &[&'a str])
+-> Result<StyleBorderImageRepeat, CssBorderImageParseError<'a>>
+{
+    match values {
+        [both] => {
+            let both = parse_border_image_repeat(both)?;
+            Ok(StyleBorderImageRepeat { horizontal: both, vertical: both })
+        },
+        [horizontal, vertical] => Ok(StyleBorderImageRepeat {
+            horizontal: parse_border_image_repeat(horizontal)?,
+            vertical: parse_border_image_repeat(vertical)?,
+        }),
+        [] => Err(CssBorderImageParseError::InvalidValue(input)),
+        _ => Err(CssBorderImageParseError::TooManyValues(input)),
+    }
+}
+
+/// Parses one side of a `border-image-width` or `border-image-outset`:
+/// `auto`, a number (multiple of the `border-width`) or a length
+fn parse_border_image_length(value: &str) -> Option<BorderImageLength> {
+    if value == "auto" {
+        Some(BorderImageLength::Auto)
+    } else if let Ok(number) = value.parse::<f32>() {
+        if number < 0.0 { None } else { Some(BorderImageLength::Number(FloatValue::new(number))) }
+    } else {
+        parse_pixel_value(value).ok().map(BorderImageLength::Length)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum CssGridParseError<'a> {
+    /// Value that is not valid for the property, i.e. `grid-template-columns: 1fr foo`
+    InvalidValue(&'a str),
+    /// `repeat()` with a count that isn't a positive whole number, i.e. `repeat(0, 1fr)`
+    InvalidRepeatCount(&'a str),
+    /// Grid line `0` or `span 0` - grid lines are counted starting at 1
+    InvalidLine(&'a str),
+    /// More slash-separated lines or more gaps than the property accepts, i.e. `1 / 2 / 3`
+    TooManyValues(&'a str),
+    PixelParseError(PixelParseError<'a>),
+}
+
+impl_debug_as_display!(CssGridParseError<'a>);
+impl_display!{ CssGridParseError<'a>, {
+    InvalidValue(e) => format!("Invalid value: \"{}\"", e),
+    InvalidRepeatCount(e) => format!("Invalid repeat count, expected a positive whole number: \"{}\"", e),
+    InvalidLine(e) => format!("Invalid grid line, lines start at 1: \"{}\"", e),
+    TooManyValues(e) => format!("Too many values: \"{}\"", e),
+    PixelParseError(e) => format!("{}", e),
+}}
+
+impl_from!(PixelParseError<'a>, CssGridParseError::PixelParseError);
+
+/// Start and end line of a grid item in one direction, parsed from a `grid-column`
+/// or `grid-row` shorthand - `None` means `auto`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridPlacement {
+    pub start: Option<GridLine>,
+    pub end: Option<GridLine>,
+}
+
+/// Represents a parsed `grid-area` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridArea {
+    pub row: GridPlacement,
+    pub column: GridPlacement,
+}
+
+/// Represents a parsed `gap` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGap {
+    pub row: PixelValue,
+    pub column: PixelValue,
+}
+
+/// Parses a `grid-template-columns` attribute, i.e. `"200px repeat(3, 1fr)"`
+pub fn parse_layout_grid_template_columns<'a>(input: &'a str)
+-> Result<LayoutGridTemplateColumns, CssGridParseError<'a>>
+{
+    parse_grid_track_list(input).map(LayoutGridTemplateColumns)
+}
+
+/// Parses a `grid-template-rows` attribute, i.e. `"auto minmax(50px, 1fr)"`
+pub fn parse_layout_grid_template_rows<'a>(input: &'a str)
+-> Result<LayoutGridTemplateRows, CssGridParseError<'a>>
+{
+    parse_grid_track_list(input).map(LayoutGridTemplateRows)
+}
+
+/// Parses a space-separated list of grid tracks. `repeat(count, tracks)` is expanded
+/// into `count` copies of the tracks, only whole numbers are supported as the count.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_grid_track_list;
+/// # use azul_css::{GridTrackSize, GridTrackBreadth, PixelValue, FloatValue};
+/// let fr = GridTrackSize::Breadth(GridTrackBreadth::Fraction(FloatValue::new(1.0)));
+/// let sidebar = GridTrackSize::MinMax(
+///     GridTrackBreadth::Length(PixelValue::px(100.0)),
+///     GridTrackBreadth::Length(PixelValue::percent(20.0)),
+/// );
+/// assert_eq!(parse_grid_track_list("minmax(100px, 20%) repeat(2, 1fr)"), Ok(vec![sidebar, fr, fr]));
+/// ```
+pub fn parse_grid_track_list<'a>(input: &'a str)
+-> Result<Vec<GridTrackSize>, CssGridParseError<'a>>
+{
+    use self::CssGridParseError::*;
+
+    let mut tracks = Vec::new();
+
+    for value in split_outside_parentheses(input, char::is_whitespace) {
+        if !value.starts_with("repeat(") {
+            tracks.push(parse_grid_track_size(value)?);
+            continue;
+        }
+
+        let (_, arguments) = parse_parentheses(value, &["repeat"]).map_err(|_| InvalidValue(value))?;
+        let (count, repeated) = match arguments.find(',') {
+            Some(comma) => (&arguments[..comma], &arguments[(comma + 1)..]),
+            None => return Err(InvalidValue(value)),
+        };
+        let count = count.trim().parse::<usize>().ok().filter(|count| *count > 0).ok_or(InvalidRepeatCount(value))?;
+
+        let repeated = split_outside_parentheses(repeated, char::is_whitespace)
+            .into_iter()
+            .map(|track| {
+                // repeat() can't be nested
+                if track.starts_with("repeat(") {
+                    Err(InvalidValue(track))
+                } else {
+                    parse_grid_track_size(track)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if repeated.is_empty() {
+            return Err(InvalidValue(value));
+        }
+
+        for _ in 0..count {
+            tracks.extend_from_slice(&repeated);
+        }
+    }
+
+    if tracks.is_empty() {
+        Err(InvalidValue(input))
+    } else {
+        Ok(tracks)
+    }
+}
+
+/// Parses the size of a single grid track, i.e. `"1fr"`, `"50%"` or `"minmax(100px, 1fr)"`
+pub fn parse_grid_track_size<'a>(input: &'a str)
+-> Result<GridTrackSize, CssGridParseError<'a>>
+{
+    let input = input.trim();
+
+    if !input.starts_with("minmax(") {
+        return parse_grid_track_breadth(input).map(GridTrackSize::Breadth);
+    }
+
+    let (_, arguments) = parse_parentheses(input, &["minmax"]).map_err(|_| CssGridParseError::InvalidValue(input))?;
+    match split_outside_parentheses(arguments, |c| c == ',').as_slice() {
+        [min, max] => match parse_grid_track_breadth(min)? {
+            // The minimum of a track can't be flexible
+            GridTrackBreadth::Fraction(_) => Err(CssGridParseError::InvalidValue(input)),
+            min => Ok(GridTrackSize::MinMax(min, parse_grid_track_breadth(max)?)),
+        },
+        _ => Err(CssGridParseError::InvalidValue(input)),
+    }
+}
+
+fn parse_grid_track_breadth<'a>(input: &'a str)
+-> Result<GridTrackBreadth, CssGridParseError<'a>>
+{
+    let input = input.trim();
+
+    if input == "auto" {
+        return Ok(GridTrackBreadth::Auto);
+    }
+
+    if input.ends_with("fr") {
+        match input[..input.len() - "fr".len()].parse::<f32>() {
+            Ok(fraction) if fraction >= 0.0 => Ok(GridTrackBreadth::Fraction(FloatValue::new(fraction))),
+            _ => Err(CssGridParseError::InvalidValue(input)),
+        }
+    } else {
+        Ok(GridTrackBreadth::Length(parse_pixel_value(input)?))
+    }
+}
+
+/// Parses the start or end of a grid item, i.e. `"2"`, `"-1"` (last line of the explicit grid)
+/// or `"span 2"` - `"auto"` is handled by the caller.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::{parse_grid_line, CssGridParseError};
+/// # use azul_css::GridLine;
+/// assert_eq!(parse_grid_line("-1"), Ok(GridLine::Line(-1)));
+/// assert_eq!(parse_grid_line("span 2"), Ok(GridLine::Span(2)));
+/// assert_eq!(parse_grid_line("0"), Err(CssGridParseError::InvalidLine("0")));
+/// ```
+pub fn parse_grid_line<'a>(input: &'a str)
+-> Result<GridLine, CssGridParseError<'a>>
+{
+    use self::CssGridParseError::*;
+
+    let input = input.trim();
+
+    let (is_span, number) = match input.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["span"] => return Ok(GridLine::Span(1)),
+        ["span", number] | [number, "span"] => (true, *number),
+        [number] => (false, *number),
+        _ => return Err(InvalidValue(input)),
+    };
+
+    match number.parse::<i32>() {
+        Ok(0) => Err(InvalidLine(input)),
+        Ok(span) if is_span && span < 0 => Err(InvalidLine(input)),
+        Ok(span) if is_span => Ok(GridLine::Span(span as u32)),
+        Ok(line) => Ok(GridLine::Line(line)),
+        Err(_) => Err(InvalidValue(input)),
+    }
+}
+
+macro_rules! typed_grid_line_parser {($fn:ident, $return:ident) => (
+    /// Parses the start or end line of a grid item, i.e. `"2"`, `"-1"` or `"span 2"`
+    pub fn $fn<'a>(input: &'a str) -> Result<$return, CssGridParseError<'a>> {
+        parse_grid_line(input).map($return)
+    }
+)}
+
+typed_grid_line_parser!(parse_layout_grid_column_start, LayoutGridColumnStart);
+typed_grid_line_parser!(parse_layout_grid_column_end, LayoutGridColumnEnd);
+typed_grid_line_parser!(parse_layout_grid_row_start, LayoutGridRowStart);
+typed_grid_line_parser!(parse_layout_grid_row_end, LayoutGridRowEnd);
+
+/// Parses the slash-separated lines of a `grid-column`, `grid-row` or `grid-area`, `auto` is returned as `None`
+fn parse_grid_lines<'a>(input: &'a str, max_lines: usize)
+-> Result<Vec<Option<GridLine>>, CssGridParseError<'a>>
+{
+    let lines = input.split('/').map(str::trim).collect::<Vec<_>>();
+    if lines.len() > max_lines {
+        return Err(CssGridParseError::TooManyValues(input));
+    }
+
+    lines.into_iter().map(|line| {
+        if line == "auto" {
+            Ok(None)
+        } else {
+            parse_grid_line(line).map(Some)
+        }
+    }).collect()
+}
+
+/// Parses a `grid-column` or `grid-row` shorthand, i.e. `"1 / 3"`, `"2 / span 2"` or `"3"`.
+/// If the end line is omitted, it defaults to `auto` (the item spans one track).
+pub fn parse_layout_grid_placement<'a>(input: &'a str)
+-> Result<GridPlacement, CssGridParseError<'a>>
+{
+    let lines = parse_grid_lines(input, 2)?;
+    Ok(GridPlacement {
+        start: lines[0],
+        end: lines.get(1).cloned().unwrap_or(None),
+    })
+}
+
+/// Parses a `grid-area` shorthand in the order `row-start / column-start / row-end / column-end`,
+/// i.e. `"1 / 2 / 3 / 4"` or `"2 / 1"` - omitted lines default to `auto`.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::{parse_layout_grid_area, GridArea, GridPlacement};
+/// # use azul_css::GridLine;
+/// assert_eq!(parse_layout_grid_area("2 / 1 / span 2"), Ok(GridArea {
+///     row: GridPlacement { start: Some(GridLine::Line(2)), end: Some(GridLine::Span(2)) },
+///     column: GridPlacement { start: Some(GridLine::Line(1)), end: None },
+/// }));
+/// ```
+pub fn parse_layout_grid_area<'a>(input: &'a str)
+-> Result<GridArea, CssGridParseError<'a>>
+{
+    let lines = parse_grid_lines(input, 4)?;
+    let line = |idx: usize| lines.get(idx).cloned().unwrap_or(None);
+    Ok(GridArea {
+        row: GridPlacement { start: line(0), end: line(2) },
+        column: GridPlacement { start: line(1), end: line(3) },
+    })
+}
+
+/// Parses a `gap` shorthand, i.e. `"10px"` (both gaps) or `"10px 5%"` (row gap, column gap)
+pub fn parse_layout_gap<'a>(input: &'a str)
+-> Result<LayoutGap, CssGridParseError<'a>>
+{
+    match split_outside_parentheses(input, char::is_whitespace).as_slice() {
+        [both] => {
+            let both = parse_pixel_value(both)?;
+            Ok(LayoutGap { row: both, column: both })
+        },
+        [row, column] => Ok(LayoutGap {
+            row: parse_pixel_value(row)?,
+            column: parse_pixel_value(column)?,
+        }),
+        [] => Err(CssGridParseError::InvalidValue(input)),
+        _ => Err(CssGridParseError::TooManyValues(input)),
+    }
+}
+
+/// Converts a parsed grid line of a shorthand to the value of the longhand property
+fn grid_line_to_property<T>(line: Option<GridLine>, constructor: fn(GridLine) -> T) -> CssPropertyValue<T> {
+    match line {
+        Some(line) => CssPropertyValue::Exact(constructor(line)),
+        None => CssPropertyValue::Auto,
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CssFontFaceParseError<'a> {
+    /// `@font-face` without a `font-family` descriptor
+    MissingFontFamily,
+    /// `@font-face` without a `src` descriptor
+    MissingSrc,
+    /// A `src` entry that is neither `url(...)` nor `local(...)`
+    InvalidSrc(&'a str),
+    /// A descriptor without a colon, i.e. `font-family "Arial"`
+    MalformedDescriptor(&'a str),
+    /// A `font-weight` or `font-style` descriptor with an invalid value
+    InvalidDescriptorValue(&'a str),
+    UnclosedQuotes(&'a str),
+}
+
+impl_display!{CssFontFaceParseError<'a>, {
+    MissingFontFamily => format!("@font-face is missing a \"font-family\""),
+    MissingSrc => format!("@font-face is missing a \"src\""),
+    InvalidSrc(val) => format!("Invalid @font-face src: \"{}\" - expected \"url(...)\" or \"local(...)\"", val),
+    MalformedDescriptor(val) => format!("Malformed @font-face descriptor: \"{}\"", val),
+    InvalidDescriptorValue(val) => format!("Invalid @font-face descriptor value: \"{}\"", val),
+    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+}}
+
+impl<'a> From<UnclosedQuotesError<'a>> for CssFontFaceParseError<'a> {
+    fn from(err: UnclosedQuotesError<'a>) -> Self {
+        CssFontFaceParseError::UnclosedQuotes(err.0)
+    }
+}
+
+/// Parses the contents of a `@font-face { ... }` block. Descriptors other than
+/// `font-family`, `src`, `font-weight` and `font-style` (such as `font-display`) are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_font_face;
+/// # use azul_css::{FontFace, FontFaceSource, StyleFontWeight, StyleFontStyle};
+/// let input = "font-family: 'My Font'; src: local(Arial), url(\"fonts/MyFont.ttf\") format(\"truetype\");";
+///
+/// assert_eq!(parse_font_face(input), Ok(FontFace {
+///     font_family: "My Font".into(),
+///     sources: vec![
+///         FontFaceSource::Local("Arial".into()),
+///         FontFaceSource::Url("fonts/MyFont.ttf".into()),
+///     ],
+///     font_weight: StyleFontWeight::NORMAL,
+///     font_style: StyleFontStyle::Normal,
+/// }));
+/// ```
+pub fn parse_font_face<'a>(input: &'a str) -> Result<FontFace, CssFontFaceParseError<'a>> {
+
+    let mut font_family = None;
+    let mut sources = None;
+    let mut font_weight = StyleFontWeight::NORMAL;
+    let mut font_style = StyleFontStyle::Normal;
+
+    for descriptor in input.split(';').map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        let colon = descriptor.find(':').ok_or(CssFontFaceParseError::MalformedDescriptor(descriptor))?;
+        let value = descriptor[(colon + 1)..].trim();
+        match descriptor[..colon].trim() {
+            "font-family" => font_family = Some(strip_optional_quotes(value)?.to_string()),
+            "src" => sources = Some(parse_font_face_src(value)?),
+            "font-weight" => font_weight = parse_style_font_weight(value).map_err(|e| CssFontFaceParseError::InvalidDescriptorValue(e.0))?,
+            "font-style" => font_style = parse_style_font_style(value).map_err(|e| CssFontFaceParseError::InvalidDescriptorValue(e.0))?,
+            _ => { },
+        }
+    }
+
+    Ok(FontFace {
+        font_family: font_family.ok_or(CssFontFaceParseError::MissingFontFamily)?,
+        sources: sources.ok_or(CssFontFaceParseError::MissingSrc)?,
+        font_weight,
+        font_style,
+    })
+}
+
+/// Parses `url("a.ttf") format("truetype"), local(Arial)`
+fn parse_font_face_src<'a>(input: &'a str) -> Result<Vec<FontFaceSource>, CssFontFaceParseError<'a>> {
+
+    let mut sources = Vec::new();
+    let mut source_start = 0;
+    let mut depth = 0_usize;
+    let mut quote = None;
+
+    // split at all commas that are not inside of quotes or parentheses
+    for (idx, ch) in input.char_indices().chain(Some((input.len(), ','))) {
+        match (ch, quote) {
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => { },
+            ('"', None) | ('\'', None) => quote = Some(ch),
+            ('(', None) => depth += 1,
+            (')', None) => depth = depth.saturating_sub(1),
+            (',', None) if depth == 0 => {
+                sources.push(parse_font_face_source(input[source_start..idx].trim())?);
+                source_start = idx + 1;
+            },
+            _ => { },
+        }
+    }
+
+    if quote.is_some() {
+        return Err(CssFontFaceParseError::UnclosedQuotes(input));
+    }
+
+    Ok(sources)
+}
+
+/// Parses a single `url("a.ttf") format("truetype")` or `local(Arial)` entry,
+/// the optional `format()` hint is ignored
+fn parse_font_face_source<'a>(input: &'a str) -> Result<FontFaceSource, CssFontFaceParseError<'a>> {
+
+    let function = match input.find(')') {
+        Some(closing_brace) => &input[..=closing_brace],
+        None => input,
+    };
+
+    let (stopword, value) = parse_parentheses(function, &["url", "local"])
+        .map_err(|_| CssFontFaceParseError::InvalidSrc(input))?;
+    let value = strip_optional_quotes(value.trim())?;
+
+    if value.is_empty() {
+        return Err(CssFontFaceParseError::InvalidSrc(input));
+    }
+
+    Ok(match stopword {
+        "url" => FontFaceSource::Url(value.to_string()),
+        _ => FontFaceSource::Local(value.to_string()),
+    })
+}
+
+/// `"Arial"` -> `Arial`, but also accepts unquoted values
+fn strip_optional_quotes<'a>(input: &'a str) -> Result<&'a str, UnclosedQuotesError<'a>> {
+    if input.starts_with('"') || input.starts_with('\'') {
+        strip_quotes(input).map(|q| q.0)
+    } else {
+        Ok(input)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssAnimationParseError<'a> {
+    EmptyInput,
+    /// Unknown property in a `transition` list, i.e. `transition: colour 1s`
+    UnknownProperty(&'a str),
+    InvalidDuration(&'a str),
+    InvalidTimingFunction(&'a str),
+    /// Value that doesn't fit anywhere, i.e. a third duration or a second animation name
+    UnexpectedValue(&'a str),
+    /// `animation` without the name of a `@keyframes` rule
+    MissingAnimationName(&'a str),
+}
+
+impl_display!{ CssAnimationParseError<'a>, {
+    EmptyInput => format!("Empty transition or animation"),
+    UnknownProperty(e) => format!("Unknown property in transition: \"{}\"", e),
+    InvalidDuration(e) => format!("Invalid duration: \"{}\" - expected a value in \"s\" or \"ms\"", e),
+    InvalidTimingFunction(e) => format!("Invalid timing function: \"{}\"", e),
+    UnexpectedValue(e) => format!("Unexpected value: \"{}\"", e),
+    MissingAnimationName(e) => format!("Animation is missing the name of the @keyframes rule: \"{}\"", e),
+}}
+
+/// Splits the input at every character matching `is_separator` that is not inside of
+/// parentheses, i.e. `"a(b, c), d"` is split at `,` into `["a(b, c)", "d"]`.
+/// The parts are trimmed, empty parts are skipped.
+fn split_outside_parentheses<'a, F: Fn(char) -> bool>(input: &'a str, is_separator: F) -> Vec<&'a str> {
+
+    let mut parts = Vec::new();
+    let mut part_start = 0;
+    let mut depth = 0_usize;
+
+    for (idx, ch) in input.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && is_separator(c) => {
+                parts.push(input[part_start..idx].trim());
+                part_start = idx + c.len_utf8();
+            },
+            _ => { },
+        }
+    }
+
+    parts.push(input[part_start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Parses a duration such as `"200ms"` or `"1.5s"`
+pub fn parse_duration<'a>(input: &'a str) -> Result<Duration, CssAnimationParseError<'a>> {
+
+    let input = input.trim();
+    let (number, multiplier) = if input.ends_with("ms") {
+        (&input[..(input.len() - 2)], 1.0)
+    } else if input.ends_with('s') {
+        (&input[..(input.len() - 1)], 1000.0)
+    } else {
+        return Err(CssAnimationParseError::InvalidDuration(input));
+    };
+
+    let millis = number.parse::<f32>().map_err(|_| CssAnimationParseError::InvalidDuration(input))? * multiplier;
+    if millis < 0.0 || !millis.is_finite() {
+        return Err(CssAnimationParseError::InvalidDuration(input));
+    }
+
+    Ok(Duration::from_millis(millis.round() as u64))
+}
+
+/// Parses an easing function such as `"ease-in"`, `"cubic-bezier(0.1, 0.7, 1.0, 0.1)"` or `"steps(4, end)"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_animation_timing_function;
+/// # use azul_css::{AnimationTimingFunction, StepPosition, FloatValue};
+/// assert_eq!(parse_animation_timing_function("ease-in-out"), Ok(AnimationTimingFunction::EaseInOut));
+/// assert_eq!(parse_animation_timing_function("steps(4, start)"), Ok(AnimationTimingFunction::Steps(4, StepPosition::Start)));
+/// assert_eq!(
+///     parse_animation_timing_function("cubic-bezier(0.1, 0.7, 1.0, 0.1)"),
+///     Ok(AnimationTimingFunction::CubicBezier(FloatValue::new(0.1), FloatValue::new(0.7), FloatValue::new(1.0), FloatValue::new(0.1)))
+/// );
+/// ```
+pub fn parse_animation_timing_function<'a>(input: &'a str) -> Result<AnimationTimingFunction, CssAnimationParseError<'a>> {
+
+    use azul_css::AnimationTimingFunction::*;
+
+    let input = input.trim();
+    let invalid = || CssAnimationParseError::InvalidTimingFunction(input);
+
+    match input {
+        "linear" => return Ok(Linear),
+        "ease" => return Ok(Ease),
+        "ease-in" => return Ok(EaseIn),
+        "ease-out" => return Ok(EaseOut),
+        "ease-in-out" => return Ok(EaseInOut),
+        "step-start" => return Ok(Steps(1, StepPosition::Start)),
+        "step-end" => return Ok(Steps(1, StepPosition::End)),
+        _ => { },
+    }
+
+    let (function, arguments) = parse_parentheses(input, &["cubic-bezier", "steps"]).map_err(|_| invalid())?;
+    let arguments = arguments.split(',').map(|a| a.trim()).collect::<Vec<_>>();
+
+    match (function, arguments.as_slice()) {
+        ("cubic-bezier", [x1, y1, x2, y2]) => {
+            let x1 = parse_float_value(x1).map_err(|_| invalid())?;
+            let y1 = parse_float_value(y1).map_err(|_| invalid())?;
+            let x2 = parse_float_value(x2).map_err(|_| invalid())?;
+            let y2 = parse_float_value(y2).map_err(|_| invalid())?;
+            // the x coordinates have to be in the range of the animation
+            let in_range = |x: FloatValue| x.get() >= 0.0 && x.get() <= 1.0;
+            if !in_range(x1) || !in_range(x2) {
+                return Err(invalid());
+            }
+            Ok(CubicBezier(x1, y1, x2, y2))
+        },
+        ("steps", [steps]) | ("steps", [steps, _]) => {
+            let steps = steps.parse::<usize>().map_err(|_| invalid())?;
+            if steps == 0 {
+                return Err(invalid());
+            }
+            let position = match arguments.get(1) {
+                None | Some(&"end") | Some(&"jump-end") => StepPosition::End,
+                Some(&"start") | Some(&"jump-start") => StepPosition::Start,
+                Some(_) => return Err(invalid()),
+            };
+            Ok(Steps(steps, position))
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a `transition` attribute, i.e. `"background-color 200ms ease-in, width 1s"`.
+/// Shorthand properties such as `padding` are expanded to the properties they consist of.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_transitions;
+/// # use azul_css::{StyleTransitions, StyleTransition, AnimationTimingFunction, CssPropertyType};
+/// # use std::time::Duration;
+/// assert_eq!(parse_style_transitions("color 200ms ease-in 1s, all 0.5s"), Ok(StyleTransitions(vec![
+///     StyleTransition {
+///         property: Some(CssPropertyType::TextColor),
+///         duration: Duration::from_millis(200),
+///         timing_function: AnimationTimingFunction::EaseIn,
+///         delay: Duration::from_secs(1),
+///     },
+///     StyleTransition {
+///         property: None,
+///         duration: Duration::from_millis(500),
+///         timing_function: AnimationTimingFunction::Ease,
+///         delay: Duration::from_millis(0),
+///     },
+/// ])));
+/// ```
+pub fn parse_style_transitions<'a>(input: &'a str) -> Result<StyleTransitions, CssAnimationParseError<'a>> {
+
+    let css_key_map = get_css_key_map();
+    let mut transitions = Vec::new();
+
+    for transition in split_outside_parentheses(input, |c| c == ',') {
+        transitions.extend(parse_style_transition(transition, &css_key_map)?);
+    }
+
+    if transitions.is_empty() {
+        return Err(CssAnimationParseError::EmptyInput);
+    }
+
+    Ok(StyleTransitions(transitions))
+}
+
+/// Parses a single `background-color 200ms ease-in 1s` transition - returns
+/// multiple transitions if the property is a shorthand property
+fn parse_style_transition<'a>(input: &'a str, css_key_map: &CssKeyMap)
+-> Result<Vec<StyleTransition>, CssAnimationParseError<'a>>
+{
+    let mut properties = None;
+    let mut durations = Vec::new();
+    let mut timing_function = None;
+
+    for value in split_outside_parentheses(input, char::is_whitespace) {
+        if let Ok(duration) = parse_duration(value) {
+            if durations.len() == 2 {
+                return Err(CssAnimationParseError::UnexpectedValue(value));
+            }
+            durations.push(duration);
+        } else if let Ok(timing) = parse_animation_timing_function(value) {
+            if timing_function.is_some() {
+                return Err(CssAnimationParseError::UnexpectedValue(value));
+            }
+            timing_function = Some(timing);
+        } else {
+            if properties.is_some() {
+                return Err(CssAnimationParseError::UnexpectedValue(value));
+            }
+            properties = Some(if value == "all" {
+                vec![None]
+            } else if let Some(property) = CssPropertyType::from_str(value, css_key_map) {
+                vec![Some(property)]
+            } else if let Some(combined) = CombinedCssPropertyType::from_str(value, css_key_map) {
+                get_combined_css_property_keys(combined).into_iter().map(Some).collect()
+            } else {
+                return Err(CssAnimationParseError::UnknownProperty(value));
+            });
+        }
+    }
+
+    let duration = durations.get(0).cloned().unwrap_or_default();
+    let delay = durations.get(1).cloned().unwrap_or_default();
+    let timing_function = timing_function.unwrap_or_default();
+
+    Ok(properties.unwrap_or(vec![None]).into_iter().map(|property| StyleTransition {
+        property,
+        duration,
+        timing_function,
+        delay,
+    }).collect())
+}
+
+/// Parses an `animation` attribute, i.e. `"pulse 2s ease-in-out infinite alternate"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_animations;
+/// # use azul_css::*;
+/// # use std::time::Duration;
+/// assert_eq!(parse_style_animations("pulse 2s linear infinite alternate both"), Ok(StyleAnimations(vec![
+///     StyleAnimation {
+///         name: "pulse".into(),
+///         duration: Duration::from_secs(2),
+///         timing_function: AnimationTimingFunction::Linear,
+///         delay: Duration::from_millis(0),
+///         iteration_count: AnimationIterationCount::Infinite,
+///         direction: AnimationDirection::Alternate,
+///         fill_mode: AnimationFillMode::Both,
+///     },
+/// ])));
+/// ```
+pub fn parse_style_animations<'a>(input: &'a str) -> Result<StyleAnimations, CssAnimationParseError<'a>> {
+
+    let animations = split_outside_parentheses(input, |c| c == ',')
+        .into_iter()
+        .map(|animation| parse_style_animation(animation))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if animations.is_empty() {
+        return Err(CssAnimationParseError::EmptyInput);
+    }
+
+    Ok(StyleAnimations(animations))
+}
+
+/// Parses a single `pulse 2s ease-in-out 500ms infinite alternate forwards` animation.
+/// The values can be in any order, the first duration is the duration, the second the delay.
+fn parse_style_animation<'a>(input: &'a str) -> Result<StyleAnimation, CssAnimationParseError<'a>> {
+
+    use self::CssAnimationParseError::UnexpectedValue;
+
+    let mut name = None;
+    let mut durations = Vec::new();
+    let mut timing_function = None;
+    let mut iteration_count = None;
+    let mut direction = None;
+    let mut fill_mode = None;
+
+    // Returns an error if the value was already set
+    fn set_once<'a, T>(target: &mut Option<T>, value: T, input: &'a str) -> Result<(), CssAnimationParseError<'a>> {
+        if target.is_some() {
+            return Err(UnexpectedValue(input));
+        }
+        *target = Some(value);
+        Ok(())
+    }
+
+    for value in split_outside_parentheses(input, char::is_whitespace) {
+        if let Ok(duration) = parse_duration(value) {
+            if durations.len() == 2 {
+                return Err(UnexpectedValue(value));
+            }
+            durations.push(duration);
+        } else if let Ok(timing) = parse_animation_timing_function(value) {
+            set_once(&mut timing_function, timing, value)?;
+        } else if value == "infinite" {
+            set_once(&mut iteration_count, AnimationIterationCount::Infinite, value)?;
+        } else if let Ok(count) = value.parse::<f32>() {
+            if count < 0.0 {
+                return Err(UnexpectedValue(value));
+            }
+            set_once(&mut iteration_count, AnimationIterationCount::Count(FloatValue::new(count)), value)?;
+        } else if let Ok(d) = parse_animation_direction(value) {
+            set_once(&mut direction, d, value)?;
+        } else if let Ok(f) = parse_animation_fill_mode(value) {
+            set_once(&mut fill_mode, f, value)?;
+        } else {
+            set_once(&mut name, value.to_string(), value)?;
+        }
+    }
+
+    Ok(StyleAnimation {
+        name: name.ok_or(CssAnimationParseError::MissingAnimationName(input))?,
+        duration: durations.get(0).cloned().unwrap_or_default(),
+        timing_function: timing_function.unwrap_or_default(),
+        delay: durations.get(1).cloned().unwrap_or_default(),
+        iteration_count: iteration_count.unwrap_or_default(),
+        direction: direction.unwrap_or_default(),
+        fill_mode: fill_mode.unwrap_or_default(),
+    })
+}
+
+multi_type_parser!(parse_animation_direction, AnimationDirection,
+                    ["normal", Normal],
+                    ["reverse", Reverse],
+                    ["alternate", Alternate],
+                    ["alternate-reverse", AlternateReverse]);
+
+multi_type_parser!(parse_animation_fill_mode, AnimationFillMode,
+                    ["none", None],
+                    ["forwards", Forwards],
+                    ["backwards", Backwards],
+                    ["both", Both]);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssKeyframesParseError<'a> {
+    /// `@keyframes { ... }` without a name
+    MissingName,
+    /// Keyframe selector that is neither `from`, `to` nor a percentage, i.e. `half { ... }`
+    InvalidSelector(&'a str),
+    /// Keyframe without a `{ ... }` block or with a missing closing brace
+    MalformedKeyframe(&'a str),
+    /// A declaration without a colon, i.e. `color red`
+    MalformedDeclaration(&'a str),
+    UnsupportedProperty(&'a str),
+    InvalidValue(CssParsingError<'a>),
+}
+
+impl_display!{CssKeyframesParseError<'a>, {
+    MissingName => format!("@keyframes is missing a name"),
+    InvalidSelector(val) => format!("Invalid keyframe selector: \"{}\" - expected \"from\", \"to\" or a percentage", val),
+    MalformedKeyframe(val) => format!("Malformed keyframe: \"{}\"", val),
+    MalformedDeclaration(val) => format!("Malformed declaration in keyframe: \"{}\"", val),
+    UnsupportedProperty(val) => format!("Unsupported property in keyframe: \"{}\"", val),
+    InvalidValue(e) => format!("{}", e),
+}}
+
+impl_from!(CssParsingError<'a>, CssKeyframesParseError::InvalidValue);
+
+/// Parses the contents of a `@keyframes pulse { ... }` block.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_keyframes;
+/// # use azul_css::*;
+/// let input = "from, to { width: 0px; } 50% { width: 100px; }";
+///
+/// assert_eq!(parse_keyframes("grow", input), Ok(CssKeyframes {
+///     name: "grow".into(),
+///     keyframes: vec![
+///         CssKeyframe { offset: PercentageValue::new(0.0), declarations: vec![LayoutWidth::px(0.0).into()] },
+///         CssKeyframe { offset: PercentageValue::new(50.0), declarations: vec![LayoutWidth::px(100.0).into()] },
+///         CssKeyframe { offset: PercentageValue::new(100.0), declarations: vec![LayoutWidth::px(0.0).into()] },
+///     ],
+/// }));
+/// ```
+pub fn parse_keyframes<'a>(name: &'a str, input: &'a str) -> Result<CssKeyframes, CssKeyframesParseError<'a>> {
+
+    use self::CssKeyframesParseError::*;
+
+    let name = strip_optional_quotes(name.trim()).map_err(|_| MissingName)?;
+    if name.is_empty() {
+        return Err(MissingName);
+    }
+
+    let css_key_map = get_css_key_map();
+    let mut keyframes = Vec::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+
+        let block_start = rest.find('{').ok_or(MalformedKeyframe(rest))?;
+        let block_end = rest.find('}').ok_or(MalformedKeyframe(rest))?;
+        if block_end < block_start {
+            return Err(MalformedKeyframe(rest));
+        }
+
+        let declarations = parse_keyframe_declarations(&rest[(block_start + 1)..block_end], &css_key_map)?;
+
+        for selector in rest[..block_start].split(',').map(|s| s.trim()) {
+            let offset = match selector {
+                "from" => PercentageValue::new(0.0),
+                "to" => PercentageValue::new(100.0),
+                percentage => parse_percentage(percentage)
+                    .ok()
+                    .filter(|p| p.get() >= 0.0 && p.get() <= 100.0)
+                    .ok_or(InvalidSelector(selector))?,
+            };
+            keyframes.push(CssKeyframe { offset, declarations: declarations.clone() });
+        }
+
+        rest = rest[(block_end + 1)..].trim();
+    }
+
+    // stable sort, so that keyframes with the same offset keep their order
+    keyframes.sort_by_key(|keyframe| keyframe.offset);
+
+    Ok(CssKeyframes { name: name.to_string(), keyframes })
+}
+
+/// Parses the `width: 0px; padding: 5px` declarations of a keyframe
+fn parse_keyframe_declarations<'a>(input: &'a str, css_key_map: &CssKeyMap)
+-> Result<Vec<CssProperty>, CssKeyframesParseError<'a>>
+{
+    use self::CssKeyframesParseError::*;
+
+    let mut declarations = Vec::new();
+
+    for declaration in input.split(';').map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        let colon = declaration.find(':').ok_or(MalformedDeclaration(declaration))?;
+        let key = declaration[..colon].trim();
+        let value = declaration[(colon + 1)..].trim();
+        if let Some(combined_key) = CombinedCssPropertyType::from_str(key, css_key_map) {
+            declarations.extend(parse_combined_css_property(combined_key, value)?);
+        } else if let Some(key) = CssPropertyType::from_str(key, css_key_map) {
+            declarations.push(parse_css_property(key, value)?);
+        } else {
+            return Err(UnsupportedProperty(key));
+        }
+    }
+
+    Ok(declarations)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssStyleTransformParseError<'a> {
+    /// Unknown function or a value that isn't a function, i.e. `rotate3d(...)` or `45deg`
+    InvalidFunction(&'a str),
+    WrongNumberOfArguments(&'a str),
+    /// Angle without a unit (other than `0`), i.e. `rotate(45)`
+    InvalidAngle(&'a str),
+    InvalidNumber(&'a str),
+    PixelParseError(PixelParseError<'a>),
+    /// `transform-origin` with more than two values or two values for the same axis
+    InvalidOrigin(&'a str),
+}
+
+impl_display!{ CssStyleTransformParseError<'a>, {
+    InvalidFunction(e) => format!("Invalid transform function: \"{}\"", e),
+    WrongNumberOfArguments(e) => format!("Wrong number of arguments in transform function: \"{}\"", e),
+    InvalidAngle(e) => format!("Invalid angle: \"{}\" - expected a value in \"deg\", \"rad\", \"grad\" or \"turn\"", e),
+    InvalidNumber(e) => format!("Invalid number: \"{}\"", e),
+    PixelParseError(e) => format!("{}", e),
+    InvalidOrigin(e) => format!("Invalid transform-origin: \"{}\"", e),
+}}
+
+impl_from!(PixelParseError<'a>, CssStyleTransformParseError::PixelParseError);
+
+/// Parses an angle such as `"45deg"`, `"1.5rad"`, `"100grad"` or `"0.25turn"` into degrees.
+/// Unlike the angle of a gradient, the angle is not normalized, since `rotate(720deg)`
+/// is not the same as `rotate(0deg)` when animated.
+pub fn parse_angle<'a>(input: &'a str) -> Result<FloatValue, CssStyleTransformParseError<'a>> {
+
+    use std::f32::consts::PI;
+
+    let input = input.trim();
+    let (number, multiplier) = if input.ends_with("deg") {
+        (&input[..(input.len() - 3)], 1.0)
+    } else if input.ends_with("grad") {
+        (&input[..(input.len() - 4)], 360.0 / 400.0)
+    } else if input.ends_with("rad") {
+        (&input[..(input.len() - 3)], 180.0 / PI)
+    } else if input.ends_with("turn") {
+        (&input[..(input.len() - 4)], 360.0)
+    } else if input == "0" {
+        (input, 0.0)
+    } else {
+        return Err(CssStyleTransformParseError::InvalidAngle(input));
+    };
+
+    let number = number.trim().parse::<f32>().map_err(|_| CssStyleTransformParseError::InvalidAngle(input))?;
+    Ok(FloatValue::new(number * multiplier))
+}
+
+/// Parses a `transform` attribute, i.e. `"translate(-50%, 10px) rotate(45deg) scale(1.5)"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_transform;
+/// # use azul_css::{StyleTransform, StyleTransformFunction::*, PixelValue, FloatValue};
+/// assert_eq!(parse_style_transform("translateX(-50%) rotate(0.25turn) scale(2)"), Ok(StyleTransform(vec![
+///     Translate(PixelValue::percent(-50.0), PixelValue::px(0.0)),
+///     Rotate(FloatValue::new(90.0)),
+///     Scale(FloatValue::new(2.0), FloatValue::new(2.0)),
+/// ])));
+/// ```
+pub fn parse_style_transform<'a>(input: &'a str) -> Result<StyleTransform, CssStyleTransformParseError<'a>> {
+    split_outside_parentheses(input, char::is_whitespace)
+        .into_iter()
+        .map(parse_style_transform_function)
+        .collect::<Result<Vec<_>, _>>()
+        .map(StyleTransform)
+}
+
+fn parse_style_transform_function<'a>(input: &'a str) -> Result<StyleTransformFunction, CssStyleTransformParseError<'a>> {
+
+    use azul_css::StyleTransformFunction::*;
+    use self::CssStyleTransformParseError::*;
+
+    let (function, arguments) = parse_parentheses(input, &[
+        "matrix", "translate", "translateX", "translateY",
+        "rotate", "scale", "scaleX", "scaleY", "skew", "skewX", "skewY",
+    ]).map_err(|_| InvalidFunction(input))?;
+
+    let arguments = arguments.split(',').map(|a| a.trim()).collect::<Vec<_>>();
+    let number = |n: &'a str| parse_float_value(n).map_err(|_| InvalidNumber(n));
+    let zero_px = PixelValue::const_px(0);
+    let zero_deg = FloatValue::const_new(0);
+    let one = FloatValue::const_new(1);
+
+    Ok(match (function, arguments.as_slice()) {
+        ("matrix", [a, b, c, d, tx, ty]) => Matrix([number(a)?, number(b)?, number(c)?, number(d)?, number(tx)?, number(ty)?]),
+        ("translate", [x]) => Translate(parse_pixel_value(x)?, zero_px),
+        ("translate", [x, y]) => Translate(parse_pixel_value(x)?, parse_pixel_value(y)?),
+        ("translateX", [x]) => Translate(parse_pixel_value(x)?, zero_px),
+        ("translateY", [y]) => Translate(zero_px, parse_pixel_value(y)?),
+        ("rotate", [angle]) => Rotate(parse_angle(angle)?),
+        ("scale", [s]) => Scale(number(s)?, number(s)?),
+        ("scale", [x, y]) => Scale(number(x)?, number(y)?),
+        ("scaleX", [x]) => Scale(number(x)?, one),
+        ("scaleY", [y]) => Scale(one, number(y)?),
+        ("skew", [x]) => Skew(parse_angle(x)?, zero_deg),
+        ("skew", [x, y]) => Skew(parse_angle(x)?, parse_angle(y)?),
+        ("skewX", [x]) => Skew(parse_angle(x)?, zero_deg),
+        ("skewY", [y]) => Skew(zero_deg, parse_angle(y)?),
+        _ => return Err(WrongNumberOfArguments(input)),
+    })
+}
+
+/// Parses a `transform-origin` attribute, i.e. `"left top"`, `"50% 20px"` or `"bottom"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_transform_origin;
+/// # use azul_css::{StyleTransformOrigin, PixelValue};
+/// assert_eq!(parse_style_transform_origin("right 10px"), Ok(StyleTransformOrigin {
+///     x: PixelValue::percent(100.0),
+///     y: PixelValue::px(10.0),
+/// }));
+/// assert_eq!(parse_style_transform_origin("top left"), Ok(StyleTransformOrigin {
+///     x: PixelValue::percent(0.0),
+///     y: PixelValue::percent(0.0),
+/// }));
+/// ```
+pub fn parse_style_transform_origin<'a>(input: &'a str) -> Result<StyleTransformOrigin, CssStyleTransformParseError<'a>> {
+
+    #[derive(Copy, Clone, PartialEq)]
+    enum Axis { X, Y, Both }
+
+    let parse_value = |value: &'a str| -> Result<(Axis, PixelValue), CssStyleTransformParseError<'a>> {
+        Ok(match value {
+            "left" => (Axis::X, PixelValue::const_percent(0)),
+            "right" => (Axis::X, PixelValue::const_percent(100)),
+            "top" => (Axis::Y, PixelValue::const_percent(0)),
+            "bottom" => (Axis::Y, PixelValue::const_percent(100)),
+            "center" => (Axis::Both, PixelValue::const_percent(50)),
+            other => (Axis::Both, parse_pixel_value(other)?),
+        })
+    };
+
+    let invalid = || CssStyleTransformParseError::InvalidOrigin(input);
+    let center = PixelValue::const_percent(50);
+    let values = input.split_whitespace().collect::<Vec<_>>();
 
-pub fn parse_style_line_height(input: &str)
--> Result<StyleLineHeight, PercentageParseError>
-{
-    parse_percentage_value(input).and_then(|e| Ok(StyleLineHeight(e)))
+    match values.as_slice() {
+        [value] => match parse_value(value)? {
+            (Axis::Y, y) => Ok(StyleTransformOrigin { x: center, y }),
+            (_, x) => Ok(StyleTransformOrigin { x, y: center }),
+        },
+        [first, second] => {
+            let is_keyword = |value: &str| ["left", "right", "top", "bottom", "center"].contains(&value);
+            match (parse_value(first)?, parse_value(second)?) {
+                ((Axis::X, _), (Axis::X, _)) | ((Axis::Y, _), (Axis::Y, _)) => Err(invalid()),
+                // two keywords can be written in any order, i.e. "top left"
+                ((Axis::Y, y), (_, x)) | ((_, y), (Axis::X, x)) if is_keyword(first) && is_keyword(second) => {
+                    Ok(StyleTransformOrigin { x, y })
+                },
+                ((Axis::Y, _), _) | (_, (Axis::X, _)) => Err(invalid()),
+                ((_, x), (_, y)) => Ok(StyleTransformOrigin { x, y }),
+            }
+        },
+        _ => Err(invalid()),
+    }
 }
 
-typed_pixel_value_parser!(parse_style_font_size, StyleFontSize);
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub enum CssStyleFontFamilyParseError<'a> {
-    InvalidStyleFontFamily(&'a str),
-    UnclosedQuotes(&'a str),
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssStyleFilterParseError<'a> {
+    /// Unknown function or a value that isn't a function, i.e. `sepia(...)` or `5px`
+    InvalidFunction(&'a str),
+    WrongNumberOfArguments(&'a str),
+    /// Not a number or a percentage, i.e. `grayscale(5px)`
+    InvalidAmount(&'a str),
+    PixelParseError(PixelParseError<'a>),
+    ColorParseError(CssColorParseError<'a>),
 }
 
-impl_display!{CssStyleFontFamilyParseError<'a>, {
-    InvalidStyleFontFamily(val) => format!("Invalid font-family: \"{}\"", val),
-    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+impl_display!{ CssStyleFilterParseError<'a>, {
+    InvalidFunction(e) => format!("Invalid filter function: \"{}\"", e),
+    WrongNumberOfArguments(e) => format!("Wrong number of arguments in filter function: \"{}\"", e),
+    InvalidAmount(e) => format!("Invalid amount: \"{}\" - expected a number or a percentage", e),
+    PixelParseError(e) => format!("{}", e),
+    ColorParseError(e) => format!("{}", e),
 }}
 
-impl<'a> From<UnclosedQuotesError<'a>> for CssStyleFontFamilyParseError<'a> {
-    fn from(err: UnclosedQuotesError<'a>) -> Self {
-        CssStyleFontFamilyParseError::UnclosedQuotes(err.0)
-    }
+impl_from!(PixelParseError<'a>, CssStyleFilterParseError::PixelParseError);
+impl_from!(CssColorParseError<'a>, CssStyleFilterParseError::ColorParseError);
+
+/// Parses an amount that can be written as a number or as a percentage, i.e. `"0.5"` or `"50%"`
+fn parse_amount<'a>(input: &'a str) -> Result<FloatValue, CssStyleFilterParseError<'a>> {
+    let input = input.trim();
+    let number = if input.ends_with('%') {
+        input[..input.len() - 1].trim().parse::<f32>().map(|n| n / 100.0)
+    } else {
+        input.parse::<f32>()
+    };
+    number.map(FloatValue::new).map_err(|_| CssStyleFilterParseError::InvalidAmount(input))
 }
 
-/// Parses a `StyleFontFamily` declaration from a `&str`
+/// Parses an `opacity` attribute, i.e. `"0.5"` or `"50%"`
 ///
 /// # Example
 ///
 /// ```rust
 /// # extern crate azul_css;
 /// # extern crate azul_css_parser;
-/// # use azul_css_parser::parse_style_font_family;
-/// # use azul_css::{StyleFontFamily, FontId};
-/// let input = "\"Helvetica\", 'Arial', Times New Roman";
-/// let fonts = vec![
-///     FontId("Helvetica".into()),
-///     FontId("Arial".into()),
-///     FontId("Times New Roman".into())
-/// ];
-///
-/// assert_eq!(parse_style_font_family(input), Ok(StyleFontFamily { fonts }));
+/// # use azul_css_parser::parse_style_opacity;
+/// # use azul_css::{StyleOpacity, FloatValue};
+/// assert_eq!(parse_style_opacity("25%"), Ok(StyleOpacity(FloatValue::new(0.25))));
 /// ```
-pub fn parse_style_font_family<'a>(input: &'a str) -> Result<StyleFontFamily, CssStyleFontFamilyParseError<'a>> {
-    let multiple_fonts = input.split(',');
-    let mut fonts = Vec::with_capacity(1);
+pub fn parse_style_opacity<'a>(input: &'a str) -> Result<StyleOpacity, CssStyleFilterParseError<'a>> {
+    parse_amount(input).map(StyleOpacity)
+}
 
-    for font in multiple_fonts {
-        let font = font.trim();
-        let font = font.trim_matches('\'');
-        let font = font.trim_matches('\"');
-        let font = font.trim();
-        fonts.push(FontId(font.into()));
-    }
+/// Parses a `filter` attribute, i.e. `"blur(5px) grayscale(100%)"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_filter;
+/// # use azul_css::{StyleFilter, StyleFilterFunction::*, PixelValue, PixelValueNoPercent, FloatValue};
+/// assert_eq!(parse_style_filter("blur(2px) brightness(1.5)"), Ok(StyleFilter(vec![
+///     Blur(PixelValueNoPercent(PixelValue::px(2.0))),
+///     Brightness(FloatValue::new(1.5)),
+/// ])));
+/// ```
+pub fn parse_style_filter<'a>(input: &'a str) -> Result<StyleFilter, CssStyleFilterParseError<'a>> {
+    split_outside_parentheses(input, char::is_whitespace)
+        .into_iter()
+        .map(parse_style_filter_function)
+        .collect::<Result<Vec<_>, _>>()
+        .map(StyleFilter)
+}
 
-    Ok(StyleFontFamily {
-        fonts: fonts,
+fn parse_style_filter_function<'a>(input: &'a str) -> Result<StyleFilterFunction, CssStyleFilterParseError<'a>> {
+
+    use azul_css::StyleFilterFunction::*;
+    use self::CssStyleFilterParseError::*;
+
+    let (function, argument) = parse_parentheses(input, &[
+        "blur", "grayscale", "brightness", "opacity", "drop-shadow",
+    ]).map_err(|_| InvalidFunction(input))?;
+
+    let argument = argument.trim();
+
+    Ok(match function {
+        "blur" => Blur(parse_pixel_value_no_percent(argument)?),
+        "grayscale" => Grayscale(parse_amount(argument)?),
+        "brightness" => Brightness(parse_amount(argument)?),
+        "opacity" => Opacity(parse_amount(argument)?),
+        "drop-shadow" => {
+            // drop-shadow(offset-x offset-y [blur-radius] [color]), the color may contain spaces
+            let mut lengths = Vec::new();
+            let mut rest = argument;
+            while let Some(next) = rest.split_whitespace().next() {
+                match parse_pixel_value_no_percent(next) {
+                    Ok(length) => {
+                        lengths.push(length);
+                        rest = rest[next.len()..].trim();
+                    },
+                    Err(_) => break,
+                }
+            }
+            let color = if rest.is_empty() { ColorU { r: 0, g: 0, b: 0, a: 255 } } else { parse_css_color(rest)? };
+            let zero_px = PixelValueNoPercent(PixelValue::const_px(0));
+            match lengths.as_slice() {
+                [x, y] => DropShadow { offset: [*x, *y], blur_radius: zero_px, color },
+                [x, y, blur] => DropShadow { offset: [*x, *y], blur_radius: *blur, color },
+                _ => return Err(WrongNumberOfArguments(input)),
+            }
+        },
+        _ => unreachable!(),
     })
 }
 
@@ -2373,19 +4479,47 @@ multi_type_parser!(parse_style_cursor, StyleCursor,
                     ["zoom-in", ZoomIn],
                     ["zoom-out", ZoomOut]);
 
-multi_type_parser!(parse_style_background_size, StyleBackgroundSize,
-                    ["contain", Contain],
-                    ["cover", Cover]);
-
 multi_type_parser!(parse_style_background_repeat, StyleBackgroundRepeat,
                     ["no-repeat", NoRepeat],
                     ["repeat", Repeat],
                     ["repeat-x", RepeatX],
                     ["repeat-y", RepeatY]);
 
+multi_type_parser!(parse_border_image_repeat, BorderImageRepeat,
+                    ["stretch", Stretch],
+                    ["repeat", Repeat],
+                    ["round", Round],
+                    ["space", Space]);
+
+multi_type_parser!(parse_style_font_style, StyleFontStyle,
+                    ["normal", Normal],
+                    ["italic", Italic],
+                    ["oblique", Oblique]);
+
+multi_type_parser!(parse_style_mix_blend_mode, StyleMixBlendMode,
+                    ["normal", Normal],
+                    ["multiply", Multiply],
+                    ["screen", Screen],
+                    ["overlay", Overlay],
+                    ["darken", Darken],
+                    ["lighten", Lighten],
+                    ["color-dodge", ColorDodge],
+                    ["color-burn", ColorBurn],
+                    ["hard-light", HardLight],
+                    ["soft-light", SoftLight],
+                    ["difference", Difference],
+                    ["exclusion", Exclusion],
+                    ["hue", Hue],
+                    ["saturation", Saturation],
+                    ["color", Color],
+                    ["luminosity", Luminosity]);
+
 multi_type_parser!(parse_layout_display, LayoutDisplay,
                     ["flex", Flex],
-                    ["inline", Inline]);
+                    ["grid", Grid],
+                    ["block", Block],
+                    ["inline", Inline],
+                    ["inline-block", InlineBlock]);
 
 multi_type_parser!(parse_layout_float, LayoutFloat,
                     ["left", Left],
@@ -2427,6 +4561,10 @@ multi_type_parser!(parse_layout_align_content, LayoutAlignContent,
                     ["space-between", SpaceBetween],
                     ["space-around", SpaceAround]);
 
+multi_type_parser!(parse_layout_grid_auto_flow, LayoutGridAutoFlow,
+                    ["row", Row],
+                    ["column", Column]);
+
 multi_type_parser!(parse_shape, Shape,
                     ["circle", Circle],
                     ["ellipse", Ellipse]);
@@ -2434,7 +4572,9 @@ multi_type_parser!(parse_shape, Shape,
 multi_type_parser!(parse_layout_position, LayoutPosition,
                     ["static", Static],
                     ["absolute", Absolute],
-                    ["relative", Relative]);
+                    ["relative", Relative],
+                    ["fixed", Fixed],
+                    ["sticky", Sticky]);
 
 multi_type_parser!(parse_layout_overflow, Overflow,
                     ["auto", Auto],
@@ -2447,6 +4587,193 @@ multi_type_parser!(parse_layout_text_align, StyleTextAlignmentHorz,
                     ["left", Left],
                     ["right", Right]);
 
+multi_type_parser!(parse_media_type, MediaType,
+                    ["all", All],
+                    ["screen", Screen],
+                    ["print", Print]);
+
+multi_type_parser!(parse_media_orientation, MediaOrientation,
+                    ["portrait", Portrait],
+                    ["landscape", Landscape]);
+
+multi_type_parser!(parse_color_scheme, ColorScheme,
+                    ["light", Light],
+                    ["dark", Dark]);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssMediaQueryParseError<'a> {
+    EmptyQuery,
+    /// Unknown media type or missing "and" between two conditions
+    UnexpectedToken(&'a str),
+    UnclosedParenthesis(&'a str),
+    /// Feature is not of the form `(name: value)`
+    MissingFeatureValue(&'a str),
+    UnsupportedFeature(&'a str),
+    InvalidMediaType(InvalidValueErr<'a>),
+    InvalidSize(PixelParseError<'a>),
+    InvalidResolution(&'a str),
+}
+
+impl_display!{ CssMediaQueryParseError<'a>, {
+    EmptyQuery => format!("Empty media query"),
+    UnexpectedToken(e) => format!("Unexpected token in media query: \"{}\"", e),
+    UnclosedParenthesis(e) => format!("Unclosed parenthesis in media query: \"{}\"", e),
+    MissingFeatureValue(e) => format!("Expected media feature of the form \"(name: value)\", got: \"({})\"", e),
+    UnsupportedFeature(e) => format!("Media feature \"{}\" is not (yet) supported", e),
+    InvalidMediaType(e) => format!("Invalid media query value: \"{}\"", e.0),
+    InvalidSize(e) => format!("Invalid size in media query: {}", e),
+    InvalidResolution(e) => format!("Invalid resolution in media query: \"{}\" - expected \"dppx\", \"x\", \"dpi\" or \"dpcm\"", e),
+}}
+
+impl_from!(InvalidValueErr<'a>, CssMediaQueryParseError::InvalidMediaType);
+impl_from!(PixelParseError<'a>, CssMediaQueryParseError::InvalidSize);
+
+/// Parses the condition of an `@media` block, i.e. `screen and (min-width: 600px), print`
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_media_query_list;
+/// # use azul_css::{MediaQueryList, MediaQuery, MediaType, MediaFeature, MediaOrientation, PixelValue};
+/// assert_eq!(
+///     parse_media_query_list("screen and (min-width: 600px), (orientation: portrait)"),
+///     Ok(MediaQueryList { queries: vec![
+///         MediaQuery {
+///             negated: false,
+///             media_type: MediaType::Screen,
+///             features: vec![MediaFeature::MinWidth(PixelValue::px(600.0))],
+///         },
+///         MediaQuery {
+///             negated: false,
+///             media_type: MediaType::All,
+///             features: vec![MediaFeature::Orientation(MediaOrientation::Portrait)],
+///         },
+///     ]})
+/// );
+/// ```
+pub fn parse_media_query_list<'a>(input: &'a str)
+-> Result<MediaQueryList, CssMediaQueryParseError<'a>>
+{
+    let queries = input
+        .split(',')
+        .map(|query| parse_media_query(query))
+        .collect::<Result<Vec<MediaQuery>, _>>()?;
+
+    Ok(MediaQueryList { queries })
+}
+
+/// Parses a single `not screen and (min-width: 600px) and (orientation: portrait)` query
+fn parse_media_query<'a>(input: &'a str)
+-> Result<MediaQuery, CssMediaQueryParseError<'a>>
+{
+    use self::CssMediaQueryParseError::*;
+
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(EmptyQuery);
+    }
+
+    let mut negated = false;
+    let mut media_type = MediaType::All;
+    // Whether the next feature has to be preceded by an "and"
+    let mut expect_and = false;
+
+    if !rest.starts_with('(') {
+        let (first_word, remaining) = split_first_word(rest);
+        let (media_type_str, remaining) = match first_word {
+            "not" => { negated = true; split_first_word(remaining) },
+            "only" => split_first_word(remaining),
+            _ => (first_word, remaining),
+        };
+        media_type = parse_media_type(media_type_str)?;
+        rest = remaining;
+        expect_and = true;
+    }
+
+    let mut features = Vec::new();
+
+    while !rest.is_empty() {
+        if expect_and {
+            let (and, remaining) = split_first_word(rest);
+            if and != "and" {
+                return Err(UnexpectedToken(if and.is_empty() { rest } else { and }));
+            }
+            rest = remaining;
+        }
+
+        if !rest.starts_with('(') {
+            return Err(UnexpectedToken(rest));
+        }
+
+        let closing_brace = rest.find(')').ok_or(UnclosedParenthesis(rest))?;
+        features.push(parse_media_feature(&rest[1..closing_brace])?);
+        rest = rest[(closing_brace + 1)..].trim_start();
+        expect_and = true;
+    }
+
+    Ok(MediaQuery { negated, media_type, features })
+}
+
+/// Splits `"screen and (min-width: 600px)"` into `("screen", "and (min-width: 600px)")`
+fn split_first_word(input: &str) -> (&str, &str) {
+    let input = input.trim_start();
+    match input.find(|c: char| c.is_whitespace() || c == '(') {
+        Some(pos) => (&input[..pos], input[pos..].trim_start()),
+        None => (input, ""),
+    }
+}
+
+/// Parses the inside of a `(min-width: 600px)` media feature
+fn parse_media_feature<'a>(input: &'a str)
+-> Result<MediaFeature, CssMediaQueryParseError<'a>>
+{
+    use self::CssMediaQueryParseError::*;
+
+    let mut split_colon = input.splitn(2, ':');
+    let name = split_colon.next().ok_or(MissingFeatureValue(input))?.trim();
+    let value = split_colon.next().ok_or(MissingFeatureValue(input))?.trim();
+
+    Ok(match name {
+        "width" => MediaFeature::Width(parse_pixel_value_no_percent(value)?.0),
+        "min-width" => MediaFeature::MinWidth(parse_pixel_value_no_percent(value)?.0),
+        "max-width" => MediaFeature::MaxWidth(parse_pixel_value_no_percent(value)?.0),
+        "height" => MediaFeature::Height(parse_pixel_value_no_percent(value)?.0),
+        "min-height" => MediaFeature::MinHeight(parse_pixel_value_no_percent(value)?.0),
+        "max-height" => MediaFeature::MaxHeight(parse_pixel_value_no_percent(value)?.0),
+        "orientation" => MediaFeature::Orientation(parse_media_orientation(value)?),
+        "resolution" => MediaFeature::Resolution(parse_media_resolution(value)?),
+        "min-resolution" => MediaFeature::MinResolution(parse_media_resolution(value)?),
+        "max-resolution" => MediaFeature::MaxResolution(parse_media_resolution(value)?),
+        "prefers-color-scheme" => MediaFeature::PrefersColorScheme(parse_color_scheme(value)?),
+        _ => return Err(UnsupportedFeature(name)),
+    })
+}
+
+/// Parses a resolution such as `2dppx`, `2x` or `192dpi` into dots per pixel (`2.0`)
+fn parse_media_resolution<'a>(input: &'a str)
+-> Result<FloatValue, CssMediaQueryParseError<'a>>
+{
+    const DPI_PER_DPPX: f32 = 96.0;
+    const CM_PER_INCH: f32 = 2.54;
+
+    let input = input.trim();
+
+    let (number, factor) = if input.ends_with("dppx") {
+        (&input[..input.len() - 4], 1.0)
+    } else if input.ends_with("dpcm") {
+        (&input[..input.len() - 4], CM_PER_INCH / DPI_PER_DPPX)
+    } else if input.ends_with("dpi") {
+        (&input[..input.len() - 3], 1.0 / DPI_PER_DPPX)
+    } else if input.ends_with('x') {
+        (&input[..input.len() - 1], 1.0)
+    } else {
+        return Err(CssMediaQueryParseError::InvalidResolution(input));
+    };
+
+    let number = number.trim().parse::<f32>().map_err(|_| CssMediaQueryParseError::InvalidResolution(input))?;
+    Ok(FloatValue::new(number * factor))
+}
+
 #[cfg(test)]
 mod css_tests {
     use super::*;
@@ -2658,6 +4985,74 @@ mod css_tests {
         );
     }
 
+    #[test]
+    fn test_parse_box_shadow_11() {
+        // the color and the inset keyword can also be written in front of the lengths
+        assert_eq!(
+            parse_style_box_shadow("inset #888888 5px 10px 5px 10px"),
+            parse_style_box_shadow("5px 10px 5px 10px #888888 inset")
+        );
+        assert_eq!(
+            parse_style_box_shadow("5px 10px 5px inset"),
+            Ok(BoxShadowPreDisplayItem {
+                offset: [
+                    PixelValueNoPercent(PixelValue::px(5.0)),
+                    PixelValueNoPercent(PixelValue::px(10.0))
+                ],
+                color: ColorU { r: 0, g: 0, b: 0, a: 255 },
+                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
+                spread_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+                clip_mode: BoxShadowClipMode::Inset,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_box_shadow_invalid() {
+        assert_eq!(parse_style_box_shadow("5px red 10px"), Err(CssShadowParseError::InvalidSingleStatement("5px red 10px")));
+        assert_eq!(parse_style_box_shadow("red 5px 5px inset 5px"), Err(CssShadowParseError::InvalidSingleStatement("red 5px 5px inset 5px")));
+        assert_eq!(parse_style_box_shadow("5px 10px red blue"), Err(CssShadowParseError::DuplicateValue("blue")));
+        assert_eq!(parse_style_box_shadow("inset 5px 10px inset"), Err(CssShadowParseError::DuplicateValue("inset")));
+        assert_eq!(parse_style_box_shadow("1px 2px 3px 4px 5px"), Err(CssShadowParseError::InvalidSingleStatement("1px 2px 3px 4px 5px")));
+    }
+
+    #[test]
+    fn test_parse_box_shadow_list() {
+        let shadows = parse_style_box_shadow_list("0px 0px 5px rgba(255, 0, 0, 1.0), 2px 2px 10px 5px black inset").unwrap();
+        assert_eq!(shadows, StyleBoxShadowList(vec![
+            parse_style_box_shadow("0px 0px 5px #ff0000").unwrap(),
+            parse_style_box_shadow("2px 2px 10px 5px #000000 inset").unwrap(),
+        ]));
+        assert_eq!(parse_style_box_shadow_list(shadows.to_string().as_str()), Ok(shadows.clone()));
+        assert_eq!(
+            parse_css_property(CssPropertyType::BoxShadowTop, "0px 0px 5px rgba(255, 0, 0, 1.0), 2px 2px 10px 5px black inset"),
+            Ok(CssProperty::BoxShadowTop(CssPropertyValue::Exact(shadows)))
+        );
+        assert_eq!(parse_style_box_shadow_list(" , "), Err(CssShadowParseError::InvalidSingleStatement(" , ")));
+    }
+
+    #[test]
+    fn test_parse_text_shadow() {
+        assert_eq!(
+            parse_style_text_shadow("1px 2px 3px red, -1px -1px"),
+            Ok(StyleTextShadow(vec![
+                TextShadowPreDisplayItem {
+                    offset: [PixelValueNoPercent(PixelValue::px(1.0)), PixelValueNoPercent(PixelValue::px(2.0))],
+                    color: Some(ColorU { r: 255, g: 0, b: 0, a: 255 }),
+                    blur_radius: PixelValueNoPercent(PixelValue::px(3.0)),
+                },
+                TextShadowPreDisplayItem {
+                    offset: [PixelValueNoPercent(PixelValue::px(-1.0)), PixelValueNoPercent(PixelValue::px(-1.0))],
+                    color: None,
+                    blur_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+                },
+            ]))
+        );
+        assert_eq!(parse_style_text_shadow("1px 2px 3px 4px"), Err(CssShadowParseError::InvalidSingleStatement("1px 2px 3px 4px")));
+        assert_eq!(parse_style_text_shadow("1px 2px inset"), Err(CssShadowParseError::InvalidSingleStatement("1px 2px inset")));
+        assert_eq!(parse_style_text_shadow("1px 2px 3px 4px red"), Err(CssShadowParseError::TooManyComponents("1px 2px 3px 4px red")));
+    }
+
 
     #[test]
     fn test_parse_css_border_1() {
@@ -2727,11 +5122,11 @@ mod css_tests {
                     color: ColorU { r: 0, g: 255, b: 0, a: 255 },
                 },
                 GradientStopPre {
-                    offset: Some(PercentageValue::new(66.666664)),
+                    offset: Some(PercentageValue::new(66.666)), // note: stops are accumulated, 33.333 + 33.333
                     color: ColorU { r: 0, g: 0, b: 255, a: 255 },
                 },
                 GradientStopPre {
-                    offset: Some(PercentageValue::new(99.9999)), // note: not 100%, but close enough
+                    offset: Some(PercentageValue::new(99.999)), // note: not 100%, but close enough
                     color: ColorU { r: 255, g: 255, b: 0, a: 255 },
                 }],
         })));
@@ -3168,15 +5563,56 @@ mod css_tests {
         assert_eq!(parse_pixel_value("aslkfdjasdflk"), Err(PixelParseError::NoValueGiven("aslkfdjasdflk")));
     }
 
+    #[test]
+    fn test_parse_pixel_value_relative_units() {
+        assert_eq!(parse_pixel_value("2rem"), Ok(PixelValue::rem(2.0)));
+        assert_eq!(parse_pixel_value("50vw"), Ok(PixelValue::vw(50.0)));
+        assert_eq!(parse_pixel_value("33.5vh"), Ok(PixelValue::vh(33.5)));
+        assert_eq!(parse_pixel_value("10vmin"), Ok(PixelValue::vmin(10.0)));
+        assert_eq!(parse_pixel_value("10vmax"), Ok(PixelValue::vmax(10.0)));
+    }
+
+    #[test]
+    fn test_parse_pixel_value_calc_1() {
+        let value = parse_pixel_value("calc(100% - 2em - 12px)").unwrap();
+        assert_eq!(value, PixelValue::calc(&[(SizeMetric::Percent, 100.0), (SizeMetric::Em, -2.0), (SizeMetric::Px, -12.0)]).unwrap());
+        assert!(value.is_calc());
+        assert_eq!(value.to_pixels(200.0), 200.0 - 32.0 - 12.0);
+        assert_eq!(format!("{}", value), "calc(-12px - 2em + 100%)");
+        assert_eq!(parse_pixel_value(&format!("{}", value)), Ok(value));
+    }
+
+    #[test]
+    fn test_parse_pixel_value_calc_2() {
+        // terms are merged and multiplications / divisions are applied
+        assert_eq!(parse_pixel_value("calc(5px + 5px)"), Ok(PixelValue::px(10.0)));
+        assert_eq!(parse_pixel_value("calc(2 * (10px + 5vw) / 4)").unwrap(), PixelValue::calc(&[(SizeMetric::Px, 5.0), (SizeMetric::Vw, 2.5)]).unwrap());
+        assert_eq!(parse_pixel_value("calc(-5px + calc(50vh - 1rem))").unwrap().to_pixels_in_viewport(0.0, 0.0, 100.0), 29.0);
+        assert_eq!(parse_pixel_value("calc(100vmin - 10vmax)").unwrap().to_pixels_in_viewport(0.0, 100.0, 50.0), 40.0);
+    }
+
+    #[test]
+    fn test_parse_pixel_value_calc_invalid() {
+        assert_eq!(parse_pixel_value("calc(10px * 5px)"), Err(PixelParseError::InvalidCalcExpression("calc(10px * 5px)")));
+        assert_eq!(parse_pixel_value("calc(10px + 5)"), Err(PixelParseError::InvalidCalcExpression("calc(10px + 5)")));
+        assert_eq!(parse_pixel_value("calc(10px / 0)"), Err(PixelParseError::InvalidCalcExpression("calc(10px / 0)")));
+        assert_eq!(parse_pixel_value("calc(10px + (5px)"), Err(PixelParseError::InvalidCalcExpression("calc(10px + (5px)")));
+        assert_eq!(parse_pixel_value("calc(1px + 1pt + 1em + 1rem + 1%)"), Err(PixelParseError::TooManyCalcTerms("calc(1px + 1pt + 1em + 1rem + 1%)")));
+    }
+
+    fn circular(px: f32) -> PixelSize {
+        PixelSize::new(PixelValue::px(px), PixelValue::px(px))
+    }
+
     #[test]
     fn test_parse_style_border_radius_1() {
         assert_eq!(
             parse_style_border_radius("15px"),
             Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                top_right: PixelValue::px(15.0),
-                bottom_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(15.0),
+                top_left: circular(15.0),
+                top_right: circular(15.0),
+                bottom_left: circular(15.0),
+                bottom_right: circular(15.0),
             })
         );
     }
@@ -3186,10 +5622,10 @@ mod css_tests {
         assert_eq!(
             parse_style_border_radius("15px 50px"),
             Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(15.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(50.0),
+                top_left: circular(15.0),
+                bottom_right: circular(15.0),
+                top_right: circular(50.0),
+                bottom_left: circular(50.0),
             })
         );
     }
@@ -3199,10 +5635,10 @@ mod css_tests {
         assert_eq!(
             parse_style_border_radius("15px 50px 30px"),
             Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(30.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(50.0),
+                top_left: circular(15.0),
+                bottom_right: circular(30.0),
+                top_right: circular(50.0),
+                bottom_left: circular(50.0),
             })
         );
     }
@@ -3212,12 +5648,32 @@ mod css_tests {
         assert_eq!(
             parse_style_border_radius("15px 50px 30px 5px"),
             Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(30.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(5.0),
+                top_left: circular(15.0),
+                bottom_right: circular(30.0),
+                top_right: circular(50.0),
+                bottom_left: circular(5.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_style_border_radius_elliptical() {
+        assert_eq!(
+            parse_style_border_radius("10px 20% / 5px"),
+            Ok(StyleBorderRadius {
+                top_left: PixelSize::new(PixelValue::px(10.0), PixelValue::px(5.0)),
+                bottom_right: PixelSize::new(PixelValue::px(10.0), PixelValue::px(5.0)),
+                top_right: PixelSize::new(PixelValue::percent(20.0), PixelValue::px(5.0)),
+                bottom_left: PixelSize::new(PixelValue::percent(20.0), PixelValue::px(5.0)),
             })
         );
+        assert_eq!(
+            parse_style_border_top_left_radius("50% 10px"),
+            Ok(StyleBorderTopLeftRadius::elliptical(PixelValue::percent(50.0), PixelValue::px(10.0)))
+        );
+        assert_eq!(parse_style_border_radius("10px / 5px / 2px"), Err(CssStyleBorderRadiusParseError::TooManyValues("10px / 5px / 2px")));
+        assert_eq!(parse_style_border_radius("10px /"), Err(CssStyleBorderRadiusParseError::MissingValue("")));
+        assert_eq!(parse_style_border_bottom_right_radius("1px 2px 3px"), Err(CssStyleBorderRadiusParseError::TooManyValues("1px 2px 3px")));
     }
 
     #[test]
@@ -3246,6 +5702,148 @@ mod css_tests {
         )));
     }
 
+    #[test]
+    fn test_parse_conic_gradient() {
+        assert_eq!(parse_style_background_content("conic-gradient(from 0.25turn at 25% top, red, blue 75%)"),
+            Ok(StyleBackgroundContent::ConicGradient(ConicGradient {
+                angle: FloatValue::new(90.0),
+                center: StyleBackgroundPosition {
+                    horizontal: BackgroundPositionHorizontal::Exact(PixelValue::percent(25.0)),
+                    vertical: BackgroundPositionVertical::Top,
+                },
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: ColorU { r: 255, g: 0, b: 0, a: 255 } },
+                    GradientStopPre { offset: Some(PercentageValue::new(75.0)), color: ColorU { r: 0, g: 0, b: 255, a: 255 } },
+                ],
+            })));
+        assert_eq!(parse_style_background_content("repeating-conic-gradient(red, blue 25%)"),
+            Ok(StyleBackgroundContent::ConicGradient(ConicGradient {
+                angle: FloatValue::new(0.0),
+                center: ConicGradient::DEFAULT_CENTER,
+                extend_mode: ExtendMode::Repeat,
+                stops: vec![
+                    GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: ColorU { r: 255, g: 0, b: 0, a: 255 } },
+                    GradientStopPre { offset: Some(PercentageValue::new(25.0)), color: ColorU { r: 0, g: 0, b: 255, a: 255 } },
+                ],
+            })));
+        assert_eq!(
+            parse_style_background_content("conic-gradient(from to right, red, blue)"),
+            Err(CssBackgroundParseError::ConicAngleParseError("to right"))
+        );
+    }
+
+    #[test]
+    fn test_parse_background_layers() {
+        assert_eq!(parse_style_background_content_list("image(\"pattern\"), linear-gradient(rgb(255, 0, 0), blue)"), Ok(StyleBackgroundContentList(vec![
+            StyleBackgroundContent::Image(CssImageId(String::from("pattern"))),
+            parse_style_background_content("linear-gradient(red, blue)").unwrap(),
+        ])));
+        assert_eq!(parse_style_background_size_list("20px 10px, cover, 50%"), Ok(StyleBackgroundSizeList(vec![
+            StyleBackgroundSize::ExactSize(PixelValue::px(20.0), PixelValue::px(10.0)),
+            StyleBackgroundSize::Cover,
+            StyleBackgroundSize::ExactSize(PixelValue::percent(50.0), PixelValue::percent(50.0)),
+        ])));
+        assert_eq!(parse_style_background_repeat_list("repeat-x, no-repeat"), Ok(StyleBackgroundRepeatList(vec![
+            StyleBackgroundRepeat::RepeatX,
+            StyleBackgroundRepeat::NoRepeat,
+        ])));
+        assert_eq!(parse_style_background_position_list("center, 10px bottom"), Ok(StyleBackgroundPositionList(vec![
+            StyleBackgroundPosition { horizontal: BackgroundPositionHorizontal::Center, vertical: BackgroundPositionVertical::Center },
+            StyleBackgroundPosition { horizontal: BackgroundPositionHorizontal::Exact(PixelValue::px(10.0)), vertical: BackgroundPositionVertical::Bottom },
+        ])));
+        assert_eq!(parse_style_background_size_list("10px 10px 10px"), Err(InvalidValueErr("10px 10px 10px")));
+
+        // values are repeated if there are less values than layers
+        let repeat = StyleBackgroundRepeatList(vec![StyleBackgroundRepeat::RepeatX, StyleBackgroundRepeat::NoRepeat]);
+        assert_eq!(repeat.get_layer(2), Some(&StyleBackgroundRepeat::RepeatX));
+        assert_eq!(StyleBackgroundRepeatList(Vec::new()).get_layer(0), None);
+    }
+
+    #[test]
+    fn test_parse_border_image() {
+        assert_eq!(parse_style_border_image_slice("10 20 30"), Ok(StyleBorderImageSlice {
+            top: PixelValue::px(10.0),
+            right: PixelValue::px(20.0),
+            bottom: PixelValue::px(30.0),
+            left: PixelValue::px(20.0),
+            fill: false,
+        }));
+        assert_eq!(parse_style_border_image_width("2 auto"), Ok(StyleBorderImageWidth {
+            top: BorderImageLength::Number(FloatValue::new(2.0)),
+            right: BorderImageLength::Auto,
+            bottom: BorderImageLength::Number(FloatValue::new(2.0)),
+            left: BorderImageLength::Auto,
+        }));
+        assert_eq!(parse_style_border_image_repeat("round space"), Ok(StyleBorderImageRepeat {
+            horizontal: BorderImageRepeat::Round,
+            vertical: BorderImageRepeat::Space,
+        }));
+        assert_eq!(parse_style_border_image("image(\"button\") 10/2/1px repeat"), Ok(NinePatchBorder {
+            source: CssImageId(String::from("button")),
+            slice: parse_style_border_image_slice("10").unwrap(),
+            width: parse_style_border_image_width("2").unwrap(),
+            outset: parse_style_border_image_outset("1px").unwrap(),
+            repeat: parse_style_border_image_repeat("repeat").unwrap(),
+        }));
+        // without a slice, the whole image is used for the corners
+        assert_eq!(parse_style_border_image("stretch image(\"button\")").map(|b| b.slice), Ok(StyleBorderImageSlice::default()));
+    }
+
+    #[test]
+    fn test_parse_border_image_invalid() {
+        assert_eq!(parse_style_border_image_slice("10px"), Err(CssBorderImageParseError::InvalidValue("10px")));
+        assert_eq!(parse_style_border_image_slice("10 fill fill"), Err(CssBorderImageParseError::DuplicateValue("fill")));
+        assert_eq!(parse_style_border_image_width("1 2 3 4 5"), Err(CssBorderImageParseError::TooManyValues("1 2 3 4 5")));
+        assert_eq!(parse_style_border_image_outset("auto"), Err(CssBorderImageParseError::InvalidValue("auto")));
+        assert_eq!(parse_style_border_image_outset("10%"), Err(CssBorderImageParseError::InvalidValue("10%")));
+        assert_eq!(parse_style_border_image_repeat("round round round"), Err(CssBorderImageParseError::TooManyValues("round round round")));
+        assert_eq!(parse_style_border_image("10 / 2"), Err(CssBorderImageParseError::MissingSource("10 / 2")));
+        assert_eq!(parse_style_border_image("image(\"a\") 10 / 2 / 1 / 0"), Err(CssBorderImageParseError::TooManyValues("image(\"a\") 10 / 2 / 1 / 0")));
+    }
+
+    #[test]
+    fn test_parse_grid_template() {
+        let px = |px| GridTrackBreadth::Length(PixelValue::px(px));
+        let fr = |fr| GridTrackBreadth::Fraction(FloatValue::new(fr));
+        assert_eq!(parse_layout_grid_template_columns("200px repeat(2, 1fr 50%) auto"), Ok(LayoutGridTemplateColumns(vec![
+            GridTrackSize::Breadth(px(200.0)),
+            GridTrackSize::Breadth(fr(1.0)),
+            GridTrackSize::Breadth(GridTrackBreadth::Length(PixelValue::percent(50.0))),
+            GridTrackSize::Breadth(fr(1.0)),
+            GridTrackSize::Breadth(GridTrackBreadth::Length(PixelValue::percent(50.0))),
+            GridTrackSize::Breadth(GridTrackBreadth::Auto),
+        ])));
+        assert_eq!(parse_layout_grid_template_rows("repeat(2, minmax(auto, 0.5fr)) minmax(10px, 20px)"), Ok(LayoutGridTemplateRows(vec![
+            GridTrackSize::MinMax(GridTrackBreadth::Auto, fr(0.5)),
+            GridTrackSize::MinMax(GridTrackBreadth::Auto, fr(0.5)),
+            GridTrackSize::MinMax(px(10.0), px(20.0)),
+        ])));
+        assert_eq!(parse_layout_grid_template_columns("repeat(0, 1fr)"), Err(CssGridParseError::InvalidRepeatCount("repeat(0, 1fr)")));
+        assert_eq!(parse_layout_grid_template_columns("repeat(2, repeat(2, 1fr))"), Err(CssGridParseError::InvalidValue("repeat(2, 1fr)")));
+        assert_eq!(parse_layout_grid_template_columns("minmax(1fr, 100px)"), Err(CssGridParseError::InvalidValue("minmax(1fr, 100px)")));
+        assert_eq!(parse_layout_grid_template_columns("-1fr"), Err(CssGridParseError::InvalidValue("-1fr")));
+    }
+
+    #[test]
+    fn test_parse_grid_placement() {
+        assert_eq!(parse_layout_grid_column_start("span 3"), Ok(LayoutGridColumnStart(GridLine::Span(3))));
+        assert_eq!(parse_layout_grid_row_end("2 span"), Ok(LayoutGridRowEnd(GridLine::Span(2))));
+        assert_eq!(parse_layout_grid_placement("1 / -1"), Ok(GridPlacement { start: Some(GridLine::Line(1)), end: Some(GridLine::Line(-1)) }));
+        assert_eq!(parse_layout_grid_placement("auto / span 2"), Ok(GridPlacement { start: None, end: Some(GridLine::Span(2)) }));
+        assert_eq!(parse_layout_grid_placement("1 / 2 / 3"), Err(CssGridParseError::TooManyValues("1 / 2 / 3")));
+        assert_eq!(parse_grid_line("span -1"), Err(CssGridParseError::InvalidLine("span -1")));
+        assert_eq!(parse_grid_line("span 1 2"), Err(CssGridParseError::InvalidValue("span 1 2")));
+        assert_eq!(parse_layout_gap("10px 5%"), Ok(LayoutGap { row: PixelValue::px(10.0), column: PixelValue::percent(5.0) }));
+
+        assert_eq!(parse_combined_css_property(CombinedCssPropertyType::GridArea, "2 / 1 / 4"), Ok(vec![
+            CssProperty::GridRowStart(CssPropertyValue::Exact(LayoutGridRowStart(GridLine::Line(2)))),
+            CssProperty::GridColumnStart(CssPropertyValue::Exact(LayoutGridColumnStart(GridLine::Line(1)))),
+            CssProperty::GridRowEnd(CssPropertyValue::Exact(LayoutGridRowEnd(GridLine::Line(4)))),
+            CssProperty::GridColumnEnd(CssPropertyValue::Auto),
+        ]));
+    }
+
     #[test]
     fn test_parse_padding_1() {
         assert_eq!(
@@ -3297,4 +5895,119 @@ mod css_tests {
             })
         );
     }
+
+    #[test]
+    fn test_parse_media_query_list() {
+        assert_eq!(
+            parse_media_query_list("not print and (min-resolution: 192dpi) and (prefers-color-scheme: dark)"),
+            Ok(MediaQueryList { queries: vec![MediaQuery {
+                negated: true,
+                media_type: MediaType::Print,
+                features: vec![
+                    MediaFeature::MinResolution(FloatValue::new(2.0)),
+                    MediaFeature::PrefersColorScheme(ColorScheme::Dark),
+                ],
+            }]})
+        );
+        assert_eq!(
+            parse_media_query_list("only screen and (max-height: 20em)"),
+            Ok(MediaQueryList { queries: vec![MediaQuery {
+                negated: false,
+                media_type: MediaType::Screen,
+                features: vec![MediaFeature::MaxHeight(PixelValue::em(20.0))],
+            }]})
+        );
+    }
+
+    #[test]
+    fn test_parse_media_query_list_errors() {
+        assert_eq!(parse_media_query_list(""), Err(CssMediaQueryParseError::EmptyQuery));
+        assert_eq!(parse_media_query_list("(min-width: 5px) (max-width: 10px)"), Err(CssMediaQueryParseError::UnexpectedToken("(max-width: 10px)")));
+        assert_eq!(parse_media_query_list("(hover: hover)"), Err(CssMediaQueryParseError::UnsupportedFeature("hover")));
+        assert_eq!(parse_media_query_list("(resolution: 2)"), Err(CssMediaQueryParseError::InvalidResolution("2")));
+        assert_eq!(parse_media_query_list("tv"), Err(CssMediaQueryParseError::InvalidMediaType(InvalidValueErr("tv"))));
+    }
+
+    #[test]
+    fn test_parse_style_transform() {
+        use azul_css::StyleTransformFunction::*;
+        let f = FloatValue::new;
+        assert_eq!(
+            parse_style_transform("translate(10px,  5%)  skew(0, -1.5rad)\nmatrix(1, 0.5, 0, 1, 10, 0)"),
+            Ok(StyleTransform(vec![
+                Translate(PixelValue::px(10.0), PixelValue::percent(5.0)),
+                Skew(f(0.0), f(-1.5 * 180.0 / ::std::f32::consts::PI)),
+                Matrix([f(1.0), f(0.5), f(0.0), f(1.0), f(10.0), f(0.0)]),
+            ]))
+        );
+        assert_eq!(parse_style_transform("rotate(45)"), Err(CssStyleTransformParseError::InvalidAngle("45")));
+        assert_eq!(parse_style_transform("scale(1, 2, 3)"), Err(CssStyleTransformParseError::WrongNumberOfArguments("scale(1, 2, 3)")));
+        assert_eq!(parse_style_transform("rotate3d(1, 1, 1, 45deg)"), Err(CssStyleTransformParseError::InvalidFunction("rotate3d(1, 1, 1, 45deg)")));
+    }
+
+    #[test]
+    fn test_parse_style_filter() {
+        use azul_css::StyleFilterFunction::*;
+        let px = |value| PixelValueNoPercent(PixelValue::px(value));
+        assert_eq!(
+            parse_style_filter("grayscale(50%)  drop-shadow(2px 4px rgba(0, 0, 0, 0.5)) opacity(0)"),
+            Ok(StyleFilter(vec![
+                Grayscale(FloatValue::new(0.5)),
+                DropShadow { offset: [px(2.0), px(4.0)], blur_radius: px(0.0), color: ColorU { r: 0, g: 0, b: 0, a: 128 } },
+                Opacity(FloatValue::new(0.0)),
+            ]))
+        );
+        assert_eq!(parse_style_filter("blur(5%)").is_err(), true);
+        assert_eq!(parse_style_filter("grayscale(5px)"), Err(CssStyleFilterParseError::InvalidAmount("5px")));
+        assert_eq!(parse_style_filter("drop-shadow(2px)"), Err(CssStyleFilterParseError::WrongNumberOfArguments("drop-shadow(2px)")));
+        assert_eq!(parse_style_filter("sepia(1)"), Err(CssStyleFilterParseError::InvalidFunction("sepia(1)")));
+    }
+
+    #[test]
+    fn test_parse_layout_z_index() {
+        assert_eq!(parse_css_property(CssPropertyType::ZIndex, "auto"), Ok(CssProperty::ZIndex(CssPropertyValue::Auto)));
+        assert_eq!(parse_layout_z_index(" 10 "), Ok(LayoutZIndex(10)));
+        assert_eq!(parse_layout_z_index("1.5"), Err(InvalidValueErr("1.5")));
+    }
+
+    #[test]
+    fn test_parse_style_font_weight() {
+        assert_eq!(parse_style_font_weight("normal"), Ok(StyleFontWeight(400)));
+        assert_eq!(parse_style_font_weight(" 900 "), Ok(StyleFontWeight(900)));
+        assert_eq!(parse_style_font_weight("0"), Err(InvalidValueErr("0")));
+        assert_eq!(parse_style_font_weight("bolder"), Err(InvalidValueErr("bolder")));
+    }
+
+    #[test]
+    fn test_parse_style_text_decoration() {
+        assert_eq!(parse_style_text_decoration("overline  underline"), Ok(StyleTextDecoration {
+            underline: true,
+            overline: true,
+            .. Default::default()
+        }));
+        assert_eq!(parse_style_text_decoration("0.1em blue underline"), Ok(StyleTextDecoration {
+            underline: true,
+            color: Some(ColorU { r: 0, g: 0, b: 255, a: 255 }),
+            thickness: Some(PixelValue::em(0.1)),
+            .. Default::default()
+        }));
+        assert_eq!(parse_style_text_decoration("none red"), Ok(StyleTextDecoration {
+            color: Some(ColorU { r: 255, g: 0, b: 0, a: 255 }),
+            .. Default::default()
+        }));
+        assert_eq!(parse_style_text_decoration("underline blink"), Err(CssStyleTextDecorationParseError::InvalidValue("blink")));
+        assert_eq!(parse_style_text_decoration("red underline blue"), Err(CssStyleTextDecorationParseError::DuplicateValue("blue")));
+        assert_eq!(parse_style_text_decoration("none underline"), Err(CssStyleTextDecorationParseError::InvalidValue("none underline")));
+    }
+
+    #[test]
+    fn test_parse_style_transform_origin() {
+        let origin = |x, y| Ok(StyleTransformOrigin { x, y });
+        assert_eq!(parse_style_transform_origin("bottom"), origin(PixelValue::percent(50.0), PixelValue::percent(100.0)));
+        assert_eq!(parse_style_transform_origin("10px"), origin(PixelValue::px(10.0), PixelValue::percent(50.0)));
+        assert_eq!(parse_style_transform_origin("center right"), origin(PixelValue::percent(100.0), PixelValue::percent(50.0)));
+        assert_eq!(parse_style_transform_origin("2em bottom"), origin(PixelValue::em(2.0), PixelValue::percent(100.0)));
+        assert_eq!(parse_style_transform_origin("top 10px"), Err(CssStyleTransformParseError::InvalidOrigin("top 10px")));
+        assert_eq!(parse_style_transform_origin("left right"), Err(CssStyleTransformParseError::InvalidOrigin("left right")));
+    }
 }
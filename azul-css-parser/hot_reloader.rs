@@ -7,7 +7,7 @@ use std::path::PathBuf;
 
 pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
 
-/// Allows dynamic reloading of a CSS file at application runtime.
+/// Allows dynamic reloading of a CSS file (and all the files it imports) at application runtime.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HotReloader {
     file_path: PathBuf,
@@ -28,15 +28,14 @@ impl HotReloader {
 
 impl HotReloadHandler for HotReloader {
     fn reload_style(&mut self) -> Result<Css, String> {
-        use std::fs;
-        use crate::css;
-
-        let file_name = self.file_path.file_name().map(|os_str| os_str.to_string_lossy()).unwrap_or_default();
-
-        let reloaded_css = fs::read_to_string(&self.file_path)
-            .map_err(|e| format!("Error parsing CSS file: \"{}\":\r\nIO Error: {}", file_name, e))?;
+        // Re-reads the entire import graph, so that changes to imported files
+        // (or newly added @import rules) are picked up as well.
+        crate::css::new_from_file(&self.file_path).map_err(|e| format!("{}", e))
+    }
 
-        css::new_from_str(&reloaded_css).map_err(|e| format!("Error parsing CSS file: \"{}\":\r\n{}", file_name, e))
+    fn reload_style_lenient(&mut self) -> Result<(Css, Vec<String>), String> {
+        // Invalid rules are skipped, so that a typo doesn't throw away the rest of the style
+        crate::css::new_from_file_lenient(&self.file_path).map_err(|e| format!("{}", e))
     }
 
     fn get_reload_interval(&self) -> Duration {
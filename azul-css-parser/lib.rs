@@ -25,8 +25,16 @@ mod hot_reloader;
 
 pub use crate::css::{
     new_from_str,
+    new_from_str_lenient,
+    new_from_file,
+    new_from_file_lenient,
     parse_css_path,
     CssParseError,
+    CssParseErrorInner,
+    CssParseWarnMsg,
+    CssParseWarnMsgInner,
+    ErrorLocation,
+    CssImportError,
     CssPathParseError,
 };
 
@@ -1,6 +1,8 @@
 //! Types and methods used to describe the style of an application
-use crate::css_properties::{CssProperty, CssPropertyType};
+use crate::css_properties::{CssProperty, CssPropertyType, PixelValue, FloatValue, PercentageValue, StyleFontWeight, StyleFontStyle};
 use std::fmt;
+use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Css stylesheet - contains a parsed CSS stylesheet in "rule blocks",
 /// i.e. blocks of key-value pairs associated with a selector path.
@@ -16,11 +18,149 @@ pub struct Css {
 pub struct Stylesheet {
     /// The style rules making up the document - for example, de-duplicated CSS rules
     pub rules: Vec<CssRuleBlock>,
+    /// Fonts declared via `@font-face` rules, registered automatically
+    /// when the stylesheet is applied to a window
+    pub font_faces: Vec<FontFace>,
+    /// Animations declared via `@keyframes` rules, referenced by the `animation` property
+    pub keyframes: Vec<CssKeyframes>,
 }
 
 impl From<Vec<CssRuleBlock>> for Stylesheet {
     fn from(rules: Vec<CssRuleBlock>) -> Self {
-        Self { rules }
+        Self { rules, font_faces: Vec::new(), keyframes: Vec::new() }
+    }
+}
+
+/// A `@font-face` rule, which makes a font file or a system font
+/// available under a custom `font-family` name:
+///
+/// ```no_run,ignore
+/// @font-face {
+///     font-family: "My Font";
+///     src: url("fonts/MyFont.ttf") format("truetype"), local("Helvetica");
+/// }
+/// ```
+///
+/// Multiple `@font-face` rules with the same `font-family`, but a different
+/// `font-weight` or `font-style` register multiple faces of the same family.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FontFace {
+    /// The name under which the font can be used, i.e. `"My Font"`
+    pub font_family: String,
+    /// The sources of the `src` descriptor, in order of preference
+    pub sources: Vec<FontFaceSource>,
+    /// The `font-weight` descriptor, `400` if not specified
+    pub font_weight: StyleFontWeight,
+    /// The `font-style` descriptor, `normal` if not specified
+    pub font_style: StyleFontStyle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FontFaceSource {
+    /// `url("fonts/MyFont.ttf")` - if the stylesheet was loaded from a file,
+    /// the path is already resolved relative to that file
+    Url(String),
+    /// `local("Helvetica")` - a font installed on the system
+    Local(String),
+}
+
+impl fmt::Display for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "@font-face {{ font-family: \"{}\"; src: ", self.font_family)?;
+        for (source_idx, source) in self.sources.iter().enumerate() {
+            if source_idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", source)?;
+        }
+        if self.font_weight != StyleFontWeight::NORMAL {
+            write!(f, "; font-weight: {}", self.font_weight)?;
+        }
+        if self.font_style != StyleFontStyle::Normal {
+            write!(f, "; font-style: {}", self.font_style)?;
+        }
+        write!(f, "; }}")
+    }
+}
+
+impl fmt::Display for FontFaceSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::FontFaceSource::*;
+        match self {
+            Url(url) => write!(f, "url(\"{}\")", url),
+            Local(name) => write!(f, "local(\"{}\")", name),
+        }
+    }
+}
+
+/// A `@keyframes` rule, which defines the steps of an `animation`:
+///
+/// ```no_run,ignore
+/// @keyframes pulse {
+///     from { background-color: #ffffff; }
+///     50% { background-color: #ff0000; }
+///     to { background-color: #ffffff; }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssKeyframes {
+    /// The name that the `animation` property refers to, i.e. `"pulse"`
+    pub name: String,
+    /// Keyframes, sorted by their offset
+    pub keyframes: Vec<CssKeyframe>,
+}
+
+/// A single step of a `@keyframes` rule, i.e. `50% { background-color: #ff0000; }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssKeyframe {
+    /// Position of the keyframe in the animation, `from` is `0%`, `to` is `100%`
+    pub offset: PercentageValue,
+    pub declarations: Vec<CssProperty>,
+}
+
+impl fmt::Display for CssKeyframes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "@keyframes {} {{", self.name)?;
+        for keyframe in &self.keyframes {
+            write!(f, " {}", keyframe)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl fmt::Display for CssKeyframe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {{", self.offset)?;
+        for declaration in &self.declarations {
+            write!(f, " {};", declaration)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+/// Writes all stylesheets as one CSS string. Parsing the output again results
+/// in the same rules, but in a single stylesheet.
+impl fmt::Display for Css {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for stylesheet in &self.stylesheets {
+            write!(f, "{}", stylesheet)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for font_face in &self.font_faces {
+            writeln!(f, "{}", font_face)?;
+        }
+        for keyframes in &self.keyframes {
+            writeln!(f, "{}", keyframes)?;
+        }
+        for rule in &self.rules {
+            writeln!(f, "{}", rule)?;
+        }
+        Ok(())
     }
 }
 
@@ -65,6 +205,16 @@ impl CssDeclaration {
     }
 }
 
+impl fmt::Display for CssDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::CssDeclaration::*;
+        match self {
+            Static(s) => write!(f, "{}", s),
+            Dynamic(d) => write!(f, "{}", d),
+        }
+    }
+}
+
 /// A `DynamicCssProperty` is a type of css property that can be changed on possibly
 /// every frame by the Rust code - for example to implement an `On::Hover` behaviour.
 ///
@@ -93,6 +243,38 @@ pub struct DynamicCssProperty {
     pub default_value: CssProperty,
 }
 
+/// A custom property (`--main-bg-color: #e3e3e3`), declared on a selector in the stylesheet.
+///
+/// Custom properties are always inherited down the DOM tree during the cascade. A declaration
+/// such as `color: var(--main-bg-color, black)` on a node (or any of its children) then picks
+/// up the value of the nearest custom property with the same name, instead of the default.
+///
+/// Since the type of the value is only known at the place where it is used, the parser
+/// stores the value pre-parsed for every property type it is valid for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomCssProperty {
+    /// Name of the property without the leading `--`, i.e. `"main-bg-color"`
+    pub name: String,
+    /// The unparsed value, i.e. `"#e3e3e3"`
+    pub value: String,
+    /// `value`, parsed as every `CssPropertyType` that accepts it
+    pub parsed_values: Vec<CssProperty>,
+}
+
+impl CustomCssProperty {
+    /// Returns the value of this custom property, parsed as the given property type
+    /// (or `None` if the value isn't valid for that property)
+    pub fn get_value(&self, property_type: CssPropertyType) -> Option<&CssProperty> {
+        self.parsed_values.iter().find(|p| p.get_type() == property_type)
+    }
+}
+
+impl fmt::Display for CustomCssProperty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "--{}: {}", self.name, self.value)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CssPropertyValue<T> {
     Auto,
@@ -226,15 +408,330 @@ impl DynamicCssProperty {
     }
 }
 
+impl fmt::Display for DynamicCssProperty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: var(--{}, {})", self.default_value.get_key(), self.dynamic_id, self.default_value.get_value_string())
+    }
+}
+
 /// One block of rules that applies a bunch of rules to a "path" in the style, i.e.
 /// `div#myid.myclass -> { ("justify-content", "center") }`
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Two rule blocks are equal if they declare the same style, regardless of the `source`.
+#[derive(Debug, Clone)]
 pub struct CssRuleBlock {
     /// The css path (full selector) of the style ruleset
     pub path: CssPath,
     /// `"justify-content: center"` =>
     /// `CssDeclaration::Static(CssProperty::JustifyContent(LayoutJustifyContent::Center))`
     pub declarations: Vec<CssDeclaration>,
+    /// `"--main-bg-color: #e3e3e3"` => `CustomCssProperty { name: "main-bg-color", .. }`
+    pub custom_properties: Vec<CustomCssProperty>,
+    /// Conditions of all `@media` blocks this rule is nested in - the rule
+    /// only applies if all of them match. Empty if the rule is not in a `@media` block.
+    pub media_queries: Vec<MediaQueryList>,
+    /// Where the rule block and its declarations are located in the source code,
+    /// `None` if the rule wasn't parsed from a stylesheet
+    pub source: Option<CssRuleSource>,
+}
+
+impl PartialEq for CssRuleBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path &&
+        self.declarations == other.declarations &&
+        self.custom_properties == other.custom_properties &&
+        self.media_queries == other.media_queries
+    }
+}
+
+/// Position in the source code of a stylesheet, displayed as `file:line:column`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssSourceLocation {
+    /// File that the stylesheet was loaded from, `None` if it was parsed from a string
+    pub file: Option<Arc<str>>,
+    /// Line, starting at 1
+    pub line: usize,
+    /// Column (in characters), starting at 1
+    pub column: usize,
+}
+
+impl fmt::Display for CssSourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Source locations of a rule block and its declarations
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssRuleSource {
+    /// Location of the selector of the rule block
+    pub block: CssSourceLocation,
+    /// Location of the declaration of each property. A shorthand such as `border: 1px solid red`
+    /// is stored once for every property it expands to.
+    pub declarations: BTreeMap<CssPropertyType, CssSourceLocation>,
+    /// Location of each custom property (`--main-bg-color: red`), by name
+    pub custom_properties: BTreeMap<String, CssSourceLocation>,
+}
+
+impl CssRuleBlock {
+    /// Returns whether the `@media` conditions of this rule match the current window
+    pub fn matches_media(&self, environment: &MediaQueryEnvironment) -> bool {
+        self.media_queries.iter().all(|media_query| media_query.matches(environment))
+    }
+
+    /// Returns whether the rule doesn't declare any (custom) properties
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty() && self.custom_properties.is_empty()
+    }
+
+    /// Returns where the property of the given type was declared in this rule block
+    pub fn get_declaration_source(&self, property_type: CssPropertyType) -> Option<&CssSourceLocation> {
+        self.source.as_ref()?.declarations.get(&property_type)
+    }
+
+    /// Returns whether both rules declare a property or a custom property with the same name,
+    /// i.e. whether the order of the two rules matters for nodes matching both paths
+    fn declares_same_property_as(&self, other: &CssRuleBlock) -> bool {
+        self.declarations.iter().any(|a| other.declarations.iter().any(|b| a.get_type() == b.get_type())) ||
+        self.custom_properties.iter().any(|a| other.custom_properties.iter().any(|b| a.name == b.name))
+    }
+}
+
+/// Writes the rule as a `path { key: value; }` block, nested in one
+/// `@media` block for each of its media queries
+impl fmt::Display for CssRuleBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const INDENT: &str = "    ";
+
+        for (depth, media_query) in self.media_queries.iter().enumerate() {
+            writeln!(f, "{}@media {} {{", INDENT.repeat(depth), media_query)?;
+        }
+
+        let indent = INDENT.repeat(self.media_queries.len());
+        writeln!(f, "{}{} {{", indent, self.path)?;
+        for declaration in &self.declarations {
+            writeln!(f, "{}{}{};", indent, INDENT, declaration)?;
+        }
+        for custom_property in &self.custom_properties {
+            writeln!(f, "{}{}{};", indent, INDENT, custom_property)?;
+        }
+        write!(f, "{}}}", indent)?;
+
+        for depth in (0..self.media_queries.len()).rev() {
+            write!(f, "\n{}}}", INDENT.repeat(depth))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Condition of an `@media` block, for example
+/// `@media screen and (min-width: 600px), (orientation: portrait)`.
+/// Matches if any of the comma-separated queries match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MediaQueryList {
+    pub queries: Vec<MediaQuery>,
+}
+
+/// One query in a `MediaQueryList`, i.e. `not screen and (min-width: 600px)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MediaQuery {
+    /// Whether the query was prefixed with `not`
+    pub negated: bool,
+    /// `screen`, `print` or `all` (the default if no media type is given)
+    pub media_type: MediaType,
+    /// `(min-width: 600px) and (orientation: portrait)`, all of them have to match
+    pub features: Vec<MediaFeature>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MediaType {
+    All,
+    Screen,
+    Print,
+}
+
+/// A single `(feature: value)` condition of a media query
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MediaFeature {
+    Width(PixelValue),
+    MinWidth(PixelValue),
+    MaxWidth(PixelValue),
+    Height(PixelValue),
+    MinHeight(PixelValue),
+    MaxHeight(PixelValue),
+    Orientation(MediaOrientation),
+    /// Resolution in dots per pixel (i.e. the HiDPI factor), `2dppx` or `192dpi` => `2.0`
+    Resolution(FloatValue),
+    MinResolution(FloatValue),
+    MaxResolution(FloatValue),
+    PrefersColorScheme(ColorScheme),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MediaOrientation {
+    Portrait,
+    Landscape,
+}
+
+/// Light or dark theme, for `@media (prefers-color-scheme: dark)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ColorScheme {
+    fn default() -> Self {
+        ColorScheme::Light
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorScheme::Light => write!(f, "light"),
+            ColorScheme::Dark => write!(f, "dark"),
+        }
+    }
+}
+
+/// Properties of the window that `@media` queries are evaluated against
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct MediaQueryEnvironment {
+    /// Width of the window in logical pixels
+    pub width: f32,
+    /// Height of the window in logical pixels
+    pub height: f32,
+    /// HiDPI factor of the window, used for `resolution` queries
+    pub hidpi_factor: f32,
+    /// Color scheme that the user prefers
+    pub color_scheme: ColorScheme,
+}
+
+impl Default for MediaQueryEnvironment {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 600.0,
+            hidpi_factor: 1.0,
+            color_scheme: ColorScheme::Light,
+        }
+    }
+}
+
+impl MediaQueryList {
+    pub fn matches(&self, environment: &MediaQueryEnvironment) -> bool {
+        self.queries.iter().any(|query| query.matches(environment))
+    }
+}
+
+impl fmt::Display for MediaQueryList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, query) in self.queries.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", query)?;
+        }
+        Ok(())
+    }
+}
+
+impl MediaQuery {
+    pub fn matches(&self, environment: &MediaQueryEnvironment) -> bool {
+        let media_type_matches = match self.media_type {
+            MediaType::All | MediaType::Screen => true,
+            MediaType::Print => false,
+        };
+        let matches = media_type_matches && self.features.iter().all(|feature| feature.matches(environment));
+        matches != self.negated
+    }
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "(min-width: 600px)" is short for "all and (min-width: 600px)"
+        let write_media_type = self.negated || self.media_type != MediaType::All || self.features.is_empty();
+
+        if self.negated {
+            write!(f, "not ")?;
+        }
+        if write_media_type {
+            write!(f, "{}", self.media_type)?;
+        }
+        for (i, feature) in self.features.iter().enumerate() {
+            if write_media_type || i != 0 {
+                write!(f, " and ")?;
+            }
+            write!(f, "{}", feature)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MediaType::All => write!(f, "all"),
+            MediaType::Screen => write!(f, "screen"),
+            MediaType::Print => write!(f, "print"),
+        }
+    }
+}
+
+impl fmt::Display for MediaOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MediaOrientation::Portrait => write!(f, "portrait"),
+            MediaOrientation::Landscape => write!(f, "landscape"),
+        }
+    }
+}
+
+impl MediaFeature {
+    pub fn matches(&self, environment: &MediaQueryEnvironment) -> bool {
+        use self::MediaFeature::*;
+        // Percentages are not allowed in media queries, so they resolve to 0
+        let to_pixels = |value: &PixelValue| value.to_pixels_in_viewport(0.0, environment.width, environment.height);
+        match self {
+            Width(w) => environment.width == to_pixels(w),
+            MinWidth(w) => environment.width >= to_pixels(w),
+            MaxWidth(w) => environment.width <= to_pixels(w),
+            Height(h) => environment.height == to_pixels(h),
+            MinHeight(h) => environment.height >= to_pixels(h),
+            MaxHeight(h) => environment.height <= to_pixels(h),
+            Orientation(MediaOrientation::Portrait) => environment.height >= environment.width,
+            Orientation(MediaOrientation::Landscape) => environment.width > environment.height,
+            Resolution(r) => environment.hidpi_factor == r.get(),
+            MinResolution(r) => environment.hidpi_factor >= r.get(),
+            MaxResolution(r) => environment.hidpi_factor <= r.get(),
+            PrefersColorScheme(c) => environment.color_scheme == *c,
+        }
+    }
+}
+
+impl fmt::Display for MediaFeature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::MediaFeature::*;
+        match self {
+            Width(w) => write!(f, "(width: {})", w),
+            MinWidth(w) => write!(f, "(min-width: {})", w),
+            MaxWidth(w) => write!(f, "(max-width: {})", w),
+            Height(h) => write!(f, "(height: {})", h),
+            MinHeight(h) => write!(f, "(min-height: {})", h),
+            MaxHeight(h) => write!(f, "(max-height: {})", h),
+            Orientation(o) => write!(f, "(orientation: {})", o),
+            Resolution(r) => write!(f, "(resolution: {}dppx)", r),
+            MinResolution(r) => write!(f, "(min-resolution: {}dppx)", r),
+            MaxResolution(r) => write!(f, "(max-resolution: {}dppx)", r),
+            PrefersColorScheme(c) => write!(f, "(prefers-color-scheme: {})", c),
+        }
+    }
 }
 
 pub type CssContentGroup<'a> = Vec<&'a CssPathSelector>;
@@ -324,6 +821,38 @@ impl fmt::Debug for CssPath {
     }
 }
 
+impl CssPath {
+    /// Returns `false` if the path can't match any node, because the pseudo-selectors of one
+    /// compound selector contradict each other, for example `.a:not(.a)`, `:first:nth-child(2)`
+    /// or `:nth-child(0)` (children are counted starting at 1)
+    pub fn can_match(&self) -> bool {
+        use self::CssPathSelector::*;
+        self.selectors
+            .split(|selector| match selector {
+                DirectChildren | Children | AdjacentSibling | GeneralSibling => true,
+                _ => false,
+            })
+            .all(compound_selector_can_match)
+    }
+}
+
+/// Checks a group of selectors without combinators (i.e. `div.a:hover`), see `CssPath::can_match`
+fn compound_selector_can_match(selectors: &[CssPathSelector]) -> bool {
+    use self::CssPathSelector::PseudoSelector;
+    use self::CssPathPseudoSelector::*;
+
+    let is_first_child = selectors.contains(&PseudoSelector(First)) || selectors.contains(&PseudoSelector(OnlyChild));
+    let is_last_child = selectors.contains(&PseudoSelector(Last)) || selectors.contains(&PseudoSelector(OnlyChild));
+
+    selectors.iter().all(|selector| match selector {
+        PseudoSelector(NthChild(nth)) => nth.can_match() && (!is_first_child || nth.matches(1)),
+        PseudoSelector(NthLastChild(nth)) => nth.can_match() && (!is_last_child || nth.matches(1)),
+        // :not() never matches if the element is required to match all of its selectors
+        PseudoSelector(Not(not_selectors)) => !not_selectors.iter().all(|not_selector| selectors.contains(not_selector)),
+        _ => true,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CssPathSelector {
     /// Represents the `*` selector
@@ -340,6 +869,10 @@ pub enum CssPathSelector {
     DirectChildren,
     /// Represents the ` ` selector
     Children,
+    /// Represents the `+` selector
+    AdjacentSibling,
+    /// Represents the `~` selector
+    GeneralSibling,
 }
 
 impl Default for CssPathSelector {
@@ -359,24 +892,36 @@ impl fmt::Display for CssPathSelector {
             PseudoSelector(p) => write!(f, ":{}", p),
             DirectChildren => write!(f, ">"),
             Children => write!(f, " "),
+            AdjacentSibling => write!(f, "+"),
+            GeneralSibling => write!(f, "~"),
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CssPathPseudoSelector {
-    /// `:first`
+    /// `:first` or `:first-child`
     First,
-    /// `:last`
+    /// `:last` or `:last-child`
     Last,
     /// `:nth-child`
     NthChild(CssNthChildSelector),
+    /// `:nth-last-child` - same as `:nth-child`, but counting from the last child
+    NthLastChild(CssNthChildSelector),
+    /// `:only-child` - element has no siblings
+    OnlyChild,
+    /// `:empty` - element has no children and no text
+    Empty,
+    /// `:not(.class#id)` - element does not match the (combinator-free) selector
+    Not(Vec<CssPathSelector>),
     /// `:hover` - mouse is over element
     Hover,
     /// `:active` - mouse is pressed and over element
     Active,
     /// `:focus` - element has received focus
     Focus,
+    /// `:focus-within` - element or any of its children has received focus
+    FocusWithin,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -387,6 +932,26 @@ pub enum CssNthChildSelector {
     Pattern { repeat: usize, offset: usize },
 }
 
+impl CssNthChildSelector {
+    /// Matches the (1-indexed) position of a node against a `:nth-child` / `:nth-last-child` selector
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn matches(&self, index: usize) -> bool {
+        use self::CssNthChildSelector::*;
+        match *self {
+            Number(value) => index == value,
+            Even => index % 2 == 0,
+            Odd => index % 2 == 1,
+            Pattern { repeat: 0, offset } => index == offset,
+            Pattern { repeat, offset } => index >= offset && (index - offset) % repeat == 0,
+        }
+    }
+
+    /// Returns `false` if the selector doesn't match any position, i.e. `:nth-child(0)`
+    pub fn can_match(&self) -> bool {
+        *self != CssNthChildSelector::Number(0) && *self != CssNthChildSelector::Pattern { repeat: 0, offset: 0 }
+    }
+}
+
 impl fmt::Display for CssNthChildSelector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::CssNthChildSelector::*;
@@ -406,9 +971,20 @@ impl fmt::Display for CssPathPseudoSelector {
             First => write!(f, "first"),
             Last => write!(f, "last"),
             NthChild(u) => write!(f, "nth-child({})", u),
+            NthLastChild(u) => write!(f, "nth-last-child({})", u),
+            OnlyChild => write!(f, "only-child"),
+            Empty => write!(f, "empty"),
+            Not(selectors) => {
+                write!(f, "not(")?;
+                for selector in selectors {
+                    write!(f, "{}", selector)?;
+                }
+                write!(f, ")")
+            },
             Hover => write!(f, "hover"),
             Active => write!(f, "active"),
             Focus => write!(f, "focus"),
+            FocusWithin => write!(f, "focus-within"),
         }
     }
 }
@@ -436,6 +1012,27 @@ impl Css {
         }
     }
 
+    /// Removes redundant rules and declarations from all stylesheets (see `Stylesheet::optimize`),
+    /// which speeds up styling, since every rule has to be matched against every node
+    pub fn optimize(&mut self) {
+        for stylesheet in &mut self.stylesheets {
+            stylesheet.optimize()
+        }
+    }
+
+    /// Returns the `@font-face` rules of all stylesheets
+    pub fn font_faces<'a>(&'a self) -> impl Iterator<Item = &'a FontFace> + 'a {
+        self.stylesheets.iter().flat_map(|stylesheet| stylesheet.font_faces.iter())
+    }
+
+    /// Returns the `@keyframes` rule with the given name - if multiple
+    /// rules have the same name, the last one wins
+    pub fn get_keyframes(&self, name: &str) -> Option<&CssKeyframes> {
+        self.stylesheets.iter().rev()
+            .flat_map(|stylesheet| stylesheet.keyframes.iter().rev())
+            .find(|keyframes| keyframes.name == name)
+    }
+
     pub fn rules<'a>(&'a self) -> RuleIterator<'a> {
         RuleIterator {
             current_stylesheet: 0,
@@ -482,6 +1079,86 @@ impl Stylesheet {
     pub fn sort_by_specificity(&mut self) {
         self.rules.sort_by(|a, b| get_specificity(&a.path).cmp(&get_specificity(&b.path)));
     }
+
+    /// Removes rules and declarations that don't affect the style of any node, without changing
+    /// the result of the cascade. Should be called after `sort_by_specificity()`:
+    ///
+    /// - Declarations that are overridden by a later rule with the same path and `@media`
+    ///   conditions (or later in the same rule) are removed
+    /// - Rules with the same path and `@media` conditions are merged into the earlier rule,
+    ///   unless a rule in between the two declares one of the moved properties
+    /// - Rules that are empty or can never match a node (see `CssPath::can_match`) are removed
+    pub fn optimize(&mut self) {
+        remove_overridden_declarations(&mut self.rules);
+        merge_rule_blocks(&mut self.rules);
+        self.rules.retain(|rule| !rule.is_empty() && rule.path.can_match());
+    }
+}
+
+/// A rule always overrides the earlier rules with the same path and `@media` conditions,
+/// so the declarations of the earlier rules that it redeclares can be removed
+fn remove_overridden_declarations(rules: &mut [CssRuleBlock]) {
+
+    // Properties that are declared by the later rules, per path and @media conditions
+    let mut declared_later = BTreeMap::<(CssPath, Vec<MediaQueryList>), (BTreeSet<CssPropertyType>, BTreeSet<String>)>::new();
+
+    for rule in rules.iter_mut().rev() {
+        let (properties, custom_properties) = declared_later
+            .entry((rule.path.clone(), rule.media_queries.clone()))
+            .or_default();
+
+        // Iterating in reverse, so that the last declaration of a property wins
+        let mut declarations = rule.declarations.drain(..).rev()
+            .filter(|declaration| properties.insert(declaration.get_type()))
+            .collect::<Vec<_>>();
+        declarations.reverse();
+        rule.declarations = declarations;
+
+        let mut custom = rule.custom_properties.drain(..).rev()
+            .filter(|custom_property| custom_properties.insert(custom_property.name.clone()))
+            .collect::<Vec<_>>();
+        custom.reverse();
+        rule.custom_properties = custom;
+
+        if let Some(source) = &mut rule.source {
+            let declarations = &rule.declarations;
+            let custom_properties = &rule.custom_properties;
+            source.declarations.retain(|property_type, _| declarations.iter().any(|d| d.get_type() == *property_type));
+            source.custom_properties.retain(|name, _| custom_properties.iter().any(|c| c.name == *name));
+        }
+    }
+}
+
+/// Merges each rule into the previous rule with the same path and `@media` conditions. The
+/// declarations move up in the cascade, so no rule in between may declare the same properties.
+fn merge_rule_blocks(rules: &mut Vec<CssRuleBlock>) {
+
+    let mut merged_rules = Vec::<CssRuleBlock>::with_capacity(rules.len());
+
+    for rule in rules.drain(..) {
+        let merge_target = merged_rules.iter()
+            .rposition(|previous| previous.path == rule.path && previous.media_queries == rule.media_queries)
+            .filter(|target| merged_rules[(target + 1)..].iter().all(|between| !between.declares_same_property_as(&rule)));
+
+        match merge_target {
+            Some(target) => {
+                let target = &mut merged_rules[target];
+                target.declarations.extend(rule.declarations);
+                target.custom_properties.extend(rule.custom_properties);
+                target.source = match (target.source.take(), rule.source) {
+                    (Some(mut target_source), Some(source)) => {
+                        target_source.declarations.extend(source.declarations);
+                        target_source.custom_properties.extend(source.custom_properties);
+                        Some(target_source)
+                    },
+                    (target_source, source) => target_source.or(source),
+                };
+            },
+            None => merged_rules.push(rule),
+        }
+    }
+
+    *rules = merged_rules;
 }
 
 /// Returns specificity of the given css path. Further information can be found on
@@ -511,12 +1188,14 @@ fn test_specificity_sort() {
     let mut input_style = Stylesheet {
         rules: vec![
             // Rules are sorted from lowest-specificity to highest specificity
-            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
         ],
+        font_faces: Vec::new(),
+        keyframes: Vec::new(),
     };
 
     input_style.sort_by_specificity();
@@ -524,13 +1203,43 @@ fn test_specificity_sort() {
     let expected_style = Stylesheet {
         rules: vec![
             // Rules are sorted from lowest-specificity to highest specificity
-            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
+            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new(), custom_properties: Vec::new(), media_queries: Vec::new(), source: None },
         ],
+        font_faces: Vec::new(),
+        keyframes: Vec::new(),
     };
 
     assert_eq!(input_style, expected_style);
-}
\ No newline at end of file
+}
+#[test]
+fn test_media_query_matches() {
+    let environment = MediaQueryEnvironment {
+        width: 500.0,
+        height: 800.0,
+        hidpi_factor: 2.0,
+        color_scheme: ColorScheme::Dark,
+    };
+
+    let query = |negated, media_type, features| MediaQueryList { queries: vec![MediaQuery { negated, media_type, features }] };
+
+    assert!(query(false, MediaType::All, vec![MediaFeature::MaxWidth(PixelValue::px(600.0))]).matches(&environment));
+    assert!(!query(false, MediaType::All, vec![MediaFeature::MinWidth(PixelValue::px(600.0))]).matches(&environment));
+    assert!(query(true, MediaType::All, vec![MediaFeature::MinWidth(PixelValue::px(600.0))]).matches(&environment));
+    assert!(query(false, MediaType::Screen, vec![
+        MediaFeature::Orientation(MediaOrientation::Portrait),
+        MediaFeature::MinResolution(FloatValue::new(2.0)),
+        MediaFeature::PrefersColorScheme(ColorScheme::Dark),
+    ]).matches(&environment));
+    assert!(!query(false, MediaType::Print, Vec::new()).matches(&environment));
+
+    // Comma-separated queries: any of them has to match
+    let list = MediaQueryList { queries: vec![
+        MediaQuery { negated: false, media_type: MediaType::All, features: vec![MediaFeature::MinHeight(PixelValue::px(1000.0))] },
+        MediaQuery { negated: false, media_type: MediaType::All, features: vec![MediaFeature::MaxHeight(PixelValue::em(60.0))] },
+    ]};
+    assert!(list.matches(&environment));
+}
@@ -2,13 +2,14 @@
 
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Duration;
 use css::CssPropertyValue;
 
 /// Currently hard-coded: Height of one em in pixels
 pub const EM_HEIGHT: f32 = 16.0;
 pub const PT_TO_PX: f32 = 96.0 / 72.0;
 
-const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str);10] = [
+const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str);15] = [
     (CombinedCssPropertyType::BorderRadius, "border-radius"),
     (CombinedCssPropertyType::Overflow, "overflow"),
     (CombinedCssPropertyType::Padding, "padding"),
@@ -18,11 +19,16 @@ const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str);
     (CombinedCssPropertyType::BorderRight, "border-right"),
     (CombinedCssPropertyType::BorderTop, "border-top"),
     (CombinedCssPropertyType::BorderBottom, "border-bottom"),
+    (CombinedCssPropertyType::BorderImage, "border-image"),
     (CombinedCssPropertyType::BoxShadow, "box-shadow"),
+    (CombinedCssPropertyType::GridColumn, "grid-column"),
+    (CombinedCssPropertyType::GridRow, "grid-row"),
+    (CombinedCssPropertyType::GridArea, "grid-area"),
+    (CombinedCssPropertyType::Gap, "gap"),
 ];
 
 /// Map between CSS keys and a statically typed enum
-const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);66] = [
+const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);92] = [
 
     (CssPropertyType::Display,              "display"),
     (CssPropertyType::Float,                "float"),
@@ -31,6 +37,10 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);66] = [
     (CssPropertyType::TextColor,            "color"),
     (CssPropertyType::FontSize,             "font-size"),
     (CssPropertyType::FontFamily,           "font-family"),
+    (CssPropertyType::FontWeight,           "font-weight"),
+    (CssPropertyType::FontStyle,            "font-style"),
+    (CssPropertyType::TextDecoration,       "text-decoration"),
+    (CssPropertyType::TextShadow,           "text-shadow"),
     (CssPropertyType::TextAlign,            "text-align"),
 
     (CssPropertyType::LetterSpacing,        "letter-spacing"),
@@ -51,6 +61,7 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);66] = [
     (CssPropertyType::Right,                "right"),
     (CssPropertyType::Left,                 "left"),
     (CssPropertyType::Bottom,               "bottom"),
+    (CssPropertyType::ZIndex,               "z-index"),
 
     (CssPropertyType::FlexWrap,             "flex-wrap"),
     (CssPropertyType::FlexDirection,        "flex-direction"),
@@ -60,6 +71,16 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);66] = [
     (CssPropertyType::AlignItems,           "align-items"),
     (CssPropertyType::AlignContent,         "align-content"),
 
+    (CssPropertyType::GridTemplateColumns,  "grid-template-columns"),
+    (CssPropertyType::GridTemplateRows,     "grid-template-rows"),
+    (CssPropertyType::GridColumnStart,      "grid-column-start"),
+    (CssPropertyType::GridColumnEnd,        "grid-column-end"),
+    (CssPropertyType::GridRowStart,         "grid-row-start"),
+    (CssPropertyType::GridRowEnd,           "grid-row-end"),
+    (CssPropertyType::GridAutoFlow,         "grid-auto-flow"),
+    (CssPropertyType::RowGap,               "row-gap"),
+    (CssPropertyType::ColumnGap,            "column-gap"),
+
     (CssPropertyType::OverflowX,            "overflow-x"),
     (CssPropertyType::OverflowY,            "overflow-y"),
 
@@ -100,10 +121,26 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);66] = [
     (CssPropertyType::BorderLeftWidth,          "border-left-width"),
     (CssPropertyType::BorderBottomWidth,        "border-bottom-width"),
 
+    (CssPropertyType::BorderImageSource,        "border-image-source"),
+    (CssPropertyType::BorderImageSlice,         "border-image-slice"),
+    (CssPropertyType::BorderImageWidth,         "border-image-width"),
+    (CssPropertyType::BorderImageOutset,        "border-image-outset"),
+    (CssPropertyType::BorderImageRepeat,        "border-image-repeat"),
+
     (CssPropertyType::BoxShadowTop, "box-shadow-top"),
     (CssPropertyType::BoxShadowRight, "box-shadow-right"),
     (CssPropertyType::BoxShadowLeft, "box-shadow-left"),
     (CssPropertyType::BoxShadowBottom, "box-shadow-bottom"),
+
+    (CssPropertyType::Transition,               "transition"),
+    (CssPropertyType::Animation,                "animation"),
+
+    (CssPropertyType::Transform,                "transform"),
+    (CssPropertyType::TransformOrigin,          "transform-origin"),
+
+    (CssPropertyType::Opacity,                  "opacity"),
+    (CssPropertyType::Filter,                   "filter"),
+    (CssPropertyType::MixBlendMode,             "mix-blend-mode"),
 ];
 
 // The following types are present in webrender, however, azul-css should not
@@ -209,6 +246,106 @@ impl LayoutPoint {
     pub const fn zero() -> Self { Self::new(0.0, 0.0) }
 }
 
+/// Only used for calculations: 2D affine transformation matrix in layout space,
+/// same layout as the CSS `matrix(m11, m12, m21, m22, m31, m32)` function:
+///
+/// ```text
+/// x' = x * m11 + y * m21 + m31
+/// y' = x * m12 + y * m22 + m32
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct LayoutTransform {
+    pub m11: f32, pub m12: f32,
+    pub m21: f32, pub m22: f32,
+    pub m31: f32, pub m32: f32,
+}
+
+impl Default for LayoutTransform {
+    fn default() -> Self { Self::identity() }
+}
+
+impl LayoutTransform {
+    #[inline(always)]
+    pub const fn new(m11: f32, m12: f32, m21: f32, m22: f32, m31: f32, m32: f32) -> Self {
+        Self { m11, m12, m21, m22, m31, m32 }
+    }
+    #[inline(always)]
+    pub const fn identity() -> Self { Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0) }
+    #[inline(always)]
+    pub const fn translation(x: f32, y: f32) -> Self { Self::new(1.0, 0.0, 0.0, 1.0, x, y) }
+    #[inline(always)]
+    pub const fn scale(x: f32, y: f32) -> Self { Self::new(x, 0.0, 0.0, y, 0.0, 0.0) }
+
+    /// Clockwise rotation (since the y axis points down)
+    #[inline]
+    pub fn rotation(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    #[inline]
+    pub fn skew(x_degrees: f32, y_degrees: f32) -> Self {
+        Self::new(1.0, y_degrees.to_radians().tan(), x_degrees.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Returns a transform that first applies `self`, then `other`
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            m11: self.m11 * other.m11 + self.m12 * other.m21,
+            m12: self.m11 * other.m12 + self.m12 * other.m22,
+            m21: self.m21 * other.m11 + self.m22 * other.m21,
+            m22: self.m21 * other.m12 + self.m22 * other.m22,
+            m31: self.m31 * other.m11 + self.m32 * other.m21 + other.m31,
+            m32: self.m31 * other.m12 + self.m32 * other.m22 + other.m32,
+        }
+    }
+
+    /// Returns the inverse transform or `None` if the transform isn't invertible
+    /// (for example `scale(0)`, which collapses everything into a single point)
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.m11 * self.m22 - self.m12 * self.m21;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        Some(Self {
+            m11: self.m22 * inv_det,
+            m12: -self.m12 * inv_det,
+            m21: -self.m21 * inv_det,
+            m22: self.m11 * inv_det,
+            m31: (self.m21 * self.m32 - self.m22 * self.m31) * inv_det,
+            m32: (self.m12 * self.m31 - self.m11 * self.m32) * inv_det,
+        })
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: &LayoutPoint) -> LayoutPoint {
+        LayoutPoint::new(
+            point.x * self.m11 + point.y * self.m21 + self.m31,
+            point.x * self.m12 + point.y * self.m22 + self.m32,
+        )
+    }
+
+    /// Returns the axis-aligned bounding box of the transformed rectangle
+    pub fn transform_rect(&self, rect: &LayoutRect) -> LayoutRect {
+        let corners = [
+            self.transform_point(&LayoutPoint::new(rect.min_x(), rect.min_y())),
+            self.transform_point(&LayoutPoint::new(rect.max_x(), rect.min_y())),
+            self.transform_point(&LayoutPoint::new(rect.min_x(), rect.max_y())),
+            self.transform_point(&LayoutPoint::new(rect.max_x(), rect.max_y())),
+        ];
+        let min_x = corners.iter().map(|p| p.x).fold(::std::f32::INFINITY, f32::min);
+        let min_y = corners.iter().map(|p| p.y).fold(::std::f32::INFINITY, f32::min);
+        let max_x = corners.iter().map(|p| p.x).fold(::std::f32::NEG_INFINITY, f32::max);
+        let max_y = corners.iter().map(|p| p.y).fold(::std::f32::NEG_INFINITY, f32::max);
+        LayoutRect::new(LayoutPoint::new(min_x, min_y), LayoutSize::new(max_x - min_x, max_y - min_y))
+    }
+}
+
 /// Represents a parsed pair of `5px, 10px` values - useful for border radius calculation
 #[derive(Default, Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
 pub struct PixelSize { pub width: PixelValue, pub height: PixelValue }
@@ -255,7 +392,7 @@ impl ColorU {
     pub const TRANSPARENT: ColorU = ColorU { r: 0, g: 0, b: 0, a: 0 };
 
     pub fn write_hash(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "#{:x}{:x}{:x}{:x}", self.r, self.g, self.b, self.a)
+        write!(f, "#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
     }
 }
 
@@ -299,10 +436,11 @@ impl From<ColorF> for ColorU {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[allow(clippy::large_enum_variant)]
 pub enum BorderDetails {
     Normal(NormalBorder),
-    NinePatch(NinePatchBorder),
+    NinePatch(Box<NinePatchBorder>),
 }
 
 /// Represents a normal `border` property (no image border / nine-patch border)
@@ -420,9 +558,156 @@ impl Default for BorderStyle {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+/// Represents a `border-image` (nine-patch border): The `slice` cuts the image into
+/// nine regions - the corners are drawn into the corners of the border, the edges
+/// are stretched or repeated along the sides (depending on the `repeat`).
+#[derive(Debug, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
 pub struct NinePatchBorder {
-    // not implemented or parse-able yet, so no fields!
+    pub source: CssImageId,
+    pub slice: StyleBorderImageSlice,
+    pub width: StyleBorderImageWidth,
+    pub outset: StyleBorderImageOutset,
+    pub repeat: StyleBorderImageRepeat,
+}
+
+/// Represents a `border-image-source` attribute, i.e. `border-image-source: image("button")`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBorderImageSource(pub CssImageId);
+
+impl fmt::Display for StyleBorderImageSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "image(\"{}\")", self.0)
+    }
+}
+
+/// Represents a `border-image-slice` attribute, i.e. `border-image-slice: 10 20% fill`.
+/// Numbers are stored as `px` (pixels of the image), percentages refer to the size of the image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBorderImageSlice {
+    pub top: PixelValue,
+    pub right: PixelValue,
+    pub bottom: PixelValue,
+    pub left: PixelValue,
+    /// Whether the middle part of the image is drawn as the background of the rectangle
+    pub fill: bool,
+}
+
+impl Default for StyleBorderImageSlice {
+    fn default() -> Self {
+        let full = PixelValue::percent(100.0);
+        StyleBorderImageSlice { top: full, right: full, bottom: full, left: full, fill: false }
+    }
+}
+
+impl fmt::Display for StyleBorderImageSlice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // pixels of the image are written as plain numbers, like in CSS
+        fn fmt_slice(value: &PixelValue, f: &mut fmt::Formatter) -> fmt::Result {
+            match value.metric {
+                SizeMetric::Px => write!(f, "{}", value.number),
+                _ => write!(f, "{}", value),
+            }
+        }
+        for (idx, value) in [self.top, self.right, self.bottom, self.left].iter().enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            fmt_slice(value, f)?;
+        }
+        if self.fill {
+            write!(f, " fill")?;
+        }
+        Ok(())
+    }
+}
+
+/// One side of a `border-image-width` or `border-image-outset`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BorderImageLength {
+    /// Use the size of the image slice (only valid for `border-image-width`)
+    Auto,
+    /// Multiple of the `border-width` of the same side
+    Number(FloatValue),
+    /// Percentages refer to the size of the rectangle (only valid for `border-image-width`)
+    Length(PixelValue),
+}
+
+impl fmt::Display for BorderImageLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::BorderImageLength::*;
+        match self {
+            Auto => write!(f, "auto"),
+            Number(n) => write!(f, "{}", n),
+            Length(l) => write!(f, "{}", l),
+        }
+    }
+}
+
+macro_rules! impl_border_image_sides {($struct:ident, $default:expr) => (
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct $struct {
+        pub top: BorderImageLength,
+        pub right: BorderImageLength,
+        pub bottom: BorderImageLength,
+        pub left: BorderImageLength,
+    }
+
+    impl Default for $struct {
+        fn default() -> Self {
+            $struct { top: $default, right: $default, bottom: $default, left: $default }
+        }
+    }
+
+    impl fmt::Display for $struct {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} {} {} {}", self.top, self.right, self.bottom, self.left)
+        }
+    }
+)}
+
+impl_border_image_sides!(StyleBorderImageWidth, BorderImageLength::Number(FloatValue::const_new(1)));
+impl_border_image_sides!(StyleBorderImageOutset, BorderImageLength::Number(FloatValue::const_new(0)));
+
+/// How the edges of a `border-image` are filled
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BorderImageRepeat {
+    Stretch,
+    Repeat,
+    Round,
+    Space,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for BorderImageRepeat {
+    fn default() -> Self {
+        BorderImageRepeat::Stretch
+    }
+}
+
+impl fmt::Display for BorderImageRepeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::BorderImageRepeat::*;
+        match self {
+            Stretch => write!(f, "stretch"),
+            Repeat => write!(f, "repeat"),
+            Round => write!(f, "round"),
+            Space => write!(f, "space"),
+        }
+    }
+}
+
+/// Represents a `border-image-repeat` attribute, i.e. `border-image-repeat: round stretch`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBorderImageRepeat {
+    pub horizontal: BorderImageRepeat,
+    pub vertical: BorderImageRepeat,
+}
+
+impl fmt::Display for StyleBorderImageRepeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.horizontal, self.vertical)
+    }
 }
 
 macro_rules! derive_debug_zero {($struct:ident) => (
@@ -467,12 +752,75 @@ macro_rules! impl_pixel_value {($struct:ident) => (
     }
 )}
 
+/// Implements `Debug`, `Display` and the constructors for a corner radius, which
+/// wraps a `PixelSize` (`width` = horizontal radius, `height` = vertical radius).
+/// The `px`, `em`, `pt` and `percent` constructors create a circular corner.
+macro_rules! impl_border_radius {($struct:ident) => (
+
+    impl ::std::fmt::Debug for $struct {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            write!(f, "{}", self)
+        }
+    }
+
+    // Written as `10px` for circular and as `10px 5px` for elliptical corners
+    impl ::std::fmt::Display for $struct {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            if self.0.width == self.0.height {
+                write!(f, "{}", self.0.width)
+            } else {
+                write!(f, "{} {}", self.0.width, self.0.height)
+            }
+        }
+    }
+
+    impl $struct {
+        #[inline]
+        pub fn px(value: f32) -> Self {
+            Self::circular(PixelValue::px(value))
+        }
+
+        #[inline]
+        pub fn em(value: f32) -> Self {
+            Self::circular(PixelValue::em(value))
+        }
+
+        #[inline]
+        pub fn pt(value: f32) -> Self {
+            Self::circular(PixelValue::pt(value))
+        }
+
+        #[inline]
+        pub fn percent(value: f32) -> Self {
+            Self::circular(PixelValue::percent(value))
+        }
+
+        #[inline]
+        pub const fn circular(radius: PixelValue) -> Self {
+            $struct(PixelSize::new(radius, radius))
+        }
+
+        #[inline]
+        pub const fn elliptical(horizontal: PixelValue, vertical: PixelValue) -> Self {
+            $struct(PixelSize::new(horizontal, vertical))
+        }
+    }
+)}
+
 macro_rules! impl_percentage_value{($struct:ident) => (
     impl ::std::fmt::Debug for $struct {
         fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
             write!(f, "{}%", self.0.get())
         }
     }
+
+    // Written as a factor ("1.2" instead of "120%"), since the
+    // value is stored as a factor, not as a percentage
+    impl ::std::fmt::Display for $struct {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            write!(f, "{}", self.0.get())
+        }
+    }
 )}
 
 macro_rules! impl_float_value{($struct:ident) => (
@@ -481,6 +829,12 @@ macro_rules! impl_float_value{($struct:ident) => (
             write!(f, "{}", self.0.get())
         }
     }
+
+    impl ::std::fmt::Display for $struct {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            write!(f, "{}", self.0.get())
+        }
+    }
 )}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -493,13 +847,18 @@ pub enum CombinedCssPropertyType {
     BorderRight,
     BorderTop,
     BorderBottom,
+    BorderImage,
     Padding,
     BoxShadow,
+    GridColumn,
+    GridRow,
+    GridArea,
+    Gap,
 }
 
 impl fmt::Display for CombinedCssPropertyType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let key = COMBINED_CSS_PROPERTIES_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(k, _)| Some(k)).unwrap();
+        let key = COMBINED_CSS_PROPERTIES_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(_, k)| Some(k)).unwrap();
         write!(f, "{}", key)
     }
 }
@@ -550,6 +909,10 @@ pub enum CssPropertyType {
     TextColor,
     FontSize,
     FontFamily,
+    FontWeight,
+    FontStyle,
+    TextDecoration,
+    TextShadow,
     TextAlign,
 
     LetterSpacing,
@@ -573,6 +936,7 @@ pub enum CssPropertyType {
     Right,
     Left,
     Bottom,
+    ZIndex,
 
     FlexWrap,
     FlexDirection,
@@ -582,6 +946,16 @@ pub enum CssPropertyType {
     AlignItems,
     AlignContent,
 
+    GridTemplateColumns,
+    GridTemplateRows,
+    GridColumnStart,
+    GridColumnEnd,
+    GridRowStart,
+    GridRowEnd,
+    GridAutoFlow,
+    RowGap,
+    ColumnGap,
+
     OverflowX,
     OverflowY,
 
@@ -622,10 +996,26 @@ pub enum CssPropertyType {
     BorderLeftWidth,
     BorderBottomWidth,
 
+    BorderImageSource,
+    BorderImageSlice,
+    BorderImageWidth,
+    BorderImageOutset,
+    BorderImageRepeat,
+
     BoxShadowLeft,
     BoxShadowRight,
     BoxShadowTop,
     BoxShadowBottom,
+
+    Transition,
+    Animation,
+
+    Transform,
+    TransformOrigin,
+
+    Opacity,
+    Filter,
+    MixBlendMode,
 }
 
 impl CssPropertyType {
@@ -657,7 +1047,10 @@ impl CssPropertyType {
         match self {
             | TextColor
             | FontFamily
+            | FontWeight
+            | FontStyle
             | FontSize
+            | TextShadow
             | LineHeight
             | TextAlign => true,
             _ => false,
@@ -677,6 +1070,8 @@ impl CssPropertyType {
 
         match self {
             | TextColor
+            | TextDecoration
+            | TextShadow
             | Cursor
             | Background
             | BackgroundPosition
@@ -695,10 +1090,23 @@ impl CssPropertyType {
             | BorderRightStyle
             | BorderLeftStyle
             | BorderBottomStyle
+            | BorderImageSource
+            | BorderImageSlice
+            | BorderImageWidth
+            | BorderImageOutset
+            | BorderImageRepeat
             | BoxShadowLeft
             | BoxShadowRight
             | BoxShadowTop
             | BoxShadowBottom
+            | Transition
+            | Animation
+            | Transform
+            | TransformOrigin
+            | Opacity
+            | Filter
+            | MixBlendMode
+            | ZIndex
             => false,
             _ => true,
         }
@@ -707,7 +1115,7 @@ impl CssPropertyType {
 
 impl fmt::Display for CssPropertyType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let key = CSS_PROPERTY_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(k, _)| Some(k)).unwrap();
+        let key = CSS_PROPERTY_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(_, k)| Some(k)).unwrap();
         write!(f, "{}", key)
     }
 }
@@ -719,6 +1127,10 @@ pub enum CssProperty {
     TextColor(CssPropertyValue<StyleTextColor>),
     FontSize(CssPropertyValue<StyleFontSize>),
     FontFamily(CssPropertyValue<StyleFontFamily>),
+    FontWeight(CssPropertyValue<StyleFontWeight>),
+    FontStyle(CssPropertyValue<StyleFontStyle>),
+    TextDecoration(CssPropertyValue<StyleTextDecoration>),
+    TextShadow(CssPropertyValue<StyleTextShadow>),
     TextAlign(CssPropertyValue<StyleTextAlignmentHorz>),
 
     LetterSpacing(CssPropertyValue<StyleLetterSpacing>),
@@ -743,6 +1155,7 @@ pub enum CssProperty {
     Right(CssPropertyValue<LayoutRight>),
     Left(CssPropertyValue<LayoutLeft>),
     Bottom(CssPropertyValue<LayoutBottom>),
+    ZIndex(CssPropertyValue<LayoutZIndex>),
 
     FlexWrap(CssPropertyValue<LayoutWrap>),
     FlexDirection(CssPropertyValue<LayoutDirection>),
@@ -752,10 +1165,20 @@ pub enum CssProperty {
     AlignItems(CssPropertyValue<LayoutAlignItems>),
     AlignContent(CssPropertyValue<LayoutAlignContent>),
 
-    BackgroundContent(CssPropertyValue<StyleBackgroundContent>),
-    BackgroundPosition(CssPropertyValue<StyleBackgroundPosition>),
-    BackgroundSize(CssPropertyValue<StyleBackgroundSize>),
-    BackgroundRepeat(CssPropertyValue<StyleBackgroundRepeat>),
+    GridTemplateColumns(CssPropertyValue<LayoutGridTemplateColumns>),
+    GridTemplateRows(CssPropertyValue<LayoutGridTemplateRows>),
+    GridColumnStart(CssPropertyValue<LayoutGridColumnStart>),
+    GridColumnEnd(CssPropertyValue<LayoutGridColumnEnd>),
+    GridRowStart(CssPropertyValue<LayoutGridRowStart>),
+    GridRowEnd(CssPropertyValue<LayoutGridRowEnd>),
+    GridAutoFlow(CssPropertyValue<LayoutGridAutoFlow>),
+    RowGap(CssPropertyValue<LayoutRowGap>),
+    ColumnGap(CssPropertyValue<LayoutColumnGap>),
+
+    BackgroundContent(CssPropertyValue<StyleBackgroundContentList>),
+    BackgroundPosition(CssPropertyValue<StyleBackgroundPositionList>),
+    BackgroundSize(CssPropertyValue<StyleBackgroundSizeList>),
+    BackgroundRepeat(CssPropertyValue<StyleBackgroundRepeatList>),
 
     OverflowX(CssPropertyValue<Overflow>),
     OverflowY(CssPropertyValue<Overflow>),
@@ -790,10 +1213,26 @@ pub enum CssProperty {
     BorderLeftWidth(CssPropertyValue<StyleBorderLeftWidth>),
     BorderBottomWidth(CssPropertyValue<StyleBorderBottomWidth>),
 
-    BoxShadowLeft(CssPropertyValue<BoxShadowPreDisplayItem>),
-    BoxShadowRight(CssPropertyValue<BoxShadowPreDisplayItem>),
-    BoxShadowTop(CssPropertyValue<BoxShadowPreDisplayItem>),
-    BoxShadowBottom(CssPropertyValue<BoxShadowPreDisplayItem>),
+    BorderImageSource(CssPropertyValue<StyleBorderImageSource>),
+    BorderImageSlice(CssPropertyValue<StyleBorderImageSlice>),
+    BorderImageWidth(CssPropertyValue<StyleBorderImageWidth>),
+    BorderImageOutset(CssPropertyValue<StyleBorderImageOutset>),
+    BorderImageRepeat(CssPropertyValue<StyleBorderImageRepeat>),
+
+    BoxShadowLeft(CssPropertyValue<StyleBoxShadowList>),
+    BoxShadowRight(CssPropertyValue<StyleBoxShadowList>),
+    BoxShadowTop(CssPropertyValue<StyleBoxShadowList>),
+    BoxShadowBottom(CssPropertyValue<StyleBoxShadowList>),
+
+    Transition(CssPropertyValue<StyleTransitions>),
+    Animation(CssPropertyValue<StyleAnimations>),
+
+    Transform(CssPropertyValue<StyleTransform>),
+    TransformOrigin(CssPropertyValue<StyleTransformOrigin>),
+
+    Opacity(CssPropertyValue<StyleOpacity>),
+    Filter(CssPropertyValue<StyleFilter>),
+    MixBlendMode(CssPropertyValue<StyleMixBlendMode>),
 }
 
 macro_rules! css_property_from_type {($prop_type:expr, $content_type:ident) => ({
@@ -801,6 +1240,10 @@ macro_rules! css_property_from_type {($prop_type:expr, $content_type:ident) => (
         CssPropertyType::TextColor => CssProperty::TextColor(CssPropertyValue::$content_type),
         CssPropertyType::FontSize => CssProperty::FontSize(CssPropertyValue::$content_type),
         CssPropertyType::FontFamily => CssProperty::FontFamily(CssPropertyValue::$content_type),
+        CssPropertyType::FontWeight => CssProperty::FontWeight(CssPropertyValue::$content_type),
+        CssPropertyType::FontStyle => CssProperty::FontStyle(CssPropertyValue::$content_type),
+        CssPropertyType::TextDecoration => CssProperty::TextDecoration(CssPropertyValue::$content_type),
+        CssPropertyType::TextShadow => CssProperty::TextShadow(CssPropertyValue::$content_type),
         CssPropertyType::TextAlign => CssProperty::TextAlign(CssPropertyValue::$content_type),
         CssPropertyType::LetterSpacing => CssProperty::LetterSpacing(CssPropertyValue::$content_type),
         CssPropertyType::LineHeight => CssProperty::LineHeight(CssPropertyValue::$content_type),
@@ -821,6 +1264,7 @@ macro_rules! css_property_from_type {($prop_type:expr, $content_type:ident) => (
         CssPropertyType::Right => CssProperty::Right(CssPropertyValue::$content_type),
         CssPropertyType::Left => CssProperty::Left(CssPropertyValue::$content_type),
         CssPropertyType::Bottom => CssProperty::Bottom(CssPropertyValue::$content_type),
+        CssPropertyType::ZIndex => CssProperty::ZIndex(CssPropertyValue::$content_type),
         CssPropertyType::FlexWrap => CssProperty::FlexWrap(CssPropertyValue::$content_type),
         CssPropertyType::FlexDirection => CssProperty::FlexDirection(CssPropertyValue::$content_type),
         CssPropertyType::FlexGrow => CssProperty::FlexGrow(CssPropertyValue::$content_type),
@@ -828,6 +1272,15 @@ macro_rules! css_property_from_type {($prop_type:expr, $content_type:ident) => (
         CssPropertyType::JustifyContent => CssProperty::JustifyContent(CssPropertyValue::$content_type),
         CssPropertyType::AlignItems => CssProperty::AlignItems(CssPropertyValue::$content_type),
         CssPropertyType::AlignContent => CssProperty::AlignContent(CssPropertyValue::$content_type),
+        CssPropertyType::GridTemplateColumns => CssProperty::GridTemplateColumns(CssPropertyValue::$content_type),
+        CssPropertyType::GridTemplateRows => CssProperty::GridTemplateRows(CssPropertyValue::$content_type),
+        CssPropertyType::GridColumnStart => CssProperty::GridColumnStart(CssPropertyValue::$content_type),
+        CssPropertyType::GridColumnEnd => CssProperty::GridColumnEnd(CssPropertyValue::$content_type),
+        CssPropertyType::GridRowStart => CssProperty::GridRowStart(CssPropertyValue::$content_type),
+        CssPropertyType::GridRowEnd => CssProperty::GridRowEnd(CssPropertyValue::$content_type),
+        CssPropertyType::GridAutoFlow => CssProperty::GridAutoFlow(CssPropertyValue::$content_type),
+        CssPropertyType::RowGap => CssProperty::RowGap(CssPropertyValue::$content_type),
+        CssPropertyType::ColumnGap => CssProperty::ColumnGap(CssPropertyValue::$content_type),
         CssPropertyType::OverflowX => CssProperty::OverflowX(CssPropertyValue::$content_type),
         CssPropertyType::OverflowY => CssProperty::OverflowY(CssPropertyValue::$content_type),
         CssPropertyType::PaddingTop => CssProperty::PaddingTop(CssPropertyValue::$content_type),
@@ -860,10 +1313,22 @@ macro_rules! css_property_from_type {($prop_type:expr, $content_type:ident) => (
         CssPropertyType::BorderRightWidth => CssProperty::BorderRightWidth(CssPropertyValue::$content_type),
         CssPropertyType::BorderLeftWidth => CssProperty::BorderLeftWidth(CssPropertyValue::$content_type),
         CssPropertyType::BorderBottomWidth => CssProperty::BorderBottomWidth(CssPropertyValue::$content_type),
+        CssPropertyType::BorderImageSource => CssProperty::BorderImageSource(CssPropertyValue::$content_type),
+        CssPropertyType::BorderImageSlice => CssProperty::BorderImageSlice(CssPropertyValue::$content_type),
+        CssPropertyType::BorderImageWidth => CssProperty::BorderImageWidth(CssPropertyValue::$content_type),
+        CssPropertyType::BorderImageOutset => CssProperty::BorderImageOutset(CssPropertyValue::$content_type),
+        CssPropertyType::BorderImageRepeat => CssProperty::BorderImageRepeat(CssPropertyValue::$content_type),
         CssPropertyType::BoxShadowLeft => CssProperty::BoxShadowLeft(CssPropertyValue::$content_type),
         CssPropertyType::BoxShadowRight => CssProperty::BoxShadowRight(CssPropertyValue::$content_type),
         CssPropertyType::BoxShadowTop => CssProperty::BoxShadowTop(CssPropertyValue::$content_type),
         CssPropertyType::BoxShadowBottom => CssProperty::BoxShadowBottom(CssPropertyValue::$content_type),
+        CssPropertyType::Transition => CssProperty::Transition(CssPropertyValue::$content_type),
+        CssPropertyType::Animation => CssProperty::Animation(CssPropertyValue::$content_type),
+        CssPropertyType::Transform => CssProperty::Transform(CssPropertyValue::$content_type),
+        CssPropertyType::TransformOrigin => CssProperty::TransformOrigin(CssPropertyValue::$content_type),
+        CssPropertyType::Opacity => CssProperty::Opacity(CssPropertyValue::$content_type),
+        CssPropertyType::Filter => CssProperty::Filter(CssPropertyValue::$content_type),
+        CssPropertyType::MixBlendMode => CssProperty::MixBlendMode(CssPropertyValue::$content_type),
     }
 })}
 
@@ -875,6 +1340,10 @@ impl CssProperty {
             CssProperty::TextColor(_) => CssPropertyType::TextColor,
             CssProperty::FontSize(_) => CssPropertyType::FontSize,
             CssProperty::FontFamily(_) => CssPropertyType::FontFamily,
+            CssProperty::FontWeight(_) => CssPropertyType::FontWeight,
+            CssProperty::FontStyle(_) => CssPropertyType::FontStyle,
+            CssProperty::TextDecoration(_) => CssPropertyType::TextDecoration,
+            CssProperty::TextShadow(_) => CssPropertyType::TextShadow,
             CssProperty::TextAlign(_) => CssPropertyType::TextAlign,
             CssProperty::LetterSpacing(_) => CssPropertyType::LetterSpacing,
             CssProperty::LineHeight(_) => CssPropertyType::LineHeight,
@@ -895,6 +1364,7 @@ impl CssProperty {
             CssProperty::Right(_) => CssPropertyType::Right,
             CssProperty::Left(_) => CssPropertyType::Left,
             CssProperty::Bottom(_) => CssPropertyType::Bottom,
+            CssProperty::ZIndex(_) => CssPropertyType::ZIndex,
             CssProperty::FlexWrap(_) => CssPropertyType::FlexWrap,
             CssProperty::FlexDirection(_) => CssPropertyType::FlexDirection,
             CssProperty::FlexGrow(_) => CssPropertyType::FlexGrow,
@@ -902,6 +1372,15 @@ impl CssProperty {
             CssProperty::JustifyContent(_) => CssPropertyType::JustifyContent,
             CssProperty::AlignItems(_) => CssPropertyType::AlignItems,
             CssProperty::AlignContent(_) => CssPropertyType::AlignContent,
+            CssProperty::GridTemplateColumns(_) => CssPropertyType::GridTemplateColumns,
+            CssProperty::GridTemplateRows(_) => CssPropertyType::GridTemplateRows,
+            CssProperty::GridColumnStart(_) => CssPropertyType::GridColumnStart,
+            CssProperty::GridColumnEnd(_) => CssPropertyType::GridColumnEnd,
+            CssProperty::GridRowStart(_) => CssPropertyType::GridRowStart,
+            CssProperty::GridRowEnd(_) => CssPropertyType::GridRowEnd,
+            CssProperty::GridAutoFlow(_) => CssPropertyType::GridAutoFlow,
+            CssProperty::RowGap(_) => CssPropertyType::RowGap,
+            CssProperty::ColumnGap(_) => CssPropertyType::ColumnGap,
 
             CssProperty::BackgroundContent(_) => CssPropertyType::BackgroundImage, // TODO: wrong!
             CssProperty::BackgroundPosition(_) => CssPropertyType::BackgroundPosition,
@@ -934,10 +1413,22 @@ impl CssProperty {
             CssProperty::BorderRightWidth(_) => CssPropertyType::BorderRightWidth,
             CssProperty::BorderLeftWidth(_) => CssPropertyType::BorderLeftWidth,
             CssProperty::BorderBottomWidth(_) => CssPropertyType::BorderBottomWidth,
+            CssProperty::BorderImageSource(_) => CssPropertyType::BorderImageSource,
+            CssProperty::BorderImageSlice(_) => CssPropertyType::BorderImageSlice,
+            CssProperty::BorderImageWidth(_) => CssPropertyType::BorderImageWidth,
+            CssProperty::BorderImageOutset(_) => CssPropertyType::BorderImageOutset,
+            CssProperty::BorderImageRepeat(_) => CssPropertyType::BorderImageRepeat,
             CssProperty::BoxShadowLeft(_) => CssPropertyType::BoxShadowLeft,
             CssProperty::BoxShadowRight(_) => CssPropertyType::BoxShadowRight,
             CssProperty::BoxShadowTop(_) => CssPropertyType::BoxShadowTop,
             CssProperty::BoxShadowBottom(_) => CssPropertyType::BoxShadowBottom,
+            CssProperty::Transition(_) => CssPropertyType::Transition,
+            CssProperty::Animation(_) => CssPropertyType::Animation,
+            CssProperty::Transform(_) => CssPropertyType::Transform,
+            CssProperty::TransformOrigin(_) => CssPropertyType::TransformOrigin,
+            CssProperty::Opacity(_) => CssPropertyType::Opacity,
+            CssProperty::Filter(_) => CssPropertyType::Filter,
+            CssProperty::MixBlendMode(_) => CssPropertyType::MixBlendMode,
         }
     }
 
@@ -956,6 +1447,174 @@ impl CssProperty {
     pub fn inherit(prop_type: CssPropertyType) -> Self {
         css_property_from_type!(prop_type, Inherit)
     }
+
+    /// Interpolates between two values of the same property, `t = 0.0` returns `self`,
+    /// `t = 1.0` returns `other`. Returns `None` if the property can't be animated smoothly
+    /// (such as `display`) or if one of the values isn't an exact value (such as `auto`).
+    pub fn interpolate(&self, other: &CssProperty, t: f32) -> Option<CssProperty> {
+
+        // `transform: none` and `filter: none` are animated like an identity transform / filter
+        // (i.e. `scale(1)` or `blur(0px)`), the missing functions are filled in by the interpolation
+        fn none_to_empty<T: Clone + Default>(value: &CssPropertyValue<T>) -> CssPropertyValue<T> {
+            match value {
+                CssPropertyValue::None => CssPropertyValue::Exact(T::default()),
+                other => other.clone(),
+            }
+        }
+
+        match (self, other) {
+            (CssProperty::Transform(a), CssProperty::Transform(b)) => {
+                return none_to_empty(a).interpolate(&none_to_empty(b), t).map(CssProperty::Transform);
+            },
+            (CssProperty::Filter(a), CssProperty::Filter(b)) => {
+                return none_to_empty(a).interpolate(&none_to_empty(b), t).map(CssProperty::Filter);
+            },
+            _ => { },
+        }
+
+        macro_rules! interpolate_variants {($($variant:ident),+) => (
+            match (self, other) {
+                $(
+                    (CssProperty::$variant(a), CssProperty::$variant(b)) => a.interpolate(b, t).map(CssProperty::$variant),
+                )+
+                _ => None,
+            }
+        )}
+
+        interpolate_variants!(
+            TextColor, FontSize, LetterSpacing, LineHeight, WordSpacing, TabWidth, TextShadow,
+            Width, Height, MinWidth, MinHeight, MaxWidth, MaxHeight,
+            Top, Right, Left, Bottom, FlexGrow, FlexShrink, RowGap, ColumnGap,
+            BackgroundContent,
+            PaddingTop, PaddingLeft, PaddingRight, PaddingBottom,
+            MarginTop, MarginLeft, MarginRight, MarginBottom,
+            BorderTopLeftRadius, BorderTopRightRadius, BorderBottomLeftRadius, BorderBottomRightRadius,
+            BorderTopColor, BorderRightColor, BorderLeftColor, BorderBottomColor,
+            BorderTopWidth, BorderRightWidth, BorderLeftWidth, BorderBottomWidth,
+            BoxShadowLeft, BoxShadowRight, BoxShadowTop, BoxShadowBottom,
+            TransformOrigin, Opacity, ZIndex
+        )
+    }
+}
+
+/// Writes the property as a `key: value` pair, the way it would be written in a
+/// stylesheet, for example `width: 500px` - the output can be parsed again.
+impl fmt::Display for CssProperty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.get_key(), self.get_value_string())
+    }
+}
+
+impl CssProperty {
+
+    /// Returns the CSS key that this property is written with, i.e. `"width"`
+    pub fn get_key(&self) -> &'static str {
+        match self {
+            // get_type() returns BackgroundImage, but the content can also be a color or a gradient
+            CssProperty::BackgroundContent(_) => "background",
+            other => {
+                let prop_type = other.get_type();
+                CSS_PROPERTY_KEY_MAP.iter().find(|(v, _)| *v == prop_type).map(|(_, k)| *k).unwrap()
+            },
+        }
+    }
+
+    /// Returns the value of this property as a CSS string, i.e. `"500px"`
+    pub fn get_value_string(&self) -> String {
+        use self::CssProperty::*;
+        match self {
+            TextColor(v) => v.to_string(),
+            FontSize(v) => v.to_string(),
+            FontFamily(v) => v.to_string(),
+            FontWeight(v) => v.to_string(),
+            FontStyle(v) => v.to_string(),
+            TextDecoration(v) => v.to_string(),
+            TextShadow(v) => v.to_string(),
+            TextAlign(v) => v.to_string(),
+            LetterSpacing(v) => v.to_string(),
+            LineHeight(v) => v.to_string(),
+            WordSpacing(v) => v.to_string(),
+            TabWidth(v) => v.to_string(),
+            Cursor(v) => v.to_string(),
+            Display(v) => v.to_string(),
+            Float(v) => v.to_string(),
+            BoxSizing(v) => v.to_string(),
+            Width(v) => v.to_string(),
+            Height(v) => v.to_string(),
+            MinWidth(v) => v.to_string(),
+            MinHeight(v) => v.to_string(),
+            MaxWidth(v) => v.to_string(),
+            MaxHeight(v) => v.to_string(),
+            Position(v) => v.to_string(),
+            Top(v) => v.to_string(),
+            Right(v) => v.to_string(),
+            Left(v) => v.to_string(),
+            Bottom(v) => v.to_string(),
+            ZIndex(v) => v.to_string(),
+            FlexWrap(v) => v.to_string(),
+            FlexDirection(v) => v.to_string(),
+            FlexGrow(v) => v.to_string(),
+            FlexShrink(v) => v.to_string(),
+            JustifyContent(v) => v.to_string(),
+            AlignItems(v) => v.to_string(),
+            AlignContent(v) => v.to_string(),
+            GridTemplateColumns(v) => v.to_string(),
+            GridTemplateRows(v) => v.to_string(),
+            GridColumnStart(v) => v.to_string(),
+            GridColumnEnd(v) => v.to_string(),
+            GridRowStart(v) => v.to_string(),
+            GridRowEnd(v) => v.to_string(),
+            GridAutoFlow(v) => v.to_string(),
+            RowGap(v) => v.to_string(),
+            ColumnGap(v) => v.to_string(),
+            BackgroundContent(v) => v.to_string(),
+            BackgroundPosition(v) => v.to_string(),
+            BackgroundSize(v) => v.to_string(),
+            BackgroundRepeat(v) => v.to_string(),
+            OverflowX(v) => v.to_string(),
+            OverflowY(v) => v.to_string(),
+            PaddingTop(v) => v.to_string(),
+            PaddingLeft(v) => v.to_string(),
+            PaddingRight(v) => v.to_string(),
+            PaddingBottom(v) => v.to_string(),
+            MarginTop(v) => v.to_string(),
+            MarginLeft(v) => v.to_string(),
+            MarginRight(v) => v.to_string(),
+            MarginBottom(v) => v.to_string(),
+            BorderTopLeftRadius(v) => v.to_string(),
+            BorderTopRightRadius(v) => v.to_string(),
+            BorderBottomLeftRadius(v) => v.to_string(),
+            BorderBottomRightRadius(v) => v.to_string(),
+            BorderTopColor(v) => v.to_string(),
+            BorderRightColor(v) => v.to_string(),
+            BorderLeftColor(v) => v.to_string(),
+            BorderBottomColor(v) => v.to_string(),
+            BorderTopStyle(v) => v.to_string(),
+            BorderRightStyle(v) => v.to_string(),
+            BorderLeftStyle(v) => v.to_string(),
+            BorderBottomStyle(v) => v.to_string(),
+            BorderTopWidth(v) => v.to_string(),
+            BorderRightWidth(v) => v.to_string(),
+            BorderLeftWidth(v) => v.to_string(),
+            BorderBottomWidth(v) => v.to_string(),
+            BorderImageSource(v) => v.to_string(),
+            BorderImageSlice(v) => v.to_string(),
+            BorderImageWidth(v) => v.to_string(),
+            BorderImageOutset(v) => v.to_string(),
+            BorderImageRepeat(v) => v.to_string(),
+            BoxShadowLeft(v) => v.to_string(),
+            BoxShadowRight(v) => v.to_string(),
+            BoxShadowTop(v) => v.to_string(),
+            BoxShadowBottom(v) => v.to_string(),
+            Transition(v) => v.to_string(),
+            Animation(v) => v.to_string(),
+            Transform(v) => v.to_string(),
+            TransformOrigin(v) => v.to_string(),
+            Opacity(v) => v.to_string(),
+            Filter(v) => v.to_string(),
+            MixBlendMode(v) => v.to_string(),
+        }
+    }
 }
 
 macro_rules! impl_from_css_prop {
@@ -971,6 +1630,10 @@ macro_rules! impl_from_css_prop {
 impl_from_css_prop!(StyleTextColor, CssProperty::TextColor);
 impl_from_css_prop!(StyleFontSize, CssProperty::FontSize);
 impl_from_css_prop!(StyleFontFamily, CssProperty::FontFamily);
+impl_from_css_prop!(StyleFontWeight, CssProperty::FontWeight);
+impl_from_css_prop!(StyleFontStyle, CssProperty::FontStyle);
+impl_from_css_prop!(StyleTextDecoration, CssProperty::TextDecoration);
+impl_from_css_prop!(StyleTextShadow, CssProperty::TextShadow);
 impl_from_css_prop!(StyleTextAlignmentHorz, CssProperty::TextAlign);
 impl_from_css_prop!(StyleLetterSpacing, CssProperty::LetterSpacing);
 impl_from_css_prop!(StyleLineHeight, CssProperty::LineHeight);
@@ -991,6 +1654,7 @@ impl_from_css_prop!(LayoutTop, CssProperty::Top);
 impl_from_css_prop!(LayoutRight, CssProperty::Right);
 impl_from_css_prop!(LayoutLeft, CssProperty::Left);
 impl_from_css_prop!(LayoutBottom, CssProperty::Bottom);
+impl_from_css_prop!(LayoutZIndex, CssProperty::ZIndex);
 impl_from_css_prop!(LayoutWrap, CssProperty::FlexWrap);
 impl_from_css_prop!(LayoutDirection, CssProperty::FlexDirection);
 impl_from_css_prop!(LayoutFlexGrow, CssProperty::FlexGrow);
@@ -998,10 +1662,19 @@ impl_from_css_prop!(LayoutFlexShrink, CssProperty::FlexShrink);
 impl_from_css_prop!(LayoutJustifyContent, CssProperty::JustifyContent);
 impl_from_css_prop!(LayoutAlignItems, CssProperty::AlignItems);
 impl_from_css_prop!(LayoutAlignContent, CssProperty::AlignContent);
-impl_from_css_prop!(StyleBackgroundContent, CssProperty::BackgroundContent);
-impl_from_css_prop!(StyleBackgroundPosition, CssProperty::BackgroundPosition);
-impl_from_css_prop!(StyleBackgroundSize, CssProperty::BackgroundSize);
-impl_from_css_prop!(StyleBackgroundRepeat, CssProperty::BackgroundRepeat);
+impl_from_css_prop!(LayoutGridTemplateColumns, CssProperty::GridTemplateColumns);
+impl_from_css_prop!(LayoutGridTemplateRows, CssProperty::GridTemplateRows);
+impl_from_css_prop!(LayoutGridColumnStart, CssProperty::GridColumnStart);
+impl_from_css_prop!(LayoutGridColumnEnd, CssProperty::GridColumnEnd);
+impl_from_css_prop!(LayoutGridRowStart, CssProperty::GridRowStart);
+impl_from_css_prop!(LayoutGridRowEnd, CssProperty::GridRowEnd);
+impl_from_css_prop!(LayoutGridAutoFlow, CssProperty::GridAutoFlow);
+impl_from_css_prop!(LayoutRowGap, CssProperty::RowGap);
+impl_from_css_prop!(LayoutColumnGap, CssProperty::ColumnGap);
+impl_from_css_prop!(StyleBackgroundContentList, CssProperty::BackgroundContent);
+impl_from_css_prop!(StyleBackgroundPositionList, CssProperty::BackgroundPosition);
+impl_from_css_prop!(StyleBackgroundSizeList, CssProperty::BackgroundSize);
+impl_from_css_prop!(StyleBackgroundRepeatList, CssProperty::BackgroundRepeat);
 impl_from_css_prop!(LayoutPaddingTop, CssProperty::PaddingTop);
 impl_from_css_prop!(LayoutPaddingLeft, CssProperty::PaddingLeft);
 impl_from_css_prop!(LayoutPaddingRight, CssProperty::PaddingRight);
@@ -1026,6 +1699,18 @@ impl_from_css_prop!(StyleBorderTopWidth, CssProperty::BorderTopWidth);
 impl_from_css_prop!(StyleBorderRightWidth, CssProperty::BorderRightWidth);
 impl_from_css_prop!(StyleBorderLeftWidth, CssProperty::BorderLeftWidth);
 impl_from_css_prop!(StyleBorderBottomWidth, CssProperty::BorderBottomWidth);
+impl_from_css_prop!(StyleBorderImageSource, CssProperty::BorderImageSource);
+impl_from_css_prop!(StyleBorderImageSlice, CssProperty::BorderImageSlice);
+impl_from_css_prop!(StyleBorderImageWidth, CssProperty::BorderImageWidth);
+impl_from_css_prop!(StyleBorderImageOutset, CssProperty::BorderImageOutset);
+impl_from_css_prop!(StyleBorderImageRepeat, CssProperty::BorderImageRepeat);
+impl_from_css_prop!(StyleTransitions, CssProperty::Transition);
+impl_from_css_prop!(StyleAnimations, CssProperty::Animation);
+impl_from_css_prop!(StyleTransform, CssProperty::Transform);
+impl_from_css_prop!(StyleTransformOrigin, CssProperty::TransformOrigin);
+impl_from_css_prop!(StyleOpacity, CssProperty::Opacity);
+impl_from_css_prop!(StyleFilter, CssProperty::Filter);
+impl_from_css_prop!(StyleMixBlendMode, CssProperty::MixBlendMode);
 
 /// Multiplier for floating point accuracy. Elements such as px or %
 /// are only accurate until a certain number of decimal points, therefore
@@ -1046,22 +1731,51 @@ impl fmt::Display for PixelValueNoPercent {
 }
 
 impl PixelValueNoPercent {
+    /// Returns the value in pixels, viewport-relative units resolve to 0
     pub fn to_pixels(&self) -> f32 {
         self.0.to_pixels(0.0)
     }
+
+    /// Returns the value in pixels, resolving `vw` / `vh` / `vmin` / `vmax` against the size of the viewport
+    pub fn to_pixels_in_viewport(&self, viewport_width: f32, viewport_height: f32) -> f32 {
+        self.0.to_pixels_in_viewport(0.0, viewport_width, viewport_height)
+    }
 }
 
+/// Maximum number of differently-typed terms that a `calc()` expression
+/// can consist of, i.e. `calc(100% - 2em - 12px)` consists of three terms.
+pub const MAX_CALC_TERMS: usize = 4;
+
 /// FloatValue, but associated with a certain metric (i.e. px, em, etc.)
+///
+/// A `calc()` expression is stored as a sum of terms with different metrics,
+/// the first term is stored in `metric` / `number`, the remaining terms in
+/// `calc_terms`. Terms are sorted by metric and merged, so that two equal
+/// expressions always compare and hash the same.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PixelValue {
     pub metric: SizeMetric,
     pub number: FloatValue,
+    calc_terms: [Option<(SizeMetric, FloatValue)>; MAX_CALC_TERMS - 1],
 }
 
 // Manual Debug implementation, because the auto-generated one is nearly unreadable
 impl fmt::Display for PixelValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", self.number, self.metric)
+        if !self.is_calc() {
+            return write!(f, "{}{}", self.number, self.metric);
+        }
+
+        write!(f, "calc(")?;
+        for (term_idx, (metric, number)) in self.terms().enumerate() {
+            let number = number.get();
+            match (term_idx, number < 0.0) {
+                (0, _) => write!(f, "{}{}", number, metric)?,
+                (_, true) => write!(f, " - {}{}", -number, metric)?,
+                (_, false) => write!(f, " + {}{}", number, metric)?,
+            }
+        }
+        write!(f, ")")
     }
 }
 
@@ -1071,8 +1785,13 @@ impl fmt::Display for SizeMetric {
         match self {
             Px => write!(f, "px"),
             Pt => write!(f, "pt"),
-            Em => write!(f, "pt"),
+            Em => write!(f, "em"),
+            Rem => write!(f, "rem"),
             Percent => write!(f, "%"),
+            Vw => write!(f, "vw"),
+            Vh => write!(f, "vh"),
+            Vmin => write!(f, "vmin"),
+            Vmax => write!(f, "vmax"),
         }
     }
 }
@@ -1118,6 +1837,7 @@ impl PixelValue {
         Self {
             metric: metric,
             number: FloatValue::const_new(value),
+            calc_terms: [None; MAX_CALC_TERMS - 1],
         }
     }
 
@@ -1131,6 +1851,11 @@ impl PixelValue {
         Self::from_metric(SizeMetric::Em, value)
     }
 
+    #[inline]
+    pub fn rem(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Rem, value)
+    }
+
     #[inline]
     pub fn pt(value: f32) -> Self {
         Self::from_metric(SizeMetric::Pt, value)
@@ -1141,23 +1866,125 @@ impl PixelValue {
         Self::from_metric(SizeMetric::Percent, value)
     }
 
+    #[inline]
+    pub fn vw(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vw, value)
+    }
+
+    #[inline]
+    pub fn vh(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vh, value)
+    }
+
+    #[inline]
+    pub fn vmin(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vmin, value)
+    }
+
+    #[inline]
+    pub fn vmax(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vmax, value)
+    }
+
     #[inline]
     pub fn from_metric(metric: SizeMetric, value: f32) -> Self {
         Self {
             metric: metric,
             number: FloatValue::new(value),
+            calc_terms: [None; MAX_CALC_TERMS - 1],
+        }
+    }
+
+    /// Creates a `calc()` value from a sum of terms, i.e.
+    /// `calc(100% - 2em - 12px)` is `&[(Percent, 100.0), (Em, -2.0), (Px, -12.0)]`.
+    ///
+    /// Terms with the same metric are merged and terms that sum up to zero are
+    /// removed, so `calc(5px + 5px)` is the same as `10px`. Returns `None` if the
+    /// expression has more than `MAX_CALC_TERMS` differently-typed terms.
+    pub fn calc(terms: &[(SizeMetric, f32)]) -> Option<Self> {
+
+        let mut sums = [0.0; SizeMetric::COUNT];
+        for (metric, number) in terms {
+            sums[*metric as usize] += *number;
+        }
+
+        let mut merged = SizeMetric::ALL.iter()
+            .map(|metric| (*metric, FloatValue::new(sums[*metric as usize])))
+            .filter(|(_, number)| number.number != 0);
+
+        let (metric, number) = match merged.next() {
+            Some(first) => first,
+            None => return Some(Self::zero()),
+        };
+
+        let mut calc_terms = [None; MAX_CALC_TERMS - 1];
+        for term in merged {
+            let free_slot = calc_terms.iter_mut().find(|t| t.is_none())?;
+            *free_slot = Some(term);
         }
+
+        Some(Self { metric, number, calc_terms })
+    }
+
+    /// Returns whether this value has to be printed as a `calc()` expression
+    #[inline]
+    pub fn is_calc(&self) -> bool {
+        self.calc_terms[0].is_some()
     }
 
-    /// Returns the value of the SizeMetric in pixels
+    /// Returns all terms (metric + number) that this value is the sum of.
+    /// Regular (non-`calc()`) values consist of exactly one term.
+    pub fn terms<'a>(&'a self) -> impl Iterator<Item = (SizeMetric, FloatValue)> + 'a {
+        Some((self.metric, self.number)).into_iter().chain(self.calc_terms.iter().filter_map(|t| *t))
+    }
+
+    /// Returns the value of the SizeMetric in pixels. Viewport-relative
+    /// units (`vw`, `vh`, `vmin`, `vmax`) resolve to 0, use
+    /// `to_pixels_in_viewport` if the size of the viewport is known.
     #[inline]
     pub fn to_pixels(&self, percent_resolve: f32) -> f32 {
-        match self.metric {
-            SizeMetric::Px => self.number.get(),
-            SizeMetric::Pt => self.number.get() * PT_TO_PX,
-            SizeMetric::Em => self.number.get() * EM_HEIGHT,
-            SizeMetric::Percent => self.number.get() / 100.0 * percent_resolve,
+        self.to_pixels_in_viewport(percent_resolve, 0.0, 0.0)
+    }
+
+    /// Replaces the `vw` / `vh` / `vmin` / `vmax` terms with their value in pixels, so that
+    /// the value can be resolved later on without knowing the size of the viewport.
+    pub fn resolve_viewport_units(&self, viewport_width: f32, viewport_height: f32) -> Self {
+
+        if !self.terms().any(|(metric, _)| metric.is_viewport_relative()) {
+            return *self;
         }
+
+        let terms = self.terms().map(|(metric, number)| {
+            let number = number.get();
+            match metric {
+                SizeMetric::Vw => (SizeMetric::Px, number / 100.0 * viewport_width),
+                SizeMetric::Vh => (SizeMetric::Px, number / 100.0 * viewport_height),
+                SizeMetric::Vmin => (SizeMetric::Px, number / 100.0 * viewport_width.min(viewport_height)),
+                SizeMetric::Vmax => (SizeMetric::Px, number / 100.0 * viewport_width.max(viewport_height)),
+                other => (other, number),
+            }
+        }).collect::<Vec<_>>();
+
+        // Merging the terms never results in more terms than before
+        Self::calc(&terms).unwrap_or(*self)
+    }
+
+    /// Returns the value in pixels, resolving `%` against `percent_resolve`
+    /// and `vw` / `vh` / `vmin` / `vmax` against the size of the viewport.
+    pub fn to_pixels_in_viewport(&self, percent_resolve: f32, viewport_width: f32, viewport_height: f32) -> f32 {
+        self.terms().map(|(metric, number)| {
+            let number = number.get();
+            match metric {
+                SizeMetric::Px => number,
+                SizeMetric::Pt => number * PT_TO_PX,
+                SizeMetric::Em | SizeMetric::Rem => number * EM_HEIGHT,
+                SizeMetric::Percent => number / 100.0 * percent_resolve,
+                SizeMetric::Vw => number / 100.0 * viewport_width,
+                SizeMetric::Vh => number / 100.0 * viewport_height,
+                SizeMetric::Vmin => number / 100.0 * viewport_width.min(viewport_height),
+                SizeMetric::Vmax => number / 100.0 * viewport_width.max(viewport_height),
+            }
+        }).sum()
     }
 }
 
@@ -1220,7 +2047,9 @@ impl FloatValue {
     }
 
     pub fn new(value: f32) -> Self {
-        Self { number: (value * FP_PRECISION_MULTIPLIER) as isize }
+        // round instead of truncating, so that printing and re-parsing
+        // a value always results in the same FloatValue
+        Self { number: (value * FP_PRECISION_MULTIPLIER).round() as isize }
     }
 
     pub fn get(&self) -> f32 {
@@ -1240,7 +2069,31 @@ pub enum SizeMetric {
     Px,
     Pt,
     Em,
+    /// Relative to the font size of the root element - like `em`, this is currently
+    /// resolved against the default font size (`EM_HEIGHT`), not the actual font size
+    Rem,
     Percent,
+    /// 1% of the viewport width
+    Vw,
+    /// 1% of the viewport height
+    Vh,
+    /// 1% of the smaller viewport dimension
+    Vmin,
+    /// 1% of the larger viewport dimension
+    Vmax,
+}
+
+impl SizeMetric {
+    const COUNT: usize = 9;
+    const ALL: [SizeMetric; SizeMetric::COUNT] = [
+        SizeMetric::Px, SizeMetric::Pt, SizeMetric::Em, SizeMetric::Rem, SizeMetric::Percent,
+        SizeMetric::Vw, SizeMetric::Vh, SizeMetric::Vmin, SizeMetric::Vmax,
+    ];
+
+    /// Returns whether the metric depends on the size of the viewport
+    pub fn is_viewport_relative(&self) -> bool {
+        [SizeMetric::Vw, SizeMetric::Vh, SizeMetric::Vmin, SizeMetric::Vmax].contains(self)
+    }
 }
 
 impl Default for SizeMetric {
@@ -1255,6 +2108,17 @@ pub enum StyleBackgroundSize {
     Cover,
 }
 
+impl fmt::Display for StyleBackgroundSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleBackgroundSize::*;
+        match self {
+            ExactSize(w, h) => write!(f, "{} {}", w, h),
+            Contain => write!(f, "contain"),
+            Cover => write!(f, "cover"),
+        }
+    }
+}
+
 /// Represents a `background-position` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StyleBackgroundPosition {
@@ -1271,6 +2135,12 @@ impl Default for StyleBackgroundPosition {
     }
 }
 
+impl fmt::Display for StyleBackgroundPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.horizontal, self.vertical)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum BackgroundPositionHorizontal {
     Left,
@@ -1279,14 +2149,38 @@ pub enum BackgroundPositionHorizontal {
     Exact(PixelValue),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum BackgroundPositionVertical {
-    Top,
+impl fmt::Display for BackgroundPositionHorizontal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::BackgroundPositionHorizontal::*;
+        match self {
+            Left => write!(f, "left"),
+            Center => write!(f, "center"),
+            Right => write!(f, "right"),
+            Exact(p) => write!(f, "{}", p),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackgroundPositionVertical {
+    Top,
     Center,
     Bottom,
     Exact(PixelValue),
 }
 
+impl fmt::Display for BackgroundPositionVertical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::BackgroundPositionVertical::*;
+        match self {
+            Top => write!(f, "top"),
+            Center => write!(f, "center"),
+            Bottom => write!(f, "bottom"),
+            Exact(p) => write!(f, "{}", p),
+        }
+    }
+}
+
 /// Represents a `background-repeat` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum StyleBackgroundRepeat {
@@ -1302,6 +2196,18 @@ impl Default for StyleBackgroundRepeat {
     }
 }
 
+impl fmt::Display for StyleBackgroundRepeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleBackgroundRepeat::*;
+        match self {
+            NoRepeat => write!(f, "no-repeat"),
+            Repeat => write!(f, "repeat"),
+            RepeatX => write!(f, "repeat-x"),
+            RepeatY => write!(f, "repeat-y"),
+        }
+    }
+}
+
 /// Represents a `color` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StyleTextColor(pub ColorU);
@@ -1309,25 +2215,23 @@ pub struct StyleTextColor(pub ColorU);
 derive_debug_zero!(StyleTextColor);
 derive_display_zero!(StyleTextColor);
 
-// -- TODO: Technically, border-radius can take two values for each corner!
-
-/// Represents a `border-top-width` attribute
+/// Represents a `border-top-left-radius` attribute
 #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleBorderTopLeftRadius(pub PixelValue);
-/// Represents a `border-left-width` attribute
+pub struct StyleBorderTopLeftRadius(pub PixelSize);
+/// Represents a `border-bottom-left-radius` attribute
 #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleBorderBottomLeftRadius(pub PixelValue);
-/// Represents a `border-right-width` attribute
+pub struct StyleBorderBottomLeftRadius(pub PixelSize);
+/// Represents a `border-top-right-radius` attribute
 #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleBorderTopRightRadius(pub PixelValue);
-/// Represents a `border-bottom-width` attribute
+pub struct StyleBorderTopRightRadius(pub PixelSize);
+/// Represents a `border-bottom-right-radius` attribute
 #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleBorderBottomRightRadius(pub PixelValue);
+pub struct StyleBorderBottomRightRadius(pub PixelSize);
 
-impl_pixel_value!(StyleBorderTopLeftRadius);
-impl_pixel_value!(StyleBorderBottomLeftRadius);
-impl_pixel_value!(StyleBorderTopRightRadius);
-impl_pixel_value!(StyleBorderBottomRightRadius);
+impl_border_radius!(StyleBorderTopLeftRadius);
+impl_border_radius!(StyleBorderBottomLeftRadius);
+impl_border_radius!(StyleBorderTopRightRadius);
+impl_border_radius!(StyleBorderBottomRightRadius);
 
 /// Represents a `border-top-width` attribute
 #[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1412,13 +2316,72 @@ pub struct BoxShadowPreDisplayItem {
 
 impl fmt::Display for BoxShadowPreDisplayItem {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // box-shadow values are split by whitespace, so the color is written as a hash
+        write!(f, "{} {} {} {} ",
+            self.offset[0], self.offset[1],
+            self.blur_radius, self.spread_radius,
+        )?;
+        self.color.write_hash(f)?;
         if self.clip_mode == BoxShadowClipMode::Inset {
-            write!(f, "{} ", self.clip_mode)?;
+            write!(f, " {}", self.clip_mode)?;
         }
-        write!(f, "{} {} {} {} {}",
-            self.offset[0], self.offset[1],
-            self.blur_radius, self.spread_radius, self.color,
-        )
+        Ok(())
+    }
+}
+
+/// Represents a comma-separated list of shadows, i.e. `box-shadow: 0px 0px 5px red, 2px 2px 10px 5px black inset`.
+/// The first shadow in the list is painted on top, `box-shadow: none` is represented as `CssPropertyValue::None`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBoxShadowList(pub Vec<BoxShadowPreDisplayItem>);
+
+impl fmt::Display for StyleBoxShadowList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, shadow) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", shadow)?;
+        }
+        Ok(())
+    }
+}
+
+/// Single shadow of a `text-shadow`, i.e. `2px 2px 5px red` - unlike box shadows,
+/// text shadows have no spread radius and can't be inset
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextShadowPreDisplayItem {
+    pub offset: [PixelValueNoPercent;2],
+    /// Color of the shadow, `None` draws the shadow in the color of the text
+    pub color: Option<ColorU>,
+    pub blur_radius: PixelValueNoPercent,
+}
+
+impl fmt::Display for TextShadowPreDisplayItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.offset[0], self.offset[1], self.blur_radius)?;
+        // text-shadow values are split by whitespace, so the color is written as a hash
+        if let Some(color) = &self.color {
+            write!(f, " ")?;
+            color.write_hash(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a `text-shadow` attribute, i.e. `text-shadow: 1px 1px 2px black, 0px 0px 5px blue`.
+/// The first shadow in the list is painted on top, `text-shadow: none` is represented as `CssPropertyValue::None`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextShadow(pub Vec<TextShadowPreDisplayItem>);
+
+impl fmt::Display for StyleTextShadow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, shadow) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", shadow)?;
+        }
+        Ok(())
     }
 }
 
@@ -1426,6 +2389,7 @@ impl fmt::Display for BoxShadowPreDisplayItem {
 pub enum StyleBackgroundContent {
     LinearGradient(LinearGradient),
     RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
     Image(CssImageId),
     Color(ColorU),
 }
@@ -1436,12 +2400,26 @@ impl fmt::Debug for StyleBackgroundContent {
         match self {
             LinearGradient(l) => write!(f, "{}", l),
             RadialGradient(r) => write!(f, "{}", r),
+            ConicGradient(c) => write!(f, "{}", c),
             Image(id) => write!(f, "image({})", id),
             Color(c) => write!(f, "{}", c),
         }
     }
 }
 
+impl fmt::Display for StyleBackgroundContent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleBackgroundContent::*;
+        match self {
+            LinearGradient(l) => write!(f, "{}", l),
+            RadialGradient(r) => write!(f, "{}", r),
+            ConicGradient(c) => write!(f, "{}", c),
+            Image(id) => write!(f, "image(\"{}\")", id),
+            Color(c) => write!(f, "{}", c),
+        }
+    }
+}
+
 impl StyleBackgroundContent {
     pub fn get_css_image_id(&self) -> Option<&CssImageId> {
         match self {
@@ -1457,6 +2435,61 @@ impl<'a> From<CssImageId> for StyleBackgroundContent {
     }
 }
 
+macro_rules! impl_background_layer_list {($list:ident, $item:ident) => (
+    impl $list {
+        /// Returns the value for the background layer with the given index. If there are
+        /// less values than layers, the values are repeated (`background-size: 10px, cover`
+        /// uses `10px` for the first, third, fifth, etc. layer)
+        pub fn get_layer(&self, layer: usize) -> Option<&$item> {
+            if self.0.is_empty() {
+                None
+            } else {
+                self.0.get(layer % self.0.len())
+            }
+        }
+    }
+
+    impl fmt::Display for $list {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for (i, item) in self.0.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", item)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl From<$item> for $list {
+        fn from(item: $item) -> Self {
+            $list(vec![item])
+        }
+    }
+)}
+
+/// Represents the comma-separated layers of a `background`, `background-image` or
+/// `background-color` attribute. The first layer is painted on top.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBackgroundContentList(pub Vec<StyleBackgroundContent>);
+
+/// Represents a `background-position` attribute with one position per background layer
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBackgroundPositionList(pub Vec<StyleBackgroundPosition>);
+
+/// Represents a `background-size` attribute with one size per background layer
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBackgroundSizeList(pub Vec<StyleBackgroundSize>);
+
+/// Represents a `background-repeat` attribute with one repeat mode per background layer
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBackgroundRepeatList(pub Vec<StyleBackgroundRepeat>);
+
+impl_background_layer_list!(StyleBackgroundContentList, StyleBackgroundContent);
+impl_background_layer_list!(StyleBackgroundPositionList, StyleBackgroundPosition);
+impl_background_layer_list!(StyleBackgroundSizeList, StyleBackgroundSize);
+impl_background_layer_list!(StyleBackgroundRepeatList, StyleBackgroundRepeat);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LinearGradient {
     pub direction: Direction,
@@ -1501,6 +2534,80 @@ impl fmt::Display for RadialGradient {
     }
 }
 
+/// Represents a `conic-gradient(from 45deg at 25% 50%, red, blue)`, the colors are
+/// distributed clockwise around the center, starting at the top
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConicGradient {
+    /// Rotation of the gradient in degrees (clockwise)
+    pub angle: FloatValue,
+    /// Center of the gradient, relative to the background
+    pub center: StyleBackgroundPosition,
+    pub extend_mode: ExtendMode,
+    /// Stops of the gradient, the offset is the percentage of a full turn
+    pub stops: Vec<GradientStopPre>,
+}
+
+impl ConicGradient {
+
+    /// Default center of a conic gradient (`at center`)
+    pub const DEFAULT_CENTER: StyleBackgroundPosition = StyleBackgroundPosition {
+        horizontal: BackgroundPositionHorizontal::Center,
+        vertical: BackgroundPositionVertical::Center,
+    };
+
+    /// Returns the color at the given percentage of a full turn (starting at the
+    /// `angle` of the gradient), assumes that the stops have been normalized
+    pub fn get_color_at(&self, percentage: f32) -> ColorU {
+
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return ColorU::TRANSPARENT,
+        };
+
+        let first_offset = first.offset.map(|o| o.get()).unwrap_or(0.0);
+        let last_offset = last.offset.map(|o| o.get()).unwrap_or(100.0);
+
+        let mut percentage = percentage;
+        if self.extend_mode == ExtendMode::Repeat && last_offset > first_offset {
+            let range = last_offset - first_offset;
+            percentage = first_offset + ((percentage - first_offset) % range + range) % range;
+        }
+
+        if percentage <= first_offset {
+            return first.color;
+        }
+
+        for stops in self.stops.windows(2) {
+            let start = stops[0].offset.map(|o| o.get()).unwrap_or(first_offset);
+            let end = stops[1].offset.map(|o| o.get()).unwrap_or(last_offset);
+            if percentage <= end {
+                if end <= start {
+                    return stops[1].color;
+                }
+                let t = (percentage - start) / (end - start);
+                return stops[0].color.interpolate(&stops[1].color, t).unwrap_or(stops[1].color);
+            }
+        }
+
+        last.color
+    }
+}
+
+impl fmt::Display for ConicGradient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = match self.extend_mode {
+            ExtendMode::Clamp => "conic-gradient",
+            ExtendMode::Repeat => "repeating-conic-gradient",
+        };
+
+        write!(f, "{}(from {}deg at {}", prefix, self.angle.get(), self.center)?;
+        for s in &self.stops {
+            write!(f, ", {}", s)?;
+        }
+        write!(f, ")")
+    }
+}
+
 /// CSS direction (necessary for gradients). Can either be a fixed angle or
 /// a direction ("to right" / "to left", etc.).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1674,6 +2781,44 @@ impl Default for StyleCursor {
     }
 }
 
+impl fmt::Display for StyleCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleCursor::*;
+        match self {
+            Alias => write!(f, "alias"),
+            AllScroll => write!(f, "all-scroll"),
+            Cell => write!(f, "cell"),
+            ColResize => write!(f, "col-resize"),
+            ContextMenu => write!(f, "context-menu"),
+            Copy => write!(f, "copy"),
+            Crosshair => write!(f, "crosshair"),
+            Default => write!(f, "default"),
+            EResize => write!(f, "e-resize"),
+            EwResize => write!(f, "ew-resize"),
+            Grab => write!(f, "grab"),
+            Grabbing => write!(f, "grabbing"),
+            Help => write!(f, "help"),
+            Move => write!(f, "move"),
+            NResize => write!(f, "n-resize"),
+            NsResize => write!(f, "ns-resize"),
+            NeswResize => write!(f, "nesw-resize"),
+            NwseResize => write!(f, "nwse-resize"),
+            Pointer => write!(f, "pointer"),
+            Progress => write!(f, "progress"),
+            RowResize => write!(f, "row-resize"),
+            SResize => write!(f, "s-resize"),
+            SeResize => write!(f, "se-resize"),
+            Text => write!(f, "text"),
+            Unset => write!(f, "unset"),
+            VerticalText => write!(f, "vertical-text"),
+            WResize => write!(f, "w-resize"),
+            Wait => write!(f, "wait"),
+            ZoomIn => write!(f, "zoom-in"),
+            ZoomOut => write!(f, "zoom-out"),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DirectionCorner {
     Right,
@@ -1751,6 +2896,8 @@ pub enum GradientType {
     RepeatingLinearGradient,
     RadialGradient,
     RepeatingRadialGradient,
+    ConicGradient,
+    RepeatingConicGradient,
 }
 
 /// Note: In theory, we could take a reference here,
@@ -1904,6 +3051,18 @@ impl Default for LayoutDirection {
     }
 }
 
+impl fmt::Display for LayoutDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutDirection::*;
+        match self {
+            Row => write!(f, "row"),
+            RowReverse => write!(f, "row-reverse"),
+            Column => write!(f, "column"),
+            ColumnReverse => write!(f, "column-reverse"),
+        }
+    }
+}
+
 impl LayoutDirection {
     pub fn get_axis(&self) -> LayoutAxis {
         use self::{LayoutAxis::*, LayoutDirection::*};
@@ -1932,6 +3091,16 @@ impl Default for LayoutBoxSizing {
     }
 }
 
+impl fmt::Display for LayoutBoxSizing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutBoxSizing::*;
+        match self {
+            ContentBox => write!(f, "content-box"),
+            BorderBox => write!(f, "border-box"),
+        }
+    }
+}
+
 /// Represents a `line-height` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StyleLineHeight(pub PercentageValue);
@@ -1965,7 +3134,10 @@ pub enum LayoutAxis {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutDisplay {
     Flex,
+    Grid,
+    Block,
     Inline,
+    InlineBlock,
 }
 
 impl Default for LayoutDisplay {
@@ -1974,6 +3146,19 @@ impl Default for LayoutDisplay {
     }
 }
 
+impl fmt::Display for LayoutDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutDisplay::*;
+        match self {
+            Flex => write!(f, "flex"),
+            Grid => write!(f, "grid"),
+            Block => write!(f, "block"),
+            Inline => write!(f, "inline"),
+            InlineBlock => write!(f, "inline-block"),
+        }
+    }
+}
+
 /// Represents a `float` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutFloat {
@@ -1987,6 +3172,16 @@ impl Default for LayoutFloat {
     }
 }
 
+impl fmt::Display for LayoutFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutFloat::*;
+        match self {
+            Left => write!(f, "left"),
+            Right => write!(f, "right"),
+        }
+    }
+}
+
 
 /// Represents a `position` attribute - default: `Static`
 ///
@@ -1996,6 +3191,11 @@ pub enum LayoutPosition {
     Static,
     Relative,
     Absolute,
+    /// Positioned relative to the window, doesn't scroll with its scroll frame
+    Fixed,
+    /// Scrolls with its scroll frame, but sticks to the edges of it (offset by `top / right /
+    /// bottom / left`) as long as it is inside of the content box of its parent
+    Sticky,
 }
 
 impl Default for LayoutPosition {
@@ -2004,6 +3204,36 @@ impl Default for LayoutPosition {
     }
 }
 
+impl fmt::Display for LayoutPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutPosition::*;
+        match self {
+            Static => write!(f, "static"),
+            Relative => write!(f, "relative"),
+            Absolute => write!(f, "absolute"),
+            Fixed => write!(f, "fixed"),
+            Sticky => write!(f, "sticky"),
+        }
+    }
+}
+
+/// Represents a `z-index` attribute, i.e. `z-index: 5` - `z-index: auto`
+/// is represented as `CssPropertyValue::Auto`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutZIndex(pub i32);
+
+impl fmt::Display for LayoutZIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Interpolate for LayoutZIndex {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(LayoutZIndex((self.0 as f32 + (other.0 - self.0) as f32 * t).round() as i32))
+    }
+}
+
 /// Represents a `flex-wrap` attribute - default: `Wrap`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutWrap {
@@ -2017,6 +3247,16 @@ impl Default for LayoutWrap {
     }
 }
 
+impl fmt::Display for LayoutWrap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutWrap::*;
+        match self {
+            Wrap => write!(f, "wrap"),
+            NoWrap => write!(f, "nowrap"),
+        }
+    }
+}
+
 /// Represents a `justify-content` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutJustifyContent {
@@ -2041,6 +3281,20 @@ impl Default for LayoutJustifyContent {
     }
 }
 
+impl fmt::Display for LayoutJustifyContent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutJustifyContent::*;
+        match self {
+            Start => write!(f, "flex-start"),
+            End => write!(f, "flex-end"),
+            Center => write!(f, "center"),
+            SpaceBetween => write!(f, "space-between"),
+            SpaceAround => write!(f, "space-around"),
+            SpaceEvenly => write!(f, "space-evenly"),
+        }
+    }
+}
+
 /// Represents a `align-items` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutAlignItems {
@@ -2060,6 +3314,18 @@ impl Default for LayoutAlignItems {
     }
 }
 
+impl fmt::Display for LayoutAlignItems {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutAlignItems::*;
+        match self {
+            Stretch => write!(f, "stretch"),
+            Center => write!(f, "center"),
+            Start => write!(f, "flex-start"),
+            End => write!(f, "flex-end"),
+        }
+    }
+}
+
 /// Represents a `align-content` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutAlignContent {
@@ -2083,87 +3349,271 @@ impl Default for LayoutAlignContent {
     }
 }
 
-/// Represents a `overflow-x` or `overflow-y` property, see
-/// [`TextOverflowBehaviour`](./struct.TextOverflowBehaviour.html) - default: `Auto`
+impl fmt::Display for LayoutAlignContent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutAlignContent::*;
+        match self {
+            Stretch => write!(f, "stretch"),
+            Center => write!(f, "center"),
+            Start => write!(f, "flex-start"),
+            End => write!(f, "flex-end"),
+            SpaceBetween => write!(f, "space-between"),
+            SpaceAround => write!(f, "space-around"),
+        }
+    }
+}
+
+/// One size of a grid track: `auto`, a length / percentage or a flexible fraction (`fr`)
+/// of the free space in the grid container
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Overflow {
-    /// Always shows a scroll bar, overflows on scroll
-    Scroll,
-    /// Does not show a scroll bar by default, only when text is overflowing
+pub enum GridTrackBreadth {
     Auto,
-    /// Never shows a scroll bar, simply clips text
-    Hidden,
-    /// Doesn't show a scroll bar, simply overflows the text
-    Visible,
+    Length(PixelValue),
+    Fraction(FloatValue),
 }
 
-impl Default for Overflow {
-    fn default() -> Self {
-        Overflow::Auto
+impl fmt::Display for GridTrackBreadth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridTrackBreadth::*;
+        match self {
+            Auto => write!(f, "auto"),
+            Length(l) => write!(f, "{}", l),
+            Fraction(fr) => write!(f, "{}fr", fr),
+        }
     }
 }
 
-impl Overflow {
+/// Size of a single row or column in a `grid-template-rows` / `grid-template-columns`,
+/// i.e. `100px` or `minmax(100px, 1fr)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GridTrackSize {
+    Breadth(GridTrackBreadth),
+    /// `minmax(min, max)` - the minimum can't be a fraction
+    MinMax(GridTrackBreadth, GridTrackBreadth),
+}
 
-    /// Returns whether this overflow value needs to display the scrollbars.
-    ///
-    /// - `overflow:scroll` always shows the scrollbar
-    /// - `overflow:auto` only shows the scrollbar when the content is currently overflowing
-    /// - `overflow:hidden` and `overflow:visible` do not show any scrollbars
-    pub fn needs_scrollbar(&self, currently_overflowing: bool) -> bool {
-        use self::Overflow::*;
+impl fmt::Display for GridTrackSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridTrackSize::*;
         match self {
-            Scroll => true,
-            Auto => currently_overflowing,
-            Hidden | Visible => false,
+            Breadth(b) => write!(f, "{}", b),
+            MinMax(min, max) => write!(f, "minmax({}, {})", min, max),
         }
     }
+}
 
-    /// Returns whether this is an `overflow:visible` node
-    /// (the only overflow type that doesn't clip its children)
-    pub fn is_overflow_visible(&self) -> bool {
-        *self == Overflow::Visible
+/// Writes the tracks separated by spaces - `repeat()` is expanded when parsing,
+/// so `repeat(2, 1fr)` is written as `1fr 1fr`
+fn fmt_grid_tracks(f: &mut fmt::Formatter, tracks: &[GridTrackSize]) -> fmt::Result {
+    for (idx, track) in tracks.iter().enumerate() {
+        if idx != 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", track)?;
     }
+    Ok(())
 }
 
-/// Horizontal text alignment enum (left, center, right) - default: `Center`
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum StyleTextAlignmentHorz {
-    Left,
-    Center,
-    Right,
+/// Represents a `grid-template-columns` attribute, i.e. `grid-template-columns: 200px repeat(2, 1fr)`.
+/// `grid-template-columns: none` is represented as `CssPropertyValue::None`
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridTemplateColumns(pub Vec<GridTrackSize>);
+/// Represents a `grid-template-rows` attribute, i.e. `grid-template-rows: auto minmax(50px, 1fr)`
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridTemplateRows(pub Vec<GridTrackSize>);
+
+impl fmt::Display for LayoutGridTemplateColumns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_grid_tracks(f, &self.0)
+    }
 }
 
-impl Default for StyleTextAlignmentHorz {
-    fn default() -> Self {
-        StyleTextAlignmentHorz::Left
+impl fmt::Display for LayoutGridTemplateRows {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_grid_tracks(f, &self.0)
     }
 }
 
-/// Vertical text alignment enum (top, center, bottom) - default: `Center`
+/// Start or end of a grid item: either a line number (starting at 1, negative numbers
+/// count from the end of the explicit grid) or the number of tracks the item spans.
+/// `auto` is represented as `CssPropertyValue::Auto`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum StyleTextAlignmentVert {
-    Top,
-    Center,
-    Bottom,
+pub enum GridLine {
+    Line(i32),
+    Span(u32),
 }
 
-impl Default for StyleTextAlignmentVert {
-    fn default() -> Self {
-        StyleTextAlignmentVert::Top
+impl fmt::Display for GridLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridLine::*;
+        match self {
+            Line(l) => write!(f, "{}", l),
+            Span(s) => write!(f, "span {}", s),
+        }
     }
 }
 
-/// Stylistic options of the rectangle that don't influence the layout
-#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents a `grid-column-start` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridColumnStart(pub GridLine);
+/// Represents a `grid-column-end` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridColumnEnd(pub GridLine);
+/// Represents a `grid-row-start` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridRowStart(pub GridLine);
+/// Represents a `grid-row-end` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridRowEnd(pub GridLine);
+
+derive_display_zero!(LayoutGridColumnStart);
+derive_display_zero!(LayoutGridColumnEnd);
+derive_display_zero!(LayoutGridRowStart);
+derive_display_zero!(LayoutGridRowEnd);
+
+/// Represents a `grid-auto-flow` attribute: whether items without an explicit
+/// position fill the grid row by row or column by column - default: `Row`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LayoutGridAutoFlow {
+    Row,
+    Column,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LayoutGridAutoFlow {
+    fn default() -> Self {
+        LayoutGridAutoFlow::Row
+    }
+}
+
+impl fmt::Display for LayoutGridAutoFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::LayoutGridAutoFlow::*;
+        match self {
+            Row => write!(f, "row"),
+            Column => write!(f, "column"),
+        }
+    }
+}
+
+/// Represents a `row-gap` attribute (space between the rows of a grid)
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutRowGap(pub PixelValue);
+/// Represents a `column-gap` attribute (space between the columns of a grid)
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutColumnGap(pub PixelValue);
+
+impl_pixel_value!(LayoutRowGap);
+impl_pixel_value!(LayoutColumnGap);
+
+/// Represents a `overflow-x` or `overflow-y` property, see
+/// [`TextOverflowBehaviour`](./struct.TextOverflowBehaviour.html) - default: `Auto`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Overflow {
+    /// Always shows a scroll bar, overflows on scroll
+    Scroll,
+    /// Does not show a scroll bar by default, only when text is overflowing
+    Auto,
+    /// Never shows a scroll bar, simply clips text
+    Hidden,
+    /// Doesn't show a scroll bar, simply overflows the text
+    Visible,
+}
+
+impl Default for Overflow {
+    fn default() -> Self {
+        Overflow::Auto
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Overflow::*;
+        match self {
+            Scroll => write!(f, "scroll"),
+            Auto => write!(f, "auto"),
+            Hidden => write!(f, "hidden"),
+            Visible => write!(f, "visible"),
+        }
+    }
+}
+
+impl Overflow {
+
+    /// Returns whether this overflow value needs to display the scrollbars.
+    ///
+    /// - `overflow:scroll` always shows the scrollbar
+    /// - `overflow:auto` only shows the scrollbar when the content is currently overflowing
+    /// - `overflow:hidden` and `overflow:visible` do not show any scrollbars
+    pub fn needs_scrollbar(&self, currently_overflowing: bool) -> bool {
+        use self::Overflow::*;
+        match self {
+            Scroll => true,
+            Auto => currently_overflowing,
+            Hidden | Visible => false,
+        }
+    }
+
+    /// Returns whether this is an `overflow:visible` node
+    /// (the only overflow type that doesn't clip its children)
+    pub fn is_overflow_visible(&self) -> bool {
+        *self == Overflow::Visible
+    }
+}
+
+/// Horizontal text alignment enum (left, center, right) - default: `Center`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextAlignmentHorz {
+    Left,
+    Center,
+    Right,
+}
+
+impl Default for StyleTextAlignmentHorz {
+    fn default() -> Self {
+        StyleTextAlignmentHorz::Left
+    }
+}
+
+impl fmt::Display for StyleTextAlignmentHorz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleTextAlignmentHorz::*;
+        match self {
+            Left => write!(f, "left"),
+            Center => write!(f, "center"),
+            Right => write!(f, "right"),
+        }
+    }
+}
+
+/// Vertical text alignment enum (top, center, bottom) - default: `Center`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextAlignmentVert {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl Default for StyleTextAlignmentVert {
+    fn default() -> Self {
+        StyleTextAlignmentVert::Top
+    }
+}
+
+/// Stylistic options of the rectangle that don't influence the layout
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RectStyle {
 
-    pub background: Option<CssPropertyValue<StyleBackgroundContent>>,
-    pub background_position: Option<CssPropertyValue<StyleBackgroundPosition>>,
-    pub background_size: Option<CssPropertyValue<StyleBackgroundSize>>,
-    pub background_repeat: Option<CssPropertyValue<StyleBackgroundRepeat>>,
+    pub background: Option<CssPropertyValue<StyleBackgroundContentList>>,
+    pub background_position: Option<CssPropertyValue<StyleBackgroundPositionList>>,
+    pub background_size: Option<CssPropertyValue<StyleBackgroundSizeList>>,
+    pub background_repeat: Option<CssPropertyValue<StyleBackgroundRepeatList>>,
     pub font_size: Option<CssPropertyValue<StyleFontSize>>,
     pub font_family: Option<CssPropertyValue<StyleFontFamily>>,
+    pub font_weight: Option<CssPropertyValue<StyleFontWeight>>,
+    pub font_style: Option<CssPropertyValue<StyleFontStyle>>,
+    pub text_decoration: Option<CssPropertyValue<StyleTextDecoration>>,
+    pub text_shadow: Option<CssPropertyValue<StyleTextShadow>>,
     pub text_color: Option<CssPropertyValue<StyleTextColor>>,
     pub text_align: Option<CssPropertyValue<StyleTextAlignmentHorz>>,
     pub line_height: Option<CssPropertyValue<StyleLineHeight>>,
@@ -2172,10 +3622,10 @@ pub struct RectStyle {
     pub tab_width: Option<CssPropertyValue<StyleTabWidth>>,
     pub cursor: Option<CssPropertyValue<StyleCursor>>,
 
-    pub box_shadow_left: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub box_shadow_right: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub box_shadow_top: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
-    pub box_shadow_bottom: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
+    pub box_shadow_left: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub box_shadow_right: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub box_shadow_top: Option<CssPropertyValue<StyleBoxShadowList>>,
+    pub box_shadow_bottom: Option<CssPropertyValue<StyleBoxShadowList>>,
 
     pub border_top_color: Option<CssPropertyValue<StyleBorderTopColor>>,
     pub border_left_color: Option<CssPropertyValue<StyleBorderLeftColor>>,
@@ -2191,10 +3641,23 @@ pub struct RectStyle {
     pub border_top_right_radius: Option<CssPropertyValue<StyleBorderTopRightRadius>>,
     pub border_bottom_left_radius: Option<CssPropertyValue<StyleBorderBottomLeftRadius>>,
     pub border_bottom_right_radius: Option<CssPropertyValue<StyleBorderBottomRightRadius>>,
+
+    pub border_image_source: Option<CssPropertyValue<StyleBorderImageSource>>,
+    pub border_image_slice: Option<CssPropertyValue<StyleBorderImageSlice>>,
+    pub border_image_width: Option<CssPropertyValue<StyleBorderImageWidth>>,
+    pub border_image_outset: Option<CssPropertyValue<StyleBorderImageOutset>>,
+    pub border_image_repeat: Option<CssPropertyValue<StyleBorderImageRepeat>>,
+
+    pub transform: Option<CssPropertyValue<StyleTransform>>,
+    pub transform_origin: Option<CssPropertyValue<StyleTransformOrigin>>,
+
+    pub opacity: Option<CssPropertyValue<StyleOpacity>>,
+    pub filter: Option<CssPropertyValue<StyleFilter>>,
+    pub mix_blend_mode: Option<CssPropertyValue<StyleMixBlendMode>>,
 }
 
 // Layout constraints for a given rectangle, such as "width", "min-width", "height", etc.
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RectLayout {
     pub display: Option<CssPropertyValue<LayoutDisplay>>,
     pub float: Option<CssPropertyValue<LayoutFloat>>,
@@ -2212,6 +3675,7 @@ pub struct RectLayout {
     pub bottom: Option<CssPropertyValue<LayoutBottom>>,
     pub right: Option<CssPropertyValue<LayoutRight>>,
     pub left: Option<CssPropertyValue<LayoutLeft>>,
+    pub z_index: Option<CssPropertyValue<LayoutZIndex>>,
 
     pub padding_top: Option<CssPropertyValue<LayoutPaddingTop>>,
     pub padding_bottom: Option<CssPropertyValue<LayoutPaddingBottom>>,
@@ -2238,6 +3702,16 @@ pub struct RectLayout {
     pub justify_content: Option<CssPropertyValue<LayoutJustifyContent>>,
     pub align_items: Option<CssPropertyValue<LayoutAlignItems>>,
     pub align_content: Option<CssPropertyValue<LayoutAlignContent>>,
+
+    pub grid_template_columns: Option<CssPropertyValue<LayoutGridTemplateColumns>>,
+    pub grid_template_rows: Option<CssPropertyValue<LayoutGridTemplateRows>>,
+    pub grid_column_start: Option<CssPropertyValue<LayoutGridColumnStart>>,
+    pub grid_column_end: Option<CssPropertyValue<LayoutGridColumnEnd>>,
+    pub grid_row_start: Option<CssPropertyValue<LayoutGridRowStart>>,
+    pub grid_row_end: Option<CssPropertyValue<LayoutGridRowEnd>>,
+    pub grid_auto_flow: Option<CssPropertyValue<LayoutGridAutoFlow>>,
+    pub row_gap: Option<CssPropertyValue<LayoutRowGap>>,
+    pub column_gap: Option<CssPropertyValue<LayoutColumnGap>>,
 }
 
 /// Holds info necessary for layouting / styling scrollbars (-webkit-scrollbar)
@@ -2270,21 +3744,21 @@ impl Default for ScrollbarInfo {
             padding_left: LayoutPaddingLeft(PixelValue::px(2.0)),
             padding_right: LayoutPaddingRight(PixelValue::px(2.0)),
             track: RectStyle {
-                background: Some(CssPropertyValue::Exact(StyleBackgroundContent::Color(ColorU {
+                background: Some(CssPropertyValue::Exact(StyleBackgroundContentList::from(StyleBackgroundContent::Color(ColorU {
                     r: 241, g: 241, b: 241, a: 255
-                }))),
+                })))),
                 .. Default::default()
             },
             thumb: RectStyle {
-                background: Some(CssPropertyValue::Exact(StyleBackgroundContent::Color(ColorU {
+                background: Some(CssPropertyValue::Exact(StyleBackgroundContentList::from(StyleBackgroundContent::Color(ColorU {
                     r: 193, g: 193, b: 193, a: 255
-                }))),
+                })))),
                 .. Default::default()
             },
             button: RectStyle {
-                background: Some(CssPropertyValue::Exact(StyleBackgroundContent::Color(ColorU {
+                background: Some(CssPropertyValue::Exact(StyleBackgroundContentList::from(StyleBackgroundContent::Color(ColorU {
                     r: 163, g: 163, b: 163, a: 255
-                }))),
+                })))),
                 .. Default::default()
             },
             corner: RectStyle::default(),
@@ -2318,10 +3792,10 @@ impl RectStyle {
     }
 
     pub fn has_box_shadow(&self) -> bool {
-        self.box_shadow_left.and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
-        self.box_shadow_right.and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
-        self.box_shadow_top.and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
-        self.box_shadow_bottom.and_then(|bs| bs.get_property().map(|_| ())).is_some()
+        self.box_shadow_left.as_ref().and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
+        self.box_shadow_right.as_ref().and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
+        self.box_shadow_top.as_ref().and_then(|bs| bs.get_property().map(|_| ())).is_some() ||
+        self.box_shadow_bottom.as_ref().and_then(|bs| bs.get_property().map(|_| ())).is_some()
     }
 
     pub fn has_border(&self) -> bool {
@@ -2330,6 +3804,49 @@ impl RectStyle {
         self.border_top_style.and_then(|bs| bs.get_property_or_default()).is_some() ||
         self.border_bottom_style.and_then(|bs| bs.get_property_or_default()).is_some()
     }
+
+    /// Returns the `border-image` of the rectangle, if a `border-image-source` is set
+    pub fn get_nine_patch_border(&self) -> Option<NinePatchBorder> {
+        let source = self.border_image_source.as_ref()?.get_property()?.0.clone();
+        Some(NinePatchBorder {
+            source,
+            slice: self.border_image_slice.and_then(|s| s.get_property_or_default()).unwrap_or_default(),
+            width: self.border_image_width.and_then(|w| w.get_property_or_default()).unwrap_or_default(),
+            outset: self.border_image_outset.and_then(|o| o.get_property_or_default()).unwrap_or_default(),
+            repeat: self.border_image_repeat.and_then(|r| r.get_property_or_default()).unwrap_or_default(),
+        })
+    }
+
+    /// Returns the resolved `transform` of a rectangle with the given bounds (in the same
+    /// coordinate space as the bounds) or `None` if the rectangle isn't transformed
+    pub fn get_transform(&self, bounds: &LayoutRect) -> Option<LayoutTransform> {
+        let transform = self.transform.as_ref()?.get_property()?;
+        let origin = self.transform_origin.as_ref().and_then(|o| o.get_property().cloned()).unwrap_or_default();
+        let transform = transform.to_layout_transform(bounds, &origin);
+        if transform.is_identity() { None } else { Some(transform) }
+    }
+
+    /// Returns the `filter` functions of the rectangle, followed by the `opacity`
+    /// (as an `opacity()` filter), or an empty list if neither is set
+    pub fn get_filters(&self) -> Vec<StyleFilterFunction> {
+        let mut filters = self.filter.as_ref()
+            .and_then(|f| f.get_property())
+            .map(|f| f.0.clone())
+            .unwrap_or_default();
+        if let Some(opacity) = self.opacity.as_ref().and_then(|o| o.get_property()) {
+            if opacity.get() < 1.0 {
+                filters.push(StyleFilterFunction::Opacity(opacity.0));
+            }
+        }
+        filters
+    }
+
+    /// Returns the `mix-blend-mode` of the rectangle, `None` if the mode is `normal`
+    pub fn get_mix_blend_mode(&self) -> Option<StyleMixBlendMode> {
+        self.mix_blend_mode.as_ref()
+            .and_then(|m| m.get_property().cloned())
+            .filter(|m| *m != StyleMixBlendMode::Normal)
+    }
 }
 
 impl RectLayout {
@@ -2356,6 +3873,104 @@ pub struct StyleFontFamily {
     pub fonts: Vec<FontId>
 }
 
+impl fmt::Display for StyleFontFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, font) in self.fonts.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "\"{}\"", font.get_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a `font-weight` attribute, i.e. `font-weight: bold` or `font-weight: 300`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFontWeight(pub u16);
+
+impl StyleFontWeight {
+    pub const NORMAL: StyleFontWeight = StyleFontWeight(400);
+    pub const BOLD: StyleFontWeight = StyleFontWeight(700);
+}
+
+impl Default for StyleFontWeight {
+    fn default() -> Self {
+        StyleFontWeight::NORMAL
+    }
+}
+
+impl fmt::Display for StyleFontWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Represents a `font-style` attribute, i.e. `font-style: italic`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleFontStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for StyleFontStyle {
+    fn default() -> Self {
+        StyleFontStyle::Normal
+    }
+}
+
+impl fmt::Display for StyleFontStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleFontStyle::*;
+        match self {
+            Normal => write!(f, "normal"),
+            Italic => write!(f, "italic"),
+            Oblique => write!(f, "oblique"),
+        }
+    }
+}
+
+/// Represents a `text-decoration` attribute, i.e. `text-decoration: underline overline red 2px`.
+/// `text-decoration: none` is represented as `CssPropertyValue::None`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextDecoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+    /// Color of the lines, `None` draws the lines in the color of the text
+    pub color: Option<ColorU>,
+    /// Thickness of the lines (percentages refer to the font size),
+    /// `None` derives the thickness from the font size
+    pub thickness: Option<PixelValue>,
+}
+
+impl fmt::Display for StyleTextDecoration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines = [(self.underline, "underline"), (self.overline, "overline"), (self.line_through, "line-through")];
+        let mut lines = lines.iter().filter(|(is_set, _)| *is_set).map(|(_, line)| *line).peekable();
+        if lines.peek().is_none() {
+            write!(f, "none")?;
+        }
+        for (idx, line) in lines.enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", line)?;
+        }
+        // values are split by whitespace, so the color is written as a hash
+        if let Some(color) = &self.color {
+            write!(f, " ")?;
+            color.write_hash(f)?;
+        }
+        if let Some(thickness) = &self.thickness {
+            write!(f, " {}", thickness)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FontId(pub String);
 
@@ -2364,3 +3979,861 @@ impl FontId {
         &self.0
     }
 }
+
+/// Values that can be animated smoothly via `transition` or `@keyframes` animations
+pub trait Interpolate: Sized {
+    /// Interpolates between `self` (`t = 0.0`) and `other` (`t = 1.0`). Returns
+    /// `None` if there is no smooth transition between the two values.
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self>;
+}
+
+impl Interpolate for FloatValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(FloatValue::new(self.get() + (other.get() - self.get()) * t))
+    }
+}
+
+impl Interpolate for PercentageValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(PercentageValue { number: self.number.interpolate(&other.number, t)? })
+    }
+}
+
+/// If the metrics of the two values differ, the result is a `calc()` expression,
+/// i.e. `10px` -> `50%` is `calc(5px + 25%)` at `t = 0.5`.
+impl Interpolate for PixelValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let terms = self.terms().map(|(metric, number)| (metric, number.get() * (1.0 - t)))
+            .chain(other.terms().map(|(metric, number)| (metric, number.get() * t)))
+            .collect::<Vec<_>>();
+        PixelValue::calc(&terms)
+    }
+}
+
+impl Interpolate for PixelSize {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(PixelSize::new(self.width.interpolate(&other.width, t)?, self.height.interpolate(&other.height, t)?))
+    }
+}
+
+impl Interpolate for PixelValueNoPercent {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        self.0.interpolate(&other.0, t).map(PixelValueNoPercent)
+    }
+}
+
+impl Interpolate for ColorU {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let mix = |a: u8, b: u8| clamp_f32((a as f32 + (b as f32 - a as f32) * t).round(), 0.0, 255.0) as u8;
+        Some(ColorU {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        })
+    }
+}
+
+/// Background layers can only be interpolated if both backgrounds have the same number of layers
+impl Interpolate for StyleBackgroundContentList {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0.iter().zip(other.0.iter()).map(|(a, b)| a.interpolate(b, t)).collect::<Option<Vec<_>>>().map(StyleBackgroundContentList)
+    }
+}
+
+/// Only colors can be interpolated, images and gradients switch abruptly
+impl Interpolate for StyleBackgroundContent {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self, other) {
+            (StyleBackgroundContent::Color(a), StyleBackgroundContent::Color(b)) => a.interpolate(b, t).map(StyleBackgroundContent::Color),
+            _ => None,
+        }
+    }
+}
+
+/// Shadows can only be interpolated if both are either inset or outset shadows
+impl Interpolate for BoxShadowPreDisplayItem {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.clip_mode != other.clip_mode {
+            return None;
+        }
+        Some(BoxShadowPreDisplayItem {
+            offset: [
+                self.offset[0].interpolate(&other.offset[0], t)?,
+                self.offset[1].interpolate(&other.offset[1], t)?,
+            ],
+            color: self.color.interpolate(&other.color, t)?,
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t)?,
+            spread_radius: self.spread_radius.interpolate(&other.spread_radius, t)?,
+            clip_mode: self.clip_mode,
+        })
+    }
+}
+
+/// Shadow lists can only be interpolated if they contain the same number of shadows
+impl Interpolate for StyleBoxShadowList {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0.iter().zip(other.0.iter()).map(|(a, b)| a.interpolate(b, t)).collect::<Option<Vec<_>>>().map(StyleBoxShadowList)
+    }
+}
+
+/// Text shadows without a color can only be interpolated with other text shadows without a color
+impl Interpolate for TextShadowPreDisplayItem {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let color = match (self.color, other.color) {
+            (Some(a), Some(b)) => Some(a.interpolate(&b, t)?),
+            (None, None) => None,
+            _ => return None,
+        };
+        Some(TextShadowPreDisplayItem {
+            offset: [
+                self.offset[0].interpolate(&other.offset[0], t)?,
+                self.offset[1].interpolate(&other.offset[1], t)?,
+            ],
+            color,
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t)?,
+        })
+    }
+}
+
+/// Shadow lists can only be interpolated if they contain the same number of shadows
+impl Interpolate for StyleTextShadow {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0.iter().zip(other.0.iter()).map(|(a, b)| a.interpolate(b, t)).collect::<Option<Vec<_>>>().map(StyleTextShadow)
+    }
+}
+
+/// Only exact values can be interpolated, `auto`, `none`, etc. switch abruptly
+impl<T: Interpolate> Interpolate for CssPropertyValue<T> {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self, other) {
+            (CssPropertyValue::Exact(a), CssPropertyValue::Exact(b)) => a.interpolate(b, t).map(CssPropertyValue::Exact),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_interpolate_zero {($($struct:ident),+) => (
+    $(
+        impl Interpolate for $struct {
+            fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+                self.0.interpolate(&other.0, t).map($struct)
+            }
+        }
+    )+
+)}
+
+impl_interpolate_zero!(
+    StyleTextColor, StyleFontSize, StyleLetterSpacing, StyleLineHeight, StyleWordSpacing, StyleTabWidth,
+    LayoutWidth, LayoutHeight, LayoutMinWidth, LayoutMinHeight, LayoutMaxWidth, LayoutMaxHeight,
+    LayoutTop, LayoutRight, LayoutLeft, LayoutBottom, LayoutFlexGrow, LayoutFlexShrink,
+    LayoutRowGap, LayoutColumnGap,
+    LayoutPaddingTop, LayoutPaddingLeft, LayoutPaddingRight, LayoutPaddingBottom,
+    LayoutMarginTop, LayoutMarginLeft, LayoutMarginRight, LayoutMarginBottom,
+    StyleBorderTopLeftRadius, StyleBorderTopRightRadius, StyleBorderBottomLeftRadius, StyleBorderBottomRightRadius,
+    StyleBorderTopColor, StyleBorderRightColor, StyleBorderLeftColor, StyleBorderBottomColor,
+    StyleBorderTopWidth, StyleBorderRightWidth, StyleBorderLeftWidth, StyleBorderBottomWidth,
+    StyleOpacity
+);
+
+/// Writes a duration in milliseconds, i.e. `200ms`
+fn fmt_duration(f: &mut fmt::Formatter, duration: &Duration) -> fmt::Result {
+    write!(f, "{}ms", duration.as_secs() * 1000 + u64::from(duration.subsec_millis()))
+}
+
+/// Represents a `transition` attribute, i.e. `transition: background-color 200ms ease-in, width 1s`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransitions(pub Vec<StyleTransition>);
+
+impl StyleTransitions {
+    /// Returns the transition for the given property - if multiple transitions
+    /// apply to the same property, the last one wins
+    pub fn get(&self, property: CssPropertyType) -> Option<&StyleTransition> {
+        self.0.iter().rev().find(|transition| transition.applies_to(property))
+    }
+}
+
+impl fmt::Display for StyleTransitions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (transition_idx, transition) in self.0.iter().enumerate() {
+            if transition_idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", transition)?;
+        }
+        Ok(())
+    }
+}
+
+/// One entry of a `transition` list, i.e. `background-color 200ms ease-in`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransition {
+    /// The property to animate, `None` for `all`
+    pub property: Option<CssPropertyType>,
+    pub duration: Duration,
+    pub timing_function: AnimationTimingFunction,
+    pub delay: Duration,
+}
+
+impl StyleTransition {
+    /// Returns whether a change of the given property should be animated by this transition
+    pub fn applies_to(&self, property: CssPropertyType) -> bool {
+        // background, background-color and background-image are all stored as a StyleBackgroundContent
+        fn background_content(property: CssPropertyType) -> CssPropertyType {
+            match property {
+                CssPropertyType::Background | CssPropertyType::BackgroundColor => CssPropertyType::BackgroundImage,
+                other => other,
+            }
+        }
+
+        match self.property {
+            None => true,
+            Some(p) => background_content(p) == background_content(property),
+        }
+    }
+}
+
+impl fmt::Display for StyleTransition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.property {
+            Some(p) => write!(f, "{} ", p)?,
+            None => write!(f, "all ")?,
+        }
+        fmt_duration(f, &self.duration)?;
+        write!(f, " {} ", self.timing_function)?;
+        fmt_duration(f, &self.delay)
+    }
+}
+
+/// Easing function of a transition or animation, i.e. `ease-in-out` or `cubic-bezier(0.1, 0.7, 1.0, 0.1)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnimationTimingFunction {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// `cubic-bezier(x1, y1, x2, y2)`
+    CubicBezier(FloatValue, FloatValue, FloatValue, FloatValue),
+    /// `steps(4, jump-end)` - number of steps and where the jumps happen
+    Steps(usize, StepPosition),
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for AnimationTimingFunction {
+    fn default() -> Self {
+        AnimationTimingFunction::Ease
+    }
+}
+
+impl fmt::Display for AnimationTimingFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::AnimationTimingFunction::*;
+        match self {
+            Linear => write!(f, "linear"),
+            Ease => write!(f, "ease"),
+            EaseIn => write!(f, "ease-in"),
+            EaseOut => write!(f, "ease-out"),
+            EaseInOut => write!(f, "ease-in-out"),
+            CubicBezier(x1, y1, x2, y2) => write!(f, "cubic-bezier({}, {}, {}, {})", x1, y1, x2, y2),
+            Steps(steps, position) => write!(f, "steps({}, {})", steps, position),
+        }
+    }
+}
+
+impl AnimationTimingFunction {
+
+    /// Maps the linear progress of an animation (`0.0` - `1.0`) to the eased progress
+    pub fn evaluate(&self, t: f32) -> f32 {
+        use self::AnimationTimingFunction::*;
+        let t = clamp_f32(t, 0.0, 1.0);
+        match self {
+            Linear => t,
+            Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1.get(), y1.get(), x2.get(), y2.get(), t),
+            Steps(steps, position) => {
+                let steps = (*steps).max(1) as f32;
+                match position {
+                    StepPosition::Start => (t * steps).ceil() / steps,
+                    StepPosition::End => (t * steps).floor() / steps,
+                }
+            },
+        }
+    }
+}
+
+/// Same as `f32::clamp`, which is not available on older compilers
+#[inline]
+fn clamp_f32(value: f32, min: f32, max: f32) -> f32 {
+    value.max(min).min(max)
+}
+
+/// Evaluates the cubic bezier curve from `(0, 0)` to `(1, 1)` with the control points
+/// `(x1, y1)` and `(x2, y2)` at the horizontal position `x`
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+
+    let bezier = |p1: f32, p2: f32, t: f32| {
+        let inv_t = 1.0 - t;
+        3.0 * inv_t * inv_t * t * p1 + 3.0 * inv_t * t * t * p2 + t * t * t
+    };
+
+    // x(t) is monotonic for x1, x2 in 0..1, so the t for x can be found via bisection
+    let mut low = 0.0;
+    let mut high = 1.0;
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if bezier(x1, x2, mid) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    bezier(y1, y2, (low + high) / 2.0)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StepPosition {
+    /// `jump-start` / `start`: The first jump happens when the animation begins
+    Start,
+    /// `jump-end` / `end`: The last jump happens when the animation ends
+    End,
+}
+
+impl fmt::Display for StepPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepPosition::Start => write!(f, "jump-start"),
+            StepPosition::End => write!(f, "jump-end"),
+        }
+    }
+}
+
+/// Represents an `animation` attribute, i.e. `animation: pulse 2s ease-in-out infinite alternate`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimations(pub Vec<StyleAnimation>);
+
+impl fmt::Display for StyleAnimations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (animation_idx, animation) in self.0.iter().enumerate() {
+            if animation_idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", animation)?;
+        }
+        Ok(())
+    }
+}
+
+/// One entry of an `animation` list, plays the `@keyframes` rule with the given name
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimation {
+    /// Name of the `@keyframes` rule
+    pub name: String,
+    /// Duration of a single iteration
+    pub duration: Duration,
+    pub timing_function: AnimationTimingFunction,
+    pub delay: Duration,
+    pub iteration_count: AnimationIterationCount,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+}
+
+impl fmt::Display for StyleAnimation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.name)?;
+        fmt_duration(f, &self.duration)?;
+        write!(f, " {} ", self.timing_function)?;
+        fmt_duration(f, &self.delay)?;
+        write!(f, " {} {} {}", self.iteration_count, self.direction, self.fill_mode)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnimationIterationCount {
+    Count(FloatValue),
+    Infinite,
+}
+
+impl Default for AnimationIterationCount {
+    fn default() -> Self {
+        AnimationIterationCount::Count(FloatValue::const_new(1))
+    }
+}
+
+impl fmt::Display for AnimationIterationCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnimationIterationCount::Count(count) => write!(f, "{}", count),
+            AnimationIterationCount::Infinite => write!(f, "infinite"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnimationDirection {
+    Normal,
+    Reverse,
+    /// Every second iteration is played backwards
+    Alternate,
+    /// Every first iteration is played backwards
+    AlternateReverse,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for AnimationDirection {
+    fn default() -> Self {
+        AnimationDirection::Normal
+    }
+}
+
+impl fmt::Display for AnimationDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::AnimationDirection::*;
+        match self {
+            Normal => write!(f, "normal"),
+            Reverse => write!(f, "reverse"),
+            Alternate => write!(f, "alternate"),
+            AlternateReverse => write!(f, "alternate-reverse"),
+        }
+    }
+}
+
+/// Whether the values of the first / last keyframe are applied before / after the animation runs
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnimationFillMode {
+    None,
+    /// Keep the values of the last keyframe after the animation has finished
+    Forwards,
+    /// Apply the values of the first keyframe during the `animation-delay`
+    Backwards,
+    Both,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for AnimationFillMode {
+    fn default() -> Self {
+        AnimationFillMode::None
+    }
+}
+
+impl fmt::Display for AnimationFillMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::AnimationFillMode::*;
+        match self {
+            None => write!(f, "none"),
+            Forwards => write!(f, "forwards"),
+            Backwards => write!(f, "backwards"),
+            Both => write!(f, "both"),
+        }
+    }
+}
+
+impl AnimationFillMode {
+    pub fn fills_forwards(&self) -> bool {
+        *self == AnimationFillMode::Forwards || *self == AnimationFillMode::Both
+    }
+
+    pub fn fills_backwards(&self) -> bool {
+        *self == AnimationFillMode::Backwards || *self == AnimationFillMode::Both
+    }
+}
+
+/// Represents a `transform` attribute, i.e. `transform: translate(-50%, 0px) rotate(45deg)`.
+/// `transform: none` is represented as `CssPropertyValue::None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransform(pub Vec<StyleTransformFunction>);
+
+impl fmt::Display for StyleTransform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, function) in self.0.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
+
+impl StyleTransform {
+    /// Resolves the transform for a rectangle with the given bounds: Percentages in
+    /// `translate()` and the `transform-origin` refer to the size of the rectangle.
+    ///
+    /// The returned transform maps points in the coordinate space of the `bounds`
+    /// to the transformed points in the same coordinate space.
+    pub fn to_layout_transform(&self, bounds: &LayoutRect, origin: &StyleTransformOrigin) -> LayoutTransform {
+        let origin_x = bounds.origin.x + origin.x.to_pixels(bounds.size.width);
+        let origin_y = bounds.origin.y + origin.y.to_pixels(bounds.size.height);
+        // `transform: a b` applies `b` first, then `a`
+        let transform = self.0.iter().rev().fold(LayoutTransform::translation(-origin_x, -origin_y), |transform, function| {
+            transform.then(&function.to_layout_transform(&bounds.size))
+        });
+        transform.then(&LayoutTransform::translation(origin_x, origin_y))
+    }
+}
+
+/// A single function of a `transform` list. `translateX(5px)`, `scaleY(2)`, etc. are stored
+/// as their two-dimensional equivalent (`translate(5px, 0px)`, `scale(1, 2)`), angles are in degrees.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTransformFunction {
+    /// `matrix(a, b, c, d, tx, ty)`
+    Matrix([FloatValue;6]),
+    Translate(PixelValue, PixelValue),
+    /// Clockwise rotation
+    Rotate(FloatValue),
+    Scale(FloatValue, FloatValue),
+    Skew(FloatValue, FloatValue),
+}
+
+impl fmt::Display for StyleTransformFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleTransformFunction::*;
+        match self {
+            Matrix(m) => write!(f, "matrix({}, {}, {}, {}, {}, {})", m[0], m[1], m[2], m[3], m[4], m[5]),
+            Translate(x, y) => write!(f, "translate({}, {})", x, y),
+            Rotate(angle) => write!(f, "rotate({}deg)", angle),
+            Scale(x, y) => write!(f, "scale({}, {})", x, y),
+            Skew(x, y) => write!(f, "skew({}deg, {}deg)", x, y),
+        }
+    }
+}
+
+impl StyleTransformFunction {
+
+    /// Returns the transform matrix of this function, percentages are resolved against `size`
+    pub fn to_layout_transform(&self, size: &LayoutSize) -> LayoutTransform {
+        use self::StyleTransformFunction::*;
+        match self {
+            Matrix(m) => LayoutTransform::new(m[0].get(), m[1].get(), m[2].get(), m[3].get(), m[4].get(), m[5].get()),
+            Translate(x, y) => LayoutTransform::translation(x.to_pixels(size.width), y.to_pixels(size.height)),
+            Rotate(angle) => LayoutTransform::rotation(angle.get()),
+            Scale(x, y) => LayoutTransform::scale(x.get(), y.get()),
+            Skew(x, y) => LayoutTransform::skew(x.get(), y.get()),
+        }
+    }
+
+    /// Returns the function of the same type that doesn't transform anything,
+    /// necessary for animating from / to `transform: none`
+    fn identity(&self) -> Self {
+        use self::StyleTransformFunction::*;
+        let zero = FloatValue::const_new(0);
+        let one = FloatValue::const_new(1);
+        match self {
+            Matrix(_) => Matrix([one, zero, zero, one, zero, zero]),
+            Translate(_, _) => Translate(PixelValue::const_px(0), PixelValue::const_px(0)),
+            Rotate(_) => Rotate(zero),
+            Scale(_, _) => Scale(one, one),
+            Skew(_, _) => Skew(zero, zero),
+        }
+    }
+}
+
+/// Represents a `transform-origin` attribute, i.e. `transform-origin: left 20%`.
+/// Both values are offsets from the top left corner of the rectangle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransformOrigin {
+    pub x: PixelValue,
+    pub y: PixelValue,
+}
+
+impl Default for StyleTransformOrigin {
+    fn default() -> Self {
+        Self { x: PixelValue::const_percent(50), y: PixelValue::const_percent(50) }
+    }
+}
+
+impl fmt::Display for StyleTransformOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.x, self.y)
+    }
+}
+
+/// Transforms can only be interpolated if the functions have the same type, i.e.
+/// `rotate(0deg) scale(1)` -> `rotate(90deg) scale(2)`. If one of the lists is shorter,
+/// it is padded with identity functions, so `none` -> `scale(2)` is animated as `scale(1)` -> `scale(2)`.
+impl Interpolate for StyleTransform {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        use self::StyleTransformFunction::*;
+
+        let len = self.0.len().max(other.0.len());
+        let mut functions = Vec::with_capacity(len);
+
+        for idx in 0..len {
+            let (a, b) = match (self.0.get(idx), other.0.get(idx)) {
+                (Some(a), Some(b)) => (*a, *b),
+                (Some(a), None) => (*a, a.identity()),
+                (None, Some(b)) => (b.identity(), *b),
+                (None, None) => break,
+            };
+            functions.push(match (a, b) {
+                (Translate(ax, ay), Translate(bx, by)) => Translate(ax.interpolate(&bx, t)?, ay.interpolate(&by, t)?),
+                (Rotate(a), Rotate(b)) => Rotate(a.interpolate(&b, t)?),
+                (Scale(ax, ay), Scale(bx, by)) => Scale(ax.interpolate(&bx, t)?, ay.interpolate(&by, t)?),
+                (Skew(ax, ay), Skew(bx, by)) => Skew(ax.interpolate(&bx, t)?, ay.interpolate(&by, t)?),
+                // interpolating the individual matrix components would distort rotations
+                (Matrix(a), Matrix(b)) if a == b => Matrix(a),
+                _ => return None,
+            });
+        }
+
+        Some(StyleTransform(functions))
+    }
+}
+
+impl Interpolate for StyleTransformOrigin {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(StyleTransformOrigin {
+            x: self.x.interpolate(&other.x, t)?,
+            y: self.y.interpolate(&other.y, t)?,
+        })
+    }
+}
+
+/// Represents an `opacity` attribute, i.e. `opacity: 0.5` or `opacity: 50%`,
+/// stored as a number between `0.0` (fully transparent) and `1.0` (fully opaque)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleOpacity(pub FloatValue);
+
+impl StyleOpacity {
+    /// Returns the opacity, clamped to the range `0.0..=1.0`
+    pub fn get(&self) -> f32 {
+        clamp_f32(self.0.get(), 0.0, 1.0)
+    }
+}
+
+impl fmt::Display for StyleOpacity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Represents a `filter` attribute, i.e. `filter: blur(5px) grayscale(100%)`.
+/// `filter: none` is represented as `CssPropertyValue::None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFilter(pub Vec<StyleFilterFunction>);
+
+impl fmt::Display for StyleFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, function) in self.0.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single function of a `filter` list. Amounts are stored as numbers,
+/// i.e. `grayscale(50%)` is stored as `Grayscale(0.5)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleFilterFunction {
+    Blur(PixelValueNoPercent),
+    Grayscale(FloatValue),
+    Brightness(FloatValue),
+    Opacity(FloatValue),
+    DropShadow {
+        offset: [PixelValueNoPercent;2],
+        blur_radius: PixelValueNoPercent,
+        color: ColorU,
+    },
+}
+
+impl fmt::Display for StyleFilterFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleFilterFunction::*;
+        match self {
+            Blur(radius) => write!(f, "blur({})", radius),
+            Grayscale(amount) => write!(f, "grayscale({})", amount),
+            Brightness(amount) => write!(f, "brightness({})", amount),
+            Opacity(amount) => write!(f, "opacity({})", amount),
+            DropShadow { offset, blur_radius, color } => {
+                write!(f, "drop-shadow({} {} {} ", offset[0], offset[1], blur_radius)?;
+                color.write_hash(f)?;
+                write!(f, ")")
+            },
+        }
+    }
+}
+
+impl StyleFilterFunction {
+    /// Returns the function of the same type that doesn't change anything,
+    /// necessary for animating from / to `filter: none`
+    fn identity(&self) -> Self {
+        use self::StyleFilterFunction::*;
+        let zero_px = PixelValueNoPercent(PixelValue::const_px(0));
+        match self {
+            Blur(_) => Blur(zero_px),
+            Grayscale(_) => Grayscale(FloatValue::const_new(0)),
+            Brightness(_) => Brightness(FloatValue::const_new(1)),
+            Opacity(_) => Opacity(FloatValue::const_new(1)),
+            DropShadow { .. } => DropShadow {
+                offset: [zero_px, zero_px],
+                blur_radius: zero_px,
+                color: ColorU::TRANSPARENT,
+            },
+        }
+    }
+}
+
+/// Filters can only be interpolated if the functions have the same type, the shorter
+/// list is padded with identity functions (see the `Interpolate` impl for `StyleTransform`)
+impl Interpolate for StyleFilter {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        use self::StyleFilterFunction::*;
+
+        let len = self.0.len().max(other.0.len());
+        let mut functions = Vec::with_capacity(len);
+
+        for idx in 0..len {
+            let (a, b) = match (self.0.get(idx), other.0.get(idx)) {
+                (Some(a), Some(b)) => (*a, *b),
+                (Some(a), None) => (*a, a.identity()),
+                (None, Some(b)) => (b.identity(), *b),
+                (None, None) => break,
+            };
+            functions.push(match (a, b) {
+                (Blur(a), Blur(b)) => Blur(a.interpolate(&b, t)?),
+                (Grayscale(a), Grayscale(b)) => Grayscale(a.interpolate(&b, t)?),
+                (Brightness(a), Brightness(b)) => Brightness(a.interpolate(&b, t)?),
+                (Opacity(a), Opacity(b)) => Opacity(a.interpolate(&b, t)?),
+                (
+                    DropShadow { offset: a_offset, blur_radius: a_blur, color: a_color },
+                    DropShadow { offset: b_offset, blur_radius: b_blur, color: b_color },
+                ) => DropShadow {
+                    offset: [
+                        a_offset[0].interpolate(&b_offset[0], t)?,
+                        a_offset[1].interpolate(&b_offset[1], t)?,
+                    ],
+                    blur_radius: a_blur.interpolate(&b_blur, t)?,
+                    color: a_color.interpolate(&b_color, t)?,
+                },
+                _ => return None,
+            });
+        }
+
+        Some(StyleFilter(functions))
+    }
+}
+
+/// Represents a `mix-blend-mode` attribute, i.e. `mix-blend-mode: multiply`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleMixBlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for StyleMixBlendMode {
+    fn default() -> Self {
+        StyleMixBlendMode::Normal
+    }
+}
+
+impl fmt::Display for StyleMixBlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleMixBlendMode::*;
+        match self {
+            Normal => write!(f, "normal"),
+            Multiply => write!(f, "multiply"),
+            Screen => write!(f, "screen"),
+            Overlay => write!(f, "overlay"),
+            Darken => write!(f, "darken"),
+            Lighten => write!(f, "lighten"),
+            ColorDodge => write!(f, "color-dodge"),
+            ColorBurn => write!(f, "color-burn"),
+            HardLight => write!(f, "hard-light"),
+            SoftLight => write!(f, "soft-light"),
+            Difference => write!(f, "difference"),
+            Exclusion => write!(f, "exclusion"),
+            Hue => write!(f, "hue"),
+            Saturation => write!(f, "saturation"),
+            Color => write!(f, "color"),
+            Luminosity => write!(f, "luminosity"),
+        }
+    }
+}
+
+#[test]
+fn test_transform_to_layout_transform() {
+
+    use self::StyleTransformFunction::*;
+
+    let bounds = LayoutRect::new(LayoutPoint::new(100.0, 100.0), LayoutSize::new(200.0, 100.0));
+    let assert_point_eq = |a: LayoutPoint, b: LayoutPoint| assert!((a.x - b.x).abs() < 0.001 && (a.y - b.y).abs() < 0.001, "{:?} != {:?}", a, b);
+
+    // rotated around the center of the rectangle
+    let rotate = StyleTransform(vec![Rotate(FloatValue::new(90.0))]).to_layout_transform(&bounds, &StyleTransformOrigin::default());
+    assert_point_eq(rotate.transform_point(&LayoutPoint::new(100.0, 100.0)), LayoutPoint::new(250.0, 50.0));
+    assert_point_eq(rotate.inverse().unwrap().transform_point(&LayoutPoint::new(250.0, 50.0)), LayoutPoint::new(100.0, 100.0));
+
+    // the last function is applied first: scale, then translate by 50% of the width
+    let origin = StyleTransformOrigin { x: PixelValue::px(0.0), y: PixelValue::px(0.0) };
+    let transform = StyleTransform(vec![
+        Translate(PixelValue::percent(50.0), PixelValue::px(0.0)),
+        Scale(FloatValue::new(2.0), FloatValue::new(2.0)),
+    ]).to_layout_transform(&bounds, &origin);
+    assert_point_eq(transform.transform_point(&LayoutPoint::new(150.0, 100.0)), LayoutPoint::new(300.0, 100.0));
+    assert_eq!(
+        transform.transform_rect(&bounds),
+        LayoutRect::new(LayoutPoint::new(200.0, 100.0), LayoutSize::new(400.0, 200.0)),
+    );
+
+    assert_eq!(LayoutTransform::scale(0.0, 1.0).inverse(), None);
+}
+
+#[test]
+fn test_interpolate_filter() {
+
+    use self::StyleFilterFunction::*;
+
+    let px = |value: f32| PixelValueNoPercent(PixelValue::px(value));
+
+    // `none` -> `blur(10px) grayscale(1)` is animated from `blur(0px) grayscale(0)`
+    let from = CssProperty::Filter(CssPropertyValue::None);
+    let to = CssProperty::Filter(CssPropertyValue::Exact(StyleFilter(vec![Blur(px(10.0)), Grayscale(FloatValue::new(1.0))])));
+    assert_eq!(
+        from.interpolate(&to, 0.5),
+        Some(CssProperty::Filter(CssPropertyValue::Exact(StyleFilter(vec![Blur(px(5.0)), Grayscale(FloatValue::new(0.5))])))),
+    );
+
+    // functions of different types can't be interpolated
+    let from = StyleFilter(vec![Blur(px(10.0))]);
+    let to = StyleFilter(vec![Brightness(FloatValue::new(2.0))]);
+    assert_eq!(from.interpolate(&to, 0.5), None);
+
+    // the opacity is applied after the filters
+    let style = RectStyle {
+        opacity: Some(CssPropertyValue::Exact(StyleOpacity(FloatValue::new(0.5)))),
+        filter: Some(CssPropertyValue::Exact(StyleFilter(vec![Blur(px(2.0))]))),
+        .. Default::default()
+    };
+    assert_eq!(style.get_filters(), vec![Blur(px(2.0)), Opacity(FloatValue::new(0.5))]);
+}
@@ -10,10 +10,16 @@ use std::time::Duration;
 /// You can, for example, parse and load styles directly from a SASS, LESS or JSON parser.
 /// The default parser is `azul-css-parser`.
 pub trait HotReloadHandler {
-    /// Reloads the style from the source format. Should return Ok() when the CSS has be correctly
+    /// Reloads the style from the source format. Should return Ok() when the CSS has been
     /// reloaded, and an human-readable error string otherwise (since the error needs to be printed
     /// to stdout when hot-reloading).
     fn reload_style(&mut self) -> Result<Css, String>;
+    /// Same as `reload_style`, but the reloaded style may be only partially valid: in that case,
+    /// the style is still applied and the second field should contain a human-readable message
+    /// for every part of the source that was skipped. By default, this calls `reload_style`.
+    fn reload_style_lenient(&mut self) -> Result<(Css, Vec<String>), String> {
+        self.reload_style().map(|css| (css, Vec::new()))
+    }
     /// Returns how quickly the hot-reloader should reload the source format.
     fn get_reload_interval(&self) -> Duration;
 }
@@ -38,16 +44,28 @@ impl HotReloadOverrideHandler {
     }
 }
 
-impl HotReloadHandler for HotReloadOverrideHandler {
-    fn reload_style(&mut self) -> Result<Css, String> {
+impl HotReloadOverrideHandler {
+    fn merge_onto_base_style(&self, reloaded_css: Css) -> Css {
         let mut css = Css::new();
         for stylesheet in self.base_style.clone().stylesheets {
             css.append_stylesheet(stylesheet);
         }
-        for stylesheet in self.hot_reloader.reload_style()?.stylesheets {
+        for stylesheet in reloaded_css.stylesheets {
             css.append_stylesheet(stylesheet);
         }
-        Ok(css)
+        css
+    }
+}
+
+impl HotReloadHandler for HotReloadOverrideHandler {
+    fn reload_style(&mut self) -> Result<Css, String> {
+        let reloaded_css = self.hot_reloader.reload_style()?;
+        Ok(self.merge_onto_base_style(reloaded_css))
+    }
+
+    fn reload_style_lenient(&mut self) -> Result<(Css, Vec<String>), String> {
+        let (reloaded_css, errors) = self.hot_reloader.reload_style_lenient()?;
+        Ok((self.merge_onto_base_style(reloaded_css), errors))
     }
 
     fn get_reload_interval(&self) -> Duration {
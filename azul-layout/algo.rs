@@ -30,7 +30,7 @@ use azul_core::{
 };
 
 use {
-    RectContent, GetTextLayout,
+    RectContent, GetTextLayout, LayoutCache, grid, flow,
     style::*,
     number::{OrElse, MinMax, ToNumber, Number::{self, *}},
     geometry::{Rect, RectSize, Offsets, Size},
@@ -81,6 +81,7 @@ pub(crate) fn compute<T: GetTextLayout>(
     node_hierarchy: &NodeHierarchy,
     node_styles: &NodeDataContainer<Style>,
     rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
     root_size: LayoutSize,
 ) -> NodeDataContainer<PositionedRectangle> {
 
@@ -116,6 +117,7 @@ pub(crate) fn compute<T: GetTextLayout>(
             &mut first_pass,
             &mut resolved_text_layout_options,
             rect_contents,
+            layout_cache,
             Size {
                 width: node_styles[root_id].size.width.resolve(root_size.width),
                 height: node_styles[root_id].size.height.resolve(root_size.height),
@@ -131,6 +133,7 @@ pub(crate) fn compute<T: GetTextLayout>(
             &mut node_rects,
             &mut resolved_text_layout_options,
             rect_contents,
+            layout_cache,
             Size {
                 width: first_pass[root_id].size.width
                     .maybe_max(root_min_size.width.resolve(root_size.width))
@@ -150,6 +153,7 @@ pub(crate) fn compute<T: GetTextLayout>(
             &mut node_rects,
             &mut resolved_text_layout_options,
             rect_contents,
+            layout_cache,
             Size {
                 width: node_styles[root_id].size.width.resolve(root_size.width),
                 height: node_styles[root_id].size.height.resolve(root_size.height),
@@ -159,6 +163,10 @@ pub(crate) fn compute<T: GetTextLayout>(
         );
     };
 
+    // The `position: fixed` and `position: absolute` nodes that are laid out by `layout_out_of_flow_nodes`
+    // are laid out again in every pass, unless they are laid out with the same constraints as before
+    layout_cache.end_layout(node_hierarchy, &node_rects, &resolved_text_layout_options);
+
     // Until now, all divs have the correct layout relative to their siblings, but not relative to their parents
     for (_, parent_id) in node_hierarchy.get_parents_sorted_by_depth() {
         let parent_rect_origin = node_rects[parent_id].origin;
@@ -168,6 +176,16 @@ pub(crate) fn compute<T: GetTextLayout>(
         }
     }
 
+    layout_out_of_flow_nodes(
+        node_hierarchy,
+        node_styles,
+        &mut node_rects,
+        &mut resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        Size { width: root_size.width.unwrap_or_zero(), height: root_size.height.unwrap_or_zero() },
+    );
+
     node_rects.transform(|rect, node_id| {
         PositionedRectangle {
             bounds: LayoutRect {
@@ -189,7 +207,7 @@ pub(crate) fn compute<T: GetTextLayout>(
 }
 
 
-fn resolve_offsets(input: Offsets<f32>) -> ResolvedOffsets {
+pub(crate) fn resolve_offsets(input: Offsets<f32>) -> ResolvedOffsets {
     ResolvedOffsets {
         top: input.top,
         left: input.left,
@@ -198,13 +216,58 @@ fn resolve_offsets(input: Offsets<f32>) -> ResolvedOffsets {
     }
 }
 
-fn compute_internal<T: GetTextLayout>(
+/// Lays out the node and its descendants, or restores their layout from the `layout_cache`
+/// if the node didn't change since it was last laid out (or measured) with the same constraints
+pub(crate) fn compute_internal<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    node_size: Size<Number>,
+    parent_size: Size<Number>,
+    perform_layout: bool,
+) {
+    let is_restored = if perform_layout {
+        layout_cache.restore_layout(node_id, node_hierarchy, node_rects, resolved_text_layout_options, node_size, parent_size)
+    } else {
+        layout_cache.restore_measured_layout(node_id, node_rects, resolved_text_layout_options, node_size, parent_size)
+    };
+
+    if is_restored {
+        return;
+    }
+
+    compute_internal_uncached(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        node_size,
+        parent_size,
+        perform_layout,
+    );
+
+    if perform_layout {
+        layout_cache.insert_layout(node_id, node_rects, node_size, parent_size);
+    } else {
+        layout_cache.insert_measured_layout(node_id, node_rects, resolved_text_layout_options, node_size, parent_size);
+    }
+}
+
+fn compute_internal_uncached<T: GetTextLayout>(
     node_id: NodeId,
     node_hierarchy: &NodeHierarchy,
     node_styles: &NodeDataContainer<Style>,
     node_rects: &mut NodeDataContainer<Rect>,
     resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
     rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
     node_size: Size<Number>,
     parent_size: Size<Number>,
     perform_layout: bool,
@@ -275,24 +338,14 @@ fn compute_internal<T: GetTextLayout>(
         match content {
             Text(t) => {
 
-                use azul_core::ui_solver::{DEFAULT_FONT_SIZE_PX, DEFAULT_LETTER_SPACING, DEFAULT_WORD_SPACING};
-
-                let text_holes = Vec::new(); // TODO: All children that have float:left / float:right!
-                let rect_style = &node_styles[node_id];
                 let parent_id = node_hierarchy[node_id].parent.unwrap_or(NodeId::ZERO);
                 let parent_style = &node_styles[parent_id];
                 let allows_overflow = parent_style.overflow == Overflow::Visible;
 
-                let text_layout_options = ResolvedTextLayoutOptions {
-                    max_horizontal_width: if allows_overflow { None } else { available_space.width.to_option() },
-                    leading: None, // TODO!
-                    holes: text_holes.clone(),
-                    font_size_px: rect_style.font_size_px.to_pixels(DEFAULT_FONT_SIZE_PX as f32),
-                    letter_spacing: rect_style.letter_spacing.map(|ls| ls.to_pixels(DEFAULT_LETTER_SPACING)),
-                    word_spacing: rect_style.word_spacing.map(|ls| ls.to_pixels(DEFAULT_WORD_SPACING)),
-                    line_height: rect_style.line_height,
-                    tab_width: rect_style.tab_width,
-                };
+                let text_layout_options = get_text_layout_options(
+                    &node_styles[node_id],
+                    if allows_overflow { None } else { available_space.width.to_option() },
+                );
 
                 let layouted_inline_text = t.get_text_layout(&text_layout_options);
 
@@ -347,6 +400,73 @@ fn compute_internal<T: GetTextLayout>(
         height: parent_height.or_else(parent_size.height) - padding_border.vertical(),
     };
 
+    if parent_node_style.display == Display::Grid {
+        let grid_size = grid::compute_grid(
+            node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            node_inner_size,
+            parent_height - padding_border.vertical(),
+            &padding_border,
+            perform_layout,
+        );
+        node_rects[node_id].size = RectSize {
+            width: parent_width.or_else(Number::Defined(grid_size.width)),
+            height: parent_height.or_else(Number::Defined(grid_size.height)),
+        };
+        node_rects[node_id].margin = resolve_offsets(margin);
+        node_rects[node_id].padding = resolve_offsets(padding);
+        node_rects[node_id].border_widths = resolve_offsets(border);
+        return;
+    }
+
+    // display: inline is only laid out on the lines of a paragraph if the parent is a
+    // block container, otherwise (i.e. for flex items) it is treated like display: block
+    let is_block_container = match parent_node_style.display {
+        Display::Block | Display::Inline | Display::InlineBlock => true,
+        _ => false,
+    };
+
+    if is_block_container {
+        // Blocks fill the width of their container, inline-blocks shrink to fit their content.
+        // Blocks that are only measured (as flex items) shrink to fit as well, otherwise
+        // their flex base size would always be the width of the flex container.
+        let fill_width = match parent_node_style.display {
+            Display::InlineBlock => Undefined,
+            _ if !perform_layout => Undefined,
+            _ => parent_size.width - margin.horizontal(),
+        };
+        let block_inner_size = Size {
+            width: parent_width.or_else(node_size.width).or_else(fill_width) - padding_border.horizontal(),
+            height: parent_height.or_else(node_size.height) - padding_border.vertical(),
+        };
+        let block_size = flow::compute_block(
+            node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            block_inner_size,
+            &padding_border,
+        );
+        node_rects[node_id].size = RectSize {
+            width: Number::Defined(block_size.width),
+            height: Number::Defined(block_size.height)
+                .maybe_max(parent_node_style.min_size.height.resolve(parent_size.height))
+                .maybe_min(parent_node_style.max_size.height.resolve(parent_size.height)),
+        };
+        node_rects[node_id].margin = resolve_offsets(margin);
+        node_rects[node_id].padding = resolve_offsets(padding);
+        node_rects[node_id].border_widths = resolve_offsets(border);
+        return;
+    }
+
     let mut container_size = Size { width: 0.0, height: 0.0 };
     let mut inner_container_size = Size { width: 0.0, height: 0.0 };
 
@@ -362,10 +482,12 @@ fn compute_internal<T: GetTextLayout>(
     //    margin, border, and padding from the space available to the flex container
     //    in that dimension and use that value. This might result in an infinite value.
 
+    // Every child that is displayed is a flex item, regardless of its display: `block`, `grid`
+    // or `inline` only change how the children of the item are laid out (the item is "blockified")
     let mut flex_items: Vec<FlexItem> = node_id
         .children(node_hierarchy)
-        .filter(|child_id| node_styles[*child_id].position_type != PositionType::Absolute)
-        .filter(|child_id| node_styles[*child_id].display == Display::Flex)
+        .filter(|child_id| !node_styles[*child_id].position_type.is_out_of_flow())
+        .filter(|child_id| node_styles[*child_id].display != Display::None)
         .map(|child_id| {
             let child_style = &node_styles[child_id];
 
@@ -388,7 +510,7 @@ fn compute_internal<T: GetTextLayout>(
                     height: child_style.max_size.height.resolve(node_inner_size.height),
                 },
 
-                position: child_style.position.map(|p| p.resolve(node_inner_size.width)),
+                position: child_style.relative_offsets().map(|p| p.resolve(node_inner_size.width)),
                 margin: child_style.margin.map(|m| m.resolve(node_inner_size.width).or_else(0.0)),
                 padding: child_style.padding.map(|p| p.resolve(node_inner_size.width).or_else(0.0)),
                 border: child_style.border.map(|b| b.resolve(node_inner_size.width).or_else(0.0)),
@@ -490,6 +612,7 @@ fn compute_internal<T: GetTextLayout>(
             node_rects,
             resolved_text_layout_options,
             rect_contents,
+            layout_cache,
             Size {
                 width: width.maybe_max(child.min_size.width).maybe_min(child.max_size.width),
                 height: height.maybe_max(child.min_size.height).maybe_min(child.max_size.height),
@@ -525,6 +648,7 @@ fn compute_internal<T: GetTextLayout>(
             node_rects,
             resolved_text_layout_options,
             rect_contents,
+            layout_cache,
             Size { width: Undefined, height: Undefined },
             available_space,
             false,
@@ -621,6 +745,7 @@ fn compute_internal<T: GetTextLayout>(
                     node_rects,
                     resolved_text_layout_options,
                     rect_contents,
+                    layout_cache,
                     Size {
                         width: child.size.width.maybe_max(child.min_size.width).maybe_min(child.max_size.width),
                         height: child.size.height.maybe_max(child.min_size.height).maybe_min(child.max_size.height),
@@ -778,6 +903,7 @@ fn compute_internal<T: GetTextLayout>(
                     node_rects,
                     resolved_text_layout_options,
                     rect_contents,
+                    layout_cache,
                     Size { width: Undefined, height: Undefined },
                     available_space,
                     false,
@@ -855,6 +981,7 @@ fn compute_internal<T: GetTextLayout>(
                 node_rects,
                 resolved_text_layout_options,
                 rect_contents,
+                layout_cache,
                 Size {
                     width: if is_row { child.target_size.width.to_number() } else { child_cross },
                     height: if is_row { child_cross } else { child.target_size.height.to_number() },
@@ -894,6 +1021,7 @@ fn compute_internal<T: GetTextLayout>(
                     node_rects,
                     resolved_text_layout_options,
                     rect_contents,
+                    layout_cache,
                     Size {
                         width: if is_row {
                             child.target_size.width.to_number()
@@ -1234,6 +1362,7 @@ fn compute_internal<T: GetTextLayout>(
                 node_rects,
                 node_styles,
                 rect_contents,
+                layout_cache,
                 resolved_text_layout_options,
                 &mut total_offset_cross,
                 &padding_border,
@@ -1249,6 +1378,7 @@ fn compute_internal<T: GetTextLayout>(
                 node_rects,
                 node_styles,
                 rect_contents,
+                layout_cache,
                 resolved_text_layout_options,
                 &mut total_offset_cross,
                 &padding_border,
@@ -1264,10 +1394,11 @@ fn compute_internal<T: GetTextLayout>(
     //     lines.into_iter().flat_map(|x| x).collect()
     // }
 
-    // Before returning we perform absolute layout on all absolutely positioned children
+    // Before returning we perform absolute layout on all absolutely positioned children -
+    // if this node is not positioned, they are laid out by `layout_out_of_flow_nodes` instead
     node_id
         .children(node_hierarchy)
-        .filter(|child_id| node_styles[*child_id].position_type == PositionType::Absolute)
+        .filter(|child_id| parent_node_style.position_type.is_positioned() && node_styles[*child_id].position_type == PositionType::Absolute)
         .for_each(|child_id| {
 
             let child_style = &node_styles[child_id];
@@ -1313,6 +1444,7 @@ fn compute_internal<T: GetTextLayout>(
                 node_rects,
                 resolved_text_layout_options,
                 rect_contents,
+                layout_cache,
                 Size { width, height },
                 Size { width: container_width, height: container_height },
                 true,
@@ -1401,6 +1533,214 @@ fn compute_internal<T: GetTextLayout>(
     node_rects[node_id].border_widths = resolve_offsets(border);
 }
 
+/// Resolves the font size and spacing of the text of a node
+pub(crate) fn get_text_layout_options(rect_style: &Style, max_horizontal_width: Option<f32>) -> ResolvedTextLayoutOptions {
+
+    use azul_core::ui_solver::{DEFAULT_FONT_SIZE_PX, DEFAULT_LETTER_SPACING, DEFAULT_WORD_SPACING};
+
+    ResolvedTextLayoutOptions {
+        max_horizontal_width,
+        leading: None,
+        // Filled in by the paragraph layout if the text flows around floating boxes
+        holes: Vec::new(),
+        font_size_px: rect_style.font_size_px.to_pixels(DEFAULT_FONT_SIZE_PX as f32),
+        letter_spacing: rect_style.letter_spacing.map(|ls| ls.to_pixels(DEFAULT_LETTER_SPACING)),
+        word_spacing: rect_style.word_spacing.map(|ls| ls.to_pixels(DEFAULT_WORD_SPACING)),
+        line_height: rect_style.line_height,
+        tab_width: rect_style.tab_width,
+        is_inline_fragment: false,
+    }
+}
+
+/// Positions the `position: absolute` children of a grid or block container relative
+/// to its padding box. `container_size` is the size of the container (including the border).
+/// If the container is not positioned, it is not the containing block of its children.
+pub(crate) fn layout_absolute_children<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    container_size: Size<f32>,
+    padding_border: &Offsets<f32>,
+) {
+    if !node_styles[node_id].position_type.is_positioned() {
+        return;
+    }
+
+    for child_id in node_id.children(node_hierarchy).filter(|child_id| node_styles[*child_id].position_type == PositionType::Absolute) {
+
+        let child_style = &node_styles[child_id];
+        let container_size = container_size.map(Number::Defined);
+
+        compute_internal(
+            child_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            Size {
+                width: child_style.size.width.resolve(container_size.width),
+                height: child_style.size.height.resolve(container_size.height),
+            },
+            container_size,
+            true,
+        );
+
+        let margin = child_style.margin.map(|m| m.resolve(container_size.width).or_else(0.0));
+        node_rects[child_id].origin.x = Defined(child_style.position.left.resolve(container_size.width).or_else(padding_border.left) + margin.left);
+        node_rects[child_id].origin.y = Defined(child_style.position.top.resolve(container_size.height).or_else(padding_border.top) + margin.top);
+    }
+}
+
+/// Lays out the `position: fixed` nodes relative to the viewport and the `position: absolute` nodes
+/// whose parent is not positioned relative to the padding box of their nearest positioned ancestor
+/// (or the viewport, if there is none). Runs after the origins of all nodes have been made absolute,
+/// nodes without a `top / left` (or `bottom / right`) offset stay at the start of their parent.
+fn layout_out_of_flow_nodes<T: GetTextLayout>(
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    viewport_size: Size<f32>,
+) {
+    // Parents are laid out before their children, so that the containing
+    // block of a node is already at its final position
+    for (_, parent_id) in node_hierarchy.get_parents_sorted_by_depth() {
+
+        for node_id in parent_id.children(node_hierarchy) {
+
+            let node_style = &node_styles[node_id];
+
+            let containing_block = match node_style.position_type {
+                PositionType::Fixed => None,
+                PositionType::Absolute if !node_styles[parent_id].position_type.is_positioned() => {
+                    let mut ancestor = node_hierarchy[parent_id].parent;
+                    while let Some(ancestor_id) = ancestor {
+                        if node_styles[ancestor_id].position_type.is_positioned() {
+                            break;
+                        }
+                        ancestor = node_hierarchy[ancestor_id].parent;
+                    }
+                    ancestor
+                },
+                // In-flow nodes and nodes that have already been laid out by their parent
+                _ => continue,
+            };
+
+            // Padding box of the containing block
+            let (cb_origin, cb_size) = match containing_block {
+                Some(cb_id) => {
+                    let cb_rect = &node_rects[cb_id];
+                    let cb_border = cb_rect.border_widths;
+                    (
+                        (cb_rect.origin.x.unwrap_or_zero() + cb_border.left, cb_rect.origin.y.unwrap_or_zero() + cb_border.top),
+                        Size {
+                            width: cb_rect.size.width.unwrap_or_zero() - cb_border.left - cb_border.right,
+                            height: cb_rect.size.height.unwrap_or_zero() - cb_border.top - cb_border.bottom,
+                        },
+                    )
+                },
+                None => ((0.0, 0.0), viewport_size),
+            };
+
+            let container_size = cb_size.map(Number::Defined);
+            let margin = node_style.margin.map(|m| m.resolve(container_size.width).or_else(0.0));
+            let left = node_style.position.left.resolve(container_size.width);
+            let right = node_style.position.right.resolve(container_size.width);
+            let top = node_style.position.top.resolve(container_size.height);
+            let bottom = node_style.position.bottom.resolve(container_size.height);
+
+            let width = node_style.size.width
+                .resolve(container_size.width)
+                .maybe_max(node_style.min_size.width.resolve(container_size.width))
+                .maybe_min(node_style.max_size.width.resolve(container_size.width))
+                .or_else(if left.is_defined() && right.is_defined() {
+                    container_size.width - left - right - margin.horizontal()
+                } else {
+                    Undefined
+                });
+
+            let height = node_style.size.height
+                .resolve(container_size.height)
+                .maybe_max(node_style.min_size.height.resolve(container_size.height))
+                .maybe_min(node_style.max_size.height.resolve(container_size.height))
+                .or_else(if top.is_defined() && bottom.is_defined() {
+                    container_size.height - top - bottom - margin.vertical()
+                } else {
+                    Undefined
+                });
+
+            compute_internal(
+                node_id,
+                node_hierarchy,
+                node_styles,
+                node_rects,
+                resolved_text_layout_options,
+                rect_contents,
+                layout_cache,
+                Size { width, height },
+                container_size,
+                true,
+            );
+
+            // Without any offsets, the node is placed at the start of the content box of its parent
+            let parent_rect = &node_rects[parent_id];
+            let static_x = parent_rect.origin.x.unwrap_or_zero() + parent_rect.border_widths.left + parent_rect.padding.left;
+            let static_y = parent_rect.origin.y.unwrap_or_zero() + parent_rect.border_widths.top + parent_rect.padding.top;
+
+            let node_width = node_rects[node_id].size.width.unwrap_or_zero();
+            let node_height = node_rects[node_id].size.height.unwrap_or_zero();
+
+            let x = match (left, right) {
+                (Defined(left), _) => cb_origin.0 + left + margin.left,
+                (Undefined, Defined(right)) => cb_origin.0 + cb_size.width - right - margin.right - node_width,
+                (Undefined, Undefined) => static_x + margin.left,
+            };
+            let y = match (top, bottom) {
+                (Defined(top), _) => cb_origin.1 + top + margin.top,
+                (Undefined, Defined(bottom)) => cb_origin.1 + cb_size.height - bottom - margin.bottom - node_height,
+                (Undefined, Undefined) => static_y + margin.top,
+            };
+
+            node_rects[node_id].origin.x = Defined(x);
+            node_rects[node_id].origin.y = Defined(y);
+
+            // The descendants of the node are still positioned relative to their parents
+            make_origins_absolute(node_id, node_hierarchy, node_rects);
+        }
+    }
+}
+
+/// Adds the origin of each node to the origins of its children, for all descendants of the `node_id`
+fn make_origins_absolute(node_id: NodeId, node_hierarchy: &NodeHierarchy, node_rects: &mut NodeDataContainer<Rect>) {
+    let origin = node_rects[node_id].origin;
+    for child_id in node_id.children(node_hierarchy) {
+        node_rects[child_id].origin.x += origin.x;
+        node_rects[child_id].origin.y += origin.y;
+        make_origins_absolute(child_id, node_hierarchy, node_rects);
+    }
+}
+
+/// Measuring a node caches the layout of its text at the width it was measured with -
+/// removes the cached layouts, so that the text is wrapped at the width it is finally laid out with
+pub(crate) fn clear_text_layouts(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+) {
+    resolved_text_layout_options.remove(&node_id);
+    for child_id in node_id.children(node_hierarchy) {
+        clear_text_layouts(child_id, node_hierarchy, resolved_text_layout_options);
+    }
+}
+
 #[inline]
 fn layout_line<T: GetTextLayout>(
     line: &mut FlexLine,
@@ -1408,6 +1748,7 @@ fn layout_line<T: GetTextLayout>(
     node_rects: &mut NodeDataContainer<Rect>,
     node_styles: &NodeDataContainer<Style>,
     rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
     resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
     total_offset_cross: &mut f32,
     padding_border: &Offsets<f32>,
@@ -1425,6 +1766,7 @@ fn layout_line<T: GetTextLayout>(
                 node_rects,
                 node_styles,
                 rect_contents,
+                layout_cache,
                 resolved_text_layout_options,
                 total_offset_cross,
                 &mut total_offset_main,
@@ -1441,6 +1783,7 @@ fn layout_line<T: GetTextLayout>(
                 node_rects,
                 node_styles,
                 rect_contents,
+                layout_cache,
                 resolved_text_layout_options,
                 total_offset_cross,
                 &mut total_offset_main,
@@ -1466,6 +1809,7 @@ fn layout_item<T: GetTextLayout>(
     node_rects: &mut NodeDataContainer<Rect>,
     node_styles: &NodeDataContainer<Style>,
     rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
     resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
     total_offset_cross: &mut f32,
     total_offset_main: &mut f32,
@@ -1484,6 +1828,7 @@ fn layout_item<T: GetTextLayout>(
         node_rects,
         resolved_text_layout_options,
         rect_contents,
+        layout_cache,
         child.target_size.map(|s| s.to_number()),
         container_size.map(|s| s.to_number()),
         true,
@@ -1510,3 +1855,157 @@ fn layout_item<T: GetTextLayout>(
 
     *total_offset_main += child.offset_main + child.margin.main(dir) + node_rects[child.node_id].size.main(dir).unwrap_or_zero();
 }
+
+#[cfg(test)]
+mod flex_tests {
+
+    use test_utils::{px, sized, solve};
+    use super::*;
+
+    #[test]
+    fn test_flex_grow() {
+        let rects = solve(vec![
+            (None, sized(px(300.0), px(100.0))),
+            (Some(0), Style { flex_grow: 1.0, flex_basis: px(0.0), .. Style::default() }),
+            (Some(1), sized(px(10.0), px(10.0))),
+            (Some(0), Style { flex_grow: 2.0, flex_basis: px(0.0), .. Style::default() }),
+            (Some(3), sized(px(10.0), px(10.0))),
+        ], vec![]);
+        assert_eq!(rects[1], (0.0, 0.0, 100.0, 100.0));
+        assert_eq!(rects[3], (100.0, 0.0, 200.0, 100.0));
+        assert_eq!(rects[4], (100.0, 0.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_flex_row_alignment() {
+        let rects = solve(vec![
+            (None, Style {
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::FlexEnd,
+                padding: Offsets { left: px(10.0), right: px(10.0), top: px(10.0), bottom: px(10.0) },
+                .. sized(px(300.0), px(100.0))
+            }),
+            (Some(0), sized(px(100.0), px(50.0))),
+            (Some(0), Style { margin: Offsets { left: px(5.0), right: px(5.0), .. Offsets::default() }, .. sized(px(50.0), px(30.0)) }),
+            (Some(0), sized(px(20.0), px(20.0))),
+        ], vec![]);
+        assert_eq!(rects[1], (10.0, 40.0, 100.0, 50.0));
+        assert_eq!(rects[2], (165.0, 60.0, 50.0, 30.0));
+        assert_eq!(rects[3], (270.0, 70.0, 20.0, 20.0));
+    }
+
+    #[test]
+    fn test_flex_column_alignment() {
+        let rects = solve(vec![
+            (None, Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexEnd,
+                .. sized(px(300.0), px(200.0))
+            }),
+            (Some(0), sized(px(100.0), px(50.0))),
+            (Some(0), sized(px(50.0), px(30.0))),
+        ], vec![]);
+        assert_eq!(rects[1], (100.0, 120.0, 100.0, 50.0));
+        assert_eq!(rects[2], (125.0, 170.0, 50.0, 30.0));
+    }
+
+    #[test]
+    fn test_all_displayed_children_are_flex_items() {
+        let rects = solve(vec![
+            (None, sized(px(300.0), px(100.0))),
+            (Some(0), Style { display: Display::Block, .. sized(px(100.0), Dimension::Auto) }),
+            (Some(0), Style { display: Display::None, .. sized(px(50.0), Dimension::Auto) }),
+            (Some(0), Style { display: Display::Grid, .. sized(px(50.0), Dimension::Auto) }),
+            (Some(0), Style { display: Display::Inline, flex_grow: 1.0, flex_basis: px(0.0), .. Style::default() }),
+            (Some(4), sized(px(10.0), px(10.0))),
+        ], vec![]);
+        assert_eq!(rects[1], (0.0, 0.0, 100.0, 100.0));
+        assert_eq!(rects[2], (0.0, 0.0, 0.0, 0.0));
+        assert_eq!(rects[3], (100.0, 0.0, 50.0, 100.0));
+        assert_eq!(rects[4], (150.0, 0.0, 150.0, 100.0));
+    }
+}
+
+#[cfg(test)]
+mod position_tests {
+
+    use test_utils::{px, sized, solve};
+    use super::*;
+
+    fn positioned(position_type: PositionType, offsets: Offsets<Dimension>, style: Style) -> Style {
+        Style { position_type, position: offsets, .. style }
+    }
+
+    fn top_left(top: f32, left: f32) -> Offsets<Dimension> {
+        Offsets { top: px(top), left: px(left), .. Offsets::default() }
+    }
+
+    fn bottom_right(bottom: f32, right: f32) -> Offsets<Dimension> {
+        Offsets { bottom: px(bottom), right: px(right), .. Offsets::default() }
+    }
+
+    /// Returns the layout of the following tree, in a 300x200 window:
+    ///
+    /// ```compile_fail
+    /// 0: static, 300x200, flex-direction: column
+    /// '- 1: relative, 200x100, margin-top: 20px, padding: 10px
+    ///    '- 2: static, 100x50, padding: 5px
+    ///    '  '- 3: absolute, top: 10px, left: 10px
+    ///    '  '- 4: absolute, bottom: 0, right: 0
+    ///    '  '- 5: absolute, no offsets, margin-left: 3px
+    ///    '  '- 6: fixed, bottom: 10px, right: 10px
+    ///    '- 7: absolute, top: 10px, left: 10px
+    /// '- 8: static, 100x20
+    ///    '- 9: absolute, top: 5px, left: 5px
+    /// ```
+    fn get_positioned_layout() -> Vec<(f32, f32, f32, f32)> {
+        let padding = |p| Offsets { top: px(p), left: px(p), bottom: px(p), right: px(p) };
+        let nodes = vec![
+            (None, Style { flex_direction: FlexDirection::Column, .. sized(px(300.0), px(200.0)) }),
+            (Some(0), Style {
+                position_type: PositionType::Relative,
+                margin: Offsets { top: px(20.0), .. Offsets::default() },
+                padding: padding(10.0),
+                .. sized(px(200.0), px(100.0))
+            }),
+            (Some(1), Style { padding: padding(5.0), .. sized(px(100.0), px(50.0)) }),
+            (Some(2), positioned(PositionType::Absolute, top_left(10.0, 10.0), sized(px(20.0), px(20.0)))),
+            (Some(2), positioned(PositionType::Absolute, bottom_right(0.0, 0.0), sized(px(20.0), px(20.0)))),
+            (Some(2), Style { margin: Offsets { left: px(3.0), .. Offsets::default() }, .. positioned(PositionType::Absolute, Offsets::default(), sized(px(20.0), px(20.0))) }),
+            (Some(2), positioned(PositionType::Fixed, bottom_right(10.0, 10.0), sized(px(20.0), px(20.0)))),
+            (Some(1), positioned(PositionType::Absolute, top_left(10.0, 10.0), sized(px(20.0), px(20.0)))),
+            (Some(0), sized(px(100.0), px(20.0))),
+            (Some(8), positioned(PositionType::Absolute, top_left(5.0, 5.0), sized(px(10.0), px(10.0)))),
+        ];
+        solve(nodes, vec![])
+    }
+
+    #[test]
+    fn test_absolute_nodes_are_positioned_in_their_containing_block() {
+        let rects = get_positioned_layout();
+        assert_eq!(rects[1], (0.0, 20.0, 200.0, 100.0));
+        assert_eq!(rects[2], (10.0, 30.0, 100.0, 50.0));
+        // The containing block is the padding box of the nearest positioned ancestor (1), not the parent
+        assert_eq!(rects[3], (10.0, 30.0, 20.0, 20.0));
+        assert_eq!(rects[4], (180.0, 100.0, 20.0, 20.0));
+        assert_eq!(rects[7], (10.0, 30.0, 20.0, 20.0));
+        // Without a positioned ancestor, the containing block is the viewport
+        assert_eq!(rects[9], (5.0, 5.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_fixed_nodes_are_positioned_in_the_viewport() {
+        let rects = get_positioned_layout();
+        assert_eq!(rects[6], (270.0, 170.0, 20.0, 20.0));
+    }
+
+    #[test]
+    fn test_absolute_nodes_without_offsets_stay_at_their_static_position() {
+        let rects = get_positioned_layout();
+        // start of the content box of the parent (2), plus the margin of the node
+        assert_eq!(rects[5], (18.0, 35.0, 20.0, 20.0));
+        // Out-of-flow nodes don't take up space in their parent
+        assert_eq!(rects[8], (0.0, 120.0, 100.0, 20.0));
+    }
+}
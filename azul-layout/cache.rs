@@ -0,0 +1,490 @@
+//! Caches the layout of every node between two layout passes, so that only the subtrees
+//! whose styles, contents or available space changed have to be laid out again.
+//!
+//! At the end of each layout pass, the cache stores one layout per node: its rectangle
+//! (relative to its parent), its text layout and the constraints (the arguments of
+//! `compute_internal`) that it was last laid out with. In the next pass, a node that is laid
+//! out with the same constraints again restores the stored layouts of itself and all of its
+//! descendants instead - since they were produced by the last layout of the node, they are
+//! still valid as long as the subtree didn't change. If a node changes, the layouts of the
+//! node and all of its ancestors are dropped.
+//!
+//! Nodes are usually measured (laid out with `perform_layout = false`) a few times before they
+//! are laid out, so the cache also stores the measured size of a node for each constraint, which
+//! is valid as long as the node doesn't change, even within a single layout pass.
+
+use std::{
+    mem,
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
+use azul_css::LayoutRect;
+use azul_core::{
+    ui_solver::{ResolvedTextLayoutOptions, InlineTextLayout},
+    id_tree::{NodeHierarchy, NodeDataContainer},
+    dom::NodeId,
+    diff::DomDiff,
+};
+use {
+    RectContent, GetTextLayout,
+    style::Style,
+    number::Number,
+    geometry::{Rect, Size},
+};
+
+/// How many measured sizes (with different constraints) are cached per node - a flex item is
+/// usually measured a few times before it is laid out, so this should be at least 4
+const MAX_MEASURED_LAYOUTS_PER_NODE: usize = 4;
+
+type TextLayout = (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect);
+
+/// Arguments that `compute_internal` was called with
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Constraints {
+    node_size: Size<Number>,
+    parent_size: Size<Number>,
+}
+
+/// Last layout of a node with `perform_layout = true`
+#[derive(Debug, Copy, Clone)]
+struct NodeLayout {
+    constraints: Constraints,
+    /// Rectangle of the node before its parent positioned (or stretched) it
+    rect: Rect,
+}
+
+/// Layout of a single node at the end of the last layout pass
+#[derive(Debug, Clone)]
+struct CachedLayout {
+    /// `None` if the node was never laid out by itself (i.e. inline nodes that are laid out by their parent)
+    node_layout: Option<NodeLayout>,
+    /// Final rectangle of the node, relative to its parent
+    rect: Rect,
+    text_layout: Option<TextLayout>,
+}
+
+/// Result of measuring a node with the given constraints (without its descendants)
+#[derive(Debug, Clone)]
+struct MeasuredLayout {
+    constraints: Constraints,
+    rect: Rect,
+    text_layout: Option<TextLayout>,
+}
+
+/// Everything the layout of a single node depends on (except for its descendants)
+#[derive(Debug, Clone, PartialEq)]
+struct NodeInputs {
+    /// Number of nodes in the subtree of the node, changes if descendants were inserted or removed
+    subtree_len: usize,
+    style: Style,
+    /// Hash of the text or the size of the image, `None` if the content can't be compared
+    content_hash: Option<u64>,
+}
+
+/// Layouts of the last layout pass, used by `SolvedUi::new_cached` to skip
+/// the layout of the subtrees that didn't change since then.
+#[derive(Debug, Clone)]
+pub struct LayoutCache {
+    /// Inputs of each node in the last layout pass, indexed by the node ID
+    inputs: Vec<Option<NodeInputs>>,
+    /// Layout of each node at the end of the last layout pass, indexed by the node ID
+    layouts: Vec<Option<CachedLayout>>,
+    /// Last layout of each node in the current layout pass, indexed by the node ID
+    node_layouts: Vec<Option<NodeLayout>>,
+    /// Measured layouts of each node, indexed by the node ID
+    measured_layouts: Vec<Vec<MeasuredLayout>>,
+    /// `SolvedUi::new` doesn't reuse the layouts, so it doesn't need to record them either
+    is_enabled: bool,
+}
+
+impl Default for LayoutCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayoutCache {
+
+    /// Creates an empty cache - the first layout with this cache lays out all nodes
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            layouts: Vec::new(),
+            node_layouts: Vec::new(),
+            measured_layouts: Vec::new(),
+            is_enabled: true,
+        }
+    }
+
+    pub(crate) fn disabled() -> Self {
+        Self {
+            is_enabled: false,
+            .. Self::new()
+        }
+    }
+
+    /// Drops all cached layouts
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+        self.layouts.clear();
+        self.node_layouts.clear();
+        self.measured_layouts.clear();
+    }
+
+    /// Has to be called whenever the DOM is replaced: Moves the cached layouts to the IDs of the
+    /// nodes in the new DOM and drops the layouts of the nodes that were added or that changed.
+    pub fn apply_dom_diff(&mut self, dom_diff: &DomDiff) {
+
+        let mut inputs = Vec::with_capacity(dom_diff.get_old_node_ids().len());
+        let mut layouts = Vec::with_capacity(dom_diff.get_old_node_ids().len());
+        let mut measured_layouts = Vec::with_capacity(dom_diff.get_old_node_ids().len());
+
+        for old_node_id in dom_diff.get_old_node_ids() {
+            let old_node_idx = old_node_id.map(|id| id.index()).filter(|idx| *idx < self.inputs.len());
+            match old_node_idx {
+                Some(idx) => {
+                    inputs.push(self.inputs[idx].take());
+                    layouts.push(self.layouts[idx].take());
+                    measured_layouts.push(mem::replace(&mut self.measured_layouts[idx], Vec::new()));
+                },
+                None => {
+                    inputs.push(None);
+                    layouts.push(None);
+                    measured_layouts.push(Vec::new());
+                },
+            }
+        }
+
+        // Nodes without inputs count as changed in the next layout pass
+        for node_id in dom_diff.get_nodes_that_need_relayout() {
+            if let Some(node_inputs) = inputs.get_mut(node_id.index()) {
+                *node_inputs = None;
+            }
+        }
+
+        self.inputs = inputs;
+        self.layouts = layouts;
+        self.measured_layouts = measured_layouts;
+    }
+
+    /// Compares the styles and contents of the nodes with the last layout pass
+    /// and drops the layouts of the nodes that changed and of their ancestors
+    pub(crate) fn begin_layout<T: GetTextLayout>(
+        &mut self,
+        node_hierarchy: &NodeHierarchy,
+        node_styles: &NodeDataContainer<Style>,
+        rect_contents: &BTreeMap<NodeId, RectContent<T>>,
+    ) {
+        // Without the inputs of the last layout pass, the measured layouts are only valid for this pass
+        if !self.is_enabled || self.measured_layouts.len() != node_hierarchy.len() {
+            self.measured_layouts = vec![Vec::new(); node_hierarchy.len()];
+        }
+
+        if !self.is_enabled {
+            return;
+        }
+
+        // The DOM was replaced without calling apply_dom_diff
+        if self.inputs.len() != node_hierarchy.len() {
+            self.inputs = vec![None; node_hierarchy.len()];
+            self.layouts = vec![None; node_hierarchy.len()];
+            self.measured_layouts = vec![Vec::new(); node_hierarchy.len()];
+        }
+
+        self.node_layouts = vec![None; node_hierarchy.len()];
+
+        // The nodes are stored in tree order, so the children always come after their parent
+        let mut subtree_lens = vec![1; node_hierarchy.len()];
+        for idx in (0..node_hierarchy.len()).rev() {
+            if let Some(parent_id) = node_hierarchy[NodeId::new(idx)].parent {
+                subtree_lens[parent_id.index()] += subtree_lens[idx];
+            }
+        }
+
+        let mut is_dirty = vec![false; node_hierarchy.len()];
+
+        for node_id in node_hierarchy.linear_iter() {
+
+            let node_inputs = NodeInputs {
+                subtree_len: subtree_lens[node_id.index()],
+                style: node_styles[node_id].clone(),
+                content_hash: get_content_hash(rect_contents.get(&node_id)),
+            };
+
+            let has_changed = node_inputs.content_hash.is_none() || self.inputs[node_id.index()].as_ref() != Some(&node_inputs);
+            self.inputs[node_id.index()] = Some(node_inputs);
+
+            if !has_changed {
+                continue;
+            }
+
+            // The size of a node depends on its descendants, so all ancestors have to be laid out again
+            for ancestor_id in node_id.ancestors(node_hierarchy) {
+                if is_dirty[ancestor_id.index()] {
+                    break;
+                }
+                is_dirty[ancestor_id.index()] = true;
+            }
+        }
+
+        for ((layout, measured_layouts), is_dirty) in self.layouts.iter_mut().zip(self.measured_layouts.iter_mut()).zip(is_dirty) {
+            if is_dirty {
+                *layout = None;
+                measured_layouts.clear();
+            }
+        }
+    }
+
+    /// Stores the layout of each node at the end of a layout pass, before
+    /// the origins of the `node_rects` are made absolute
+    pub(crate) fn end_layout(
+        &mut self,
+        node_hierarchy: &NodeHierarchy,
+        node_rects: &NodeDataContainer<Rect>,
+        resolved_text_layout_options: &BTreeMap<NodeId, TextLayout>,
+    ) {
+        if !self.is_enabled || self.layouts.len() != node_hierarchy.len() {
+            return;
+        }
+
+        for node_id in node_hierarchy.linear_iter() {
+            self.layouts[node_id.index()] = Some(CachedLayout {
+                node_layout: self.node_layouts[node_id.index()],
+                rect: node_rects[node_id],
+                text_layout: resolved_text_layout_options.get(&node_id).cloned(),
+            });
+        }
+    }
+
+    /// If the node was last laid out with the same constraints in the last layout pass, copies
+    /// the layouts of the node and its descendants into the `node_rects` and returns `true`
+    pub(crate) fn restore_layout(
+        &mut self,
+        node_id: NodeId,
+        node_hierarchy: &NodeHierarchy,
+        node_rects: &mut NodeDataContainer<Rect>,
+        resolved_text_layout_options: &mut BTreeMap<NodeId, TextLayout>,
+        node_size: Size<Number>,
+        parent_size: Size<Number>,
+    ) -> bool {
+
+        let constraints = Constraints { node_size, parent_size };
+
+        let node_layout = match self.layouts.get(node_id.index()) {
+            Some(Some(CachedLayout { node_layout: Some(node_layout), .. })) if node_layout.constraints == constraints => *node_layout,
+            _ => return false,
+        };
+
+        // The descendants of a clean node are clean as well, so all of them have a layout
+        for descendant_id in node_id.descendants(node_hierarchy) {
+            let layout = match &self.layouts[descendant_id.index()] {
+                Some(s) => s,
+                None => continue,
+            };
+            node_rects[descendant_id] = layout.rect;
+            match &layout.text_layout {
+                Some(text_layout) => { resolved_text_layout_options.insert(descendant_id, text_layout.clone()); },
+                None => { resolved_text_layout_options.remove(&descendant_id); },
+            }
+            self.node_layouts[descendant_id.index()] = layout.node_layout;
+        }
+
+        // The parent of the node may position it differently than in the last layout pass
+        let origin = node_rects[node_id].origin;
+        node_rects[node_id] = Rect { origin, .. node_layout.rect };
+
+        true
+    }
+
+    /// Remembers the constraints and the rectangle of the node, after the node has been laid out
+    pub(crate) fn insert_layout(&mut self, node_id: NodeId, node_rects: &NodeDataContainer<Rect>, node_size: Size<Number>, parent_size: Size<Number>) {
+        if let Some(node_layout) = self.node_layouts.get_mut(node_id.index()) {
+            *node_layout = Some(NodeLayout {
+                constraints: Constraints { node_size, parent_size },
+                rect: node_rects[node_id],
+            });
+        }
+    }
+
+    /// If the node has already been measured with the same constraints, copies the
+    /// measured rectangle and text layout of the node into the `node_rects` and returns `true`
+    pub(crate) fn restore_measured_layout(
+        &self,
+        node_id: NodeId,
+        node_rects: &mut NodeDataContainer<Rect>,
+        resolved_text_layout_options: &mut BTreeMap<NodeId, TextLayout>,
+        node_size: Size<Number>,
+        parent_size: Size<Number>,
+    ) -> bool {
+
+        let constraints = Constraints { node_size, parent_size };
+
+        let measured_layout = match self.measured_layouts.get(node_id.index()).and_then(|m| m.iter().find(|m| m.constraints == constraints)) {
+            Some(s) => s,
+            None => return false,
+        };
+
+        // The origin of the node is set by its parent
+        let origin = node_rects[node_id].origin;
+        node_rects[node_id] = Rect { origin, .. measured_layout.rect };
+
+        if let Some(text_layout) = &measured_layout.text_layout {
+            resolved_text_layout_options.insert(node_id, text_layout.clone());
+        }
+
+        true
+    }
+
+    /// Stores the rectangle and text layout of the node, after the node has been measured
+    pub(crate) fn insert_measured_layout(
+        &mut self,
+        node_id: NodeId,
+        node_rects: &NodeDataContainer<Rect>,
+        resolved_text_layout_options: &BTreeMap<NodeId, TextLayout>,
+        node_size: Size<Number>,
+        parent_size: Size<Number>,
+    ) {
+        let measured_layouts = match self.measured_layouts.get_mut(node_id.index()) {
+            Some(s) => s,
+            None => return,
+        };
+
+        let constraints = Constraints { node_size, parent_size };
+
+        measured_layouts.retain(|m| m.constraints != constraints);
+        if measured_layouts.len() >= MAX_MEASURED_LAYOUTS_PER_NODE {
+            measured_layouts.remove(0);
+        }
+        measured_layouts.push(MeasuredLayout {
+            constraints,
+            rect: node_rects[node_id],
+            text_layout: resolved_text_layout_options.get(&node_id).cloned(),
+        });
+    }
+}
+
+/// Nodes without content always have the same hash, text that can't be hashed has none
+fn get_content_hash<T: GetTextLayout>(content: Option<&RectContent<T>>) -> Option<u64> {
+
+    use std::collections::hash_map::DefaultHasher as HashAlgorithm;
+
+    let mut hasher = HashAlgorithm::default();
+
+    match content {
+        None => 0_u8.hash(&mut hasher),
+        Some(RectContent::Image(w, h)) => (1_u8, w, h).hash(&mut hasher),
+        Some(RectContent::Text(t)) => (2_u8, t.get_text_hash()?).hash(&mut hasher),
+    }
+
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod cache_tests {
+
+    use std::{cell::Cell, rc::Rc, collections::hash_map::DefaultHasher};
+    use azul_css::PixelValue;
+    use test_utils::{TestText, solve_test_layout, solve_test_layout_cached};
+    use style::{Dimension, Display, FlexDirection};
+    use geometry::Offsets;
+    use super::*;
+
+    /// `TestText` that counts how often it is laid out
+    struct CountedText(TestText, Rc<Cell<usize>>);
+
+    impl GetTextLayout for CountedText {
+        fn get_text_layout(&mut self, text_layout_options: &ResolvedTextLayoutOptions) -> InlineTextLayout {
+            self.1.set(self.1.get() + 1);
+            self.0.get_text_layout(text_layout_options)
+        }
+
+        fn get_text_hash(&self) -> Option<u64> {
+            let mut hasher = DefaultHasher::new();
+            for word_width in &(self.0).0 {
+                word_width.to_bits().hash(&mut hasher);
+            }
+            Some(hasher.finish())
+        }
+    }
+
+    /// Column with two rows, each of which contains a text (node 2 and node 4). The column has a
+    /// fixed width, otherwise it would be as wide as its widest row and both rows would depend on each other:
+    ///
+    /// ```compile_fail
+    /// 0: column (300px wide)
+    ///     1: row
+    ///         2: text
+    ///     3: row (with the given padding)
+    ///         4: text
+    /// ```
+    fn get_nodes(padding_left: f32) -> Vec<(Option<usize>, Style)> {
+        let row = Style { display: Display::Flex, flex_direction: FlexDirection::Row, .. Style::default() };
+        let text = Style { font_size_px: PixelValue::px(10.0), .. Style::default() };
+        vec![
+            (None, Style { flex_direction: FlexDirection::Column, size: Size { width: Dimension::Pixels(300.0), height: Dimension::Undefined }, .. Style::default() }),
+            (Some(0), row.clone()),
+            (Some(1), text.clone()),
+            (Some(0), Style { padding: Offsets { left: Dimension::Pixels(padding_left), .. row.padding }, .. row }),
+            (Some(3), text),
+        ]
+    }
+
+    fn get_texts(second_text: Vec<f32>, layout_counts: &[Rc<Cell<usize>>; 2]) -> BTreeMap<NodeId, RectContent<CountedText>> {
+        let mut rect_contents = BTreeMap::new();
+        rect_contents.insert(NodeId::new(2), RectContent::Text(CountedText(TestText(vec![40.0, 60.0]), layout_counts[0].clone())));
+        rect_contents.insert(NodeId::new(4), RectContent::Text(CountedText(TestText(second_text), layout_counts[1].clone())));
+        rect_contents
+    }
+
+    fn reset(layout_counts: &[Rc<Cell<usize>>; 2]) {
+        for layout_count in layout_counts {
+            layout_count.set(0);
+        }
+    }
+
+    #[test]
+    fn test_unchanged_subtrees_are_restored() {
+        let layout_counts = [Rc::new(Cell::new(0)), Rc::new(Cell::new(0))];
+        let mut layout_cache = LayoutCache::new();
+
+        let first_pass = solve_test_layout_cached(get_nodes(10.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+        assert_eq!(first_pass, solve_test_layout(get_nodes(10.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0)));
+
+        reset(&layout_counts);
+        let second_pass = solve_test_layout_cached(get_nodes(10.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+        assert_eq!(second_pass, first_pass);
+        assert_eq!(layout_counts[0].get(), 0);
+        assert_eq!(layout_counts[1].get(), 0);
+    }
+
+    #[test]
+    fn test_changed_style_is_laid_out_again() {
+        let layout_counts = [Rc::new(Cell::new(0)), Rc::new(Cell::new(0))];
+        let mut layout_cache = LayoutCache::new();
+
+        solve_test_layout_cached(get_nodes(10.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+
+        reset(&layout_counts);
+        let rects = solve_test_layout_cached(get_nodes(20.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+        // Only the row whose padding changed is laid out again
+        assert_eq!(layout_counts[0].get(), 0);
+        assert!(layout_counts[1].get() > 0);
+        assert_eq!(rects[4].0, 20.0);
+        assert_eq!(rects, solve_test_layout(get_nodes(20.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0)));
+    }
+
+    #[test]
+    fn test_changed_text_is_laid_out_again() {
+        let layout_counts = [Rc::new(Cell::new(0)), Rc::new(Cell::new(0))];
+        let mut layout_cache = LayoutCache::new();
+
+        solve_test_layout_cached(get_nodes(10.0), get_texts(vec![50.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+
+        reset(&layout_counts);
+        let rects = solve_test_layout_cached(get_nodes(10.0), get_texts(vec![50.0, 70.0], &layout_counts), (300.0, 200.0), &mut layout_cache);
+        assert_eq!(layout_counts[0].get(), 0);
+        assert!(layout_counts[1].get() > 0);
+        assert_eq!(rects[4].2, 125.0);
+        assert_eq!(rects, solve_test_layout(get_nodes(10.0), get_texts(vec![50.0, 70.0], &layout_counts), (300.0, 200.0)));
+    }
+}
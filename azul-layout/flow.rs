@@ -0,0 +1,904 @@
+//! Normal flow: the block-level children of a `display: block` node are stacked vertically,
+//! runs of inline-level children (and the text of the node itself) are broken into the lines
+//! of a paragraph, as in an [inline formatting context](https://www.w3.org/TR/CSS2/visuren.html#inline-formatting).
+//!
+//! Simplifications compared to the spec: vertical margins only collapse between siblings
+//! (not with the margins of the parent), inline boxes are always aligned on the baseline
+//! (there is no `vertical-align`) and the height of a line of text is its font size plus
+//! the line gap of the text.
+//!
+//! Floating children are placed at the left or right edge of the container, next to or
+//! below the floats before them, and the lines of the paragraphs flow around them. A float
+//! in the middle of a paragraph is placed at the top of the paragraph, floats only affect
+//! the paragraphs of their own container and every block container grows to contain its
+//! floats (as if it established a new block formatting context). Floating descendants of
+//! inline elements are placed on the line like an `inline-block`.
+
+use std::collections::BTreeMap;
+
+use azul_css::{LayoutRect, LayoutPoint, LayoutSize};
+use azul_core::{
+    ui_solver::{ResolvedTextLayoutOptions, InlineTextLayout},
+    id_tree::{NodeHierarchy, NodeDataContainer},
+    dom::NodeId,
+};
+
+use {
+    RectContent, GetTextLayout, LayoutCache,
+    algo::{compute_internal, layout_absolute_children, get_text_layout_options, resolve_offsets},
+    style::*,
+    number::{OrElse, MinMax, Number::{self, *}},
+    geometry::{Rect, RectSize, Offsets, Size},
+};
+
+/// Gap between two lines of text, relative to the font size - the text layout uses the
+/// width of a space as the line gap, which is roughly a quarter of the font size
+const LINE_GAP_EM: f32 = 0.25;
+
+/// Item of a paragraph, in the order in which the items are placed on the lines
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum InlineItem {
+    /// Text of a node, broken into lines by the text layout
+    Text(NodeId),
+    /// Box that is placed on a line as a whole, i.e. an image or an `inline-block`
+    Atomic(NodeId),
+    /// Start of an inline element (i.e. a `<span>`) whose content is part of the paragraph
+    Start(NodeId),
+    /// End of an inline element
+    End(NodeId),
+}
+
+/// Part of a text or an atomic inline box on one line
+#[derive(Debug)]
+struct Fragment {
+    node_id: NodeId,
+    /// Index of the line of the text layout, `None` for atomic inline boxes
+    text_line: Option<usize>,
+    /// Index of the line of the paragraph
+    line: usize,
+    /// Position of the fragment on the line, relative to the start of the line
+    x: f32,
+    width: f32,
+    height: f32,
+    /// Distance from the top of the fragment to the baseline
+    baseline: f32,
+    /// Space above the fragment that the line has to reserve
+    margin_top: f32,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct LineBox {
+    /// Height of the line above / below the baseline
+    ascent: f32,
+    descent: f32,
+    width: f32,
+}
+
+/// Margin box of a floating child, relative to the border box of the container
+/// (or to the top left corner of a paragraph, in `layout_paragraph`)
+#[derive(Debug, Copy, Clone)]
+struct PlacedFloat {
+    side: Float,
+    bounds: LayoutRect,
+}
+
+/// Inline element with the positions (line, x) of its start and end
+#[derive(Debug)]
+struct InlineElement {
+    node_id: NodeId,
+    start: (usize, f32),
+    end: (usize, f32),
+    /// Fragments of the content of the element
+    first_fragment: usize,
+    last_fragment: usize,
+}
+
+/// Lays out the children of a block container. `node_inner_size` is the size of the content box of
+/// the container - if the width is not definite, the container is shrink-wrapped around its content.
+/// Returns the size of the container, including the padding and border.
+pub(crate) fn compute_block<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    node_inner_size: Size<Number>,
+    padding_border: &Offsets<f32>,
+) -> Size<f32> {
+
+    let mut cursor_y = padding_border.top;
+    let mut previous_margin_bottom = 0.0;
+    let mut content_width = 0.0_f32;
+    let mut floats = Vec::<PlacedFloat>::new();
+
+    // The text of the container itself is the start of its first paragraph
+    let mut paragraph = Vec::new();
+    if rect_contents.get(&node_id).map(|content| content.is_text()).unwrap_or(false) {
+        paragraph.push(InlineItem::Text(node_id));
+    }
+
+    // Runs of inline-level children are wrapped in an anonymous block
+    macro_rules! finish_paragraph {() => ({
+        if !paragraph.is_empty() {
+            cursor_y += previous_margin_bottom;
+            previous_margin_bottom = 0.0;
+            let paragraph_size = layout_paragraph(
+                node_id,
+                &paragraph,
+                node_hierarchy,
+                node_styles,
+                node_rects,
+                resolved_text_layout_options,
+                rect_contents,
+                layout_cache,
+                &floats,
+                node_inner_size.width,
+                LayoutPoint::new(padding_border.left, cursor_y),
+            );
+            cursor_y += paragraph_size.height;
+            content_width = content_width.max(paragraph_size.width);
+            paragraph.clear();
+        }
+    })}
+
+    let in_flow_children = node_id.children(node_hierarchy)
+        .filter(|child_id| node_styles[*child_id].display != Display::None)
+        .filter(|child_id| !node_styles[*child_id].position_type.is_out_of_flow())
+        .collect::<Vec<_>>();
+
+    for child_id in in_flow_children {
+
+        let child_style = &node_styles[child_id];
+
+        // Floats are placed before the paragraph that they are part of is broken into lines
+        if child_style.float != Float::None {
+            let float = place_float(
+                child_id,
+                node_hierarchy,
+                node_styles,
+                node_rects,
+                resolved_text_layout_options,
+                rect_contents,
+                layout_cache,
+                &floats,
+                cursor_y + previous_margin_bottom,
+                node_inner_size,
+                padding_border,
+            );
+            floats.push(float);
+            continue;
+        }
+
+        if child_style.display.is_inline_level() {
+            collect_inline_items(child_id, node_hierarchy, node_styles, rect_contents, &mut paragraph);
+            continue;
+        }
+
+        finish_paragraph!();
+
+        let margin = child_style.margin.map(|m| m.resolve(node_inner_size.width).or_else(0.0));
+
+        let width = child_style.size.width.resolve(node_inner_size.width)
+            .maybe_max(child_style.min_size.width.resolve(node_inner_size.width))
+            .maybe_min(child_style.max_size.width.resolve(node_inner_size.width));
+        let height = child_style.size.height.resolve(node_inner_size.height)
+            .maybe_max(child_style.min_size.height.resolve(node_inner_size.height))
+            .maybe_min(child_style.max_size.height.resolve(node_inner_size.height));
+
+        let stretch_width = !width.is_defined() && node_inner_size.width.is_defined();
+        let child_size = Size {
+            width: if stretch_width { node_inner_size.width - margin.horizontal() } else { width },
+            height,
+        };
+
+        compute_internal(
+            child_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            child_size,
+            Size {
+                width: node_inner_size.width,
+                // Blocks with an auto height are sized to fit their content
+                height: if child_style.size.height.is_defined() { node_inner_size.height } else { Undefined },
+            },
+            true,
+        );
+
+        let node = &mut node_rects[child_id];
+        if stretch_width {
+            node.size.width = child_size.width;
+        }
+
+        let child_width = node.size.width.unwrap_or_zero();
+        let child_height = node.size.height.unwrap_or_zero();
+
+        // margin: auto centers the block horizontally
+        let free_width = (node_inner_size.width - child_width - margin.horizontal()).or_else(0.0).max(0.0);
+        let margin_left = match (child_style.margin.left, child_style.margin.right) {
+            (Dimension::Auto, Dimension::Auto) => free_width / 2.0,
+            (Dimension::Auto, _) => free_width,
+            _ => margin.left,
+        };
+
+        // position: relative
+        let relative_offsets = child_style.relative_offsets();
+        let position_offset_x = relative_offsets.left.resolve(node_inner_size.width).or_else(0.0)
+            - relative_offsets.right.resolve(node_inner_size.width).or_else(0.0);
+        let position_offset_y = relative_offsets.top.resolve(node_inner_size.height).or_else(0.0)
+            - relative_offsets.bottom.resolve(node_inner_size.height).or_else(0.0);
+
+        cursor_y += collapse_margins(previous_margin_bottom, margin.top);
+        node.origin.x = Defined(padding_border.left + margin_left + position_offset_x);
+        node.origin.y = Defined(cursor_y + position_offset_y);
+
+        cursor_y += child_height;
+        previous_margin_bottom = margin.bottom;
+        content_width = content_width.max(child_width + margin.horizontal());
+    }
+
+    finish_paragraph!();
+
+    cursor_y += previous_margin_bottom;
+
+    for float in &floats {
+        cursor_y = cursor_y.max(float.bounds.max_y());
+        content_width = content_width.max(float.bounds.max_x() - padding_border.left);
+    }
+
+    let block_size = Size {
+        width: node_inner_size.width.or_else(content_width) + padding_border.horizontal(),
+        height: node_inner_size.height.or_else(cursor_y - padding_border.top) + padding_border.vertical(),
+    };
+
+    layout_absolute_children(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        block_size,
+        padding_border,
+    );
+
+    block_size
+}
+
+/// Lays out a floating child and places it at the left or right edge of the content box of the
+/// container, but not above `top` or above any of the earlier `floats`. If the float doesn't fit
+/// next to the earlier floats, it is moved down until it does. Returns the margin box of the float.
+fn place_float<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    floats: &[PlacedFloat],
+    top: f32,
+    node_inner_size: Size<Number>,
+    padding_border: &Offsets<f32>,
+) -> PlacedFloat {
+
+    let float_style = &node_styles[node_id];
+    let margin = float_style.margin.map(|m| m.resolve(node_inner_size.width).or_else(0.0));
+
+    compute_internal(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        Size {
+            width: float_style.size.width.resolve(node_inner_size.width),
+            height: float_style.size.height.resolve(node_inner_size.height),
+        },
+        // Floats without a width shrink to fit their content
+        Size {
+            width: if float_style.size.width.is_defined() { node_inner_size.width } else { Undefined },
+            height: Undefined,
+        },
+        true,
+    );
+
+    let outer_width = node_rects[node_id].size.width.unwrap_or_zero() + margin.horizontal();
+    let outer_height = node_rects[node_id].size.height.unwrap_or_zero() + margin.vertical();
+
+    // If the container is shrink-wrapped, there is no right edge to float to
+    let content_left = padding_border.left;
+    let (side, content_right) = match node_inner_size.width {
+        Defined(width) => (float_style.float, content_left + width),
+        Undefined => (Float::Left, ::std::f32::INFINITY),
+    };
+
+    let mut float_top = floats.iter().map(|float| float.bounds.min_y()).fold(top, f32::max);
+
+    let (left, right) = loop {
+        let float_bottom = float_top + outer_height;
+        let (left, right) = free_space(floats, float_top, float_bottom, content_left, content_right);
+        let next_top = floats.iter()
+            .map(|float| float.bounds.max_y())
+            .filter(|bottom| *bottom > float_top)
+            .fold(None, |next: Option<f32>, bottom| Some(next.map_or(bottom, |next| next.min(bottom))));
+        match next_top {
+            Some(next_top) if right - left < outer_width => float_top = next_top,
+            _ => break (left, right),
+        }
+    };
+
+    let float_left = match side {
+        Float::Right => right - outer_width,
+        _ => left,
+    };
+
+    let node = &mut node_rects[node_id];
+    node.origin.x = Defined(float_left + margin.left);
+    node.origin.y = Defined(float_top + margin.top);
+
+    PlacedFloat {
+        side,
+        bounds: LayoutRect::new(
+            LayoutPoint::new(float_left, float_top),
+            LayoutSize::new(outer_width, outer_height),
+        ),
+    }
+}
+
+/// Returns the part (left, right) of the horizontal space between `left` and `right` that is
+/// not covered by any of the floats that overlap the vertical space between `top` and `bottom`
+fn free_space(floats: &[PlacedFloat], top: f32, bottom: f32, left: f32, right: f32) -> (f32, f32) {
+    floats.iter()
+        .filter(|float| float.bounds.min_y() < bottom && float.bounds.max_y() > top)
+        .fold((left, right), |(left, right), float| match float.side {
+            Float::Right => (left, right.min(float.bounds.min_x())),
+            _ => (left.max(float.bounds.max_x()), right),
+        })
+}
+
+/// Adjoining vertical margins collapse into the largest positive margin plus the smallest negative margin
+fn collapse_margins(a: f32, b: f32) -> f32 {
+    a.max(b).max(0.0) + a.min(b).min(0.0)
+}
+
+/// Flattens an inline-level node and its content into the items of a paragraph
+fn collect_inline_items<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    rect_contents: &BTreeMap<NodeId, RectContent<T>>,
+    items: &mut Vec<InlineItem>,
+) {
+    let is_text = rect_contents.get(&node_id).map(|content| content.is_text()).unwrap_or(false);
+    let has_children = node_hierarchy[node_id].first_child.is_some();
+
+    // Inline elements without any content (i.e. an icon) are placed on the line like images
+    if node_styles[node_id].display != Display::Inline || !(is_text || has_children) {
+        items.push(InlineItem::Atomic(node_id));
+        return;
+    }
+
+    items.push(InlineItem::Start(node_id));
+
+    if is_text {
+        items.push(InlineItem::Text(node_id));
+    }
+
+    for child_id in node_id.children(node_hierarchy) {
+        let child_style = &node_styles[child_id];
+        if child_style.display == Display::None || child_style.position_type.is_out_of_flow() {
+            continue;
+        }
+        collect_inline_items(child_id, node_hierarchy, node_styles, rect_contents, items);
+    }
+
+    items.push(InlineItem::End(node_id));
+}
+
+/// Adds the `fragment` to its line and grows the line to fit the fragment
+fn push_fragment(lines: &mut [LineBox], fragments: &mut Vec<Fragment>, fragment: Fragment) {
+    let line = &mut lines[fragment.line];
+    line.ascent = line.ascent.max(fragment.baseline + fragment.margin_top);
+    line.descent = line.descent.max(fragment.height - fragment.baseline);
+    line.width = line.width.max(fragment.x + fragment.width);
+    fragments.push(fragment);
+}
+
+/// Breaks the `items` into lines of at most `width` pixels (or a single line if the width is not definite)
+/// and positions the nodes of the paragraph. Texts continue on the line where the previous item ended, via
+/// the `leading` of their text layout and flow around the `floats` through the `holes` of their text layout.
+/// `origin` is the top left corner of the paragraph, relative to the border box of the `container_id`.
+/// Returns the size of the paragraph.
+fn layout_paragraph<T: GetTextLayout>(
+    container_id: NodeId,
+    items: &[InlineItem],
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    floats: &[PlacedFloat],
+    width: Number,
+    origin: LayoutPoint,
+) -> Size<f32> {
+
+    use self::InlineItem::*;
+
+    let max_width = width.to_option();
+    let line_right = max_width.unwrap_or(::std::f32::INFINITY);
+
+    // Floats relative to the top left corner of the paragraph
+    let floats = floats.iter().map(|float| PlacedFloat {
+        side: float.side,
+        bounds: LayoutRect::new(
+            LayoutPoint::new(float.bounds.origin.x - origin.x, float.bounds.origin.y - origin.y),
+            float.bounds.size,
+        ),
+    }).collect::<Vec<_>>();
+
+    // The position of a line is only an estimate while the paragraph is broken into lines,
+    // since the height of the current line can still grow
+    let line_top = |lines: &[LineBox], line_idx: usize| -> f32 {
+        lines[..line_idx].iter().map(|line| line.ascent + line.descent).sum()
+    };
+
+    let mut lines = vec![LineBox::default()];
+    let mut fragments = Vec::<Fragment>::new();
+    let mut text_layouts = BTreeMap::new();
+    let mut open_elements = Vec::<(NodeId, (usize, f32), usize)>::new();
+    let mut elements = Vec::<InlineElement>::new();
+
+    let container_font_size_px = get_text_layout_options(&node_styles[container_id], max_width).font_size_px;
+    let mut caret_x = free_space(&floats, 0.0, container_font_size_px, 0.0, line_right).0;
+
+    // 1. Break the items into lines
+
+    for item in items {
+
+        let current_line = lines.len() - 1;
+
+        match *item {
+            Start(node_id) => {
+                let style = &node_styles[node_id];
+                caret_x += style.margin.left.resolve(width).or_else(0.0);
+                open_elements.push((node_id, (current_line, caret_x), fragments.len()));
+                caret_x += style.padding.left.resolve(width).or_else(0.0) + style.border.left.resolve(width).or_else(0.0);
+            },
+            End(node_id) => {
+                let style = &node_styles[node_id];
+                caret_x += style.padding.right.resolve(width).or_else(0.0) + style.border.right.resolve(width).or_else(0.0);
+                if let Some((element_id, start, first_fragment)) = open_elements.pop() {
+                    debug_assert_eq!(element_id, node_id);
+                    elements.push(InlineElement {
+                        node_id,
+                        start,
+                        end: (current_line, caret_x),
+                        first_fragment,
+                        last_fragment: fragments.len(),
+                    });
+                }
+                caret_x += style.margin.right.resolve(width).or_else(0.0);
+            },
+            Text(node_id) => {
+
+                let text_style = &node_styles[node_id];
+                let mut text_layout_options = get_text_layout_options(text_style, max_width);
+                let font_size_px = text_layout_options.font_size_px;
+                let line_gap = font_size_px * LINE_GAP_EM * text_style.line_height.unwrap_or(1.0);
+
+                // The text layout skips the holes at the start of the line before adding the leading
+                let text_top = line_top(&lines, current_line);
+                let line_left = free_space(&floats, text_top, text_top + font_size_px, 0.0, line_right).0;
+                text_layout_options.leading = Some((caret_x - line_left).max(0.0));
+                text_layout_options.is_inline_fragment = true;
+
+                // A line of text is inside of a hole if its baseline is, so the holes are extended
+                // downwards by the font size. Holes of right floats reach to the end of the line,
+                // so that the text layout breaks the line instead of continuing behind the float.
+                text_layout_options.holes = floats.iter().map(|float| {
+                    let (hole_left, hole_right) = match float.side {
+                        Float::Right => (float.bounds.min_x(), float.bounds.max_x().max(line_right)),
+                        _ => (0.0, float.bounds.max_x()),
+                    };
+                    LayoutRect::new(
+                        LayoutPoint::new(hole_left, float.bounds.min_y() - text_top),
+                        LayoutSize::new(hole_right - hole_left, float.bounds.size.height + font_size_px),
+                    )
+                }).collect();
+
+                let inline_text_layout = match rect_contents.get_mut(&node_id) {
+                    Some(RectContent::Text(t)) => t.get_text_layout(&text_layout_options),
+                    _ => continue,
+                };
+
+                for (text_line_idx, text_line) in inline_text_layout.lines.iter().enumerate() {
+                    // The width of the first line includes the leading, the width of the
+                    // other lines includes the space that is covered by left floats
+                    let line_start = if text_line_idx == 0 {
+                        caret_x
+                    } else {
+                        lines.push(LineBox::default());
+                        let estimated_top = text_top + (font_size_px + line_gap) * text_line_idx as f32;
+                        free_space(&floats, estimated_top, estimated_top + font_size_px, 0.0, line_right).0
+                            .min(text_line.bounds.size.width)
+                    };
+                    push_fragment(&mut lines, &mut fragments, Fragment {
+                        node_id,
+                        text_line: Some(text_line_idx),
+                        line: current_line + text_line_idx,
+                        x: line_start,
+                        width: (text_line.bounds.size.width - line_start).max(0.0),
+                        height: font_size_px + line_gap,
+                        baseline: font_size_px,
+                        margin_top: 0.0,
+                    });
+                }
+
+                if let Some(last_line) = inline_text_layout.lines.last() {
+                    caret_x = last_line.bounds.size.width;
+                }
+
+                text_layouts.insert(node_id, (text_layout_options, inline_text_layout));
+            },
+            Atomic(node_id) => {
+
+                let atomic_style = &node_styles[node_id];
+                let margin = atomic_style.margin.map(|m| m.resolve(width).or_else(0.0));
+
+                compute_internal(
+                    node_id,
+                    node_hierarchy,
+                    node_styles,
+                    node_rects,
+                    resolved_text_layout_options,
+                    rect_contents,
+                    layout_cache,
+                    Size {
+                        width: atomic_style.size.width.resolve(width),
+                        height: atomic_style.size.height.resolve(Undefined),
+                    },
+                    // Inline boxes without a width shrink to fit their content
+                    Size {
+                        width: if atomic_style.size.width.is_defined() { width } else { Undefined },
+                        height: Undefined,
+                    },
+                    true,
+                );
+
+                let box_width = node_rects[node_id].size.width.unwrap_or_zero();
+                let box_height = node_rects[node_id].size.height.unwrap_or_zero();
+                let outer_width = box_width + margin.horizontal();
+                let outer_height = box_height + margin.vertical();
+
+                let box_top = line_top(&lines, current_line);
+                let (line_left, line_right) = free_space(&floats, box_top, box_top + outer_height, 0.0, line_right);
+                caret_x = caret_x.max(line_left);
+
+                if caret_x > line_left && caret_x + outer_width > line_right {
+                    lines.push(LineBox::default());
+                    let box_top = line_top(&lines, current_line + 1);
+                    caret_x = free_space(&floats, box_top, box_top + outer_height, 0.0, line_right).0;
+                }
+
+                // The bottom margin edge of the box is placed on the baseline
+                let box_line = lines.len() - 1;
+                push_fragment(&mut lines, &mut fragments, Fragment {
+                    node_id,
+                    text_line: None,
+                    line: box_line,
+                    x: caret_x + margin.left,
+                    width: box_width,
+                    height: box_height,
+                    baseline: box_height + margin.bottom,
+                    margin_top: margin.top,
+                });
+
+                caret_x += outer_width;
+            },
+        }
+
+        let current_line = lines.len() - 1;
+        lines[current_line].width = lines[current_line].width.max(caret_x);
+    }
+
+    // 2. Stack the lines and align them horizontally
+
+    let text_align = node_styles[container_id].text_align;
+    let mut line_top = origin.y;
+
+    // (baseline, start) of every line, relative to the container
+    let line_positions = lines.iter().map(|line| {
+        let line_height = line.ascent + line.descent;
+        let (_, right) = free_space(&floats, line_top - origin.y, line_top - origin.y + line_height, 0.0, line_right);
+        let free_width = max_width.map(|_| (right - line.width).max(0.0)).unwrap_or(0.0);
+        let shift = match text_align {
+            TextAlign::Left => 0.0,
+            TextAlign::Center => free_width / 2.0,
+            TextAlign::Right => free_width,
+        };
+        let baseline = line_top + line.ascent;
+        line_top = baseline + line.descent;
+        (baseline, origin.x + shift)
+    }).collect::<Vec<_>>();
+
+    let paragraph_size = Size {
+        width: lines.iter().map(|line| line.width).fold(0.0, f32::max),
+        height: line_top - origin.y,
+    };
+
+    let fragment_rect = |fragment: &Fragment| {
+        let (baseline, line_start) = line_positions[fragment.line];
+        LayoutRect::new(
+            LayoutPoint::new(line_start + fragment.x, baseline - fragment.baseline),
+            LayoutSize::new(fragment.width, fragment.height),
+        )
+    };
+
+    // 3. Position the nodes, first relative to the container
+
+    let mut node_bounds = BTreeMap::<NodeId, LayoutRect>::new();
+
+    for fragment in fragments.iter().filter(|fragment| fragment.text_line.is_none()) {
+        node_bounds.insert(fragment.node_id, fragment_rect(fragment));
+    }
+
+    for element in &elements {
+
+        let style = &node_styles[element.node_id];
+        let padding = style.padding.map(|p| p.resolve(width).or_else(0.0));
+        let border = style.border.map(|b| b.resolve(width).or_else(0.0));
+        let margin = style.margin.map(|m| m.resolve(width).or_else(0.0));
+
+        let (start_line, start_x) = element.start;
+        let (end_line, end_x) = element.end;
+        let start_x = line_positions[start_line].1 + start_x;
+        let end_x = line_positions[end_line].1 + end_x;
+
+        // An element that is broken across lines covers the full width of the lines in between
+        let (left, right) = if start_line == end_line {
+            (start_x, end_x)
+        } else {
+            (start_line..=end_line).fold((start_x, end_x), |(left, right), line_idx| {
+                let (_, line_start) = line_positions[line_idx];
+                (left.min(line_start), right.max(line_start + lines[line_idx].width))
+            })
+        };
+
+        let content_bounds = LayoutRect::union(fragments[element.first_fragment..element.last_fragment].iter().map(&fragment_rect));
+        let (top, bottom) = match content_bounds {
+            Some(bounds) => (bounds.origin.y, bounds.origin.y + bounds.size.height),
+            None => {
+                let (baseline, _) = line_positions[start_line];
+                (baseline - lines[start_line].ascent, baseline + lines[start_line].descent)
+            },
+        };
+
+        let top = top - padding.top - border.top;
+        let bottom = bottom + padding.bottom + border.bottom;
+        node_bounds.insert(element.node_id, LayoutRect::new(
+            LayoutPoint::new(left, top),
+            LayoutSize::new(right - left, bottom - top),
+        ));
+
+        let node = &mut node_rects[element.node_id];
+        node.margin = resolve_offsets(margin);
+        node.padding = resolve_offsets(padding);
+        node.border_widths = resolve_offsets(border);
+    }
+
+    // Lines of the texts are relative to the node that contains the text
+    for (node_id, (text_layout_options, mut inline_text_layout)) in text_layouts {
+
+        let node_origin = if node_id == container_id {
+            LayoutPoint::zero()
+        } else {
+            node_bounds.get(&node_id).map(|bounds| bounds.origin).unwrap_or(LayoutPoint::zero())
+        };
+
+        for fragment in fragments.iter().filter(|fragment| fragment.node_id == node_id) {
+            let (baseline, line_start) = line_positions[fragment.line];
+            if let Some(line) = fragment.text_line.and_then(|line_idx| inline_text_layout.lines.get_mut(line_idx)) {
+                line.bounds.origin.x = line_start - node_origin.x;
+                line.bounds.origin.y = baseline - node_origin.y;
+            }
+        }
+
+        let text_bounds = match node_bounds.get(&node_id) {
+            Some(bounds) => LayoutRect::new(LayoutPoint::zero(), bounds.size),
+            None => inline_text_layout.get_bounds(),
+        };
+
+        resolved_text_layout_options.insert(node_id, (text_layout_options, inline_text_layout, text_bounds));
+    }
+
+    // 4. Make the positions relative to the parent of each node
+
+    for (node_id, bounds) in &node_bounds {
+        let parent_origin = node_hierarchy[*node_id].parent
+            .and_then(|parent_id| node_bounds.get(&parent_id))
+            .map(|parent_bounds| parent_bounds.origin)
+            .unwrap_or(LayoutPoint::zero());
+
+        let node = &mut node_rects[*node_id];
+        node.origin.x = Defined(bounds.origin.x - parent_origin.x);
+        node.origin.y = Defined(bounds.origin.y - parent_origin.y);
+        node.size = RectSize {
+            width: Defined(bounds.size.width),
+            height: Defined(bounds.size.height),
+        };
+    }
+
+    paragraph_size
+}
+
+#[cfg(test)]
+mod flow_tests {
+
+    use azul_css::PixelValue;
+    use test_utils::{px, solve};
+    use super::*;
+
+    fn block(width: Dimension, height: Dimension) -> Style {
+        Style {
+            display: Display::Block,
+            size: Size { width, height },
+            font_size_px: PixelValue::px(10.0),
+            .. Style::default()
+        }
+    }
+
+    fn vertical_margin(style: Style, top: f32, bottom: f32) -> Style {
+        Style { margin: Offsets { top: px(top), bottom: px(bottom), .. style.margin }, .. style }
+    }
+
+    #[test]
+    fn test_blocks_are_stacked_vertically() {
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), block(Dimension::Auto, px(20.0))),
+            (Some(0), block(px(100.0), px(30.0))),
+            (Some(0), Style { margin: Offsets { left: Dimension::Auto, right: Dimension::Auto, .. Offsets::default() }, .. block(px(100.0), px(10.0)) }),
+            (Some(0), Style { display: Display::None, .. block(Dimension::Auto, px(50.0)) }),
+            (Some(0), block(Dimension::Auto, px(10.0))),
+        ], vec![]);
+        // blocks without a width fill their container
+        assert_eq!(rects[1], (0.0, 0.0, 300.0, 20.0));
+        assert_eq!(rects[2], (0.0, 20.0, 100.0, 30.0));
+        // margin: auto centers the block
+        assert_eq!(rects[3], (100.0, 50.0, 100.0, 10.0));
+        assert_eq!(rects[5], (0.0, 60.0, 300.0, 10.0));
+    }
+
+    #[test]
+    fn test_block_height_fits_content() {
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), Style { padding: Offsets { top: px(5.0), bottom: px(5.0), .. Offsets::default() }, .. block(Dimension::Auto, Dimension::Auto) }),
+            (Some(1), block(Dimension::Auto, px(20.0))),
+            (Some(1), block(Dimension::Auto, px(30.0))),
+        ], vec![]);
+        assert_eq!(rects[1], (0.0, 0.0, 300.0, 60.0));
+        assert_eq!(rects[2], (0.0, 5.0, 300.0, 20.0));
+        assert_eq!(rects[3], (0.0, 25.0, 300.0, 30.0));
+    }
+
+    #[test]
+    fn test_sibling_margins_collapse() {
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), vertical_margin(block(Dimension::Auto, px(20.0)), 0.0, 20.0)),
+            (Some(0), vertical_margin(block(Dimension::Auto, px(20.0)), 10.0, 0.0)),
+            (Some(0), vertical_margin(block(Dimension::Auto, px(20.0)), -5.0, -10.0)),
+            (Some(0), vertical_margin(block(Dimension::Auto, px(20.0)), 15.0, 0.0)),
+        ], vec![]);
+        // the larger margin wins
+        assert_eq!(rects[2].1, 40.0);
+        // negative margins are subtracted
+        assert_eq!(rects[3].1, 55.0);
+        assert_eq!(rects[4].1, 80.0);
+    }
+
+    #[test]
+    fn test_mixed_inline_paragraph() {
+        let span = Style {
+            display: Display::Inline,
+            padding: Offsets { left: px(2.0), right: px(2.0), .. Offsets::default() },
+            .. block(Dimension::Auto, Dimension::Auto)
+        };
+        let icon = Style { display: Display::Inline, .. block(px(16.0), px(16.0)) };
+
+        // <label style="width: 100px">text <span>text</span><icon/></label><div/>
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), block(px(100.0), Dimension::Auto)),
+            (Some(1), span),
+            (Some(1), icon),
+            (Some(0), vertical_margin(block(Dimension::Auto, px(20.0)), 10.0, 0.0)),
+        ], vec![
+            (1, vec![30.0, 30.0]),
+            (2, vec![20.0, 20.0, 20.0]),
+        ]);
+
+        // The span starts after the text of the label and wraps onto the second line,
+        // so it covers the full width of the first line
+        assert_eq!(rects[2], (0.0, 0.0, 97.0, 31.0));
+        // The icon continues the second line after the span and sits on its baseline
+        assert_eq!(rects[3], (52.0, 12.5, 16.0, 16.0));
+        // The paragraph is as high as its two lines
+        assert_eq!(rects[1], (0.0, 0.0, 100.0, 31.0));
+        assert_eq!(rects[4], (0.0, 41.0, 300.0, 20.0));
+    }
+
+    fn float(side: Float, width: f32, height: f32) -> Style {
+        Style { float: side, .. block(px(width), px(height)) }
+    }
+
+    #[test]
+    fn test_left_and_right_floats() {
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), block(Dimension::Auto, Dimension::Auto)),
+            (Some(1), float(Float::Left, 50.0, 20.0)),
+            (Some(1), float(Float::Right, 60.0, 30.0)),
+            (Some(1), float(Float::Left, 40.0, 10.0)),
+        ], vec![]);
+        assert_eq!(rects[2], (0.0, 0.0, 50.0, 20.0));
+        assert_eq!(rects[3], (240.0, 0.0, 60.0, 30.0));
+        // The second left float is placed next to the first one
+        assert_eq!(rects[4], (50.0, 0.0, 40.0, 10.0));
+        // The container grows to contain its floats
+        assert_eq!(rects[1], (0.0, 0.0, 300.0, 30.0));
+    }
+
+    #[test]
+    fn test_float_moves_down_until_it_fits() {
+        let rects = solve(vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), block(Dimension::Auto, Dimension::Auto)),
+            (Some(1), float(Float::Left, 200.0, 20.0)),
+            (Some(1), float(Float::Right, 50.0, 40.0)),
+            (Some(1), float(Float::Right, 150.0, 30.0)),
+        ], vec![]);
+        assert_eq!(rects[2], (0.0, 0.0, 200.0, 20.0));
+        assert_eq!(rects[3], (250.0, 0.0, 50.0, 40.0));
+        // Only 50px are free next to the earlier floats, so the float moves
+        // down below the left float, where it is placed next to the right float
+        assert_eq!(rects[4], (100.0, 20.0, 150.0, 30.0));
+        assert_eq!(rects[1], (0.0, 0.0, 300.0, 50.0));
+    }
+
+    #[test]
+    fn test_paragraph_flows_around_float() {
+        let icon = Style { display: Display::Inline, .. block(px(16.0), px(16.0)) };
+        let span = Style { display: Display::Inline, .. block(Dimension::Auto, Dimension::Auto) };
+
+        let nodes = |float_display: Display| vec![
+            (None, block(px(300.0), px(200.0))),
+            (Some(0), block(Dimension::Auto, Dimension::Auto)),
+            (Some(1), Style { display: float_display, .. float(Float::Left, 100.0, 30.0) }),
+            (Some(1), icon.clone()),
+            (Some(1), span.clone()),
+        ];
+
+        let without_float = solve(nodes(Display::None), vec![(4, vec![90.0; 7])]);
+        let with_float = solve(nodes(Display::Block), vec![(4, vec![90.0; 7])]);
+
+        // The first line starts next to the float
+        assert_eq!(without_float[3].0, 0.0);
+        assert_eq!(with_float[3].0, 100.0);
+        // Next to the float, only two words fit on a line instead of three,
+        // so the paragraph needs four lines instead of three
+        assert_eq!(without_float[1].3, 43.5);
+        assert_eq!(with_float[1].3, 56.0);
+    }
+}
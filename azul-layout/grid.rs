@@ -0,0 +1,749 @@
+//! CSS Grid layout: places the children of a `display: grid` node into
+//! the rows and columns of the grid and sizes the tracks.
+//!
+//! Simplifications compared to the [spec](https://www.w3.org/TR/css-grid-1/#layout-algorithm):
+//! there is no min-content measurement, so `auto` tracks can shrink down to zero and
+//! grow up to the max-content size of their items - `fr` tracks are sized like
+//! `minmax(0, 1fr)` if the container has a definite size. Items are always stretched
+//! horizontally, vertically they are aligned with `align-self` / `align-items`.
+
+use std::collections::BTreeMap;
+
+use azul_css::LayoutRect;
+use azul_core::{
+    ui_solver::{ResolvedTextLayoutOptions, InlineTextLayout},
+    id_tree::{NodeHierarchy, NodeDataContainer},
+    dom::NodeId,
+};
+
+use {
+    RectContent, GetTextLayout, LayoutCache,
+    algo::{compute_internal, layout_absolute_children, clear_text_layouts},
+    style::*,
+    number::{OrElse, MinMax, Number::{self, *}},
+    geometry::{Rect, RectSize, Offsets, Size},
+};
+
+#[derive(Debug)]
+struct GridItem {
+    node_id: NodeId,
+    /// First row / column of the item, `None` until the item is placed
+    row: Option<usize>,
+    column: Option<usize>,
+    row_span: usize,
+    column_span: usize,
+}
+
+/// Tracks which cells of the grid are already occupied during the auto-placement.
+/// The grid is stored in the direction of the `grid-auto-flow`: for `row` the
+/// "primary" tracks are the columns, the "secondary" tracks are the rows, which
+/// are added as necessary.
+struct OccupancyGrid {
+    primary_count: usize,
+    cells: Vec<Vec<bool>>,
+}
+
+impl OccupancyGrid {
+
+    fn fits(&self, secondary: usize, primary: usize, secondary_span: usize, primary_span: usize) -> bool {
+        primary + primary_span <= self.primary_count &&
+        (secondary..(secondary + secondary_span)).all(|s| {
+            self.cells.get(s).map(|row| !row[primary..(primary + primary_span)].iter().any(|c| *c)).unwrap_or(true)
+        })
+    }
+
+    fn occupy(&mut self, secondary: usize, primary: usize, secondary_span: usize, primary_span: usize) {
+        let primary_count = self.primary_count.max(primary + primary_span);
+        while self.cells.len() < secondary + secondary_span {
+            self.cells.push(vec![false; primary_count]);
+        }
+        for row in self.cells[secondary..(secondary + secondary_span)].iter_mut() {
+            row.resize(primary_count, false);
+            for cell in row[primary..(primary + primary_span)].iter_mut() {
+                *cell = true;
+            }
+        }
+    }
+}
+
+/// Lays out the children of a `display: grid` node. `node_inner_size` is the size of the content box
+/// of the grid container, `definite_height` its height if it is set explicitly (otherwise the rows are
+/// sized to fit their content). If `perform_layout` is false, only the size of the grid is computed,
+/// the items are not laid out. Returns the size of the grid, including the padding and border.
+pub(crate) fn compute_grid<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    node_inner_size: Size<Number>,
+    definite_height: Number,
+    padding_border: &Offsets<f32>,
+    perform_layout: bool,
+) -> Size<f32> {
+
+    let grid_style = &node_styles[node_id];
+
+    let column_gap = grid_style.gap.width.resolve(node_inner_size.width).or_else(0.0);
+    let row_gap = grid_style.gap.height.resolve(definite_height).or_else(0.0);
+
+    // 1. Place the items into the grid
+
+    let mut items = node_id
+        .children(node_hierarchy)
+        .filter(|child_id| node_styles[*child_id].display != Display::None)
+        .filter(|child_id| !node_styles[*child_id].position_type.is_out_of_flow())
+        .map(|child_id| {
+            let child_style = &node_styles[child_id];
+            let (row, row_span) = resolve_placement(child_style.grid_row, grid_style.grid_template_rows.len());
+            let (column, column_span) = resolve_placement(child_style.grid_column, grid_style.grid_template_columns.len());
+            GridItem { node_id: child_id, row, column, row_span, column_span }
+        })
+        .collect::<Vec<_>>();
+
+    let (row_count, column_count) = place_items(
+        &mut items,
+        grid_style.grid_auto_flow,
+        grid_style.grid_template_rows.len(),
+        grid_style.grid_template_columns.len(),
+    );
+
+    // Tracks that are not in the grid-template are implicit tracks, sized to fit their content
+    let get_tracks = |template: &[GridTrack], count: usize| {
+        (0..count).map(|idx| template.get(idx).cloned().unwrap_or(GridTrack::AUTO)).collect::<Vec<_>>()
+    };
+    let column_tracks = get_tracks(&grid_style.grid_template_columns, column_count);
+    let row_tracks = get_tracks(&grid_style.grid_template_rows, row_count);
+
+    // 2. Size the columns, using the width of the items as if they had infinite space
+
+    let column_contributions = items.iter().map(|item| {
+        let child_style = &node_styles[item.node_id];
+        let item_size = measure_item(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            Size {
+                width: child_style.size.width.resolve(Undefined),
+                height: child_style.size.height.resolve(Undefined),
+            },
+            Size { width: Undefined, height: Undefined },
+        );
+        let margin = child_style.margin.map(|m| m.resolve(node_inner_size.width).or_else(0.0));
+        let width = item_size.width.unwrap_or_zero() + margin.horizontal();
+        (item.column.unwrap_or(0), item.column_span, width)
+    }).collect::<Vec<_>>();
+
+    let column_sizes = size_tracks(&column_tracks, node_inner_size.width, column_gap, &column_contributions);
+
+    // 3. Size the rows, now that the width of every item is known
+
+    let row_contributions = items.iter().map(|item| {
+        let child_style = &node_styles[item.node_id];
+        let area_width = get_span_size(&column_sizes, column_gap, item.column.unwrap_or(0), item.column_span);
+        let margin = child_style.margin.map(|m| m.resolve(Defined(area_width)).or_else(0.0));
+        let item_size = measure_item(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            Size {
+                width: child_style.size.width.resolve(Defined(area_width)).or_else(Defined(area_width - margin.horizontal())),
+                height: child_style.size.height.resolve(definite_height),
+            },
+            Size { width: Defined(area_width), height: Undefined },
+        );
+        let height = item_size.height.unwrap_or_zero() + margin.vertical();
+        (item.row.unwrap_or(0), item.row_span, height)
+    }).collect::<Vec<_>>();
+
+    let row_sizes = size_tracks(&row_tracks, definite_height, row_gap, &row_contributions);
+
+    let grid_size = Size {
+        width: get_span_size(&column_sizes, column_gap, 0, column_sizes.len()) + padding_border.horizontal(),
+        height: get_span_size(&row_sizes, row_gap, 0, row_sizes.len()) + padding_border.vertical(),
+    };
+
+    if !perform_layout {
+        return grid_size;
+    }
+
+    // 4. Lay out every item in its grid area
+
+    let column_offsets = get_track_offsets(&column_sizes, column_gap, padding_border.left);
+    let row_offsets = get_track_offsets(&row_sizes, row_gap, padding_border.top);
+
+    for item in &items {
+
+        let child_style = &node_styles[item.node_id];
+        let (column, row) = (item.column.unwrap_or(0), item.row.unwrap_or(0));
+        let area_size = Size {
+            width: get_span_size(&column_sizes, column_gap, column, item.column_span),
+            height: get_span_size(&row_sizes, row_gap, row, item.row_span),
+        };
+
+        let margin = child_style.margin.map(|m| m.resolve(Defined(area_size.width)).or_else(0.0));
+        let align_self = child_style.align_self(grid_style);
+
+        let width = child_style.size.width.resolve(Defined(area_size.width))
+            .maybe_max(child_style.min_size.width.resolve(Defined(area_size.width)))
+            .maybe_min(child_style.max_size.width.resolve(Defined(area_size.width)));
+        let height = child_style.size.height.resolve(Defined(area_size.height))
+            .maybe_max(child_style.min_size.height.resolve(Defined(area_size.height)))
+            .maybe_min(child_style.max_size.height.resolve(Defined(area_size.height)));
+
+        let stretch_width = !width.is_defined();
+        let stretch_height = !height.is_defined() && align_self == AlignSelf::Stretch;
+
+        let item_size = Size {
+            width: if stretch_width { Defined(area_size.width - margin.horizontal()) } else { width },
+            height: if stretch_height { Defined(area_size.height - margin.vertical()) } else { height },
+        };
+
+        compute_internal(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            layout_cache,
+            item_size,
+            area_size.map(Number::Defined),
+            true,
+        );
+
+        let node = &mut node_rects[item.node_id];
+        if stretch_width {
+            node.size.width = item_size.width;
+        }
+        if stretch_height {
+            node.size.height = item_size.height;
+        }
+
+        let free_height = area_size.height - margin.vertical() - node.size.height.unwrap_or_zero();
+        let align_offset = match align_self {
+            AlignSelf::Center => free_height / 2.0,
+            AlignSelf::FlexEnd => free_height,
+            _ => 0.0,
+        };
+
+        // position: relative
+        let relative_offsets = child_style.relative_offsets();
+        let position_offset_x = relative_offsets.left.resolve(Defined(area_size.width)).or_else(0.0)
+            - relative_offsets.right.resolve(Defined(area_size.width)).or_else(0.0);
+        let position_offset_y = relative_offsets.top.resolve(Defined(area_size.height)).or_else(0.0)
+            - relative_offsets.bottom.resolve(Defined(area_size.height)).or_else(0.0);
+
+        node.origin.x = Defined(column_offsets[column] + margin.left + position_offset_x);
+        node.origin.y = Defined(row_offsets[row] + margin.top + align_offset + position_offset_y);
+    }
+
+    // Absolutely positioned children are positioned relative to the padding box of the grid
+    layout_absolute_children(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        grid_size,
+        padding_border,
+    );
+
+    grid_size
+}
+
+/// Measures the size of a grid item (without laying out its children). The `layout_cache` measures
+/// every item at most once per constraint - since a nested grid measures its own items again,
+/// measuring them repeatedly would make the layout exponential in the depth of nested grids.
+fn measure_item<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<NodeId, (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect)>,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    layout_cache: &mut LayoutCache,
+    node_size: Size<Number>,
+    parent_size: Size<Number>,
+) -> RectSize {
+    compute_internal(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        layout_cache,
+        node_size,
+        parent_size,
+        false,
+    );
+    clear_text_layouts(node_id, node_hierarchy, resolved_text_layout_options);
+
+    node_rects[node_id].size
+}
+
+/// Resolves the `grid-row` or `grid-column` of an item against the explicit grid (`explicit_tracks`).
+/// Returns the first track of the item (`None` if the item has to be auto-placed) and the number
+/// of tracks it spans. Lines before the start of the grid are clamped to the first line.
+fn resolve_placement(placement: GridPlacement, explicit_tracks: usize) -> (Option<usize>, usize) {
+
+    use self::GridLine::*;
+
+    // line 1 is the start of the first track, line -1 is the end of the last explicit track
+    let line_to_track = |line: i32| -> i32 {
+        if line > 0 { line - 1 } else { explicit_tracks as i32 + 1 + line }
+    };
+
+    let clamp = |track: i32| track.max(0) as usize;
+
+    match (placement.start, placement.end) {
+        (Line(start), Line(end)) => {
+            let (start, end) = (line_to_track(start), line_to_track(end));
+            let (start, end) = if end < start { (end, start) } else { (start, end) };
+            let start = clamp(start);
+            (Some(start), (end - start as i32).max(1) as usize)
+        },
+        (Line(start), Span(span)) => (Some(clamp(line_to_track(start))), span.max(1) as usize),
+        (Line(start), Auto) => (Some(clamp(line_to_track(start))), 1),
+        (Span(span), Line(end)) => (Some(clamp(line_to_track(end) - span as i32)), span.max(1) as usize),
+        (Auto, Line(end)) => (Some(clamp(line_to_track(end) - 1)), 1),
+        (Span(span), _) | (Auto, Span(span)) => (None, span.max(1) as usize),
+        (Auto, Auto) => (None, 1),
+    }
+}
+
+/// Places all items without a definite position (sparse auto-placement). Returns the number
+/// of rows and columns of the grid, including the implicit tracks that had to be added.
+fn place_items(items: &mut [GridItem], auto_flow: GridAutoFlow, explicit_rows: usize, explicit_columns: usize) -> (usize, usize) {
+
+    // Work on (secondary, primary) positions, so that both directions can use the same code
+    let get = |item: &GridItem| match auto_flow {
+        GridAutoFlow::Row => (item.row, item.column, item.row_span, item.column_span),
+        GridAutoFlow::Column => (item.column, item.row, item.column_span, item.row_span),
+    };
+    let set = |item: &mut GridItem, secondary: usize, primary: usize| match auto_flow {
+        GridAutoFlow::Row => { item.row = Some(secondary); item.column = Some(primary); },
+        GridAutoFlow::Column => { item.column = Some(secondary); item.row = Some(primary); },
+    };
+    let (explicit_secondary, explicit_primary) = match auto_flow {
+        GridAutoFlow::Row => (explicit_rows, explicit_columns),
+        GridAutoFlow::Column => (explicit_columns, explicit_rows),
+    };
+
+    // The primary tracks are not added during the auto-placement, so the grid
+    // has to be large enough for all items from the start
+    let primary_count = items.iter().map(|item| {
+        let (_, primary, _, primary_span) = get(item);
+        primary.unwrap_or(0) + primary_span
+    }).fold(explicit_primary.max(1), usize::max);
+
+    let mut grid = OccupancyGrid { primary_count, cells: Vec::new() };
+
+    // 1. Items with a definite position in both directions
+    for item in items.iter() {
+        if let (Some(secondary), Some(primary), secondary_span, primary_span) = get(item) {
+            grid.occupy(secondary, primary, secondary_span, primary_span);
+        }
+    }
+
+    // 2. Items that are locked to a row (or column for grid-auto-flow: column)
+    let mut row_cursors = BTreeMap::new();
+    for item in items.iter_mut() {
+        if let (Some(secondary), None, secondary_span, primary_span) = get(item) {
+            let cursor = row_cursors.entry(secondary).or_insert(0);
+            while !grid.fits(secondary, *cursor, secondary_span, primary_span) && *cursor + primary_span <= primary_count {
+                *cursor += 1;
+            }
+            // The item doesn't fit into the row anymore, overlap the last cells
+            let primary = (*cursor).min(primary_count.saturating_sub(primary_span));
+            grid.occupy(secondary, primary, secondary_span, primary_span);
+            set(item, secondary, primary);
+            *cursor = primary + primary_span;
+        }
+    }
+
+    // 3. All other items, in document order
+    let (mut cursor_secondary, mut cursor_primary) = (0, 0);
+    for item in items.iter_mut() {
+        match get(item) {
+            (None, Some(primary), secondary_span, primary_span) => {
+                if primary < cursor_primary {
+                    cursor_secondary += 1;
+                }
+                cursor_primary = primary;
+                while !grid.fits(cursor_secondary, primary, secondary_span, primary_span) {
+                    cursor_secondary += 1;
+                }
+                grid.occupy(cursor_secondary, primary, secondary_span, primary_span);
+                set(item, cursor_secondary, primary);
+            },
+            (None, None, secondary_span, primary_span) => {
+                while !grid.fits(cursor_secondary, cursor_primary, secondary_span, primary_span) {
+                    cursor_primary += 1;
+                    if cursor_primary + primary_span > primary_count {
+                        cursor_secondary += 1;
+                        cursor_primary = 0;
+                    }
+                }
+                grid.occupy(cursor_secondary, cursor_primary, secondary_span, primary_span);
+                set(item, cursor_secondary, cursor_primary);
+            },
+            _ => { },
+        }
+    }
+
+    let secondary_count = explicit_secondary.max(grid.cells.len());
+    let primary_count = grid.cells.iter().map(|row| row.len()).fold(primary_count, usize::max);
+
+    match auto_flow {
+        GridAutoFlow::Row => (secondary_count, primary_count),
+        GridAutoFlow::Column => (primary_count, secondary_count),
+    }
+}
+
+/// Sizes the tracks of one direction of the grid, `contributions` are the
+/// `(first track, number of tracks, size)` of all items in that direction.
+/// `available_space` is the size of the content box of the grid, if it is definite.
+fn size_tracks(tracks: &[GridTrack], available_space: Number, gap: f32, contributions: &[(usize, usize, f32)]) -> Vec<f32> {
+
+    use self::TrackSizingFunction::*;
+
+    // Percentages of an indefinite size are treated as auto
+    let resolve = |function: TrackSizingFunction| match function {
+        Fixed(d) => match d.resolve(available_space) {
+            Defined(size) => Fixed(Dimension::Pixels(size.max(0.0))),
+            Undefined => Auto,
+        },
+        other => other,
+    };
+    let fixed_size = |function: TrackSizingFunction| match function {
+        Fixed(Dimension::Pixels(size)) => Some(size),
+        _ => None,
+    };
+
+    let tracks = tracks.iter().map(|track| GridTrack { min: resolve(track.min), max: resolve(track.max) }).collect::<Vec<_>>();
+    let is_flex = |track: &GridTrack| match track.max { Flex(_) => true, _ => false };
+    let total_gap = gap * tracks.len().saturating_sub(1) as f32;
+
+    let mut base_sizes = tracks.iter().map(|t| fixed_size(t.min).unwrap_or(0.0)).collect::<Vec<_>>();
+    // size that the content of the track would like to have
+    let mut content_sizes = vec![0.0_f32; tracks.len()];
+
+    // Items spanning multiple tracks are handled last, so that they
+    // only grow the tracks if the single-span items aren't large enough
+    let mut contributions = contributions.to_vec();
+    contributions.sort_by_key(|(_, span, _)| *span);
+
+    for (start, span, size) in contributions {
+        let spanned = start..(start + span).min(tracks.len());
+        if span == 1 {
+            content_sizes[start] = content_sizes[start].max(size);
+            continue;
+        }
+        if tracks[spanned.clone()].iter().any(&is_flex) {
+            continue;
+        }
+        let planned = spanned.clone().map(|idx| fixed_size(tracks[idx].max).unwrap_or(content_sizes[idx])).sum::<f32>();
+        let auto_tracks = spanned.clone().filter(|idx| tracks[*idx].max == Auto).collect::<Vec<_>>();
+        let extra = size - planned - gap * (span - 1) as f32;
+        if extra > 0.0 && !auto_tracks.is_empty() {
+            for idx in &auto_tracks {
+                content_sizes[*idx] += extra / auto_tracks.len() as f32;
+            }
+        }
+    }
+
+    // Maximum size of every non-flexible track
+    let growth_limits = tracks.iter().enumerate().map(|(idx, track)| match track.max {
+        Fixed(_) => fixed_size(track.max).unwrap_or(0.0).max(base_sizes[idx]),
+        _ => content_sizes[idx].max(base_sizes[idx]),
+    }).collect::<Vec<_>>();
+
+    match available_space {
+        Defined(available) => {
+            // Distribute the free space equally to all tracks until they reach their growth limit
+            loop {
+                let free_space = available - total_gap - base_sizes.iter().sum::<f32>();
+                let growable = (0..tracks.len())
+                    .filter(|idx| !is_flex(&tracks[*idx]) && base_sizes[*idx] < growth_limits[*idx])
+                    .collect::<Vec<_>>();
+                if free_space <= 0.0 || growable.is_empty() {
+                    break;
+                }
+                let share = free_space / growable.len() as f32;
+                for idx in growable {
+                    base_sizes[idx] = (base_sizes[idx] + share).min(growth_limits[idx]);
+                }
+            }
+        },
+        Undefined => {
+            for (idx, track) in tracks.iter().enumerate() {
+                if !is_flex(track) {
+                    base_sizes[idx] = growth_limits[idx];
+                }
+            }
+        },
+    }
+
+    // Flexible tracks take a fraction of the remaining space
+    let flex_tracks = (0..tracks.len()).filter(|idx| is_flex(&tracks[*idx])).collect::<Vec<_>>();
+    let flex_factor = |idx: usize| match tracks[idx].max { Flex(f) => f, _ => 0.0 };
+
+    if !flex_tracks.is_empty() {
+        let fr_size = match available_space {
+            Defined(available) => {
+                let non_flex_size = (0..tracks.len()).filter(|idx| !is_flex(&tracks[*idx])).map(|idx| base_sizes[idx]).sum::<f32>();
+                let mut leftover = available - total_gap - non_flex_size;
+                let mut flexible = flex_tracks.clone();
+                // Tracks whose minimum is larger than their share of the space are treated as inflexible
+                loop {
+                    let flex_sum = flexible.iter().map(|idx| flex_factor(*idx)).sum::<f32>().max(1.0);
+                    let fr_size = leftover.max(0.0) / flex_sum;
+                    let inflexible = flexible.iter().cloned().filter(|idx| base_sizes[*idx] > fr_size * flex_factor(*idx)).collect::<Vec<_>>();
+                    if inflexible.is_empty() {
+                        break fr_size;
+                    }
+                    for idx in inflexible {
+                        leftover -= base_sizes[idx];
+                        flexible.retain(|i| *i != idx);
+                    }
+                }
+            },
+            // Without a definite size, the tracks are as large as necessary to fit the content
+            Undefined => flex_tracks.iter().map(|idx| {
+                let factor = flex_factor(*idx);
+                if factor > 0.0 { content_sizes[*idx].max(base_sizes[*idx]) / factor.max(1.0) } else { 0.0 }
+            }).fold(0.0, f32::max),
+        };
+
+        for idx in flex_tracks {
+            base_sizes[idx] = base_sizes[idx].max(fr_size * flex_factor(idx));
+        }
+    } else if let Defined(available) = available_space {
+        // Stretch the auto tracks to fill the remaining space
+        let free_space = available - total_gap - base_sizes.iter().sum::<f32>();
+        let auto_tracks = (0..tracks.len()).filter(|idx| tracks[*idx].max == Auto).collect::<Vec<_>>();
+        if free_space > 0.0 && !auto_tracks.is_empty() {
+            for idx in &auto_tracks {
+                base_sizes[*idx] += free_space / auto_tracks.len() as f32;
+            }
+        }
+    }
+
+    base_sizes
+}
+
+/// Size of `span` tracks starting at `start`, including the gaps between them
+fn get_span_size(sizes: &[f32], gap: f32, start: usize, span: usize) -> f32 {
+    let end = (start + span).min(sizes.len());
+    if start >= end {
+        return 0.0;
+    }
+    sizes[start..end].iter().sum::<f32>() + gap * (end - start - 1) as f32
+}
+
+/// Start position of every track, relative to the border box of the grid
+fn get_track_offsets(sizes: &[f32], gap: f32, start: f32) -> Vec<f32> {
+    let mut offset = start;
+    sizes.iter().map(|size| {
+        let track_offset = offset;
+        offset += size + gap;
+        track_offset
+    }).collect()
+}
+
+#[cfg(test)]
+mod grid_tests {
+
+    use std::{rc::Rc, cell::Cell};
+    use azul_css::{LayoutPoint, LayoutSize};
+    use azul_core::ui_solver::InlineTextLine;
+    use test_utils::{px, solve, solve_test_layout};
+    use RectContent;
+    use super::*;
+
+    /// Text that is always 50x10 pixels large and counts how often it is laid out
+    struct CountedText(Rc<Cell<usize>>);
+
+    impl GetTextLayout for CountedText {
+        fn get_text_layout(&mut self, _: &ResolvedTextLayoutOptions) -> InlineTextLayout {
+            self.0.set(self.0.get() + 1);
+            InlineTextLayout {
+                lines: vec![InlineTextLine {
+                    bounds: LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(50.0, 10.0)),
+                    word_start: 0,
+                    word_end: 1,
+                }],
+            }
+        }
+    }
+
+    fn fixed(size: f32) -> GridTrack {
+        GridTrack { min: TrackSizingFunction::Fixed(px(size)), max: TrackSizingFunction::Fixed(px(size)) }
+    }
+
+    fn fr(fraction: f32) -> GridTrack {
+        GridTrack { min: TrackSizingFunction::Auto, max: TrackSizingFunction::Flex(fraction) }
+    }
+
+    fn minmax(min: TrackSizingFunction, max: TrackSizingFunction) -> GridTrack {
+        GridTrack { min, max }
+    }
+
+    fn grid(columns: Vec<GridTrack>, rows: Vec<GridTrack>, gap: (f32, f32)) -> Style {
+        Style {
+            display: Display::Grid,
+            size: Size { width: px(300.0), height: px(200.0) },
+            grid_template_columns: columns,
+            grid_template_rows: rows,
+            gap: Size { width: px(gap.0), height: px(gap.1) },
+            .. Style::default()
+        }
+    }
+
+    fn item(column: (GridLine, GridLine), row: (GridLine, GridLine)) -> Style {
+        Style {
+            grid_column: GridPlacement { start: column.0, end: column.1 },
+            grid_row: GridPlacement { start: row.0, end: row.1 },
+            .. Style::default()
+        }
+    }
+
+    fn auto_item() -> Style {
+        item((GridLine::Auto, GridLine::Auto), (GridLine::Auto, GridLine::Auto))
+    }
+
+    #[test]
+    fn test_fixed_and_fr_tracks() {
+        let rects = solve(vec![
+            (None, grid(vec![fixed(100.0), fr(1.0), fr(2.0)], vec![fixed(50.0), fr(1.0)], (0.0, 0.0))),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+        ], vec![]);
+        assert_eq!(rects[1], (0.0, 0.0, 100.0, 50.0));
+        assert_eq!(rects[2], (100.0, 0.0, 200.0 / 3.0, 50.0));
+        assert_eq!(rects[3], (100.0 + 200.0 / 3.0, 0.0, 400.0 / 3.0, 50.0));
+        // The fr row takes the remaining height
+        assert_eq!(rects[4], (0.0, 50.0, 100.0, 150.0));
+    }
+
+    #[test]
+    fn test_minmax_tracks() {
+        use self::TrackSizingFunction::*;
+
+        // The second track would be smaller than its minimum as 1fr, so it is treated as a fixed track
+        let rects = solve(vec![
+            (None, Style { size: Size { width: px(250.0), height: px(200.0) }, .. grid(vec![minmax(Fixed(px(50.0)), Flex(1.0)), minmax(Fixed(px(150.0)), Flex(1.0))], vec![], (0.0, 0.0)) }),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+        ], vec![]);
+        assert_eq!((rects[1].0, rects[1].2), (0.0, 100.0));
+        assert_eq!((rects[2].0, rects[2].2), (100.0, 150.0));
+
+        // Without flexible tracks, the tracks grow up to their maximum
+        let rects = solve(vec![
+            (None, grid(vec![minmax(Fixed(px(20.0)), Fixed(px(80.0))), minmax(Fixed(px(20.0)), Fixed(px(80.0)))], vec![], (0.0, 0.0))),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+        ], vec![]);
+        assert_eq!((rects[1].0, rects[1].2), (0.0, 80.0));
+        assert_eq!((rects[2].0, rects[2].2), (80.0, 80.0));
+    }
+
+    #[test]
+    fn test_line_placement() {
+        use self::GridLine::*;
+
+        let rects = solve(vec![
+            (None, grid(vec![fixed(100.0), fixed(100.0), fixed(100.0)], vec![fixed(50.0), fixed(50.0)], (0.0, 0.0))),
+            // grid-column: 2 / 4; grid-row: 2
+            (Some(0), item((Line(2), Line(4)), (Line(2), Auto))),
+            // grid-column: span 2 / -1
+            (Some(0), item((Span(2), Line(-1)), (Line(1), Auto))),
+            // grid-row: -2 / -1 (the last explicit row), placed in the first free column
+            (Some(0), item((Auto, Auto), (Line(-2), Line(-1)))),
+        ], vec![]);
+        assert_eq!(rects[1], (100.0, 50.0, 200.0, 50.0));
+        assert_eq!(rects[2], (100.0, 0.0, 200.0, 50.0));
+        assert_eq!(rects[3], (0.0, 50.0, 100.0, 50.0));
+    }
+
+    #[test]
+    fn test_auto_placement() {
+        use self::GridLine::*;
+
+        let rects = solve(vec![
+            (None, grid(vec![fixed(100.0), fixed(100.0), fixed(100.0)], vec![fixed(50.0)], (0.0, 0.0))),
+            (Some(0), auto_item()),
+            // doesn't fit into the rest of the first row
+            (Some(0), item((Span(3), Auto), (Auto, Auto))),
+            (Some(0), auto_item()),
+            (Some(0), item((Line(1), Auto), (Auto, Auto))),
+        ], vec![]);
+        assert_eq!((rects[1].0, rects[1].1), (0.0, 0.0));
+        assert_eq!((rects[2].0, rects[2].1, rects[2].2), (0.0, 50.0, 300.0));
+        // the implicit rows are auto-sized, so they stretch to fill the grid
+        assert_eq!((rects[3].0, rects[3].1), (0.0, 100.0));
+        // the column is before the cursor, so the item is placed in the next row
+        assert_eq!((rects[4].0, rects[4].1), (0.0, 150.0));
+
+        let rects = solve(vec![
+            (None, Style { grid_auto_flow: GridAutoFlow::Column, .. grid(vec![fixed(100.0)], vec![fixed(50.0), fixed(50.0)], (0.0, 0.0)) }),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+        ], vec![]);
+        assert_eq!((rects[1].0, rects[1].1), (0.0, 0.0));
+        assert_eq!((rects[2].0, rects[2].1), (0.0, 50.0));
+        assert_eq!((rects[3].0, rects[3].1), (100.0, 0.0));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let rects = solve(vec![
+            (None, grid(vec![fr(1.0), fr(1.0)], vec![fixed(40.0), fixed(40.0)], (20.0, 10.0))),
+            (Some(0), auto_item()),
+            (Some(0), auto_item()),
+            (Some(0), item((GridLine::Auto, GridLine::Auto), (GridLine::Line(1), GridLine::Span(2)))),
+        ], vec![]);
+        // the item that is locked to the first row is placed before the other items
+        assert_eq!(rects[1], (160.0, 0.0, 140.0, 40.0));
+        assert_eq!(rects[2], (160.0, 50.0, 140.0, 40.0));
+        // spanning two rows includes the row gap
+        assert_eq!(rects[3], (0.0, 0.0, 140.0, 90.0));
+    }
+
+    #[test]
+    fn test_nested_grids_are_measured_once_per_constraint() {
+        const DEPTH: usize = 8;
+
+        let layout_count = Rc::new(Cell::new(0));
+        let auto_grid = Style { display: Display::Grid, .. Style::default() };
+        let mut nodes = vec![(None, grid(vec![fr(1.0)], vec![], (0.0, 0.0)))];
+        nodes.extend((1..DEPTH).map(|idx| (Some(idx - 1), auto_grid.clone())));
+        nodes.push((Some(DEPTH - 1), Style::default()));
+
+        let mut rect_contents = BTreeMap::new();
+        rect_contents.insert(NodeId::new(DEPTH), RectContent::Text(CountedText(layout_count.clone())));
+
+        let rects = solve_test_layout(nodes, rect_contents, (300.0, 200.0));
+        assert_eq!(rects[DEPTH].2, 300.0);
+        // Without caching the measurements, the text would be laid out hundreds of times
+        assert!(layout_count.get() < 10 * DEPTH);
+    }
+}
@@ -33,15 +33,24 @@ use azul_core::{
 use style::Style;
 
 mod algo;
+mod cache;
+mod grid;
+mod flow;
 mod number;
 mod geometry;
 
 pub mod style;
 pub use geometry::{Size, Offsets};
 pub use number::Number;
+pub use cache::LayoutCache;
 
 pub trait GetStyle { fn get_style(&self) -> Style; }
-pub trait GetTextLayout { fn get_text_layout(&mut self, text_layout_options: &ResolvedTextLayoutOptions) -> InlineTextLayout; }
+pub trait GetTextLayout {
+    fn get_text_layout(&mut self, text_layout_options: &ResolvedTextLayoutOptions) -> InlineTextLayout;
+    /// Hash of the text and the font, used by the `LayoutCache` to detect if the text changed since the last
+    /// layout - if the text can't be hashed, the text (and its parents) are laid out again in every layout pass
+    fn get_text_hash(&self) -> Option<u64> { None }
+}
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct SolvedUi {
@@ -77,11 +86,25 @@ impl<T: GetTextLayout> RectContent<T> {
 }
 
 impl SolvedUi {
+
     pub fn new<T: GetStyle, U: GetTextLayout>(
+        bounds: LayoutRect,
+        node_hierarchy: &NodeHierarchy,
+        display_rects: &NodeDataContainer<T>,
+        rect_contents: BTreeMap<NodeId, RectContent<U>>,
+    ) -> Self {
+        Self::new_cached(bounds, node_hierarchy, display_rects, rect_contents, &mut LayoutCache::disabled())
+    }
+
+    /// Same as `new`, but reuses the layouts of the last call for all subtrees where neither the styles,
+    /// the contents nor the available space changed. If the DOM was replaced since the last call,
+    /// the `layout_cache` has to be updated with `LayoutCache::apply_dom_diff` first.
+    pub fn new_cached<T: GetStyle, U: GetTextLayout>(
         bounds: LayoutRect,
         node_hierarchy: &NodeHierarchy,
         display_rects: &NodeDataContainer<T>,
         mut rect_contents: BTreeMap<NodeId, RectContent<U>>,
+        layout_cache: &mut LayoutCache,
     ) -> Self {
 
         let styles = display_rects.transform(|node, node_id| {
@@ -93,10 +116,13 @@ impl SolvedUi {
 
             let mut style = node.get_style();
             style.aspect_ratio = image_aspect_ratio;
+            style.resolve_viewport_units(bounds.size.width, bounds.size.height);
             style
         });
 
-        let mut solved_rects = algo::compute(NodeId::ZERO, node_hierarchy, &styles, &mut rect_contents, bounds.size);
+        layout_cache.begin_layout(node_hierarchy, &styles, &rect_contents);
+
+        let mut solved_rects = algo::compute(NodeId::ZERO, node_hierarchy, &styles, &mut rect_contents, layout_cache, bounds.size);
 
         // Offset all layouted rectangles by the origin of the bounds
         let origin_x = bounds.origin.x;
@@ -109,3 +135,155 @@ impl SolvedUi {
         SolvedUi { solved_rects }
     }
 }
+
+#[cfg(test)]
+mod test_utils {
+
+    use super::*;
+    use azul_css::{LayoutPoint, LayoutSize};
+    use azul_core::{id_tree::Node, ui_solver::InlineTextLine};
+    use style::Dimension;
+
+    pub(crate) struct TestStyle(pub(crate) Style);
+
+    impl GetStyle for TestStyle {
+        fn get_style(&self) -> Style { self.0.clone() }
+    }
+
+    /// Text made of words with the given widths, separated by 5px spaces. Every line is as
+    /// high as the font size and the width of a line includes the leading of the first line.
+    /// Like the text layout of `azul`, the caret skips the holes at the start of a line and
+    /// a word that would end in a hole that reaches the end of the line starts a new line.
+    pub(crate) struct TestText(pub(crate) Vec<f32>);
+
+    impl GetTextLayout for TestText {
+        fn get_text_layout(&mut self, text_layout_options: &ResolvedTextLayoutOptions) -> InlineTextLayout {
+
+            const SPACE_WIDTH: f32 = 5.0;
+
+            let font_size_px = text_layout_options.font_size_px;
+            let max_width = text_layout_options.max_horizontal_width;
+
+            // Hole that contains the caret on the baseline of the given line
+            let get_hole = |caret: f32, line_idx: usize| text_layout_options.holes.iter()
+                .find(|hole| hole.contains(&LayoutPoint::new(caret, font_size_px * (line_idx + 1) as f32)))
+                .cloned();
+            let reaches_line_end = |hole: &LayoutRect| max_width.map(|max_width| hole.max_x() >= max_width) == Some(true);
+
+            let mut lines = Vec::new();
+            let mut word_start = 0;
+            let mut caret = 0.0;
+
+            macro_rules! skip_holes {() => ({
+                while let Some(hole) = get_hole(caret, lines.len()) {
+                    if reaches_line_end(&hole) {
+                        lines.push((word_start, word_start, 0.0));
+                        caret = 0.0;
+                    } else {
+                        caret = hole.max_x();
+                    }
+                }
+            })}
+
+            skip_holes!();
+            let mut line_start = caret;
+            caret += text_layout_options.leading.unwrap_or(0.0);
+            skip_holes!();
+
+            for (word_idx, word_width) in self.0.iter().enumerate() {
+                let overflows = max_width.map(|max_width| caret + word_width > max_width) == Some(true);
+                let ends_in_hole = get_hole(caret + word_width, lines.len()).map(|hole| reaches_line_end(&hole)) == Some(true);
+                if caret > line_start && (overflows || ends_in_hole) {
+                    lines.push((word_start, word_idx, caret));
+                    word_start = word_idx;
+                    caret = 0.0;
+                    skip_holes!();
+                    line_start = caret;
+                }
+                caret += word_width;
+                if word_idx + 1 < self.0.len() || text_layout_options.is_inline_fragment {
+                    caret += SPACE_WIDTH;
+                }
+            }
+            lines.push((word_start, self.0.len(), caret));
+
+            InlineTextLayout {
+                lines: lines.into_iter().enumerate().map(|(line_idx, (word_start, word_end, width))| InlineTextLine {
+                    bounds: LayoutRect::new(
+                        LayoutPoint::new(0.0, font_size_px * line_idx as f32),
+                        LayoutSize::new(width, font_size_px),
+                    ),
+                    word_start,
+                    word_end,
+                }).collect(),
+            }
+        }
+    }
+
+    /// Builds the hierarchy from the parent of every node - the nodes have to be in tree order
+    pub(crate) fn get_test_hierarchy(parents: &[Option<usize>]) -> NodeHierarchy {
+        let mut nodes = parents.iter().map(|parent| Node {
+            parent: parent.map(NodeId::new),
+            previous_sibling: None,
+            next_sibling: None,
+            first_child: None,
+            last_child: None,
+        }).collect::<Vec<_>>();
+
+        for (idx, parent) in parents.iter().enumerate() {
+            let parent = match parent {
+                Some(s) => *s,
+                None => continue,
+            };
+            if let Some(previous_sibling) = nodes[parent].last_child {
+                nodes[previous_sibling.index()].next_sibling = Some(NodeId::new(idx));
+                nodes[idx].previous_sibling = Some(previous_sibling);
+            } else {
+                nodes[parent].first_child = Some(NodeId::new(idx));
+            }
+            nodes[parent].last_child = Some(NodeId::new(idx));
+        }
+
+        NodeHierarchy { internal: nodes }
+    }
+
+    /// Lays out the nodes (given as `(parent, style)` in tree order) in a window of the given size
+    /// and returns the `(x, y, width, height)` of every node
+    pub(crate) fn solve_test_layout<T: GetTextLayout>(
+        nodes: Vec<(Option<usize>, Style)>,
+        rect_contents: BTreeMap<NodeId, RectContent<T>>,
+        window_size: (f32, f32),
+    ) -> Vec<(f32, f32, f32, f32)> {
+        solve_test_layout_cached(nodes, rect_contents, window_size, &mut LayoutCache::disabled())
+    }
+
+    /// Same as `solve_test_layout`, but reuses the layouts of the last pass from the `layout_cache`
+    pub(crate) fn solve_test_layout_cached<T: GetTextLayout>(
+        nodes: Vec<(Option<usize>, Style)>,
+        rect_contents: BTreeMap<NodeId, RectContent<T>>,
+        window_size: (f32, f32),
+        layout_cache: &mut LayoutCache,
+    ) -> Vec<(f32, f32, f32, f32)> {
+        let node_hierarchy = get_test_hierarchy(&nodes.iter().map(|(parent, _)| *parent).collect::<Vec<_>>());
+        let styles = NodeDataContainer { internal: nodes.into_iter().map(|(_, style)| TestStyle(style)).collect() };
+        let bounds = LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(window_size.0, window_size.1));
+        let solved_ui = SolvedUi::new_cached(bounds, &node_hierarchy, &styles, rect_contents, layout_cache);
+        solved_ui.solved_rects.internal.iter().map(|rect| {
+            (rect.bounds.origin.x, rect.bounds.origin.y, rect.bounds.size.width, rect.bounds.size.height)
+        }).collect()
+    }
+
+    pub(crate) fn px(value: f32) -> Dimension {
+        Dimension::Pixels(value)
+    }
+
+    pub(crate) fn sized(width: Dimension, height: Dimension) -> Style {
+        Style { size: Size { width, height }, .. Style::default() }
+    }
+
+    /// Lays out the nodes in a 300x200 window, `texts` are the word widths of the `TestText` of a node
+    pub(crate) fn solve(nodes: Vec<(Option<usize>, Style)>, texts: Vec<(usize, Vec<f32>)>) -> Vec<(f32, f32, f32, f32)> {
+        let rect_contents = texts.into_iter().map(|(node_idx, words)| (NodeId::new(node_idx), RectContent::Text(TestText(words)))).collect();
+        solve_test_layout(nodes, rect_contents, (300.0, 200.0))
+    }
+}
@@ -87,10 +87,24 @@ impl Default for Direction {
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Display {
     Flex,
+    Grid,
+    Block,
     Inline,
+    InlineBlock,
     None,
 }
 
+impl Display {
+    /// Whether the node is placed on the lines of a paragraph if its parent
+    /// is a block container, instead of being stacked vertically
+    pub(crate) fn is_inline_level(self) -> bool {
+        match self {
+            Display::Inline | Display::InlineBlock => true,
+            _ => false,
+        }
+    }
+}
+
 impl Default for Display {
     fn default() -> Display {
         Display::Flex
@@ -156,13 +170,46 @@ impl Default for Overflow {
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum PositionType {
+    Static,
     Relative,
     Absolute,
+    Fixed,
+    Sticky,
+}
+
+impl PositionType {
+    /// Whether the node is taken out of the flow of its parent and positioned
+    /// relative to its containing block (`absolute`) or the viewport (`fixed`)
+    pub(crate) fn is_out_of_flow(self) -> bool {
+        match self {
+            PositionType::Absolute | PositionType::Fixed => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the node is the containing block of its `position: absolute` descendants
+    pub(crate) fn is_positioned(self) -> bool {
+        self != PositionType::Static
+    }
 }
 
 impl Default for PositionType {
     fn default() -> PositionType {
-        PositionType::Relative
+        PositionType::Static
+    }
+}
+
+/// Side of the container that a floating box is placed at
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Float {
+    None,
+    Left,
+    Right,
+}
+
+impl Default for Float {
+    fn default() -> Float {
+        Float::None
     }
 }
 
@@ -185,6 +232,37 @@ pub enum Dimension {
     Auto,
     Pixels(f32),
     Percent(f32),
+    /// Result of a `calc()` expression or a viewport-relative value (`vw`, `vh`, ...)
+    Calc(CalcDimension),
+}
+
+/// A `calc()` value split up into the parts that have to be resolved at different
+/// times: viewport-relative parts are resolved before the layout starts
+/// (see `Style::resolve_viewport_units`), percentages during the layout.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct CalcDimension {
+    pub pixels: f32,
+    pub percent: f32,
+    pub vw: f32,
+    pub vh: f32,
+    pub vmin: f32,
+    pub vmax: f32,
+}
+
+impl CalcDimension {
+    fn resolve_viewport_units(self, viewport_width: f32, viewport_height: f32) -> Dimension {
+        let pixels = self.pixels
+            + self.vw / 100.0 * viewport_width
+            + self.vh / 100.0 * viewport_height
+            + self.vmin / 100.0 * viewport_width.min(viewport_height)
+            + self.vmax / 100.0 * viewport_width.max(viewport_height);
+
+        if self.percent == 0.0 {
+            Dimension::Pixels(pixels)
+        } else {
+            Dimension::Calc(CalcDimension { pixels, percent: self.percent, .. Default::default() })
+        }
+    }
 }
 
 impl Default for Dimension {
@@ -198,6 +276,8 @@ impl Dimension {
         match self {
             Dimension::Pixels(pixels) => Number::Defined(pixels),
             Dimension::Percent(percent) => parent_width * (percent / 100.0),
+            Dimension::Calc(calc) if calc.percent == 0.0 => Number::Defined(calc.pixels),
+            Dimension::Calc(calc) => parent_width * (calc.percent / 100.0) + calc.pixels,
             _ => Number::Undefined,
         }
     }
@@ -206,9 +286,17 @@ impl Dimension {
         match self {
             Dimension::Pixels(_) => true,
             Dimension::Percent(_) => true,
+            Dimension::Calc(_) => true,
             _ => false,
         }
     }
+
+    fn resolve_viewport_units(self, viewport_width: f32, viewport_height: f32) -> Dimension {
+        match self {
+            Dimension::Calc(calc) => calc.resolve_viewport_units(viewport_width, viewport_height),
+            other => other,
+        }
+    }
 }
 
 impl Default for Offsets<Dimension> {
@@ -231,6 +319,73 @@ impl Default for Size<Dimension> {
     }
 }
 
+/// Minimum or maximum size of a grid track
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TrackSizingFunction {
+    /// Sized to fit the content of the track
+    Auto,
+    Fixed(Dimension),
+    /// Fraction of the free space in the grid container (`fr`)
+    Flex(f32),
+}
+
+/// Size of a row or column of a grid, a track without a `minmax()` has the same `min` and `max`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GridTrack {
+    pub min: TrackSizingFunction,
+    pub max: TrackSizingFunction,
+}
+
+impl GridTrack {
+    pub const AUTO: GridTrack = GridTrack { min: TrackSizingFunction::Auto, max: TrackSizingFunction::Auto };
+}
+
+/// Start or end line of a grid item, lines start at 1, negative lines count from the end of the explicit grid
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridLine {
+    Auto,
+    Line(i32),
+    Span(u32),
+}
+
+impl Default for GridLine {
+    fn default() -> GridLine {
+        GridLine::Auto
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridAutoFlow {
+    Row,
+    Column,
+}
+
+impl Default for GridAutoFlow {
+    fn default() -> GridAutoFlow {
+        GridAutoFlow::Row
+    }
+}
+
+/// Horizontal alignment of the lines of a paragraph (`text-align`)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl Default for TextAlign {
+    fn default() -> TextAlign {
+        TextAlign::Left
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum BoxSizing {
     ContentBox,
@@ -243,11 +398,12 @@ impl Default for BoxSizing {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Style {
     pub display: Display,
     pub box_sizing: BoxSizing,
     pub position_type: PositionType,
+    pub float: Float,
     pub direction: Direction,
     pub flex_direction: FlexDirection,
     pub flex_wrap: FlexWrap,
@@ -267,6 +423,14 @@ pub struct Style {
     pub min_size: Size<Dimension>,
     pub max_size: Size<Dimension>,
     pub aspect_ratio: Number,
+    pub grid_template_columns: Vec<GridTrack>,
+    pub grid_template_rows: Vec<GridTrack>,
+    pub grid_column: GridPlacement,
+    pub grid_row: GridPlacement,
+    pub grid_auto_flow: GridAutoFlow,
+    /// `width` is the `column-gap`, `height` the `row-gap`
+    pub gap: Size<Dimension>,
+    pub text_align: TextAlign,
     pub font_size_px: PixelValue,
     pub letter_spacing: Option<PixelValue>,
     pub word_spacing: Option<PixelValue>,
@@ -280,6 +444,7 @@ impl Default for Style {
             display: Default::default(),
             box_sizing: Default::default(),
             position_type: Default::default(),
+            float: Default::default(),
             direction: Default::default(),
             flex_direction: Default::default(),
             flex_wrap: Default::default(),
@@ -299,6 +464,13 @@ impl Default for Style {
             min_size: Default::default(),
             max_size: Default::default(),
             aspect_ratio: Default::default(),
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_column: Default::default(),
+            grid_row: Default::default(),
+            grid_auto_flow: Default::default(),
+            gap: Size { width: Dimension::Pixels(0.0), height: Dimension::Pixels(0.0) },
+            text_align: Default::default(),
             font_size_px: PixelValue::const_px(10),
             letter_spacing: None,
             line_height: None,
@@ -309,6 +481,43 @@ impl Default for Style {
 }
 
 impl Style {
+    /// Replaces all `vw` / `vh` / `vmin` / `vmax` parts of the dimensions and
+    /// font sizes with their pixel values, given the size of the viewport
+    pub(crate) fn resolve_viewport_units(&mut self, viewport_width: f32, viewport_height: f32) {
+        let resolve = |d: Dimension| d.resolve_viewport_units(viewport_width, viewport_height);
+        self.position = self.position.map(resolve);
+        self.margin = self.margin.map(resolve);
+        self.padding = self.padding.map(resolve);
+        self.border = self.border.map(resolve);
+        self.flex_basis = resolve(self.flex_basis);
+        self.size = self.size.map(resolve);
+        self.min_size = self.min_size.map(resolve);
+        self.max_size = self.max_size.map(resolve);
+        self.gap = self.gap.map(resolve);
+
+        let resolve_track_sizing = |function: TrackSizingFunction| match function {
+            TrackSizingFunction::Fixed(d) => TrackSizingFunction::Fixed(resolve(d)),
+            other => other,
+        };
+        for track in self.grid_template_columns.iter_mut().chain(self.grid_template_rows.iter_mut()) {
+            track.min = resolve_track_sizing(track.min);
+            track.max = resolve_track_sizing(track.max);
+        }
+
+        self.font_size_px = self.font_size_px.resolve_viewport_units(viewport_width, viewport_height);
+        self.letter_spacing = self.letter_spacing.map(|ls| ls.resolve_viewport_units(viewport_width, viewport_height));
+        self.word_spacing = self.word_spacing.map(|ws| ws.resolve_viewport_units(viewport_width, viewport_height));
+    }
+
+    /// Offsets that the node is moved by after it has been laid out - `top / right / bottom / left`
+    /// only offset `position: relative` nodes, for sticky nodes they are applied when scrolling
+    pub(crate) fn relative_offsets(&self) -> Offsets<Dimension> {
+        match self.position_type {
+            PositionType::Relative => self.position,
+            _ => Offsets::default(),
+        }
+    }
+
     pub(crate) fn min_main_size(&self, direction: FlexDirection) -> Dimension {
         match direction {
             FlexDirection::Row | FlexDirection::RowReverse => self.min_size.width,
@@ -1,6 +1,6 @@
 use std::{
     rc::Rc,
-    time::Instant,
+    time::{Duration, Instant},
     collections::BTreeMap,
 };
 use glium::glutin::WindowEvent;
@@ -22,10 +22,11 @@ use {
     gl::GlShader,
     traits::Layout,
     ui_state::UiState,
-    async::{Task, TimerId, TerminateTimer},
+    async::{Task, Timer, TimerId, TerminateTimer},
     callbacks::{
         LayoutCallback, FocusTarget, UpdateScreen, HitTestItem,
-        Redraw, DontRedraw, ScrollPosition,
+        Redraw, DontRedraw, ScrollPosition, TimerCallbackInfo,
+        TimerCallbackReturn,
     },
 };
 use azul_core::{
@@ -37,6 +38,7 @@ pub use app_resources::AppResources;
 #[cfg(not(test))]
 use azul_core::{
     window::FakeWindow,
+    diff::diff_dom_tree,
 };
 #[cfg(not(test))]
 use window::{ FakeDisplay, WindowCreateError, WindowCreateOptions };
@@ -238,6 +240,7 @@ impl<T> App<T> {
     #[cfg(not(test))]
     pub fn add_window(&mut self, window: Window<T>) {
         use window_state::full_window_state_from_normal_state;
+        use app_resources::register_css_font_faces;
         let window_id = window.id;
         let fake_window = FakeWindow {
             state: window.state.clone(),
@@ -251,6 +254,9 @@ impl<T> App<T> {
         self.app_state.windows.insert(window_id, fake_window);
         self.windows.insert(window_id, window);
         self.window_states.insert(window_id, full_window_state);
+
+        // Fonts declared via @font-face have to be known before the text is laid out
+        register_css_font_faces(&mut self.app_state.resources, self.windows.values().map(|w| &w.css));
     }
 
     /// Start the rendering loop for the currently open windows
@@ -388,7 +394,7 @@ impl<T> App<T> {
             });
 
             #[cfg(debug_assertions)]
-            let (css_has_reloaded, css_has_error) = match hot_reload_css(&mut self.windows, &mut last_style_reload, false) {
+            let (css_has_reloaded, css_has_error) = match hot_reload_css(&mut self.windows, &mut self.app_state.resources, &mut last_style_reload, false) {
                 Ok(has_reloaded) => (has_reloaded, None),
                 Err(css_error) => (true, Some(css_error)),
             };
@@ -424,6 +430,15 @@ impl<T> App<T> {
                     // Since this is the root DOM of the window, set the DomID to 0
                     rendered_dom.dom_id = DomId::ROOT_ID;
 
+                    // Move the cached layouts of the old DOM to the node IDs of the new DOM
+                    match ui_state_cache.get(current_window_id).and_then(|ui_states| ui_states.get(&DomId::ROOT_ID)) {
+                        Some(old_ui_state) => {
+                            let dom_diff = diff_dom_tree(&old_ui_state.dom, &rendered_dom.dom);
+                            window.internal.layout_cache.apply_dom_diff(&dom_diff);
+                        },
+                        None => window.internal.layout_cache.clear(),
+                    }
+
                     let mut ui_state_map = BTreeMap::new();
                     ui_state_map.insert(rendered_dom.dom_id.clone(), rendered_dom);
                     *ui_state_cache.get_mut(current_window_id).ok_or(WindowIndexError)? = ui_state_map;
@@ -507,7 +522,7 @@ fn initialize_ui_state_cache<T>(
 
         #[cfg(debug_assertions)]
         let mut ui_state = {
-            let (_, css_has_error) = match hot_reload_css(windows, &mut Instant::now(), true) {
+            let (_, css_has_error) = match hot_reload_css(windows, &mut app_state.resources, &mut Instant::now(), true) {
                 Ok(has_reloaded) => (has_reloaded, None),
                 Err(css_error) => (true, Some(css_error)),
             };
@@ -710,6 +725,9 @@ fn hit_test_single_window<T>(
             // by callbacks that return `None`.
             if let Some(overwrites_focus) = callback_result.callbacks_overwrites_focus {
                 ret.new_focus_target = Some(overwrites_focus);
+                // The focus is only applied when re-styling the DOM, which
+                // also updates the :focus and :focus-within styles
+                ret.needs_relayout_hover_active = true;
             }
         }
     }
@@ -812,13 +830,32 @@ fn relayout_single_window<T>(
                 &mut full_window_state.pending_focus_target,
                 &hovered_nodes,
                 is_mouse_down,
+                &full_window_state.size.get_media_query_environment(full_window_state.color_scheme),
             ))
         }).collect();
+
+        // Start transitions and override the animated properties with their current values
+        let now = Instant::now();
+        let mut is_animating = false;
+        for ui_description in ui_description_mut.values_mut() {
+            is_animating = window.animation_state.update(ui_description, &window.css, now) || is_animating;
+        }
+
+        // Only redraw continuously while there are animations running
+        if is_animating {
+            if !app_state.has_timer(&window.animation_timer) {
+                let timer = Timer::new(animation_tick).with_interval(Duration::from_millis(16));
+                app_state.add_timer(window.animation_timer, timer);
+            }
+        } else {
+            app_state.delete_timer(&window.animation_timer);
+        }
     }
 
     let mut fake_window = app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?;
 
-    for (window_id, ui_state_map) in ui_state_cache.iter() {
+    // Only the DOMs of this window are rendered into this window (and its layout cache)
+    if let Some(ui_state_map) = ui_state_cache.get(window_id) {
         for (dom_id, ui_state) in ui_state_map.iter() {
             let ui_description = &ui_description_cache[window_id][dom_id];
             update_display_list(
@@ -838,14 +875,22 @@ fn relayout_single_window<T>(
     Ok(())
 }
 
+/// Timer that redraws the window every frame while CSS animations are running
+fn animation_tick<T>(_info: TimerCallbackInfo<T>) -> TimerCallbackReturn {
+    (Redraw, TerminateTimer::Continue)
+}
+
 /// Returns if the CSS has been successfully reloaded
 #[cfg(debug_assertions)]
 fn hot_reload_css<T>(
     windows: &mut BTreeMap<WindowId, Window<T>>,
+    app_resources: &mut AppResources,
     last_style_reload: &mut Instant,
     force_reload: bool,
 ) -> Result<bool, String> {
 
+    use app_resources::register_css_font_faces;
+
     let mut has_reloaded = false;
 
     for window in windows.values_mut() {
@@ -864,9 +909,14 @@ fn hot_reload_css<T>(
             }
         }
 
-        match hot_reloader.reload_style() {
-            Ok(mut new_css) => {
+        match hot_reloader.reload_style_lenient() {
+            Ok((mut new_css, errors)) => {
+                // Apply the partially valid style anyways, but print what was skipped
+                for error in errors {
+                    println!("{}", error);
+                }
                 new_css.sort_by_specificity();
+                new_css.optimize();
                 window.css = new_css;
                 *last_style_reload = Instant::now();
                 has_reloaded = true;
@@ -877,10 +927,15 @@ fn hot_reload_css<T>(
         };
     }
 
+    if has_reloaded {
+        register_css_font_faces(app_resources, windows.values().map(|w| &w.css));
+    }
+
     Ok(has_reloaded)
 }
 
-/// Returns the currently hit-tested results, in back-to-front order
+/// Returns the currently hit-tested results, in back-to-front order (i.e. the painting order
+/// of the display list, so that nodes with a higher `z-index` are hit-tested last)
 #[cfg(not(test))]
 fn do_hit_test<T>(
     window: &Window<T>,
@@ -961,6 +1016,8 @@ fn call_callbacks<T>(
                 let mut timers = FastHashMap::default();
                 let mut tasks = Vec::new();
 
+                let cursor_relative_to_item = get_cursor_relative_to_item(&app_state.windows[window_id], &dom_id, *node_id, hit_item);
+
                 if app_state.windows[window_id].default_callbacks.get(default_callback_id).cloned().and_then(|(callback_ptr, callback_fn)| {
                     let info = DefaultCallbackInfoUnchecked {
                         ptr: callback_ptr,
@@ -977,7 +1034,7 @@ fn call_callbacks<T>(
                         hit_dom_node: (dom_id.clone(), *node_id),
                         ui_state: ui_state_map,
                         hit_test_items: &hit_test_items,
-                        cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
+                        cursor_relative_to_item,
                         cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
                     };
                     (callback_fn.0)(info)
@@ -1011,6 +1068,7 @@ fn call_callbacks<T>(
             for callback in callback_results.normal_callbacks.values() {
 
                 let mut new_focus = None;
+                let cursor_relative_to_item = get_cursor_relative_to_item(&app_state.windows[window_id], &dom_id, *node_id, hit_item);
 
                 if (callback.0)(CallbackInfo {
                     state: app_state,
@@ -1021,7 +1079,7 @@ fn call_callbacks<T>(
                     hit_dom_node: (dom_id.clone(), *node_id),
                     ui_state: ui_state_map,
                     hit_test_items: &hit_test_items,
-                    cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
+                    cursor_relative_to_item,
                     cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
                 }) == Redraw {
                     should_update_screen = Redraw;
@@ -1042,6 +1100,23 @@ fn call_callbacks<T>(
     })
 }
 
+/// Returns the cursor position relative to the top left corner of the hit node. If the node
+/// is transformed, the cursor is mapped through the inverse of the CSS `transform`, so that
+/// the position is in the same (untransformed) coordinate space as `CallbackInfo::get_bounds`
+fn get_cursor_relative_to_item<T>(
+    fake_window: &FakeWindow<T>,
+    dom_id: &DomId,
+    node_id: NodeId,
+    hit_item: &Option<HitTestItem>,
+) -> Option<(f32, f32)> {
+    let hit_item = hit_item.as_ref()?;
+    let untransformed_point = fake_window.layout_result.get(dom_id)
+        .filter(|layout_result| layout_result.transforms.contains_key(&node_id))
+        .and_then(|layout_result| layout_result.get_point_relative_to_node(node_id, hit_item.point_in_viewport));
+    let point = untransformed_point.unwrap_or(hit_item.point_relative_to_item);
+    Some((point.x, point.y))
+}
+
 /// Build the display list and send it to webrender
 #[cfg(not(test))]
 fn update_display_list<T>(
@@ -1096,6 +1171,7 @@ fn update_display_list<T>(
         add_resources(app_resources, &mut fake_display.render_api, Vec::new(), image_resource_updates);
     }
 
+    fake_window.layout_result = layout_result.clone();
     window.internal.layout_result = layout_result;
     window.internal.scrolled_nodes = scrollable_nodes;
     window.internal.cached_display_list = cached_display_list.clone();
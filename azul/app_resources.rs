@@ -1,12 +1,13 @@
 use std::{
     fmt,
-    path::PathBuf,
+    path::{Path, PathBuf},
     io::Error as IoError,
 };
 use webrender::api::{
     AddImage, ResourceUpdate, AddFont,
     AddFontInstance, RenderApi,
 };
+use azul_css::{Css, FontFace, FontFaceSource, LayoutSize, ConicGradient};
 use {
     FastHashMap, FastHashSet,
     display_list::DisplayList,
@@ -130,9 +131,10 @@ impl FontImageApi for FakeRenderApi {
 pub(crate) fn add_fonts_and_images<T, U: FontImageApi>(
     app_resources: &mut AppResources,
     render_api: &mut U,
-    display_list: &DisplayList<T>
+    display_list: &DisplayList<T>,
+    viewport_size: LayoutSize,
 ) {
-    let font_keys = scan_ui_description_for_font_keys(&app_resources, display_list);
+    let font_keys = scan_ui_description_for_font_keys(&app_resources, display_list, viewport_size);
     let image_keys = scan_ui_description_for_image_keys(&app_resources, display_list);
 
     app_resources.last_frame_font_keys.extend(font_keys.clone().into_iter());
@@ -157,10 +159,104 @@ pub(crate) fn garbage_collect_fonts_and_images<U: FontImageApi>(
 
     delete_resources(app_resources, render_api, delete_font_resource_updates, delete_image_resource_updates);
 
+    // Rendered conic gradients aren't kept around, they are rendered again once they are displayed again
+    let mut unused_conic_gradient_images = Vec::new();
+    {
+        let last_frame_image_keys = &app_resources.last_frame_image_keys;
+        app_resources.conic_gradient_images.retain(|_, image_id| {
+            let is_used = last_frame_image_keys.contains(image_id);
+            if !is_used {
+                unused_conic_gradient_images.push(*image_id);
+            }
+            is_used
+        });
+    }
+
+    for image_id in unused_conic_gradient_images {
+        app_resources.delete_image(&image_id);
+    }
+
     app_resources.last_frame_font_keys.clear();
     app_resources.last_frame_image_keys.clear();
 }
 
+/// Webrender can't draw conic gradients, so they are rendered into an image instead. The
+/// image is only rendered once for each gradient and size and is then reused, as long as it
+/// is displayed in every frame. Returns `None` if the image couldn't be added to the `render_api`.
+pub(crate) fn add_conic_gradient_image<U: FontImageApi>(
+    app_resources: &mut AppResources,
+    render_api: &mut U,
+    conic_gradient: &ConicGradient,
+    size: (u32, u32),
+) -> Option<ImageInfo> {
+
+    let cache_key = (conic_gradient.clone(), size);
+
+    let image_id = match app_resources.conic_gradient_images.get(&cache_key).cloned() {
+        Some(image_id) => image_id,
+        None => {
+            let image_id = ImageId::new();
+            app_resources.add_image_source(image_id, ImageSource::Raw(render_conic_gradient(conic_gradient, size)));
+            app_resources.conic_gradient_images.insert(cache_key, image_id);
+            image_id
+        }
+    };
+
+    let mut image_keys = FastHashSet::default();
+    image_keys.insert(image_id);
+    app_resources.last_frame_image_keys.insert(image_id);
+
+    let add_image_resource_updates = build_add_image_resource_updates(app_resources, render_api, &image_keys);
+    add_resources(app_resources, render_api, Vec::new(), add_image_resource_updates);
+
+    app_resources.get_image_info(&image_id).cloned()
+}
+
+/// Renders the conic gradient into a (premultiplied) BGRA image - the center
+/// of the gradient must not contain any viewport-relative units
+fn render_conic_gradient(conic_gradient: &ConicGradient, (width, height): (u32, u32)) -> RawImage {
+
+    use azul_css::{BackgroundPositionHorizontal, BackgroundPositionVertical};
+
+    let (image_width, image_height) = (width as f32, height as f32);
+
+    let center_x = match conic_gradient.center.horizontal {
+        BackgroundPositionHorizontal::Left => 0.0,
+        BackgroundPositionHorizontal::Center => image_width / 2.0,
+        BackgroundPositionHorizontal::Right => image_width,
+        BackgroundPositionHorizontal::Exact(e) => e.to_pixels(image_width),
+    };
+
+    let center_y = match conic_gradient.center.vertical {
+        BackgroundPositionVertical::Top => 0.0,
+        BackgroundPositionVertical::Center => image_height / 2.0,
+        BackgroundPositionVertical::Bottom => image_height,
+        BackgroundPositionVertical::Exact(e) => e.to_pixels(image_height),
+    };
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            // CSS angles start at the top and go clockwise (the y axis points down)
+            let angle = dx.atan2(-dy).to_degrees() - conic_gradient.angle.get();
+            let percentage = ((angle % 360.0) + 360.0) % 360.0 / 360.0 * 100.0;
+            let color = conic_gradient.get_color_at(percentage);
+            pixels.extend_from_slice(&[color.b, color.g, color.r, color.a]);
+        }
+    }
+
+    premultiply(&mut pixels);
+
+    RawImage {
+        pixels,
+        image_dimensions: (width, height),
+        data_format: RawImageFormat::BGRA8,
+    }
+}
+
 /// Returns the **decoded** bytes of the image + the descriptor (contains width / height).
 /// Returns an error if the data is encoded, but the crate wasn't built with `--features="image_loading"`
 #[allow(unused_variables)]
@@ -219,10 +315,79 @@ pub fn font_source_get_bytes(font_source: &FontSource) -> Result<(Vec<u8>, i32),
     }
 }
 
+/// Registers the fonts of all `@font-face` rules in the `css`, so that they can be
+/// used via `font-family` without having to call `AppResources::add_font`.
+///
+/// Each `@font-face` registers the face given by its `font-weight` and `font-style`
+/// descriptors, so that one `font-family` can consist of multiple faces.
+///
+/// If the source of a font changed (i.e. because the CSS was hot-reloaded), the
+/// font face gets a new `FontId`, so that the old font is garbage-collected.
+/// Faces that were registered by a previous call, but aren't declared in any
+/// of the `stylesheets` anymore, are removed again.
+///
+/// Should only be called when the stylesheets change, since the font files are checked for existence.
+pub(crate) fn register_css_font_faces<'a, I: IntoIterator<Item=&'a Css>>(app_resources: &mut AppResources, stylesheets: I) {
+
+    let mut declared_font_faces = FastHashSet::default();
+
+    for font_face in stylesheets.into_iter().flat_map(|css| css.font_faces()) {
+
+        let font_source = match font_face_get_source(font_face) {
+            Some(s) => s,
+            None => {
+                #[cfg(feature = "logging")] {
+                    warn!("No usable src for @font-face \"{}\", all font files are missing", font_face.font_family);
+                }
+                continue;
+            },
+        };
+
+        let (weight, style) = (font_face.font_weight, font_face.font_style);
+        declared_font_faces.insert((font_face.font_family.clone(), weight, style));
+
+        if let Some(font_id) = app_resources.get_css_font_face_id(&font_face.font_family, weight, style) {
+            if app_resources.get_font_source(&font_id) == Some(&font_source) {
+                continue;
+            }
+        }
+
+        if let Some(old_font_id) = app_resources.delete_css_font_face_id(&font_face.font_family, weight, style) {
+            app_resources.delete_font_source(&old_font_id);
+        }
+        let font_id = app_resources.add_css_font_face(font_face.font_family.clone(), weight, style);
+        app_resources.add_font_source(font_id, font_source);
+    }
+
+    let removed_font_faces = app_resources.css_declared_font_faces
+        .difference(&declared_font_faces)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for (font_family, weight, style) in removed_font_faces {
+        if let Some(font_id) = app_resources.delete_css_font_face_id(&font_family, weight, style) {
+            app_resources.delete_font_source(&font_id);
+        }
+    }
+
+    app_resources.css_declared_font_faces = declared_font_faces;
+}
+
+/// Returns the first source of the `@font-face` that can be used: `url()` sources are
+/// skipped if the file doesn't exist, `local()` sources are always assumed to exist.
+fn font_face_get_source(font_face: &FontFace) -> Option<FontSource> {
+    font_face.sources.iter().filter_map(|source| match source {
+        FontFaceSource::Url(path) if Path::new(path).exists() => Some(FontSource::File(PathBuf::from(path))),
+        FontFaceSource::Url(_) => None,
+        FontFaceSource::Local(name) => Some(FontSource::System(name.clone())),
+    }).next()
+}
+
 /// Scans the display list for all font IDs + their font size
 fn scan_ui_description_for_font_keys<'a, T>(
     app_resources: &AppResources,
-    display_list: &DisplayList<'a, T>
+    display_list: &DisplayList<'a, T>,
+    viewport_size: LayoutSize,
 ) -> FastHashMap<ImmediateFontId, FastHashSet<Au>> {
 
     use dom::NodeType::*;
@@ -237,12 +402,8 @@ fn scan_ui_description_for_font_keys<'a, T>(
 
         match node_data.get_node_type() {
             Text(_) | Label(_) => {
-                let css_font_id = ui_solver::get_font_id(&display_rect.style);
-                let font_id = match app_resources.css_ids_to_font_ids.get(css_font_id) {
-                    Some(s) => ImmediateFontId::Resolved(*s),
-                    None => ImmediateFontId::Unresolved(css_font_id.to_string()),
-                };
-                let font_size = ui_solver::get_font_size(&display_rect.style);
+                let font_id = ui_solver::get_immediate_font_id(app_resources, &display_rect.style);
+                let font_size = ui_solver::get_font_size(&display_rect.style, viewport_size);
                 font_keys
                     .entry(font_id)
                     .or_insert_with(|| FastHashSet::default())
@@ -266,14 +427,24 @@ fn scan_ui_description_for_image_keys<'a, T>(
     display_list.rectangles
     .iter()
     .zip(display_list.ui_descr.ui_descr_arena.node_data.iter())
-    .filter_map(|(display_rect, node_data)| {
+    .flat_map(|(display_rect, node_data)| {
         match node_data.get_node_type() {
-            Image(id) => Some(*id),
+            Image(id) => vec![*id],
             _ => {
-                let background = display_rect.style.background.as_ref().and_then(|bg| bg.get_property())?;
-                let css_image_id = background.get_css_image_id()?;
-                let image_id = app_resources.get_css_image_id(&css_image_id.0)?;
-                Some(*image_id)
+                // every background layer can reference a different image
+                let background_images = display_rect.style.background.as_ref()
+                    .and_then(|bg| bg.get_property())
+                    .map(|background_layers| background_layers.0.iter().filter_map(|background| background.get_css_image_id()).collect::<Vec<_>>())
+                    .unwrap_or_else(Vec::new);
+
+                let border_image = display_rect.style.border_image_source.as_ref()
+                    .and_then(|source| source.get_property())
+                    .map(|source| &source.0);
+
+                background_images.into_iter().chain(border_image).filter_map(|css_image_id| {
+                    let image_id = app_resources.get_css_image_id(&css_image_id.0)?;
+                    Some(*image_id)
+                }).collect()
             }
         }
     }).collect()
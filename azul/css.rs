@@ -67,6 +67,13 @@ pub fn from_str(input: &str) -> Result<Css, CssParseError> {
     azul_css_parser::new_from_str(input)
 }
 
+/// Loads a CSS file from disk, including all of its `@import`-ed files.
+/// Convenience wrapper for `azul-css-parser::new_from_file`.
+#[cfg(feature = "css_parser")]
+pub fn from_file<P: AsRef<::std::path::Path>>(file_path: P) -> Result<Css, azul_css_parser::CssImportError> {
+    azul_css_parser::new_from_file(file_path)
+}
+
 /// Appends a custom stylesheet to `css::native()`.
 #[cfg(all(feature = "css_parser", feature = "native_style"))]
 pub fn override_native(input: &str) -> Result<Css, CssParseError> {
@@ -7,7 +7,8 @@ use webrender::api::{
 };
 use azul_css::{
     Css, LayoutPosition, CssProperty, ColorU, BoxShadowClipMode,
-    RectStyle, RectLayout, CssPropertyValue, LayoutPoint, LayoutSize, LayoutRect,
+    RectStyle, RectLayout, CssPropertyValue, PixelValue, LayoutPoint, LayoutSize, LayoutRect,
+    ConicGradient, StyleBackgroundSize,
 };
 use {
     FastHashMap,
@@ -24,18 +25,18 @@ use {
     compositor::new_opengl_texture_id,
     window::{Window, WindowSize, FakeWindow},
     callbacks::LayoutInfo,
-    text_layout::LayoutedGlyphs,
 };
 use azul_core::{
     callbacks::PipelineId,
-    app_resources::{ImageId, FontInstanceKey},
+    app_resources::ImageId,
     ui_solver::{
         PositionedRectangle, ResolvedOffsets, ExternalScrollId,
         LayoutResult, ScrolledNodes, OverflowingScrollNode
     },
     display_list::{
-        CachedDisplayList, DisplayListMsg, LayoutRectContent,
+        CachedDisplayList, DisplayListMsg, LayoutRectContent, GlyphInstance,
         ImageRendering, AlphaType, DisplayListFrame, StyleBoxShadow, DisplayListScrollFrame,
+        FramePosition, StickyFrame,
         StyleBorderStyles, StyleBorderColors, StyleBorderRadius, StyleBorderWidths,
     },
 };
@@ -123,7 +124,8 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
         use azul_css::{
             PixelValue, LayoutDisplay, LayoutDirection, LayoutWrap,
             LayoutAlignItems, LayoutAlignContent, LayoutJustifyContent,
-            LayoutBoxSizing, Overflow as LayoutOverflow,
+            LayoutBoxSizing, LayoutFloat, LayoutGridAutoFlow, GridTrackSize, GridTrackBreadth,
+            GridLine as CssGridLine, StyleTextAlignmentHorz, Overflow as LayoutOverflow,
         };
         use azul_core::ui_solver::DEFAULT_FONT_SIZE;
 
@@ -139,35 +141,102 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
                 Some(CssPropertyValue::None) => Dimension::Pixels(0.0),
                 Some(CssPropertyValue::Initial) => Dimension::Undefined,
                 Some(CssPropertyValue::Inherit) => Dimension::Undefined,
-                Some(CssPropertyValue::Exact(pixel_value)) => match pixel_value.metric {
-                    SizeMetric::Px => Dimension::Pixels(pixel_value.number.get()),
-                    SizeMetric::Percent => Dimension::Percent(pixel_value.number.get()),
-                    SizeMetric::Pt => Dimension::Pixels(pixel_value.number.get() * PT_TO_PX),
-                    SizeMetric::Em => Dimension::Pixels(pixel_value.number.get() * EM_HEIGHT),
+                Some(CssPropertyValue::Exact(pixel_value)) => {
+                    // Sum up all terms of a calc() expression, viewport-relative
+                    // parts are resolved later on by the layout solver
+                    let mut calc = CalcDimension::default();
+                    for (metric, number) in pixel_value.terms() {
+                        let number = number.get();
+                        match metric {
+                            SizeMetric::Px => calc.pixels += number,
+                            SizeMetric::Pt => calc.pixels += number * PT_TO_PX,
+                            SizeMetric::Em | SizeMetric::Rem => calc.pixels += number * EM_HEIGHT,
+                            SizeMetric::Percent => calc.percent += number,
+                            SizeMetric::Vw => calc.vw += number,
+                            SizeMetric::Vh => calc.vh += number,
+                            SizeMetric::Vmin => calc.vmin += number,
+                            SizeMetric::Vmax => calc.vmax += number,
+                        }
+                    }
+
+                    match (pixel_value.is_calc(), pixel_value.metric) {
+                        (false, SizeMetric::Percent) => Dimension::Percent(calc.percent),
+                        (false, metric) if !metric.is_viewport_relative() => Dimension::Pixels(calc.pixels),
+                        _ => Dimension::Calc(calc),
+                    }
                 }
             }
         }
 
+        fn translate_grid_tracks(tracks: Option<&Vec<GridTrackSize>>) -> Vec<GridTrack> {
+
+            fn translate_breadth(breadth: &GridTrackBreadth) -> TrackSizingFunction {
+                match breadth {
+                    GridTrackBreadth::Auto => TrackSizingFunction::Auto,
+                    GridTrackBreadth::Length(px) => TrackSizingFunction::Fixed(translate_dimension(Some(CssPropertyValue::Exact(*px)))),
+                    GridTrackBreadth::Fraction(fr) => TrackSizingFunction::Flex(fr.get()),
+                }
+            }
+
+            tracks.map(|tracks| tracks.iter().map(|track| match track {
+                // A plain `1fr` track is `minmax(auto, 1fr)`
+                GridTrackSize::Breadth(GridTrackBreadth::Fraction(fr)) => GridTrack {
+                    min: TrackSizingFunction::Auto,
+                    max: TrackSizingFunction::Flex(fr.get()),
+                },
+                GridTrackSize::Breadth(breadth) => {
+                    let function = translate_breadth(breadth);
+                    GridTrack { min: function, max: function }
+                },
+                GridTrackSize::MinMax(min, max) => GridTrack {
+                    min: translate_breadth(min),
+                    max: translate_breadth(max),
+                },
+            }).collect()).unwrap_or_default()
+        }
+
+        #[inline]
+        fn translate_grid_line(line: Option<CssPropertyValue<CssGridLine>>) -> GridLine {
+            match line.and_then(|l| l.get_property_owned()) {
+                Some(CssGridLine::Line(line)) => GridLine::Line(line),
+                Some(CssGridLine::Span(span)) => GridLine::Span(span),
+                None => GridLine::Auto,
+            }
+        }
+
+        let display = match rect_layout.display {
+            None => Display::Flex,
+            Some(CssPropertyValue::Auto) => Display::Flex,
+            Some(CssPropertyValue::None) => Display::None,
+            Some(CssPropertyValue::Initial) => Display::Flex,
+            Some(CssPropertyValue::Inherit) => Display::Flex,
+            Some(CssPropertyValue::Exact(LayoutDisplay::Flex)) => Display::Flex,
+            Some(CssPropertyValue::Exact(LayoutDisplay::Grid)) => Display::Grid,
+            Some(CssPropertyValue::Exact(LayoutDisplay::Block)) => Display::Block,
+            Some(CssPropertyValue::Exact(LayoutDisplay::Inline)) => Display::Inline,
+            Some(CssPropertyValue::Exact(LayoutDisplay::InlineBlock)) => Display::InlineBlock,
+        };
+
         Style {
-            display: match rect_layout.display {
-                None => Display::Flex,
-                Some(CssPropertyValue::Auto) => Display::Flex,
-                Some(CssPropertyValue::None) => Display::None,
-                Some(CssPropertyValue::Initial) => Display::Flex,
-                Some(CssPropertyValue::Inherit) => Display::Flex,
-                Some(CssPropertyValue::Exact(LayoutDisplay::Flex)) => Display::Flex,
-                Some(CssPropertyValue::Exact(LayoutDisplay::Inline)) => Display::Inline,
-            },
+            display,
             box_sizing: match rect_layout.box_sizing.unwrap_or_default().get_property_or_default() {
                 None => BoxSizing::ContentBox,
                 Some(LayoutBoxSizing::ContentBox) => BoxSizing::ContentBox,
                 Some(LayoutBoxSizing::BorderBox) => BoxSizing::BorderBox,
             },
             position_type: match rect_layout.position.unwrap_or_default().get_property_or_default() {
-                Some(LayoutPosition::Static) => PositionType::Relative, // todo - static?
+                Some(LayoutPosition::Static) => PositionType::Static,
                 Some(LayoutPosition::Relative) => PositionType::Relative,
                 Some(LayoutPosition::Absolute) => PositionType::Absolute,
-                None => PositionType::Relative,
+                Some(LayoutPosition::Fixed) => PositionType::Fixed,
+                Some(LayoutPosition::Sticky) => PositionType::Sticky,
+                None => PositionType::Static,
+            },
+            // float: initial / none / inherit don't float the node
+            float: match rect_layout.float {
+                Some(CssPropertyValue::Exact(LayoutFloat::Left)) => Float::Left,
+                Some(CssPropertyValue::Exact(LayoutFloat::Right)) => Float::Right,
+                _ => Float::None,
             },
             direction: Direction::LTR,
             flex_direction: match rect_layout.direction.unwrap_or_default().get_property_or_default() {
@@ -194,6 +263,8 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
                 Some(LayoutAlignItems::Center) => AlignItems::Center,
                 Some(LayoutAlignItems::Start) => AlignItems::FlexStart,
                 Some(LayoutAlignItems::End) => AlignItems::FlexEnd,
+                // Grid items stretch across their grid area by default
+                None if display == Display::Grid => AlignItems::Stretch,
                 None => AlignItems::FlexStart,
             },
             align_content: match rect_layout.align_content.unwrap_or_default().get_property_or_default() {
@@ -255,11 +326,42 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
             align_self: AlignSelf::Auto, // todo!
             flex_basis: Dimension::Auto, // todo!
             aspect_ratio: Number::Undefined,
+            text_align: match rect_style.text_align.and_then(|ta| ta.get_property_or_default()) {
+                Some(StyleTextAlignmentHorz::Left) => TextAlign::Left,
+                Some(StyleTextAlignmentHorz::Center) => TextAlign::Center,
+                Some(StyleTextAlignmentHorz::Right) => TextAlign::Right,
+                None => TextAlign::Left,
+            },
             font_size_px: rect_style.font_size.and_then(|fs| fs.get_property_owned()).unwrap_or(DEFAULT_FONT_SIZE).0,
             line_height: rect_style.line_height.and_then(|lh| lh.map_property(|lh| lh.0).get_property_owned()).map(|lh| lh.get()),
             letter_spacing: rect_style.letter_spacing.and_then(|ls| ls.map_property(|ls| ls.0).get_property_owned()),
             word_spacing: rect_style.word_spacing.and_then(|ws| ws.map_property(|ws| ws.0).get_property_owned()),
             tab_width: rect_style.tab_width.and_then(|tw| tw.map_property(|tw| tw.0).get_property_owned()).map(|tw| tw.get()),
+            grid_template_columns: translate_grid_tracks(rect_layout.grid_template_columns.as_ref().and_then(|t| t.get_property()).map(|t| &t.0)),
+            grid_template_rows: translate_grid_tracks(rect_layout.grid_template_rows.as_ref().and_then(|t| t.get_property()).map(|t| &t.0)),
+            grid_column: GridPlacement {
+                start: translate_grid_line(rect_layout.grid_column_start.map(|prop| prop.map_property(|l| l.0))),
+                end: translate_grid_line(rect_layout.grid_column_end.map(|prop| prop.map_property(|l| l.0))),
+            },
+            grid_row: GridPlacement {
+                start: translate_grid_line(rect_layout.grid_row_start.map(|prop| prop.map_property(|l| l.0))),
+                end: translate_grid_line(rect_layout.grid_row_end.map(|prop| prop.map_property(|l| l.0))),
+            },
+            grid_auto_flow: match rect_layout.grid_auto_flow.unwrap_or_default().get_property_or_default() {
+                Some(LayoutGridAutoFlow::Row) => GridAutoFlow::Row,
+                Some(LayoutGridAutoFlow::Column) => GridAutoFlow::Column,
+                None => GridAutoFlow::Row,
+            },
+            gap: Size {
+                width: match translate_dimension(rect_layout.column_gap.map(|prop| prop.map_property(|g| g.0))) {
+                    Dimension::Undefined | Dimension::Auto => Dimension::Pixels(0.0),
+                    d => d,
+                },
+                height: match translate_dimension(rect_layout.row_gap.map(|prop| prop.map_property(|g| g.0))) {
+                    Dimension::Undefined | Dimension::Auto => Dimension::Pixels(0.0),
+                    d => d,
+                },
+            },
         }
     }
 }
@@ -282,57 +384,172 @@ struct ContentGroup {
     children: Vec<ContentGroup>,
 }
 
+/// Webrender has no conic gradients, so the gradient is rendered into an image, with the size
+/// of the background. The image is cached as long as the gradient is displayed in the same size.
+fn get_conic_gradient_image<U: FontImageApi>(
+    app_resources: &mut AppResources,
+    render_api: &mut U,
+    conic_gradient: &ConicGradient,
+    rect_size: LayoutSize,
+    background_size: Option<StyleBackgroundSize>,
+    window_size: &WindowSize,
+) -> Option<RectBackground> {
+
+    use azul_css::{BackgroundPositionHorizontal, BackgroundPositionVertical};
+    use azul_core::display_list::RectBackground;
+    use app_resources::add_conic_gradient_image;
+
+    let (viewport_width, viewport_height) = (window_size.dimensions.width, window_size.dimensions.height);
+
+    // A gradient has no aspect ratio, so "contain" and "cover" fill the whole background
+    let (width, height) = match background_size {
+        Some(StyleBackgroundSize::ExactSize(w, h)) => (
+            w.to_pixels_in_viewport(rect_size.width, viewport_width, viewport_height),
+            h.to_pixels_in_viewport(rect_size.height, viewport_width, viewport_height),
+        ),
+        _ => (rect_size.width, rect_size.height),
+    };
+
+    let size = (width.max(0.0).round() as u32, height.max(0.0).round() as u32);
+    if size.0 == 0 || size.1 == 0 {
+        return None;
+    }
+
+    // The gradient is part of the cache key, so the center can't depend on the size of the window
+    let mut conic_gradient = conic_gradient.clone();
+    if let BackgroundPositionHorizontal::Exact(e) = conic_gradient.center.horizontal {
+        conic_gradient.center.horizontal = BackgroundPositionHorizontal::Exact(e.resolve_viewport_units(viewport_width, viewport_height));
+    }
+    if let BackgroundPositionVertical::Exact(e) = conic_gradient.center.vertical {
+        conic_gradient.center.vertical = BackgroundPositionVertical::Exact(e.resolve_viewport_units(viewport_width, viewport_height));
+    }
+
+    let image_info = add_conic_gradient_image(app_resources, render_api, &conic_gradient, size)?;
+    Some(RectBackground::Image(image_info))
+}
+
+/// Determines the painting order of the nodes, following a simplified version of the
+/// [CSS stacking rules](https://www.w3.org/TR/CSS2/zindex.html):
+///
+/// - Nodes with `position: relative / absolute` and a `z-index` other than `auto` are taken out
+///   of their parent and painted by the closest ancestor that establishes a stacking context,
+///   so that i.e. a dropdown can be painted above the siblings of its parent
+/// - The children of a node are painted in the order: negative `z-index`, non-positioned nodes,
+///   positioned nodes with `z-index: auto / 0`, positive `z-index` - nodes in the same layer
+///   are painted in DOM order
+///
+/// A stacking context is established by the root node and by nodes with a `z-index`, a `transform`,
+/// a `filter`, an `opacity` below 1 or a `mix-blend-mode`. Scroll frames also keep the `z-index`-ed
+/// nodes inside of them, so that they are still clipped and scrolled by the frame.
+///
+/// Since hit-testing is done on the display list, the hit-testing order is the same as the painting order.
 fn determine_rendering_order<'a>(
     node_hierarchy: &NodeHierarchy,
     rectangles: &NodeDataContainer<DisplayRectangle<'a>>,
+    scrolled_nodes: &ScrolledNodes,
+) -> ContentGroup {
+    get_content_group(NodeId::ZERO, node_hierarchy, rectangles, scrolled_nodes)
+}
+
+fn get_content_group<'a>(
+    group_root: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    rectangles: &NodeDataContainer<DisplayRectangle<'a>>,
+    scrolled_nodes: &ScrolledNodes,
 ) -> ContentGroup {
 
-    let children_sorted: BTreeMap<NodeId, Vec<NodeId>> = node_hierarchy
-        .get_parents_sorted_by_depth()
-        .into_iter()
-        .map(|(_, parent_id)| (parent_id, sort_children_by_position(parent_id, node_hierarchy, rectangles)))
-        .collect();
+    let mut children = Vec::new();
+    collect_content_group_children(group_root, group_root, node_hierarchy, rectangles, scrolled_nodes, &mut children);
 
-    let mut root_content_group = ContentGroup { root: NodeId::ZERO, children: Vec::new() };
-    fill_content_group_children(&mut root_content_group, &children_sorted);
-    root_content_group
+    // Stable sort, nodes in the same layer stay in DOM order
+    children.sort_by_key(|child| get_paint_layer(&rectangles[*child]));
+
+    ContentGroup {
+        root: group_root,
+        children: children.into_iter().map(|child| get_content_group(child, node_hierarchy, rectangles, scrolled_nodes)).collect(),
+    }
 }
 
-fn fill_content_group_children(group: &mut ContentGroup, children_sorted: &BTreeMap<NodeId, Vec<NodeId>>) {
-    if let Some(c) = children_sorted.get(&group.root) { // returns None for leaf nodes
-        group.children = c
-            .iter()
-            .map(|child| ContentGroup { root: *child, children: Vec::new() })
-            .collect();
+/// Collects the children of the `group_root` (in DOM order) - if the group root is a stacking context,
+/// this includes the `z-index`-ed descendants of the children (which are then not painted by their parent)
+fn collect_content_group_children<'a>(
+    group_root: NodeId,
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    rectangles: &NodeDataContainer<DisplayRectangle<'a>>,
+    scrolled_nodes: &ScrolledNodes,
+    children: &mut Vec<NodeId>,
+) {
+    let group_is_stacking_context = is_stacking_context_root(group_root, &rectangles[group_root], scrolled_nodes);
+
+    for child_id in node_id.children(node_hierarchy) {
+
+        let child = &rectangles[child_id];
+
+        if node_id == group_root {
+            // z-index-ed children are painted by the parent stacking context
+            if get_z_index(child).is_some() && !group_is_stacking_context {
+                continue;
+            }
+            children.push(child_id);
+        } else if get_z_index(child).is_some() {
+            children.push(child_id);
+        }
 
-        for c in &mut group.children {
-            fill_content_group_children(c, children_sorted);
+        if group_is_stacking_context && !is_stacking_context_root(child_id, child, scrolled_nodes) {
+            collect_content_group_children(group_root, child_id, node_hierarchy, rectangles, scrolled_nodes, children);
         }
     }
 }
 
-fn sort_children_by_position<'a>(
-    parent: NodeId,
-    node_hierarchy: &NodeHierarchy,
-    rectangles: &NodeDataContainer<DisplayRectangle<'a>>
-) -> Vec<NodeId> {
-    use azul_css::LayoutPosition::*;
+/// Returns whether the node paints all `z-index`-ed nodes inside of it (see `determine_rendering_order`)
+fn is_stacking_context_root<'a>(node_id: NodeId, rect: &DisplayRectangle<'a>, scrolled_nodes: &ScrolledNodes) -> bool {
+    let has_transform = rect.style.transform.as_ref().and_then(|t| t.get_property()).map(|t| !t.0.is_empty()).unwrap_or(false);
+    node_id == NodeId::ZERO ||
+    get_z_index(rect).is_some() ||
+    has_transform ||
+    !rect.style.get_filters().is_empty() ||
+    rect.style.get_mix_blend_mode().is_some() ||
+    is_fixed_or_sticky(rect) ||
+    scrolled_nodes.overflowing_nodes.contains_key(&node_id)
+}
 
-    let mut not_absolute_children = parent
-        .children(node_hierarchy)
-        .filter(|id| rectangles[*id].layout.position.and_then(|p| p.get_property_or_default()).unwrap_or_default() != Absolute)
-        .collect::<Vec<NodeId>>();
+fn get_position<'a>(rect: &DisplayRectangle<'a>) -> LayoutPosition {
+    rect.layout.position.and_then(|p| p.get_property_or_default()).unwrap_or_default()
+}
 
-    let mut absolute_children = parent
-        .children(node_hierarchy)
-        .filter(|id| rectangles[*id].layout.position.and_then(|p| p.get_property_or_default()).unwrap_or_default() == Absolute)
-        .collect::<Vec<NodeId>>();
+fn is_fixed_or_sticky<'a>(rect: &DisplayRectangle<'a>) -> bool {
+    match get_position(rect) {
+        LayoutPosition::Fixed | LayoutPosition::Sticky => true,
+        _ => false,
+    }
+}
 
-    // Append the position:absolute children after the regular children
-    not_absolute_children.append(&mut absolute_children);
-    not_absolute_children
+fn is_positioned<'a>(rect: &DisplayRectangle<'a>) -> bool {
+    use azul_css::LayoutPosition::*;
+    match get_position(rect) {
+        Static => false,
+        Relative | Absolute | Fixed | Sticky => true,
+    }
 }
 
+/// Returns the `z-index` of the node, `None` if the z-index is `auto` or the node isn't positioned
+fn get_z_index<'a>(rect: &DisplayRectangle<'a>) -> Option<i32> {
+    if !is_positioned(rect) {
+        return None;
+    }
+    rect.layout.z_index.and_then(|z| z.get_property().cloned()).map(|z| z.0)
+}
+
+/// Returns the layer (and the z-index inside of the layer) that the node is painted in
+fn get_paint_layer<'a>(rect: &DisplayRectangle<'a>) -> (usize, i32) {
+    match get_z_index(rect) {
+        Some(z_index) if z_index < 0 => (0, z_index),
+        _ if !is_positioned(rect) => (1, 0),
+        None | Some(0) => (2, 0),
+        Some(z_index) => (3, z_index),
+    }
+}
 
 /// Returns all node IDs where the children overflow the parent, together with the
 /// `(parent_rect, child_rect)` - the child rect is the sum of the children.
@@ -363,7 +580,12 @@ fn get_nodes_that_need_scroll_clip<'a, T: 'a>(
 
         let parent_rect = &layouted_rects[*parent];
 
-        let children_scroll_rect = match parent_rect.bounds.get_scroll_rect(parent.children(&node_hierarchy).map(|child_id| layouted_rects[child_id].bounds)) {
+        // position: fixed children are positioned relative to the window, they never overflow the parent
+        let scrolled_children = parent.children(&node_hierarchy)
+            .filter(|child_id| get_position(&display_list_rects[*child_id]) != LayoutPosition::Fixed)
+            .map(|child_id| layouted_rects[child_id].bounds);
+
+        let children_scroll_rect = match parent_rect.bounds.get_scroll_rect(scrolled_children) {
             None => continue,
             Some(sum) => sum,
         };
@@ -421,8 +643,15 @@ fn contains_rect_rounded(a: &LayoutRect, b: LayoutRect) -> bool {
     b_y + b_height <= a_y + a_height
 }
 
+/// Returns whether the children are clipped to the padding box of the node - only `overflow: hidden`
+/// and `overflow: scroll` clip, the default `auto` only clips once the node becomes a scroll frame
 fn node_needs_to_clip_children(layout: &RectLayout) -> bool {
-    !(layout.is_horizontal_overflow_visible() || layout.is_vertical_overflow_visible())
+    use azul_css::Overflow;
+    let clips = |overflow: Option<CssPropertyValue<Overflow>>| match overflow.and_then(|o| o.get_property().cloned()) {
+        Some(Overflow::Hidden) | Some(Overflow::Scroll) => true,
+        _ => false,
+    };
+    clips(layout.overflow_x) || clips(layout.overflow_y)
 }
 
 /// NOTE: This function assumes that the UiDescription has an initialized arena
@@ -499,7 +728,8 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
     //      - Insert the new font keys and image keys into the render API
     //      - Scan all IFrameCallbacks, generate the DomID for each callback
     //      - Repeat while number_of_iframe_callbacks != 0
-    add_fonts_and_images(app_resources, render_api, &display_list);
+    let window_size = LayoutSize::new(window.state.size.dimensions.width, window.state.size.dimensions.height);
+    add_fonts_and_images(app_resources, render_api, &display_list, window_size);
 
     let layout_result = do_the_layout(
         node_hierarchy,
@@ -508,13 +738,9 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
         &*app_resources,
         LayoutRect {
             origin: LayoutPoint::new(0.0, 0.0),
-            size: LayoutSize::new(window.state.size.dimensions.width, window.state.size.dimensions.height),
+            size: window_size,
         },
-    );
-
-    let rects_in_rendering_order = determine_rendering_order(
-        node_hierarchy,
-        &display_list.rectangles,
+        Some(&mut window.internal.layout_cache),
     );
 
     let scrollable_nodes = get_nodes_that_need_scroll_clip(
@@ -522,6 +748,12 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
         &layout_result.node_depths, window.internal.pipeline_id
     );
 
+    let rects_in_rendering_order = determine_rendering_order(
+        node_hierarchy,
+        &display_list.rectangles,
+        &scrollable_nodes,
+    );
+
     let mut scrollable_nodes_map = BTreeMap::new();
     scrollable_nodes_map.insert(root_dom_id.clone(), scrollable_nodes);
 
@@ -611,12 +843,53 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
 
     let rect = &display_rectangle_arena[*rect_idx];
     let bounds = referenced_mutable_content.layout_result[dom_id].rects[*rect_idx].bounds;
+    let resolved_border_widths = referenced_mutable_content.layout_result[dom_id].rects[*rect_idx].border_widths;
 
     let display_list_rect_bounds = LayoutRect::new(
          LayoutPoint::new(bounds.origin.x, bounds.origin.y),
          LayoutSize::new(bounds.size.width, bounds.size.height),
     );
 
+    // Children are clipped to the padding box (the inner edge of the border) if overflow: hidden / scroll is set
+    let clip_rect = if node_needs_to_clip_children(&rect.layout) {
+        Some(subtract_padding(&display_list_rect_bounds, &resolved_border_widths))
+    } else {
+        None
+    };
+
+    let position = match get_position(rect) {
+        LayoutPosition::Fixed => FramePosition::Fixed,
+        LayoutPosition::Sticky => {
+            // The sticky frame can't be moved outside of the content box of its parent
+            let layout_result = &referenced_mutable_content.layout_result[dom_id];
+            let parent_content_box = match referenced_content.node_hierarchy[*rect_idx].parent {
+                Some(parent_id) => {
+                    let parent = &layout_result.rects[parent_id];
+                    subtract_padding(&subtract_padding(&parent.bounds, &parent.border_widths), &parent.padding)
+                },
+                None => display_list_rect_bounds,
+            };
+            let to_pixels = |value: PixelValue, percent_resolve: f32| {
+                value.to_pixels_in_viewport(percent_resolve, window_size.dimensions.width, window_size.dimensions.height)
+            };
+            FramePosition::Sticky(StickyFrame {
+                top: rect.layout.top.and_then(|t| t.get_property().map(|t| to_pixels(t.0, parent_content_box.size.height))),
+                right: rect.layout.right.and_then(|r| r.get_property().map(|r| to_pixels(r.0, parent_content_box.size.width))),
+                bottom: rect.layout.bottom.and_then(|b| b.get_property().map(|b| to_pixels(b.0, parent_content_box.size.height))),
+                left: rect.layout.left.and_then(|l| l.get_property().map(|l| to_pixels(l.0, parent_content_box.size.width))),
+                vertical_offset_bounds: (
+                    (parent_content_box.min_y() - display_list_rect_bounds.min_y()).min(0.0),
+                    (parent_content_box.max_y() - display_list_rect_bounds.max_y()).max(0.0),
+                ),
+                horizontal_offset_bounds: (
+                    (parent_content_box.min_x() - display_list_rect_bounds.min_x()).min(0.0),
+                    (parent_content_box.max_x() - display_list_rect_bounds.max_x()).max(0.0),
+                ),
+            })
+        },
+        _ => FramePosition::Scrolled,
+    };
+
     let tag_id = rect.tag.map(|tag| (tag, 0)).or({
         referenced_mutable_content.scrollable_nodes[dom_id].overflowing_nodes
         .get(&rect_idx)
@@ -625,7 +898,7 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
 
     let mut frame = DisplayListFrame {
         tag: tag_id,
-        clip_rect: None,
+        clip_rect,
         border_radius: StyleBorderRadius {
             top_left: rect.style.border_top_left_radius,
             top_right: rect.style.border_top_right_radius,
@@ -633,6 +906,10 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
             bottom_right: rect.style.border_bottom_right_radius,
         },
         rect: display_list_rect_bounds,
+        transform: rect.style.get_transform(&display_list_rect_bounds),
+        filters: rect.style.get_filters(),
+        mix_blend_mode: rect.style.get_mix_blend_mode(),
+        position,
         content: Vec::new(),
         children: Vec::new(),
     };
@@ -640,10 +917,10 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
     if rect.style.has_box_shadow() {
         frame.content.push(LayoutRectContent::BoxShadow {
             shadow: StyleBoxShadow {
-                left: rect.style.box_shadow_left,
-                right: rect.style.box_shadow_right,
-                top: rect.style.box_shadow_top,
-                bottom: rect.style.box_shadow_bottom,
+                left: rect.style.box_shadow_left.clone(),
+                right: rect.style.box_shadow_right.clone(),
+                top: rect.style.box_shadow_top.clone(),
+                bottom: rect.style.box_shadow_bottom.clone(),
             },
             clip_mode: BoxShadowClipMode::Outset,
         });
@@ -651,7 +928,7 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
 
     // If the rect is hit-testing relevant, we need to push a rect anyway.
     // Otherwise the hit-testing gets confused
-    if let Some(bg) = rect.style.background.as_ref().and_then(|br| br.get_property()) {
+    if let Some(background_layers) = rect.style.background.as_ref().and_then(|br| br.get_property()) {
 
         use azul_css::{CssImageId, StyleBackgroundContent::*};
         use azul_core::display_list::RectBackground;
@@ -662,20 +939,36 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
             Some(RectBackground::Image(*image_info))
         }
 
-        let background_content = match bg {
-            LinearGradient(lg) => Some(RectBackground::LinearGradient(lg.clone())),
-            RadialGradient(rg) => Some(RectBackground::RadialGradient(rg.clone())),
-            Image(style_image_id) => get_image_info(referenced_mutable_content.app_resources, style_image_id),
-            Color(c) => Some(RectBackground::Color(*c)),
-        };
-
-        if let Some(background_content) = background_content {
-            frame.content.push(LayoutRectContent::Background {
-                content: background_content,
-                size: rect.style.background_size.and_then(|bs| bs.get_property().cloned()),
-                offset: rect.style.background_position.and_then(|bs| bs.get_property().cloned()),
-                repeat: rect.style.background_repeat.and_then(|bs| bs.get_property().cloned()),
-            });
+        let background_sizes = rect.style.background_size.as_ref().and_then(|bs| bs.get_property());
+        let background_positions = rect.style.background_position.as_ref().and_then(|bp| bp.get_property());
+        let background_repeats = rect.style.background_repeat.as_ref().and_then(|br| br.get_property());
+
+        // The first layer is painted on top, so the layers have to be pushed in reverse order
+        for (layer_idx, bg) in background_layers.0.iter().enumerate().rev() {
+
+            let background_content = match bg {
+                LinearGradient(lg) => Some(RectBackground::LinearGradient(lg.clone())),
+                RadialGradient(rg) => Some(RectBackground::RadialGradient(rg.clone())),
+                ConicGradient(cg) => get_conic_gradient_image(
+                    referenced_mutable_content.app_resources,
+                    referenced_mutable_content.render_api,
+                    cg,
+                    display_list_rect_bounds.size,
+                    background_sizes.and_then(|bs| bs.get_layer(layer_idx).cloned()),
+                    window_size,
+                ),
+                Image(style_image_id) => get_image_info(referenced_mutable_content.app_resources, style_image_id),
+                Color(c) => Some(RectBackground::Color(*c)),
+            };
+
+            if let Some(background_content) = background_content {
+                frame.content.push(LayoutRectContent::Background {
+                    content: background_content,
+                    size: background_sizes.and_then(|bs| bs.get_layer(layer_idx).cloned()),
+                    offset: background_positions.and_then(|bp| bp.get_layer(layer_idx).cloned()),
+                    repeat: background_repeats.and_then(|br| br.get_layer(layer_idx).cloned()),
+                });
+            }
         }
     }
 
@@ -684,22 +977,66 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
         Text(_) | Label(_) => {
             if let Some(layouted_glyphs) = referenced_mutable_content.layout_result[dom_id].layouted_glyph_cache.get(&rect_idx).cloned() {
 
-                use azul_core::ui_solver::DEFAULT_FONT_COLOR;
+                use azul_core::ui_solver::{DEFAULT_FONT_COLOR, DEFAULT_FONT_SIZE_PX};
                 use wr_translate::wr_translate_logical_size;
 
+                let layout_result = &referenced_mutable_content.layout_result[dom_id];
                 let text_color = rect.style.text_color.and_then(|tc| tc.get_property().cloned()).unwrap_or(DEFAULT_FONT_COLOR).0;
-                let positioned_words = &referenced_mutable_content.layout_result[dom_id].positioned_word_cache[&rect_idx];
+                let positioned_words = &layout_result.positioned_word_cache[&rect_idx];
                 let font_instance_key = positioned_words.1;
+                let font_size_px = layout_result.scaled_words.get(&rect_idx).map(|sw| sw.0.font_size_px).unwrap_or(DEFAULT_FONT_SIZE_PX as f32);
 
-                frame.content.push(get_text(
+                let text_clip_rect = get_text_clip_rect(
                     display_list_rect_bounds,
-                    &referenced_mutable_content.layout_result[dom_id].rects[*rect_idx].padding,
+                    &layout_result.rects[*rect_idx].padding,
                     wr_translate_logical_size(window_size.dimensions),
-                    layouted_glyphs,
-                    font_instance_key,
-                    text_color,
                     &rect.layout,
-                ));
+                );
+
+                let text_decoration = rect.style.text_decoration.and_then(|td| td.get_property().cloned()).unwrap_or_default();
+                let decoration_color = text_decoration.color.unwrap_or(text_color);
+                let decoration_thickness = text_decoration.thickness
+                    .map(|t| t.to_pixels_in_viewport(font_size_px, window_size.dimensions.width, window_size.dimensions.height))
+                    .unwrap_or(font_size_px * DEFAULT_TEXT_DECORATION_THICKNESS_EM)
+                    .max(1.0);
+
+                // Underlines and overlines are drawn below the text, line-throughs above the text
+                let mut lines_below_text = Vec::new();
+                if text_decoration.underline { lines_below_text.push(UNDERLINE_OFFSET_EM * font_size_px); }
+                if text_decoration.overline { lines_below_text.push(OVERLINE_OFFSET_EM * font_size_px); }
+                let mut lines_above_text = Vec::new();
+                if text_decoration.line_through { lines_above_text.push(LINE_THROUGH_OFFSET_EM * font_size_px); }
+
+                // Text shadows are painted below the text and all of its decorations
+                let text_shadows = rect.style.text_shadow.as_ref().and_then(|ts| ts.get_property().cloned()).map(|ts| ts.0).unwrap_or_default();
+                if !text_shadows.is_empty() {
+                    frame.content.push(LayoutRectContent::TextShadow {
+                        shadows: text_shadows,
+                        glyphs: layouted_glyphs.glyphs.clone(),
+                        font_instance_key,
+                        color: text_color,
+                        glyph_options: None,
+                        clip: text_clip_rect,
+                    });
+                }
+
+                if let Some(decoration) = get_text_decoration(&layouted_glyphs.glyphs, &lines_below_text, decoration_thickness, decoration_color, text_clip_rect) {
+                    frame.content.push(decoration);
+                }
+
+                let line_through = get_text_decoration(&layouted_glyphs.glyphs, &lines_above_text, decoration_thickness, decoration_color, text_clip_rect);
+
+                frame.content.push(LayoutRectContent::Text {
+                    glyphs: layouted_glyphs.glyphs,
+                    font_instance_key,
+                    color: text_color,
+                    glyph_options: None,
+                    clip: text_clip_rect,
+                });
+
+                if let Some(decoration) = line_through {
+                    frame.content.push(decoration);
+                }
             }
         },
         Image(image_id) => {
@@ -723,14 +1060,29 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
         },
     };
 
-    if rect.style.has_border() {
+    let border_widths = StyleBorderWidths {
+        top: rect.layout.border_top_width,
+        left: rect.layout.border_left_width,
+        bottom: rect.layout.border_bottom_width,
+        right: rect.layout.border_right_width,
+    };
+
+    // A border-image replaces the normal border, but only if the image is loaded
+    let nine_patch_border = rect.style.get_nine_patch_border().and_then(|border| {
+        let image_id = referenced_mutable_content.app_resources.get_css_image_id(&border.source.0)?;
+        let image = *referenced_mutable_content.app_resources.get_image_info(image_id)?;
+        Some((image, border))
+    });
+
+    if let Some((image, border)) = nine_patch_border {
+        frame.content.push(LayoutRectContent::NinePatchBorder {
+            image,
+            widths: border_widths,
+            border: Box::new(border),
+        });
+    } else if rect.style.has_border() {
         frame.content.push(LayoutRectContent::Border {
-            widths: StyleBorderWidths {
-                top: rect.layout.border_top_width,
-                left: rect.layout.border_left_width,
-                bottom: rect.layout.border_bottom_width,
-                right: rect.layout.border_right_width,
-            },
+            widths: border_widths,
             colors: StyleBorderColors {
                 top: rect.style.border_top_color,
                 left: rect.style.border_left_color,
@@ -749,10 +1101,10 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f, T, U: FontImageApi>(
     if rect.style.has_box_shadow() {
         frame.content.push(LayoutRectContent::BoxShadow {
             shadow: StyleBoxShadow {
-                left: rect.style.box_shadow_left,
-                right: rect.style.box_shadow_right,
-                top: rect.style.box_shadow_top,
-                bottom: rect.style.box_shadow_bottom,
+                left: rect.style.box_shadow_left.clone(),
+                right: rect.style.box_shadow_right.clone(),
+                top: rect.style.box_shadow_top.clone(),
+                bottom: rect.style.box_shadow_bottom.clone(),
             },
             clip_mode: BoxShadowClipMode::Inset,
         });
@@ -893,6 +1245,7 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
     use ui_state::ui_state_from_dom;
     use wr_translate::hidpi_rect_from_bounds;
     use azul_core::callbacks::IFrameCallbackInfoUnchecked;
+    use azul_css::MediaQueryEnvironment;
 
     let bounds = hidpi_rect_from_bounds(
         rect,
@@ -900,6 +1253,14 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
         rectangle.window_size.hidpi_factor
     );
 
+    // @media queries inside of the iframe are evaluated against the size of the iframe
+    let media_environment = MediaQueryEnvironment {
+        width: bounds.logical_size.width,
+        height: bounds.logical_size.height,
+        hidpi_factor: rectangle.window_size.hidpi_factor,
+        color_scheme: referenced_mutable_content.fake_window.state.color_scheme,
+    };
+
     let new_dom = {
         let iframe_info = IFrameCallbackInfoUnchecked {
             ptr: *iframe_pointer,
@@ -918,6 +1279,10 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
         None => return DisplayListMsg::Frame(DisplayListFrame {
             tag: None,
             clip_rect: None,
+            transform: None,
+            filters: Vec::new(),
+            mix_blend_mode: None,
+            position: FramePosition::Scrolled,
             rect,
             border_radius: StyleBorderRadius::default(),
             content: vec![],
@@ -939,6 +1304,7 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
         &mut focus_target,
         &hovered_nodes,
         is_mouse_down,
+        &media_environment,
     );
 
     let iframe_dom_id = ui_description.dom_id.clone();
@@ -948,7 +1314,8 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
     app_resources::add_fonts_and_images(
         referenced_mutable_content.app_resources,
         referenced_mutable_content.render_api,
-        &display_list
+        &display_list,
+        rect.size,
     );
 
     let arena = &ui_description.ui_descr_arena;
@@ -962,6 +1329,7 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
         &display_list.rectangles,
         &*referenced_mutable_content.app_resources,
         rect,
+        None,
     );
 
     let scrollable_nodes_iframe = get_nodes_that_need_scroll_clip(
@@ -972,6 +1340,7 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
     let rects_in_rendering_order = determine_rendering_order(
         node_hierarchy,
         &display_list.rectangles,
+        &scrollable_nodes_iframe,
     );
 
     referenced_mutable_content.scrollable_nodes.insert(iframe_dom_id.clone(), scrollable_nodes_iframe);
@@ -996,15 +1365,13 @@ fn call_iframe_callback<'a,'b,'c,'d,'e, T, U: FontImageApi>(
     )
 }
 
-fn get_text(
+/// Returns the rectangle that the text of a node is clipped to, depending on the overflow of the node
+fn get_text_clip_rect(
     bounds: LayoutRect,
     padding: &ResolvedOffsets,
     root_window_size: LayoutSize,
-    layouted_glyphs: LayoutedGlyphs,
-    font_instance_key: FontInstanceKey,
-    font_color: ColorU,
     rect_layout: &RectLayout,
-) -> LayoutRectContent {
+) -> Option<LayoutRect> {
 
     let overflow_horizontal_visible = rect_layout.is_horizontal_overflow_visible();
     let overflow_vertical_visible = rect_layout.is_horizontal_overflow_visible();
@@ -1012,7 +1379,7 @@ fn get_text(
     let padding_clip_bounds = subtract_padding(&bounds, padding);
 
     // Adjust the bounds by the padding, depending on the overflow:visible parameter
-    let text_clip_rect = match (overflow_horizontal_visible, overflow_vertical_visible) {
+    match (overflow_horizontal_visible, overflow_vertical_visible) {
         (true, true) => None,
         (false, false) => Some(padding_clip_bounds),
         (true, false) => {
@@ -1029,14 +1396,54 @@ fn get_text(
                 size: LayoutSize::new(padding_clip_bounds.size.width, root_window_size.height),
             })
         },
-    };
+    }
+}
+
+/// Offset of the center of an underline relative to the baseline, in multiples of the font size
+const UNDERLINE_OFFSET_EM: f32 = 0.15;
+/// Offset of the center of an overline relative to the baseline, in multiples of the font size
+const OVERLINE_OFFSET_EM: f32 = -0.8;
+/// Offset of the center of a line-through relative to the baseline, in multiples of the font size
+const LINE_THROUGH_OFFSET_EM: f32 = -0.3;
+/// Thickness of the `text-decoration` lines if no thickness is given, in multiples of the font size
+const DEFAULT_TEXT_DECORATION_THICKNESS_EM: f32 = 1.0 / 14.0;
+
+/// Returns the lines of a `text-decoration`: For every line of text, one line
+/// is drawn at each of the `offsets` (relative to the baseline of the text)
+fn get_text_decoration(
+    glyphs: &[GlyphInstance],
+    offsets: &[f32],
+    thickness: f32,
+    color: ColorU,
+    clip: Option<LayoutRect>,
+) -> Option<LayoutRectContent> {
+
+    if offsets.is_empty() {
+        return None;
+    }
+
+    // Glyphs on the same line of text have the same baseline: (baseline, start x, end x)
+    let mut text_lines: Vec<(f32, f32, f32)> = Vec::new();
+
+    for glyph in glyphs {
+        let glyph_end_x = glyph.point.x + glyph.size.width;
+        match text_lines.last_mut() {
+            Some((baseline, _, end_x)) if (*baseline - glyph.point.y).abs() < 0.5 => {
+                *end_x = end_x.max(glyph_end_x);
+            },
+            _ => text_lines.push((glyph.point.y, glyph.point.x, glyph_end_x)),
+        }
+    }
+
+    let lines = text_lines.iter().flat_map(|(baseline, start_x, end_x)| offsets.iter().map(move |offset| LayoutRect::new(
+        LayoutPoint::new(*start_x, baseline + offset - (thickness / 2.0)),
+        LayoutSize::new(end_x - start_x, thickness),
+    ))).collect::<Vec<_>>();
 
-    LayoutRectContent::Text {
-        glyphs: layouted_glyphs.glyphs,
-        font_instance_key,
-        color: font_color,
-        glyph_options: None,
-        clip: text_clip_rect,
+    if lines.is_empty() {
+        None
+    } else {
+        Some(LayoutRectContent::TextDecoration { lines, color, clip })
     }
 }
 
@@ -1083,7 +1490,14 @@ fn populate_css_properties(
             None
         },
         Dynamic(dynamic_property) => {
-            let overridden_property = css_overrides.get(&node_id).and_then(|overrides| overrides.get(&dynamic_property.dynamic_id.clone().into()))?;
+            let overridden_property = match css_overrides.get(&node_id).and_then(|overrides| overrides.get(&dynamic_property.dynamic_id.clone().into())) {
+                Some(overridden_property) => overridden_property,
+                None => {
+                    // Not overridden from Rust, use the fallback of the `var()`
+                    apply_style_property(rect_style, rect_layout, &dynamic_property.default_value);
+                    return None;
+                },
+            };
 
             // Apply the property default if the discriminant of the two types matches
             if mem::discriminant(overridden_property) == mem::discriminant(&dynamic_property.default_value) {
@@ -1113,6 +1527,10 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         TextColor(c)                    => style.text_color = Some(*c),
         FontSize(fs)                    => style.font_size = Some(*fs),
         FontFamily(ff)                  => style.font_family = Some(ff.clone()),
+        FontWeight(fw)                  => style.font_weight = Some(*fw),
+        FontStyle(fs)                   => style.font_style = Some(*fs),
+        TextDecoration(td)              => style.text_decoration = Some(*td),
+        TextShadow(ts)                  => style.text_shadow = Some(ts.clone()),
         TextAlign(ta)                   => style.text_align = Some(*ta),
 
         LetterSpacing(ls)               => style.letter_spacing = Some(*ls),
@@ -1131,6 +1549,7 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         Position(p)                     => layout.position = Some(*p),
         Top(t)                          => layout.top = Some(*t),
         Bottom(b)                       => layout.bottom = Some(*b),
+        ZIndex(z)                       => layout.z_index = Some(*z),
         Right(r)                        => layout.right = Some(*r),
         Left(l)                         => layout.left = Some(*l),
 
@@ -1142,10 +1561,20 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         AlignItems(ai)                  => layout.align_items = Some(*ai),
         AlignContent(ac)                => layout.align_content = Some(*ac),
 
+        GridTemplateColumns(gtc)        => layout.grid_template_columns = Some(gtc.clone()),
+        GridTemplateRows(gtr)           => layout.grid_template_rows = Some(gtr.clone()),
+        GridColumnStart(gcs)            => layout.grid_column_start = Some(*gcs),
+        GridColumnEnd(gce)              => layout.grid_column_end = Some(*gce),
+        GridRowStart(grs)               => layout.grid_row_start = Some(*grs),
+        GridRowEnd(gre)                 => layout.grid_row_end = Some(*gre),
+        GridAutoFlow(gaf)               => layout.grid_auto_flow = Some(*gaf),
+        RowGap(rg)                      => layout.row_gap = Some(*rg),
+        ColumnGap(cg)                   => layout.column_gap = Some(*cg),
+
         BackgroundContent(bc)           => style.background = Some(bc.clone()),
-        BackgroundPosition(bp)          => style.background_position = Some(*bp),
-        BackgroundSize(bs)              => style.background_size = Some(*bs),
-        BackgroundRepeat(br)            => style.background_repeat = Some(*br),
+        BackgroundPosition(bp)          => style.background_position = Some(bp.clone()),
+        BackgroundSize(bs)              => style.background_size = Some(bs.clone()),
+        BackgroundRepeat(br)            => style.background_repeat = Some(br.clone()),
 
         OverflowX(ox)                   => layout.overflow_x = Some(*ox),
         OverflowY(oy)                   => layout.overflow_y = Some(*oy),
@@ -1180,10 +1609,26 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         BorderLeftWidth(blw)            => layout.border_left_width = Some(*blw),
         BorderBottomWidth(bbw)          => layout.border_bottom_width = Some(*bbw),
 
-        BoxShadowLeft(bsl)              => style.box_shadow_left = Some(*bsl),
-        BoxShadowRight(bsr)             => style.box_shadow_right = Some(*bsr),
-        BoxShadowTop(bst)               => style.box_shadow_top = Some(*bst),
-        BoxShadowBottom(bsb)            => style.box_shadow_bottom = Some(*bsb),
+        BorderImageSource(bis)          => style.border_image_source = Some(bis.clone()),
+        BorderImageSlice(bis)           => style.border_image_slice = Some(*bis),
+        BorderImageWidth(biw)           => style.border_image_width = Some(*biw),
+        BorderImageOutset(bio)          => style.border_image_outset = Some(*bio),
+        BorderImageRepeat(bir)          => style.border_image_repeat = Some(*bir),
+
+        BoxShadowLeft(bsl)              => style.box_shadow_left = Some(bsl.clone()),
+        BoxShadowRight(bsr)             => style.box_shadow_right = Some(bsr.clone()),
+        BoxShadowTop(bst)               => style.box_shadow_top = Some(bst.clone()),
+        BoxShadowBottom(bsb)            => style.box_shadow_bottom = Some(bsb.clone()),
+
+        Transform(t)                    => style.transform = Some(t.clone()),
+        TransformOrigin(o)              => style.transform_origin = Some(*o),
+
+        Opacity(o)                      => style.opacity = Some(*o),
+        Filter(f)                       => style.filter = Some(f.clone()),
+        MixBlendMode(m)                 => style.mix_blend_mode = Some(*m),
+
+        // Already resolved to the animated values by the AnimationState before the display list is built
+        Transition(_) | Animation(_)    => { },
     }
 }
 
@@ -1193,9 +1638,9 @@ fn test_overflow_parsing() {
 
     let layout1 = RectLayout::default();
 
-    // The default for overflowing is overflow: auto, which clips
-    // children, so this should evaluate to true by default
-    assert_eq!(node_needs_to_clip_children(&layout1), true);
+    // The default for overflowing is overflow: auto, which only clips
+    // scrollable nodes, so this should evaluate to false by default
+    assert_eq!(node_needs_to_clip_children(&layout1), false);
 
     let layout2 = RectLayout {
         overflow_x: Some(CssPropertyValue::Exact(Overflow::Visible)),
@@ -1210,4 +1655,131 @@ fn test_overflow_parsing() {
         .. Default::default()
     };
     assert_eq!(node_needs_to_clip_children(&layout3), true);
+
+    let layout4 = RectLayout {
+        overflow_y: Some(CssPropertyValue::Exact(Overflow::Scroll)),
+        .. Default::default()
+    };
+    assert_eq!(node_needs_to_clip_children(&layout4), true);
+}
+
+#[cfg(test)]
+fn positioned_layout(position: LayoutPosition, z_index: Option<i32>) -> RectLayout {
+    use azul_css::LayoutZIndex;
+    RectLayout {
+        position: Some(CssPropertyValue::Exact(position)),
+        z_index: z_index.map(|z| CssPropertyValue::Exact(LayoutZIndex(z))),
+        .. Default::default()
+    }
+}
+
+/// Returns the node IDs of the DOM in the order that they are painted in
+#[cfg(test)]
+fn get_painting_order(dom: &::dom::Dom<()>, rects: Vec<(RectLayout, RectStyle)>) -> Vec<usize> {
+
+    fn flatten(group: &ContentGroup, order: &mut Vec<usize>) {
+        order.push(group.root.index());
+        for child in &group.children {
+            flatten(child, order);
+        }
+    }
+
+    let styled_node = StyledNode::default();
+    let mut rectangles = dom.arena.node_data.transform(|_, _| DisplayRectangle::new(None, &styled_node));
+    for (rect, (layout, style)) in rectangles.internal.iter_mut().zip(rects) {
+        rect.layout = layout;
+        rect.style = style;
+    }
+
+    let content_group = determine_rendering_order(&dom.arena.node_layout, &rectangles, &ScrolledNodes::default());
+    let mut order = Vec::new();
+    flatten(&content_group, &mut order);
+    order
+}
+
+#[test]
+fn test_rendering_order_of_siblings() {
+    use dom::Dom;
+    use azul_css::LayoutPosition::*;
+
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div());
+
+    let rects = vec![
+        (RectLayout::default(), RectStyle::default()),
+        (RectLayout::default(), RectStyle::default()),
+        (positioned_layout(Relative, Some(2)), RectStyle::default()),
+        (positioned_layout(Relative, Some(-1)), RectStyle::default()),
+        (positioned_layout(Relative, None), RectStyle::default()),
+        (positioned_layout(Relative, Some(0)), RectStyle::default()),
+        (positioned_layout(Absolute, Some(1)), RectStyle::default()),
+        (RectLayout::default(), RectStyle::default()),
+    ];
+
+    // negative z-index, in-flow nodes, z-index: auto / 0, positive z-index - DOM order inside of each layer
+    assert_eq!(get_painting_order(&dom, rects), vec![0, 3, 1, 7, 4, 5, 6, 2]);
+}
+
+#[test]
+fn test_z_index_is_ignored_on_static_nodes() {
+    use dom::Dom;
+
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div())
+        .with_child(Dom::div());
+
+    let rects = vec![
+        (RectLayout::default(), RectStyle::default()),
+        (positioned_layout(LayoutPosition::Static, Some(10)), RectStyle::default()),
+        (RectLayout::default(), RectStyle::default()),
+    ];
+
+    let styled_node = StyledNode::default();
+    let static_rect = DisplayRectangle {
+        layout: rects[1].0.clone(),
+        .. DisplayRectangle::new(None, &styled_node)
+    };
+    assert_eq!(get_paint_layer(&static_rect), (1, 0));
+    assert_eq!(get_painting_order(&dom, rects), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_rendering_order_of_nested_z_index() {
+    use dom::Dom;
+    use azul_css::{LayoutPosition::*, StyleMixBlendMode};
+
+    // 1 doesn't establish a stacking context, so 2 is painted by the root,
+    // while 4 and 6 keep their z-indexed children
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div()
+            .with_child(Dom::div())
+            .with_child(Dom::div()))
+        .with_child(Dom::div()
+            .with_child(Dom::div()))
+        .with_child(Dom::div()
+            .with_child(Dom::div()));
+
+    let blended = RectStyle {
+        mix_blend_mode: Some(CssPropertyValue::Exact(StyleMixBlendMode::Multiply)),
+        .. Default::default()
+    };
+
+    let rects = vec![
+        (RectLayout::default(), RectStyle::default()),
+        (positioned_layout(Relative, None), RectStyle::default()),
+        (positioned_layout(Relative, Some(5)), RectStyle::default()),
+        (RectLayout::default(), RectStyle::default()),
+        (positioned_layout(Relative, Some(1)), RectStyle::default()),
+        (positioned_layout(Relative, Some(100)), RectStyle::default()),
+        (RectLayout::default(), blended),
+        (positioned_layout(Relative, Some(-5)), RectStyle::default()),
+    ];
+
+    assert_eq!(get_painting_order(&dom, rects), vec![0, 6, 7, 1, 3, 4, 5, 2]);
 }
\ No newline at end of file
@@ -252,8 +252,12 @@ pub fn position_words(
             text_layout_options.max_horizontal_width,
         );
 
-        if let LineCaretIntersection::PushCaretOntoNextLine(_, _) = caret_intersection {
-             line_breaks.push((current_word_idx, line_caret_x));
+        if let LineCaretIntersection::PushCaretOntoNextLine(num_lines, _) = caret_intersection {
+            line_breaks.push((current_word_idx, line_caret_x));
+            // Lines that are skipped because they are fully covered by holes are empty
+            for _ in 1..num_lines {
+                line_breaks.push((current_word_idx, 0.0));
+            }
         }
 
         // Correct and advance the line caret position
@@ -302,13 +306,23 @@ pub fn position_words(
             text_layout_options.max_horizontal_width,
         );
 
-        let mut is_line_break = false;
-        if let LineCaretIntersection::PushCaretOntoNextLine(_, _) = caret_intersection {
-            line_breaks.push((current_word_idx, line_caret_x));
-            is_line_break = true;
+        // If the end of the word would be inside of a hole, the word is moved behind the hole
+        let mut is_caret_moved = false;
+        match caret_intersection {
+            LineCaretIntersection::NoIntersection => { },
+            LineCaretIntersection::AdvanceCaretTo(_) => {
+                is_caret_moved = true;
+            },
+            LineCaretIntersection::PushCaretOntoNextLine(num_lines, _) => {
+                line_breaks.push((current_word_idx, line_caret_x));
+                for _ in 1..num_lines {
+                    line_breaks.push((current_word_idx, 0.0));
+                }
+                is_caret_moved = true;
+            },
         }
 
-        if !is_line_break {
+        if !is_caret_moved {
             let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
             word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
         }
@@ -323,7 +337,7 @@ pub fn position_words(
         line_caret_x = new_caret_x;
 
         // If there was a line break, the position needs to be determined after the line break happened
-        if is_line_break {
+        if is_caret_moved {
             let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
             word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
             // important! - if the word is pushed onto the next line, the caret has to be
@@ -363,10 +377,18 @@ pub fn position_words(
         }
     }
 
-    // Handle the last word, but ignore any last Return, Space or Tab characters
+    // Handle the last word, but ignore any last Return, Space or Tab characters -
+    // unless the text is followed by other inline boxes of the same paragraph
     for word in &words.items[words.items.len().saturating_sub(1)..] {
-        if word.word_type == Word {
-            handle_word!();
+        match word.word_type {
+            Word => { handle_word!(); },
+            Space if text_layout_options.is_inline_fragment => {
+                line_caret_x += word_spacing_px;
+            },
+            Tab if text_layout_options.is_inline_fragment => {
+                line_caret_x += word_spacing_px + tab_width_px;
+            },
+            _ => { },
         }
         line_breaks.push((current_word_idx, line_caret_x));
     }
@@ -488,8 +510,9 @@ enum LineCaretIntersection {
 ///
 /// - `line_caret_x`: The current horizontal caret position
 /// - `line_number`: The current line number
-/// - `holes`: Whether the text should respect any rectangular regions
-///    where the text can't flow (preparation for inline / float layout).
+/// - `holes`: Rectangular regions where the text can't flow, i.e. floating boxes. Holes
+///    that reach the `max_width` push the caret onto the next line, other holes move the
+///    caret to their right edge.
 /// - `max_width`: Does the text have a restriction on how wide it can be (in pixels)
 fn caret_intersects_with_holes(
     line_caret_x: f32,
@@ -500,71 +523,55 @@ fn caret_intersects_with_holes(
     max_width: Option<f32>,
 ) -> LineCaretIntersection {
 
-    let mut new_line_caret_x = None;
+    let mut new_line_caret_x = line_caret_x;
     let mut line_advance = 0;
+    let mut should_move_caret = false;
 
     // If the caret is outside of the max_width, move it to the start of a new line
     if let Some(max_width) = max_width {
         if line_caret_x > max_width {
-            new_line_caret_x = Some(0.0);
+            new_line_caret_x = 0.0;
             line_advance += 1;
+            should_move_caret = true;
         }
     }
 
-    for hole in holes {
+    // NOTE: The holes don't need to be sorted - since the caret only ever moves
+    // to the right or down, the search ends once no hole contains the caret.
+    loop {
 
-        let mut should_move_caret = false;
-        let mut current_line_advance = 0;
-        let mut new_line_number = line_number + current_line_advance;
-        let mut current_caret = LayoutPoint::new(
-            new_line_caret_x.unwrap_or(line_caret_x),
-            get_line_y_position(new_line_number, font_size_px, line_height_px)
+        let current_caret = LayoutPoint::new(
+            new_line_caret_x,
+            get_line_y_position(line_number + line_advance, font_size_px, line_height_px)
         );
 
-        // NOTE: holes need to be sorted by Y origin (from smallest to largest Y),
-        // and be sorted from left to right
-        while hole.contains(&current_caret) {
-            should_move_caret = true;
-            if let Some(max_width) = max_width {
-                if hole.origin.x + hole.size.width >= max_width {
-                    // Need to break the line here
-                    current_line_advance += 1;
-                    new_line_number = line_number + current_line_advance;
-                    current_caret = LayoutPoint::new(
-                        new_line_caret_x.unwrap_or(line_caret_x),
-                        get_line_y_position(new_line_number, font_size_px, line_height_px)
-                    );
-                } else {
-                    new_line_number = line_number + current_line_advance;
-                    current_caret = LayoutPoint::new(
-                        hole.origin.x + hole.size.width,
-                        get_line_y_position(new_line_number, font_size_px, line_height_px)
-                    );
-                }
-            } else {
-                // No max width, so no need to break the line, move the caret to the right side of the hole
-                new_line_number = line_number + current_line_advance;
-                current_caret = LayoutPoint::new(
-                    hole.origin.x + hole.size.width,
-                    get_line_y_position(new_line_number, font_size_px, line_height_px)
-                );
-            }
-        }
+        let hole = match holes.iter().find(|hole| hole.contains(&current_caret)) {
+            Some(s) => s,
+            None => break,
+        };
 
-        if should_move_caret {
-            new_line_caret_x = Some(current_caret.x);
-            line_advance += current_line_advance;
+        should_move_caret = true;
+        let hole_max_x = hole.origin.x + hole.size.width;
+
+        match max_width {
+            // Need to break the line here, the start of the next line can be inside of a hole, too
+            Some(max_width) if hole_max_x >= max_width => {
+                new_line_caret_x = 0.0;
+                line_advance += 1;
+            },
+            // Move the caret to the right side of the hole
+            _ => {
+                new_line_caret_x = hole_max_x;
+            },
         }
     }
 
-    if let Some(new_line_caret_x) = new_line_caret_x {
-        if line_advance == 0 {
-            LineCaretIntersection::AdvanceCaretTo(new_line_caret_x)
-        } else {
-            LineCaretIntersection::PushCaretOntoNextLine(line_advance, new_line_caret_x)
-        }
-    } else {
+    if !should_move_caret {
         LineCaretIntersection::NoIntersection
+    } else if line_advance == 0 {
+        LineCaretIntersection::AdvanceCaretTo(new_line_caret_x)
+    } else {
+        LineCaretIntersection::PushCaretOntoNextLine(line_advance, new_line_caret_x)
     }
 }
 
@@ -803,3 +810,41 @@ fn test_caret_intersects_with_holes_4() {
 
     assert_eq!(result, LineCaretIntersection::NoIntersection);
 }
+
+// Scenario 5:
+//
+// +----------------+
+// |-----+    +--->-|
+// |     |    |     |
+// |  +->|    |     |
+// |-----+    +-----|
+// |                |
+// +----------------+
+// rectangles: 50x100 @ 0x, 0y (float: left), 50x100 @ 150x, 0y (float: right)
+// max-width: 200px, line-height 1.0, font-size: 20
+// cursor is at: 160x, 20y
+// expect cursor to advance to 50x, 40y (+= 1 lines, leading of 50px)
+//
+#[test]
+fn test_caret_intersects_with_holes_5() {
+    let line_caret_x = 160.0;
+    let line_number = 0;
+    let font_size_px = 20.0;
+    let line_height_px = 0.0;
+    let max_width = Some(200.0);
+    let holes = vec![
+        LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(50.0, 100.0)),
+        LayoutRect::new(LayoutPoint::new(150.0, 0.0), LayoutSize::new(50.0, 100.0)),
+    ];
+
+    let result = caret_intersects_with_holes(
+        line_caret_x,
+        line_number,
+        font_size_px,
+        line_height_px,
+        &holes,
+        max_width,
+    );
+
+    assert_eq!(result, LineCaretIntersection::PushCaretOntoNextLine(1, 50.0));
+}
@@ -1,21 +1,21 @@
 use std::{f32, collections::BTreeMap};
 use azul_css::{
-    RectLayout, StyleFontSize, RectStyle,
+    RectLayout, StyleFontSize, StyleFontWeight, StyleFontStyle, RectStyle,
     StyleTextAlignmentHorz, StyleTextAlignmentVert,
-    LayoutRect, LayoutSize,
+    LayoutRect, LayoutSize, LayoutTransform,
 };
 use {
     id_tree::{NodeId, NodeDataContainer, NodeHierarchy},
     display_list::DisplayRectangle,
     dom::{NodeData, NodeType},
-    app_resources::AppResources,
+    app_resources::{AppResources, ImmediateFontId},
     text_layout::{Words, ScaledWords, WordPositions, LayoutedGlyphs},
 };
 use azul_core::{
     app_resources::{Au, FontInstanceKey},
     ui_solver::{PositionedRectangle, InlineTextLayout, LayoutResult, ResolvedTextLayoutOptions},
 };
-use azul_layout::{GetTextLayout, RectContent};
+use azul_layout::{GetTextLayout, RectContent, LayoutCache};
 
 type PixelSize = f32;
 
@@ -56,14 +56,35 @@ pub(crate) fn get_font_id(rect_style: &RectStyle) -> &str {
     font_id.map(|f| f.get_str()).unwrap_or(DEFAULT_FONT_ID)
 }
 
-pub(crate) fn get_font_size(rect_style: &RectStyle) -> StyleFontSize {
+/// Returns the font size with its viewport-relative parts (`vw`, `vh`, ...) resolved against the `viewport_size`
+pub(crate) fn get_font_size(rect_style: &RectStyle, viewport_size: LayoutSize) -> StyleFontSize {
     use azul_core::ui_solver::DEFAULT_FONT_SIZE;
-    rect_style.font_size.and_then(|fs| fs.get_property().cloned()).unwrap_or(DEFAULT_FONT_SIZE)
+    let font_size = rect_style.font_size.and_then(|fs| fs.get_property().cloned()).unwrap_or(DEFAULT_FONT_SIZE);
+    StyleFontSize(font_size.0.resolve_viewport_units(viewport_size.width, viewport_size.height))
+}
+
+pub(crate) fn get_font_weight(rect_style: &RectStyle) -> StyleFontWeight {
+    rect_style.font_weight.and_then(|fw| fw.get_property().cloned()).unwrap_or_default()
+}
+
+pub(crate) fn get_font_style(rect_style: &RectStyle) -> StyleFontStyle {
+    rect_style.font_style.and_then(|fs| fs.get_property().cloned()).unwrap_or_default()
+}
+
+/// Selects the face of the `font-family` that matches the `font-weight` and `font-style` best.
+/// Fonts that aren't registered in the `AppResources` are loaded from the system (regular face only).
+pub(crate) fn get_immediate_font_id(app_resources: &AppResources, rect_style: &RectStyle) -> ImmediateFontId {
+    let css_font_id = get_font_id(rect_style);
+    match app_resources.select_css_font_face_id(css_font_id, get_font_weight(rect_style), get_font_style(rect_style)) {
+        Some(s) => ImmediateFontId::Resolved(s),
+        None => ImmediateFontId::Unresolved(css_font_id.to_string()),
+    }
 }
 
 pub struct InlineText<'a> {
     words: &'a Words,
     scaled_words: &'a ScaledWords,
+    font_instance_key: FontInstanceKey,
 }
 
 impl<'a> GetTextLayout for InlineText<'a> {
@@ -77,6 +98,15 @@ impl<'a> GetTextLayout for InlineText<'a> {
         // TODO: Cache the layouted text block on the &mut self
         text_layout::word_positions_to_inline_text_layout(&layouted_text_block, &self.scaled_words)
     }
+
+    fn get_text_hash(&self) -> Option<u64> {
+        use std::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher as HashAlgorithm;
+        let mut hasher = HashAlgorithm::default();
+        self.words.internal_str.hash(&mut hasher);
+        self.font_instance_key.hash(&mut hasher);
+        Some(hasher.finish())
+    }
 }
 
 /// At this point in time, all font keys, image keys, etc. have
@@ -87,6 +117,7 @@ pub(crate) fn do_the_layout<'a,'b, T>(
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
     app_resources: &'b AppResources,
     bounding_rect: LayoutRect,
+    layout_cache: Option<&mut LayoutCache>,
 ) -> LayoutResult {
 
     use azul_layout::SolvedUi;
@@ -98,10 +129,13 @@ pub(crate) fn do_the_layout<'a,'b, T>(
     // 5. return to caller, caller will do final text layout (not the job of the layout engine)
 
     let word_cache = create_word_cache(app_resources, node_data);
-    let scaled_words = create_scaled_words(app_resources, &word_cache, display_rects);
+    let scaled_words = create_scaled_words(app_resources, &word_cache, display_rects, bounding_rect.size);
     let mut solved_ui = {
         let rect_contents = create_rect_contents_cache(&word_cache, &scaled_words, node_data, app_resources);
-        SolvedUi::new(bounding_rect, node_hierarchy, display_rects, rect_contents)
+        match layout_cache {
+            Some(layout_cache) => SolvedUi::new_cached(bounding_rect, node_hierarchy, display_rects, rect_contents, layout_cache),
+            None => SolvedUi::new(bounding_rect, node_hierarchy, display_rects, rect_contents),
+        }
     };
 
     // TODO: overflowing rects!
@@ -110,6 +144,7 @@ pub(crate) fn do_the_layout<'a,'b, T>(
     let positioned_word_cache = create_word_positions(&word_cache, &scaled_words, &solved_ui.solved_rects);
     let layouted_glyph_cache = get_glyphs(node_hierarchy, &scaled_words, &positioned_word_cache, &display_rects, &mut solved_ui.solved_rects);
     let node_depths = node_hierarchy.get_parents_sorted_by_depth();
+    let transforms = get_transforms(node_hierarchy, display_rects, &solved_ui.solved_rects, &node_depths);
 
     // TODO: Set the final content sizes on layouted_rects!
 
@@ -120,6 +155,7 @@ pub(crate) fn do_the_layout<'a,'b, T>(
         positioned_word_cache,
         layouted_glyph_cache,
         node_depths,
+        transforms,
     }
 }
 
@@ -145,22 +181,18 @@ fn create_scaled_words<'a>(
     app_resources: &AppResources,
     words: &BTreeMap<NodeId, Words>,
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
+    viewport_size: LayoutSize,
 ) -> BTreeMap<NodeId, (ScaledWords, FontInstanceKey)> {
 
     use text_layout::words_to_scaled_words;
-    use app_resources::ImmediateFontId;
     use azul_core::ui_solver::DEFAULT_FONT_SIZE_PX;
 
     words.iter().filter_map(|(node_id, words)| {
 
         let style = &display_rects[*node_id].style;
-        let font_size = get_font_size(&style);
+        let font_size = get_font_size(&style, viewport_size);
         let font_size_au = font_size_to_au(font_size);
-        let css_font_id = get_font_id(&style);
-        let font_id = match app_resources.get_css_font_id(css_font_id) {
-            Some(s) => ImmediateFontId::Resolved(*s),
-            None => ImmediateFontId::Unresolved(css_font_id.to_string()),
-        };
+        let font_id = get_immediate_font_id(app_resources, &style);
 
         let loaded_font = app_resources.get_loaded_font(&font_id)?;
         let font_instance_key = loaded_font.font_instances.get(&font_size_au)?;
@@ -192,9 +224,11 @@ fn create_rect_contents_cache<'a, T>(
                 Some((node_id, RectContent::Image(w, h)))
             },
             Text(_) | Label(_) => {
+                let (scaled_words, font_instance_key) = scaled_words.get(&node_id)?;
                 Some((node_id, RectContent::Text(InlineText {
                     words: words.get(&node_id)?,
-                    scaled_words: scaled_words.get(&node_id).map(|(sw, _)| sw)?,
+                    scaled_words,
+                    font_instance_key: *font_instance_key,
                 })))
             },
             _ => None,
@@ -239,15 +273,56 @@ fn get_glyphs<'a>(
             Some(parent) => positioned_rectangles[*parent].bounds,
         };
         let bounds = positioned_rectangles[*node_id].bounds;
-        let (_, inline_text_layout, _) = positioned_rectangles[*node_id].resolved_text_layout_options.as_mut()?;
-        inline_text_layout.align_children_horizontal(horz_alignment);
-        inline_text_layout.align_children_vertical_in_parent_bounds(&parent_bounds, vert_alignment);
+        let (text_layout_options, inline_text_layout, _) = positioned_rectangles[*node_id].resolved_text_layout_options.as_mut()?;
+        // The lines of a paragraph with inline boxes are already aligned by the layout solver
+        if !text_layout_options.is_inline_fragment {
+            inline_text_layout.align_children_horizontal(horz_alignment);
+            inline_text_layout.align_children_vertical_in_parent_bounds(&parent_bounds, vert_alignment);
+        }
 
         let glyphs = get_layouted_glyphs(word_positions, scaled_words, &inline_text_layout, bounds.origin);
         Some((*node_id, glyphs))
     }).collect()
 }
 
+/// Combines the CSS `transform` of every node with the transforms of its parents
+fn get_transforms<'a>(
+    node_hierarchy: &NodeHierarchy,
+    display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
+    positioned_rects: &NodeDataContainer<PositionedRectangle>,
+    node_depths: &[(usize, NodeId)],
+) -> BTreeMap<NodeId, LayoutTransform> {
+
+    let mut transforms = BTreeMap::new();
+
+    if node_hierarchy.len() == 0 {
+        return transforms;
+    }
+
+    let own_transform = |node_id: NodeId| display_rects[node_id].style.get_transform(&positioned_rects[node_id].bounds);
+
+    let root_id = NodeId::new(0);
+    if let Some(root_transform) = own_transform(root_id) {
+        transforms.insert(root_id, root_transform);
+    }
+
+    // node_depths is sorted by depth, so the transform of the parent is always known
+    for (_, parent_id) in node_depths {
+        let parent_transform = transforms.get(parent_id).cloned();
+        for child_id in parent_id.children(node_hierarchy) {
+            let child_transform = match (own_transform(child_id), parent_transform) {
+                (Some(own), Some(parent)) => own.then(&parent),
+                (Some(own), None) => own,
+                (None, Some(parent)) => parent,
+                (None, None) => continue,
+            };
+            transforms.insert(child_id, child_transform);
+        }
+    }
+
+    transforms
+}
+
 /// For a given rectangle, determines what text alignment should be used
 fn determine_text_alignment(
     rect_style: &RectStyle,
@@ -364,7 +439,7 @@ mod layout_tests {
         let arena = get_testing_hierarchy();
         let mut arena_data = vec![RectLayout::default(); arena.len()];
         for (id, rect) in constraints {
-            arena_data[*id] = *rect;
+            arena_data[*id] = rect.clone();
         }
         (arena, NodeDataContainer { internal: arena_data })
     }
@@ -32,13 +32,16 @@ use {
     app::FrameEventInfo,
     callbacks::{PipelineId, ScrollPosition},
     dom::{NodeId, DomId},
+    async::TimerId,
 };
 use azul_core::{
+    animation::AnimationState,
     ui_state::UiState,
     display_list::CachedDisplayList,
     ui_solver::{ScrolledNodes, ExternalScrollId, LayoutResult, OverflowingScrollNode},
     window::WindowId,
 };
+use azul_layout::LayoutCache;
 pub use webrender::api::HitTestItem;
 pub use glium::glutin::AvailableMonitorsIter;
 pub use azul_core::window::*;
@@ -271,6 +274,10 @@ pub struct Window<T> {
     /// enabled
     #[cfg(debug_assertions)]
     pub(crate) css_loader: Option<Box<dyn HotReloadHandler>>,
+    /// Currently running CSS transitions and animations
+    pub(crate) animation_state: AnimationState,
+    /// ID of the timer that redraws the window while animations are running
+    pub(crate) animation_timer: TimerId,
     /// Purely a marker, so that `app.run()` can infer the type of `T: Layout`
     /// of the `WindowCreateOptions`, so that we can write:
     ///
@@ -381,6 +388,8 @@ pub(crate) struct WindowInternal {
     pub(crate) scrolled_nodes: BTreeMap<DomId, ScrolledNodes>,
     /// States of scrolling animations, updated every frame
     pub(crate) scroll_states: ScrollStates,
+    /// Layouts of the root DOM from the last layout pass, reused if the DOM didn't change
+    pub(crate) layout_cache: LayoutCache,
     pub(crate) epoch: Epoch,
     pub(crate) pipeline_id: PipelineId,
     pub(crate) document_id: DocumentId,
@@ -533,6 +542,7 @@ impl<T> Window<T> {
         // let thread = Builder::new().name(options.title.clone()).spawn(move || Self::handle_event(receiver))?;
 
         css.sort_by_specificity();
+        css.optimize();
 
         let display_list_dimensions = wr_translate_logical_size(state.size.dimensions);
 
@@ -544,12 +554,15 @@ impl<T> Window<T> {
             css,
             #[cfg(debug_assertions)]
             css_loader: None,
+            animation_state: AnimationState::new(),
+            animation_timer: TimerId::new(),
             internal: WindowInternal {
                 epoch,
                 pipeline_id,
                 document_id,
                 scrolled_nodes: BTreeMap::new(),
                 scroll_states: ScrollStates::new(),
+                layout_cache: LayoutCache::new(),
                 layout_result: BTreeMap::new(),
                 cached_display_list: CachedDisplayList::empty(display_list_dimensions),
             },
@@ -679,6 +692,7 @@ fn update_full_window_state(
     full_window_state.ime_position = window_state.ime_position;
     full_window_state.request_user_attention = window_state.request_user_attention;
     full_window_state.wayland_theme = window_state.wayland_theme;
+    full_window_state.color_scheme = window_state.color_scheme;
 }
 
 fn synchronize_mouse_state(
@@ -729,6 +743,7 @@ pub(crate) fn full_window_state_to_window_state(full_window_state: &FullWindowSt
         ime_position: full_window_state.ime_position,
         request_user_attention: full_window_state.request_user_attention,
         wayland_theme: full_window_state.wayland_theme,
+        color_scheme: full_window_state.color_scheme,
     }
 }
 
@@ -21,6 +21,7 @@ pub use azul_core::window::{
     WaylandTheme,
 };
 use azul_core::callbacks::FocusTarget;
+use azul_css::ColorScheme;
 
 pub(crate) mod winit_translate {
 
@@ -127,6 +128,8 @@ pub(crate) struct FullWindowState {
     pub request_user_attention: bool,
     /// Set the windows Wayland theme. Irrelevant on other platforms, set to `None`
     pub wayland_theme: Option<WaylandTheme>,
+    /// Color scheme that `@media (prefers-color-scheme: ...)` queries are evaluated against
+    pub color_scheme: ColorScheme,
 
     // --
 
@@ -164,6 +167,7 @@ impl Default for FullWindowState {
             ime_position: None,
             request_user_attention: false,
             wayland_theme: None,
+            color_scheme: ColorScheme::default(),
 
             // --
 
@@ -214,6 +218,7 @@ pub(crate) fn full_window_state_from_normal_state(window_state: WindowState) ->
         mouse_state: window_state.mouse_state,
         keyboard_state: window_state.keyboard_state,
         debug_state: window_state.debug_state,
+        color_scheme: window_state.color_scheme,
         .. Default::default()
     }
 }
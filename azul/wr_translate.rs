@@ -34,6 +34,12 @@ use webrender::api::{
     FontRenderMode as WrFontRenderMode,
     ImageRendering as WrImageRendering,
     ExternalScrollId as WrExternalScrollId,
+    LayoutTransform as WrLayoutTransform,
+    LayoutVector2D as WrLayoutVector2D,
+    FilterOp as WrFilterOp,
+    MixBlendMode as WrMixBlendMode,
+    RepeatMode as WrRepeatMode,
+    ClipId as WrClipId,
 };
 use azul_core::{
     callbacks::{HidpiAdjustedBounds, HitTestItem, PipelineId},
@@ -46,7 +52,7 @@ use azul_core::{
         CachedDisplayList, GlyphInstance, DisplayListScrollFrame,
         DisplayListFrame, LayoutRectContent, DisplayListMsg,
         FontInstanceFlags, GlyphOptions, AlphaType, FontRenderMode, ImageRendering,
-        StyleBorderRadius,
+        StyleBorderRadius, FramePosition, StickyFrame,
     },
     ui_solver::ExternalScrollId,
     window::LogicalSize,
@@ -63,6 +69,11 @@ use azul_css::{
     ExtendMode as CssExtendMode,
     BorderStyle as CssBorderStyle,
     LayoutSideOffsets as CssLayoutSideOffsets,
+    LayoutTransform as CssLayoutTransform,
+    StyleFilterFunction as CssFilterFunction,
+    StyleMixBlendMode as CssMixBlendMode,
+    BorderImageRepeat as CssBorderImageRepeat,
+    PixelSize, PixelValueNoPercent,
 };
 use app_units::Au as WrAu;
 use glium::glutin::{VirtualKeyCode as WinitVirtualKeyCode, MouseCursor as WinitCursorType};
@@ -205,6 +216,16 @@ pub fn wr_translate_extend_mode(input: CssExtendMode) -> WrExtendMode {
     }
 }
 
+#[inline(always)]
+pub fn wr_translate_border_image_repeat(input: CssBorderImageRepeat) -> WrRepeatMode {
+    match input {
+        CssBorderImageRepeat::Stretch => WrRepeatMode::Stretch,
+        CssBorderImageRepeat::Repeat => WrRepeatMode::Repeat,
+        CssBorderImageRepeat::Round => WrRepeatMode::Round,
+        CssBorderImageRepeat::Space => WrRepeatMode::Space,
+    }
+}
+
 #[inline(always)]
 pub fn wr_translate_border_style(input: CssBorderStyle) -> WrBorderStyle {
     match input {
@@ -255,7 +276,7 @@ pub const fn wr_translate_color_f(input: CssColorF) -> WrColorF {
 }
 
 #[inline]
-pub fn wr_translate_border_radius(border_radius: StyleBorderRadius, rect_size: LayoutSize) -> WrBorderRadius {
+pub fn wr_translate_border_radius(border_radius: StyleBorderRadius, rect_size: LayoutSize, viewport_size: LayoutSize) -> WrBorderRadius {
 
     let StyleBorderRadius { top_left, top_right, bottom_left, bottom_right } = border_radius;
 
@@ -263,24 +284,50 @@ pub fn wr_translate_border_radius(border_radius: StyleBorderRadius, rect_size: L
     let h = rect_size.height;
 
     // The "w / h" is necessary to convert percentage-based values into pixels, for example "border-radius: 50%;"
+    // - the horizontal radius is relative to the width, the vertical radius relative to the height of the rect
+    let resolve = |radius: PixelSize| WrLayoutSize::new(
+        radius.width.to_pixels_in_viewport(w, viewport_size.width, viewport_size.height),
+        radius.height.to_pixels_in_viewport(h, viewport_size.width, viewport_size.height),
+    );
+
+    let top_left = resolve(top_left.and_then(|tl| tl.get_property_or_default()).unwrap_or_default().0);
+    let top_right = resolve(top_right.and_then(|tr| tr.get_property_or_default()).unwrap_or_default().0);
+    let bottom_left = resolve(bottom_left.and_then(|bl| bl.get_property_or_default()).unwrap_or_default().0);
+    let bottom_right = resolve(bottom_right.and_then(|br| br.get_property_or_default()).unwrap_or_default().0);
+
+    // If the radii of two adjacent corners don't fit on one side of the rect, all radii
+    // are scaled down by the same factor, so that the corners don't overlap
+    let scale = [
+        w / (top_left.width + top_right.width),
+        w / (bottom_left.width + bottom_right.width),
+        h / (top_left.height + bottom_left.height),
+        h / (top_right.height + bottom_right.height),
+    ].iter().cloned().filter(|factor| factor.is_finite()).fold(1.0_f32, f32::min).max(0.0);
 
-    let top_left_px_h = top_left.and_then(|tl| tl.get_property_or_default()).unwrap_or_default().0.to_pixels(w);
-    let top_left_px_v = top_left.and_then(|tl| tl.get_property_or_default()).unwrap_or_default().0.to_pixels(h);
+    WrBorderRadius {
+        top_left: top_left * scale,
+        top_right: top_right * scale,
+        bottom_left: bottom_left * scale,
+        bottom_right: bottom_right * scale,
+    }
+}
 
-    let top_right_px_h = top_right.and_then(|tr| tr.get_property_or_default()).unwrap_or_default().0.to_pixels(w);
-    let top_right_px_v = top_right.and_then(|tr| tr.get_property_or_default()).unwrap_or_default().0.to_pixels(h);
+/// Returns the radius of the inner edge of a rounded rect (for example the padding box inside of
+/// a rounded border): the outer radius, minus the distance between the outer and inner rect
+fn wr_inner_border_radius(outer_radius: WrBorderRadius, outer_rect: WrLayoutRect, inner_rect: WrLayoutRect) -> WrBorderRadius {
 
-    let bottom_left_px_h = bottom_left.and_then(|bl| bl.get_property_or_default()).unwrap_or_default().0.to_pixels(w);
-    let bottom_left_px_v = bottom_left.and_then(|bl| bl.get_property_or_default()).unwrap_or_default().0.to_pixels(h);
+    let left = inner_rect.min_x() - outer_rect.min_x();
+    let top = inner_rect.min_y() - outer_rect.min_y();
+    let right = outer_rect.max_x() - inner_rect.max_x();
+    let bottom = outer_rect.max_y() - inner_rect.max_y();
 
-    let bottom_right_px_h = bottom_right.and_then(|br| br.get_property_or_default()).unwrap_or_default().0.to_pixels(w);
-    let bottom_right_px_v = bottom_right.and_then(|br| br.get_property_or_default()).unwrap_or_default().0.to_pixels(h);
+    let shrink = |radius: WrLayoutSize, dx: f32, dy: f32| WrLayoutSize::new((radius.width - dx).max(0.0), (radius.height - dy).max(0.0));
 
     WrBorderRadius {
-        top_left: WrLayoutSize::new(top_left_px_h, top_left_px_v),
-        top_right: WrLayoutSize::new(top_right_px_h, top_right_px_v),
-        bottom_left: WrLayoutSize::new(bottom_left_px_h, bottom_left_px_v),
-        bottom_right: WrLayoutSize::new(bottom_right_px_h, bottom_right_px_v),
+        top_left: shrink(outer_radius.top_left, left, top),
+        top_right: shrink(outer_radius.top_right, right, top),
+        bottom_left: shrink(outer_radius.bottom_left, left, bottom),
+        bottom_right: shrink(outer_radius.bottom_right, right, bottom),
     }
 }
 
@@ -522,6 +569,54 @@ fn wr_translate_layout_size(input: LayoutSize) -> WrLayoutSize {
     WrLayoutSize::new(input.width, input.height)
 }
 
+#[inline]
+fn wr_translate_layout_transform(input: CssLayoutTransform) -> WrLayoutTransform {
+    WrLayoutTransform::row_major_2d(input.m11, input.m12, input.m21, input.m22, input.m31, input.m32)
+}
+
+#[inline]
+fn wr_translate_filter_op(input: &CssFilterFunction, viewport_size: LayoutSize) -> WrFilterOp {
+    use azul_css::StyleFilterFunction::*;
+    use webrender::api::PropertyBinding as WrPropertyBinding;
+    let to_pixels = |value: PixelValueNoPercent| value.to_pixels_in_viewport(viewport_size.width, viewport_size.height);
+    match *input {
+        Blur(radius) => WrFilterOp::Blur(to_pixels(radius)),
+        Grayscale(amount) => WrFilterOp::Grayscale(amount.get()),
+        Brightness(amount) => WrFilterOp::Brightness(amount.get()),
+        Opacity(amount) => {
+            let opacity = amount.get().max(0.0).min(1.0);
+            WrFilterOp::Opacity(WrPropertyBinding::Value(opacity), opacity)
+        },
+        DropShadow { offset, blur_radius, color } => WrFilterOp::DropShadow(
+            WrLayoutVector2D::new(to_pixels(offset[0]), to_pixels(offset[1])),
+            to_pixels(blur_radius),
+            wr_translate_color_f(color.into()),
+        ),
+    }
+}
+
+#[inline]
+fn wr_translate_mix_blend_mode(input: CssMixBlendMode) -> WrMixBlendMode {
+    match input {
+        CssMixBlendMode::Normal => WrMixBlendMode::Normal,
+        CssMixBlendMode::Multiply => WrMixBlendMode::Multiply,
+        CssMixBlendMode::Screen => WrMixBlendMode::Screen,
+        CssMixBlendMode::Overlay => WrMixBlendMode::Overlay,
+        CssMixBlendMode::Darken => WrMixBlendMode::Darken,
+        CssMixBlendMode::Lighten => WrMixBlendMode::Lighten,
+        CssMixBlendMode::ColorDodge => WrMixBlendMode::ColorDodge,
+        CssMixBlendMode::ColorBurn => WrMixBlendMode::ColorBurn,
+        CssMixBlendMode::HardLight => WrMixBlendMode::HardLight,
+        CssMixBlendMode::SoftLight => WrMixBlendMode::SoftLight,
+        CssMixBlendMode::Difference => WrMixBlendMode::Difference,
+        CssMixBlendMode::Exclusion => WrMixBlendMode::Exclusion,
+        CssMixBlendMode::Hue => WrMixBlendMode::Hue,
+        CssMixBlendMode::Saturation => WrMixBlendMode::Saturation,
+        CssMixBlendMode::Color => WrMixBlendMode::Color,
+        CssMixBlendMode::Luminosity => WrMixBlendMode::Luminosity,
+    }
+}
+
 #[inline]
 pub(crate) fn wr_translate_layout_point(input: LayoutPoint) -> WrLayoutPoint {
     WrLayoutPoint::new(input.x, input.y)
@@ -592,25 +687,119 @@ pub(crate) fn wr_translate_external_scroll_id(scroll_id: ExternalScrollId) -> Wr
 }
 
 pub(crate) fn wr_translate_display_list(input: CachedDisplayList, pipeline_id: PipelineId) -> WrBuiltDisplayList {
+    // The root frame covers the whole window, viewport-relative units are resolved against its size
+    let viewport_size = input.root.get_size();
     let mut builder = WrDisplayListBuilder::new(
         wr_translate_pipeline_id(pipeline_id),
-        wr_translate_layout_size(input.root.get_size())
+        wr_translate_layout_size(viewport_size)
     );
-    push_display_list_msg(&mut builder, input.root);
+    push_display_list_msg(&mut builder, input.root, viewport_size);
     builder.finalize().2
 }
 
 #[inline]
-fn push_display_list_msg(builder: &mut WrDisplayListBuilder, msg: DisplayListMsg) {
+fn push_display_list_msg(builder: &mut WrDisplayListBuilder, msg: DisplayListMsg, viewport_size: LayoutSize) {
     use azul_core::display_list::DisplayListMsg::*;
+
+    // Fixed frames are pushed onto the root scroll node, so that they are not
+    // moved or clipped by the scroll frames of their ancestors, sticky frames
+    // get their own scroll node that is offset by WebRender while scrolling
+    let has_position_clip = {
+        let frame = match &msg {
+            Frame(f) => f,
+            ScrollFrame(sf) => &sf.frame,
+        };
+        match frame.position {
+            FramePosition::Scrolled => false,
+            FramePosition::Fixed => {
+                let root_scroll_node = WrClipId::root_scroll_node(builder.pipeline_id);
+                builder.push_clip_id(root_scroll_node);
+                true
+            },
+            FramePosition::Sticky(sticky) => {
+                let sticky_frame_id = push_sticky_frame(builder, frame.rect, sticky);
+                builder.push_clip_id(sticky_frame_id);
+                true
+            },
+        }
+    };
+
+    // Transforms, filters and blend modes apply to the frame and all of its children,
+    // so the frame has to be wrapped in a stacking context
+    let has_stacking_context = {
+        let frame = match &msg {
+            Frame(f) => f,
+            ScrollFrame(sf) => &sf.frame,
+        };
+        let has_stacking_context = frame.transform.is_some() || !frame.filters.is_empty() || frame.mix_blend_mode.is_some();
+        if has_stacking_context {
+            push_stacking_context(builder, frame, viewport_size);
+        }
+        has_stacking_context
+    };
+
     match msg {
-        Frame(f) => push_frame(builder, f),
-        ScrollFrame(sf) => push_scroll_frame(builder, sf),
+        Frame(f) => push_frame(builder, f, viewport_size),
+        ScrollFrame(sf) => push_scroll_frame(builder, sf, viewport_size),
+    }
+
+    if has_stacking_context {
+        builder.pop_stacking_context();
+    }
+
+    if has_position_clip {
+        builder.pop_clip_id();
     }
 }
 
 #[inline]
-fn push_frame(builder: &mut WrDisplayListBuilder, frame: DisplayListFrame) {
+fn push_sticky_frame(builder: &mut WrDisplayListBuilder, rect: LayoutRect, sticky: StickyFrame) -> WrClipId {
+
+    use euclid::SideOffsets2D;
+    use webrender::api::StickyOffsetBounds as WrStickyOffsetBounds;
+
+    builder.define_sticky_frame(
+        wr_translate_layout_rect(rect),
+        SideOffsets2D::new(sticky.top, sticky.right, sticky.bottom, sticky.left),
+        WrStickyOffsetBounds::new(sticky.vertical_offset_bounds.0, sticky.vertical_offset_bounds.1),
+        WrStickyOffsetBounds::new(sticky.horizontal_offset_bounds.0, sticky.horizontal_offset_bounds.1),
+        /* previously_applied_offset */ WrLayoutVector2D::zero(),
+    )
+}
+
+#[inline]
+fn push_stacking_context(builder: &mut WrDisplayListBuilder, frame: &DisplayListFrame, viewport_size: LayoutSize) {
+
+    use webrender::api::{
+        PropertyBinding as WrPropertyBinding,
+        TransformStyle as WrTransformStyle,
+        GlyphRasterSpace as WrGlyphRasterSpace,
+    };
+
+    // The transform is already relative to the origin of the window, so the reference
+    // frame has to start at (0, 0) in order to not offset the children
+    let wr_rect = WrLayoutRect::new(WrLayoutPoint::zero(), WrLayoutSize::new(frame.rect.max_x(), frame.rect.max_y()));
+    let info = WrLayoutPrimitiveInfo {
+        rect: wr_rect,
+        clip_rect: wr_rect,
+        is_backface_visible: false,
+        tag: None,
+    };
+
+    builder.push_stacking_context(
+        &info,
+        /* clip_node_id */ None,
+        /* transform */ frame.transform.map(|t| WrPropertyBinding::Value(wr_translate_layout_transform(t))),
+        WrTransformStyle::Flat,
+        /* perspective */ None,
+        frame.mix_blend_mode.map(wr_translate_mix_blend_mode).unwrap_or(WrMixBlendMode::Normal),
+        frame.filters.iter().map(|filter| wr_translate_filter_op(filter, viewport_size)).collect(),
+        WrGlyphRasterSpace::Screen,
+    );
+}
+
+#[inline]
+fn push_frame(builder: &mut WrDisplayListBuilder, frame: DisplayListFrame, viewport_size: LayoutSize) {
 
     use webrender::api::{
         ClipMode as WrClipMode,
@@ -618,11 +807,11 @@ fn push_frame(builder: &mut WrDisplayListBuilder, frame: DisplayListFrame) {
     };
 
     let wr_rect = wr_translate_layout_rect(frame.rect);
-    let wr_border_radius = wr_translate_border_radius(frame.border_radius, frame.rect.size);
+    let wr_border_radius = wr_translate_border_radius(frame.border_radius, frame.rect.size, viewport_size);
 
     let info = WrLayoutPrimitiveInfo {
         rect: wr_rect,
-        clip_rect: wr_translate_layout_rect(frame.clip_rect.unwrap_or(frame.rect)),
+        clip_rect: wr_rect,
         is_backface_visible: false,
         tag: frame.tag,
     };
@@ -632,23 +821,25 @@ fn push_frame(builder: &mut WrDisplayListBuilder, frame: DisplayListFrame) {
     builder.push_clip_id(content_clip_id);
 
     for item in frame.content {
-        push_display_list_content(builder, item, &info, frame.border_radius);
+        push_display_list_content(builder, item, &info, frame.border_radius, viewport_size);
     }
 
     // pop content clip
     builder.pop_clip_id();
 
-    // If the rect has an overflow:* property set
+    // If the rect has an overflow:* property set, clip the children to the
+    // padding box, rounded by the inner radius of the border
     let overflow_clip_id = frame.clip_rect.map(|clip_rect| {
         let clip_rect = wr_translate_layout_rect(clip_rect);
-        let clip = WrComplexClipRegion::new(clip_rect, wr_border_radius, WrClipMode::Clip);
+        let clip_radius = wr_inner_border_radius(wr_border_radius, wr_rect, clip_rect);
+        let clip = WrComplexClipRegion::new(clip_rect, clip_radius, WrClipMode::Clip);
         let clip_id = builder.define_clip(clip_rect, vec![clip], /* image_mask: */ None);
         builder.push_clip_id(clip_id);
         clip_id
     });
 
     for child in frame.children {
-        push_display_list_msg(builder, child);
+        push_display_list_msg(builder, child, viewport_size);
     }
 
     // pop overflow clip
@@ -658,7 +849,7 @@ fn push_frame(builder: &mut WrDisplayListBuilder, frame: DisplayListFrame) {
 }
 
 #[inline]
-fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayListScrollFrame) {
+fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayListScrollFrame, viewport_size: LayoutSize) {
 
     use azul_css::ColorU;
     use webrender::api::{
@@ -668,13 +859,20 @@ fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayLi
     };
 
     let wr_rect = wr_translate_layout_rect(scroll_frame.frame.rect);
-    let wr_border_radius = wr_translate_border_radius(scroll_frame.frame.border_radius, scroll_frame.frame.rect.size);
-
-    let scroll_frame_clip_region = WrComplexClipRegion::new(wr_rect, wr_border_radius, WrClipMode::Clip);
+    let wr_border_radius = wr_translate_border_radius(scroll_frame.frame.border_radius, scroll_frame.frame.rect.size, viewport_size);
+    let wr_clip_rect = wr_translate_layout_rect(scroll_frame.frame.clip_rect.unwrap_or(scroll_frame.frame.rect));
+
+    // The scrolled children are clipped to the padding box, rounded by the inner radius of the border
+    let hit_test_clip_region = WrComplexClipRegion::new(wr_rect, wr_border_radius, WrClipMode::Clip);
+    let scroll_frame_clip_region = WrComplexClipRegion::new(
+        wr_clip_rect,
+        wr_inner_border_radius(wr_border_radius, wr_rect, wr_clip_rect),
+        WrClipMode::Clip,
+    );
 
     let hit_test_info = WrLayoutPrimitiveInfo {
         rect: wr_rect,
-        clip_rect: wr_translate_layout_rect(scroll_frame.frame.clip_rect.unwrap_or(scroll_frame.frame.rect)),
+        clip_rect: wr_rect,
         is_backface_visible: false,
         tag: Some((scroll_frame.scroll_tag.0, 0)),
     };
@@ -689,13 +887,13 @@ fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayLi
     let scroll_frame_clip_id = builder.define_scroll_frame(
         /* external id*/ Some(wr_translate_external_scroll_id(scroll_frame.scroll_id)),
         /* content_rect */ wr_translate_layout_rect(scroll_frame.content_rect),
-        /* clip_rect */ wr_translate_layout_rect(scroll_frame.frame.clip_rect.unwrap_or(scroll_frame.frame.rect)),
+        /* clip_rect */ wr_clip_rect,
         /* complex_clips */ vec![scroll_frame_clip_region],
         /* image_mask */ None,
         /* sensitivity */ WrScrollSensitivity::Script,
     );
 
-    let hit_testing_clip_id = builder.define_clip(wr_rect, vec![scroll_frame_clip_region], None);
+    let hit_testing_clip_id = builder.define_clip(wr_rect, vec![hit_test_clip_region], None);
 
     // Push content (overflowing)
     let content_clip = WrComplexClipRegion::new(wr_rect, wr_border_radius, WrClipMode::Clip);
@@ -703,7 +901,7 @@ fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayLi
     builder.push_clip_id(content_clip_id);
 
     for item in scroll_frame.frame.content {
-        push_display_list_content(builder, item, &info, scroll_frame.frame.border_radius);
+        push_display_list_content(builder, item, &info, scroll_frame.frame.border_radius, viewport_size);
     }
 
     builder.pop_clip_id();
@@ -715,7 +913,7 @@ fn push_scroll_frame(builder: &mut WrDisplayListBuilder, scroll_frame: DisplayLi
 
     // only children should scroll, not the frame itself
     for child in scroll_frame.frame.children {
-        push_display_list_msg(builder, child);
+        push_display_list_msg(builder, child, viewport_size);
     }
 
     builder.pop_clip_id(); // pop scroll frame
@@ -728,6 +926,7 @@ fn push_display_list_content(
     content: LayoutRectContent,
     info: &WrLayoutPrimitiveInfo,
     radii: StyleBorderRadius,
+    viewport_size: LayoutSize,
 ) {
 
     use azul_core::display_list::LayoutRectContent::*;
@@ -736,17 +935,26 @@ fn push_display_list_content(
         Text { glyphs, font_instance_key, color, glyph_options, clip } => {
             text::push_text(builder, info, glyphs, font_instance_key, color, glyph_options, clip);
         },
+        TextShadow { shadows, glyphs, font_instance_key, color, glyph_options, clip } => {
+            text::push_text_shadow(builder, info, shadows, glyphs, font_instance_key, color, glyph_options, clip, viewport_size);
+        },
+        TextDecoration { lines, color, clip } => {
+            text::push_text_decoration(builder, info, lines, color, clip);
+        },
         Background { content, size, offset, repeat  } => {
-            background::push_background(builder, info, content, size, offset, repeat);
+            background::push_background(builder, info, content, size, offset, repeat, viewport_size);
         },
         Image { size, offset, image_rendering, alpha_type, image_key, background_color } => {
             image::push_image(builder, info, size, offset, image_key, alpha_type, image_rendering, background_color);
         },
         Border { widths, colors, styles } => {
-            border::push_border(builder, info, radii, widths, colors, styles);
+            border::push_border(builder, info, radii, widths, colors, styles, viewport_size);
+        },
+        NinePatchBorder { image, widths, border } => {
+            border::push_nine_patch_border(builder, info, image, widths, *border, viewport_size);
         },
         BoxShadow { shadow, clip_mode } => {
-            box_shadow::push_box_shadow(builder, translate_layout_rect_wr(info.rect), clip_mode, shadow, radii);
+            box_shadow::push_box_shadow(builder, translate_layout_rect_wr(info.rect), clip_mode, shadow, radii, viewport_size);
         },
     }
 }
@@ -761,7 +969,7 @@ mod text {
         app_resources::FontInstanceKey,
         display_list::{GlyphOptions, GlyphInstance},
     };
-    use azul_css::{ColorU, LayoutRect};
+    use azul_css::{ColorU, LayoutRect, LayoutSize, TextShadowPreDisplayItem, PixelValueNoPercent};
 
     pub(in super) fn push_text(
          builder: &mut WrDisplayListBuilder,
@@ -775,15 +983,9 @@ mod text {
         use super::{
             wr_translate_layouted_glyphs, wr_translate_font_instance_key,
             wr_translate_color_u, wr_translate_glyph_options,
-            wr_translate_layout_size,
         };
 
-        let mut info = *info;
-        if let Some(clip_rect) = clip {
-            info.clip_rect.origin.x = clip_rect.origin.x;
-            info.clip_rect.origin.y = clip_rect.origin.y;
-            info.clip_rect.size = wr_translate_layout_size(clip_rect.size);
-        }
+        let info = clip_primitive_info(info, clip);
 
         builder.push_text(
             &info,
@@ -793,6 +995,77 @@ mod text {
             glyph_options.map(wr_translate_glyph_options),
         );
     }
+
+    /// Pushes the shadows of a glyph run, but not the glyphs themselves
+    pub(in super) fn push_text_shadow(
+         builder: &mut WrDisplayListBuilder,
+         info: &WrLayoutPrimitiveInfo,
+         shadows: Vec<TextShadowPreDisplayItem>,
+         glyphs: Vec<GlyphInstance>,
+         font_instance_key: FontInstanceKey,
+         color: ColorU,
+         glyph_options: Option<GlyphOptions>,
+         clip: Option<LayoutRect>,
+         viewport_size: LayoutSize,
+    ) {
+        use webrender::api::{Shadow, ColorF, LayoutVector2D};
+        use super::{
+            wr_translate_layouted_glyphs, wr_translate_font_instance_key,
+            wr_translate_color_u, wr_translate_glyph_options,
+        };
+
+        let info = clip_primitive_info(info, clip);
+        let glyphs = wr_translate_layouted_glyphs(glyphs);
+        let font_instance_key = wr_translate_font_instance_key(font_instance_key);
+        let glyph_options = glyph_options.map(wr_translate_glyph_options);
+
+        // The first shadow is painted on top, so push the shadows in reverse order.
+        // Webrender paints the shadow of every primitive pushed while the shadow is
+        // active, so the glyphs are pushed with a transparent color, the text itself
+        // is pushed separately by push_text
+        let to_pixels = |value: PixelValueNoPercent| value.to_pixels_in_viewport(viewport_size.width, viewport_size.height);
+
+        for shadow in shadows.iter().rev() {
+            builder.push_shadow(&info, Shadow {
+                offset: LayoutVector2D::new(to_pixels(shadow.offset[0]), to_pixels(shadow.offset[1])),
+                color: wr_translate_color_u(shadow.color.unwrap_or(color)).into(),
+                blur_radius: to_pixels(shadow.blur_radius),
+            });
+            builder.push_text(&info, &glyphs, font_instance_key, ColorF::TRANSPARENT, glyph_options);
+            builder.pop_all_shadows();
+        }
+    }
+
+    pub(in super) fn push_text_decoration(
+         builder: &mut WrDisplayListBuilder,
+         info: &WrLayoutPrimitiveInfo,
+         lines: Vec<LayoutRect>,
+         color: ColorU,
+         clip: Option<LayoutRect>,
+    ) {
+        use super::{wr_translate_color_u, wr_translate_layout_rect};
+
+        let mut info = clip_primitive_info(info, clip);
+        let color = wr_translate_color_u(color).into();
+
+        for line in lines {
+            info.rect = wr_translate_layout_rect(line);
+            builder.push_rect(&info, color);
+        }
+    }
+
+    /// Restricts the clip rect of the primitive to the clip rect of the text
+    fn clip_primitive_info(info: &WrLayoutPrimitiveInfo, clip: Option<LayoutRect>) -> WrLayoutPrimitiveInfo {
+        use super::wr_translate_layout_size;
+
+        let mut info = *info;
+        if let Some(clip_rect) = clip {
+            info.clip_rect.origin.x = clip_rect.origin.x;
+            info.clip_rect.origin.y = clip_rect.origin.y;
+            info.clip_rect.size = wr_translate_layout_size(clip_rect.size);
+        }
+        info
+    }
 }
 
 mod background {
@@ -827,16 +1100,17 @@ mod background {
         background_size: Option<StyleBackgroundSize>,
         background_position: Option<StyleBackgroundPosition>,
         background_repeat: Option<StyleBackgroundRepeat>,
+        viewport_size: LayoutSize,
     ) {
         use azul_core::display_list::RectBackground::*;
 
         let content_size = background.get_content_size();
 
         match background {
-            RadialGradient(rg)  => push_radial_gradient_background(builder, info, rg, background_position, background_size, background_repeat, content_size),
-            LinearGradient(g)   => push_linear_gradient_background(builder, info, g, background_position, background_size, background_repeat, content_size),
-            Image(image_info)   => push_image_background(builder, info, image_info, background_position, background_size, background_repeat, content_size),
-            Color(col)          => push_color_background(builder, info, col, background_position, background_size, background_repeat, content_size),
+            RadialGradient(rg)  => push_radial_gradient_background(builder, info, rg, background_position, background_size, background_repeat, content_size, viewport_size),
+            LinearGradient(g)   => push_linear_gradient_background(builder, info, g, background_position, background_size, background_repeat, content_size, viewport_size),
+            Image(image_info)   => push_image_background(builder, info, image_info, background_position, background_size, background_repeat, content_size, viewport_size),
+            Color(col)          => push_color_background(builder, info, col, background_position, background_size, background_repeat, content_size, viewport_size),
         }
     }
 
@@ -848,14 +1122,15 @@ mod background {
         background_size: Option<StyleBackgroundSize>,
         background_repeat: Option<StyleBackgroundRepeat>,
         content_size: Option<(f32, f32)>,
+        viewport_size: LayoutSize,
     ) {
         use azul_css::Shape;
         use super::{wr_translate_color_u, wr_translate_layout_size, wr_translate_extend_mode};
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(info, background_size, content_size, viewport_size);
+        let offset = calculate_background_position(info, background_position, background_size, viewport_size);
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -891,6 +1166,7 @@ mod background {
         background_size: Option<StyleBackgroundSize>,
         background_repeat: Option<StyleBackgroundRepeat>,
         content_size: Option<(f32, f32)>,
+        viewport_size: LayoutSize,
     ) {
         use super::{
             wr_translate_color_u, wr_translate_extend_mode,
@@ -900,8 +1176,8 @@ mod background {
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(info, background_size, content_size, viewport_size);
+        let offset = calculate_background_position(info, background_position, background_size, viewport_size);
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -932,13 +1208,14 @@ mod background {
         background_size: Option<StyleBackgroundSize>,
         background_repeat: Option<StyleBackgroundRepeat>,
         content_size: Option<(f32, f32)>,
+        viewport_size: LayoutSize,
     ) {
         use azul_core::display_list::{AlphaType, ImageRendering};
 
         let background_position = background_position.unwrap_or_default();
         let background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let background_position = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(info, background_size, content_size, viewport_size);
+        let background_position = calculate_background_position(info, background_position, background_size, viewport_size);
         let background_repeat_info = get_background_repeat_info(info, background_repeat, background_size);
 
         // TODO: customize this for image backgrounds?
@@ -957,13 +1234,14 @@ mod background {
         background_size: Option<StyleBackgroundSize>,
         background_repeat: Option<StyleBackgroundRepeat>,
         content_size: Option<(f32, f32)>,
+        viewport_size: LayoutSize,
     ) {
         use super::wr_translate_color_u;
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(info, background_size, content_size, viewport_size);
+        let offset = calculate_background_position(info, background_position, background_size, viewport_size);
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -1013,6 +1291,7 @@ mod background {
         info: &WrLayoutPrimitiveInfo,
         bg_size: Option<StyleBackgroundSize>,
         content_size: Option<(f32, f32)>,
+        viewport_size: LayoutSize,
     ) -> LayoutSize {
 
         let content_size = content_size.unwrap_or((info.rect.size.width, info.rect.size.height));
@@ -1029,9 +1308,11 @@ mod background {
 
         let ratio = match bg_size {
             StyleBackgroundSize::ExactSize(w, h) => {
-                let w = w.to_pixels(info.rect.size.width);
-                let h = h.to_pixels(info.rect.size.height);
-                w.min(h)
+                // exact sizes don't depend on the aspect ratio of the content
+                return LayoutSize::new(
+                    w.to_pixels_in_viewport(info.rect.size.width, viewport_size.width, viewport_size.height),
+                    h.to_pixels_in_viewport(info.rect.size.height, viewport_size.width, viewport_size.height),
+                );
             },
             StyleBackgroundSize::Contain => content_aspect_ratio.width.min(content_aspect_ratio.height),
             StyleBackgroundSize::Cover => content_aspect_ratio.width.max(content_aspect_ratio.height),
@@ -1045,6 +1326,7 @@ mod background {
         info: &WrLayoutPrimitiveInfo,
         background_position: StyleBackgroundPosition,
         background_size: LayoutSize,
+        viewport_size: LayoutSize,
     ) -> LayoutPoint {
 
         use azul_css::BackgroundPositionVertical;
@@ -1057,14 +1339,14 @@ mod background {
             BackgroundPositionHorizontal::Right => 0.0,
             BackgroundPositionHorizontal::Center => (width - background_size.width) / 2.0,
             BackgroundPositionHorizontal::Left => (width - background_size.width),
-            BackgroundPositionHorizontal::Exact(e) => e.to_pixels(width),
+            BackgroundPositionHorizontal::Exact(e) => e.to_pixels_in_viewport(width, viewport_size.width, viewport_size.height),
         };
 
         let vertical_offset = match background_position.vertical {
             BackgroundPositionVertical::Top => 0.0,
             BackgroundPositionVertical::Center => (height - background_size.height) / 2.0,
             BackgroundPositionVertical::Bottom => (height - background_size.height),
-            BackgroundPositionVertical::Exact(e) => e.to_pixels(height),
+            BackgroundPositionVertical::Exact(e) => e.to_pixels_in_viewport(height, viewport_size.width, viewport_size.height),
         };
 
         LayoutPoint { x: horizontal_offset, y: vertical_offset }
@@ -1120,7 +1402,10 @@ mod image {
 
 mod box_shadow {
 
-    use azul_css::{BoxShadowClipMode, LayoutRect, ColorF, BoxShadowPreDisplayItem};
+    use azul_css::{
+        BoxShadowClipMode, LayoutRect, LayoutSize, ColorF, BoxShadowPreDisplayItem,
+        StyleBoxShadowList, PixelValueNoPercent,
+    };
     use azul_core::{
         display_list::{StyleBoxShadow, StyleBorderRadius},
     };
@@ -1147,15 +1432,18 @@ mod box_shadow {
         shadow_type: BoxShadowClipMode,
         box_shadow: StyleBoxShadow,
         border_radius: StyleBorderRadius,
+        viewport_size: LayoutSize,
     ) {
         use self::ShouldPushShadow::*;
         use azul_css::CssPropertyValue;
 
         let StyleBoxShadow { top, left, bottom, right } = &box_shadow;
 
-        fn translate_shadow_side(input: &Option<CssPropertyValue<BoxShadowPreDisplayItem>>) -> Option<BoxShadowPreDisplayItem> {
-            input.and_then(|prop| prop.get_property().cloned())
-        }
+        // Viewport-relative units are resolved here, so that the shadows can be converted with `to_pixels()`
+        let translate_shadow_side = |input: &Option<CssPropertyValue<StyleBoxShadowList>>| -> Option<Vec<BoxShadowPreDisplayItem>> {
+            let list = input.as_ref().and_then(|prop| prop.get_property().cloned()).map(|list| list.0).filter(|list| !list.is_empty())?;
+            Some(list.into_iter().map(|shadow| resolve_viewport_units(shadow, viewport_size)).collect())
+        };
 
         let (top, left, bottom, right) = (
             translate_shadow_side(top),
@@ -1164,74 +1452,88 @@ mod box_shadow {
             translate_shadow_side(right),
         );
 
-        let what_shadow_to_push = match [top, left, bottom, right].iter().filter(|x| x.is_some()).count() {
+        let what_shadow_to_push = match [&top, &left, &bottom, &right].iter().filter(|x| x.is_some()).count() {
             1 => OneShadow,
             2 => TwoShadows,
             4 => AllShadows,
             _ => return,
         };
 
+        // The first shadow of each list is painted on top, so the shadows are pushed in reverse order
         match what_shadow_to_push {
             OneShadow => {
-                let current_shadow = match (top, left, bottom, right) {
-                     | (Some(shadow), None, None, None)
-                     | (None, Some(shadow), None, None)
-                     | (None, None, Some(shadow), None)
-                     | (None, None, None, Some(shadow))
-                     => shadow,
+                let current_shadows = match (&top, &left, &bottom, &right) {
+                     | (Some(shadows), None, None, None)
+                     | (None, Some(shadows), None, None)
+                     | (None, None, Some(shadows), None)
+                     | (None, None, None, Some(shadows))
+                     => shadows,
                      _ => return, // reachable, but invalid box-shadow
                 };
 
-                push_single_box_shadow_edge(
-                    builder, &current_shadow, bounds, border_radius, shadow_type,
-                    &top, &bottom, &left, &right
-                );
+                for current_shadow in current_shadows.iter().rev() {
+                    push_single_box_shadow_edge(
+                        builder, current_shadow, bounds, border_radius, viewport_size, shadow_type,
+                        &top, &bottom, &left, &right
+                    );
+                }
             },
             // Two shadows in opposite directions:
             //
             // box-shadow-top: 0px 0px 5px red;
             // box-shadow-bottom: 0px 0px 5px blue;
             TwoShadows => {
-                match (top, left, bottom, right) {
+                match (&top, &left, &bottom, &right) {
                     // top + bottom box-shadow pair
                     (Some(t), None, Some(b), None) => {
-                        push_single_box_shadow_edge(
-                            builder, &t, bounds, border_radius, shadow_type,
-                            &top, &None, &None, &None
-                        );
-                        push_single_box_shadow_edge(
-                            builder, &b, bounds, border_radius, shadow_type,
-                            &None, &bottom, &None, &None
-                        );
+                        for shadow in t.iter().rev() {
+                            push_single_box_shadow_edge(
+                                builder, shadow, bounds, border_radius, viewport_size, shadow_type,
+                                &top, &None, &None, &None
+                            );
+                        }
+                        for shadow in b.iter().rev() {
+                            push_single_box_shadow_edge(
+                                builder, shadow, bounds, border_radius, viewport_size, shadow_type,
+                                &None, &bottom, &None, &None
+                            );
+                        }
                     },
                     // left + right box-shadow pair
                     (None, Some(l), None, Some(r)) => {
-                        push_single_box_shadow_edge(
-                            builder, &l, bounds, border_radius, shadow_type,
-                            &None, &None, &left, &None
-                        );
-                        push_single_box_shadow_edge(
-                            builder, &r, bounds, border_radius, shadow_type,
-                            &None, &None, &None, &right
-                        );
+                        for shadow in l.iter().rev() {
+                            push_single_box_shadow_edge(
+                                builder, shadow, bounds, border_radius, viewport_size, shadow_type,
+                                &None, &None, &left, &None
+                            );
+                        }
+                        for shadow in r.iter().rev() {
+                            push_single_box_shadow_edge(
+                                builder, shadow, bounds, border_radius, viewport_size, shadow_type,
+                                &None, &None, &None, &right
+                            );
+                        }
                     }
                     _ => return, // reachable, but invalid
                 }
             },
             AllShadows => {
 
-                // Assumes that all box shadows are the same, so just use the top shadow
-                let top_shadow = top.unwrap();
-                let clip_rect = get_clip_rect(&top_shadow, bounds);
-
-                push_box_shadow_inner(
-                    builder,
-                    top_shadow,
-                    border_radius,
-                    bounds,
-                    clip_rect,
-                    shadow_type,
-                );
+                // Assumes that all box shadows are the same, so just use the top shadows
+                let top_shadows = top.unwrap_or_default();
+
+                for top_shadow in top_shadows.iter().rev() {
+                    let clip_rect = get_clip_rect(top_shadow, bounds);
+                    push_box_shadow_inner(
+                        builder,
+                        *top_shadow,
+                        border_radius,
+                        viewport_size,
+                        bounds,
+                        clip_rect,
+                        shadow_type,
+                    );
+                }
             }
         }
     }
@@ -1243,11 +1545,12 @@ mod box_shadow {
             current_shadow: &BoxShadowPreDisplayItem,
             bounds: LayoutRect,
             border_radius: StyleBorderRadius,
+            viewport_size: LayoutSize,
             shadow_type: BoxShadowClipMode,
-            top: &Option<BoxShadowPreDisplayItem>,
-            bottom: &Option<BoxShadowPreDisplayItem>,
-            left: &Option<BoxShadowPreDisplayItem>,
-            right: &Option<BoxShadowPreDisplayItem>,
+            top: &Option<Vec<BoxShadowPreDisplayItem>>,
+            bottom: &Option<Vec<BoxShadowPreDisplayItem>>,
+            left: &Option<Vec<BoxShadowPreDisplayItem>>,
+            right: &Option<Vec<BoxShadowPreDisplayItem>>,
     ) {
         let is_inset_shadow = current_shadow.clip_mode == BoxShadowClipMode::Inset;
         let origin_displace = (current_shadow.spread_radius.to_pixels() + current_shadow.blur_radius.to_pixels()) * 2.0;
@@ -1305,6 +1608,7 @@ mod box_shadow {
             builder,
             *current_shadow,
             border_radius,
+            viewport_size,
             shadow_bounds,
             clip_rect,
             shadow_type
@@ -1316,6 +1620,7 @@ mod box_shadow {
         builder: &mut WrDisplayListBuilder,
         pre_shadow: BoxShadowPreDisplayItem,
         border_radius: StyleBorderRadius,
+        viewport_size: LayoutSize,
         bounds: LayoutRect,
         clip_rect: LayoutRect,
         shadow_type: BoxShadowClipMode,
@@ -1346,11 +1651,22 @@ mod box_shadow {
             wr_translate_color_f(apply_gamma(pre_shadow.color.into())),
             pre_shadow.blur_radius.to_pixels(),
             pre_shadow.spread_radius.to_pixels(),
-            wr_translate_border_radius(border_radius, bounds.size),
+            wr_translate_border_radius(border_radius, bounds.size, viewport_size),
             wr_translate_box_shadow_clip_mode(pre_shadow.clip_mode)
         );
     }
 
+    /// Replaces the viewport-relative units of the shadow with their value in pixels
+    fn resolve_viewport_units(shadow: BoxShadowPreDisplayItem, viewport_size: LayoutSize) -> BoxShadowPreDisplayItem {
+        let resolve = |value: PixelValueNoPercent| PixelValueNoPercent(value.0.resolve_viewport_units(viewport_size.width, viewport_size.height));
+        BoxShadowPreDisplayItem {
+            offset: [resolve(shadow.offset[0]), resolve(shadow.offset[1])],
+            blur_radius: resolve(shadow.blur_radius),
+            spread_radius: resolve(shadow.spread_radius),
+            .. shadow
+        }
+    }
+
     // Apply a gamma of 2.2 to the original value
     //
     // NOTE: strangely box-shadow is the only thing that needs to be gamma-corrected...
@@ -1375,15 +1691,20 @@ mod box_shadow {
             // clipped to the bounds -we trust that the calling function knows to do this
             bounds
         } else {
-            // calculate the maximum extent of the outset shadow
+            // calculate the maximum extent of the outset shadow, the shadow
+            // can be offset in any direction, so extend the clip rect in the
+            // direction of the offset
             let mut clip_rect = bounds;
 
             let origin_displace = (pre_shadow.spread_radius.to_pixels() + pre_shadow.blur_radius.to_pixels()) * 2.0;
-            clip_rect.origin.x = clip_rect.origin.x - pre_shadow.offset[0].to_pixels() - origin_displace;
-            clip_rect.origin.y = clip_rect.origin.y - pre_shadow.offset[1].to_pixels() - origin_displace;
+            let offset_x = pre_shadow.offset[0].to_pixels();
+            let offset_y = pre_shadow.offset[1].to_pixels();
 
-            clip_rect.size.height = clip_rect.size.height + (origin_displace * 2.0);
-            clip_rect.size.width = clip_rect.size.width + (origin_displace * 2.0);
+            clip_rect.origin.x = clip_rect.origin.x + offset_x.min(0.0) - origin_displace;
+            clip_rect.origin.y = clip_rect.origin.y + offset_y.min(0.0) - origin_displace;
+
+            clip_rect.size.height = clip_rect.size.height + offset_y.abs() + (origin_displace * 2.0);
+            clip_rect.size.width = clip_rect.size.width + offset_x.abs() + (origin_displace * 2.0);
             clip_rect
         }
     }
@@ -1399,11 +1720,16 @@ mod border {
         LayoutPrimitiveInfo as WrLayoutPrimitiveInfo,
         BorderStyle as WrBorderStyle,
         BorderSide as WrBorderSide,
+        LayoutRect as WrLayoutRect,
+        LayoutPoint as WrLayoutPoint,
+        LayoutSize as WrLayoutSize,
     };
     use azul_css::{
-        LayoutSize, BorderStyle, BorderStyleNoNone, CssPropertyValue, PixelValue
+        LayoutSize, BorderStyle, BorderStyleNoNone, CssPropertyValue, PixelValue,
+        NinePatchBorder, BorderImageLength,
     };
     use azul_core::{
+        app_resources::ImageInfo,
         display_list::{StyleBorderRadius, StyleBorderWidths, StyleBorderColors, StyleBorderStyles},
     };
 
@@ -1421,13 +1747,104 @@ mod border {
         widths: StyleBorderWidths,
         colors: StyleBorderColors,
         styles: StyleBorderStyles,
+        viewport_size: LayoutSize,
     ) {
         let rect_size = LayoutSize::new(info.rect.size.width, info.rect.size.height);
-        if let Some((border_widths, border_details)) = get_webrender_border(rect_size, radii, widths, colors, styles) {
+        if let Some((border_widths, border_details)) = get_webrender_border(rect_size, radii, widths, colors, styles, viewport_size) {
             builder.push_border(info, border_widths, border_details);
         }
     }
 
+    /// Pushes a `border-image`: The slice is resolved against the size of the image,
+    /// the `border-image-width` and `border-image-outset` against the border widths
+    pub(in super) fn push_nine_patch_border(
+        builder: &mut WrDisplayListBuilder,
+        info: &WrLayoutPrimitiveInfo,
+        image: ImageInfo,
+        widths: StyleBorderWidths,
+        border: NinePatchBorder,
+        viewport_size: LayoutSize,
+    ) {
+        use super::{wr_translate_image_key, wr_translate_border_image_repeat};
+        use webrender::api::{
+            NinePatchBorder as WrNinePatchBorder,
+            NinePatchBorderSource as WrNinePatchBorderSource,
+        };
+        use euclid::SideOffsets2D;
+
+        let rect_size = LayoutSize::new(info.rect.size.width, info.rect.size.height);
+        let (image_width, image_height) = image.get_dimensions();
+        let (image_width, image_height) = (image_width as f32, image_height as f32);
+
+        let to_pixels = |value: PixelValue, percent_resolve: f32| value.to_pixels_in_viewport(percent_resolve, viewport_size.width, viewport_size.height);
+
+        let get_border_width = |width: Option<CssPropertyValue<PixelValue>>, percent_resolve: f32| {
+            width.and_then(CssPropertyValue::get_property_or_default).map(|w| to_pixels(w, percent_resolve)).unwrap_or(0.0)
+        };
+
+        let (border_top, border_right, border_bottom, border_left) = (
+            get_border_width(widths.top.map(|w| w.map_property(|w| w.0)), rect_size.height),
+            get_border_width(widths.right.map(|w| w.map_property(|w| w.0)), rect_size.width),
+            get_border_width(widths.bottom.map(|w| w.map_property(|w| w.0)), rect_size.height),
+            get_border_width(widths.left.map(|w| w.map_property(|w| w.0)), rect_size.width),
+        );
+
+        // Slices are pixels of the image, percentages refer to the size of the image
+        let (slice_top, slice_right, slice_bottom, slice_left) = (
+            to_pixels(border.slice.top, image_height).max(0.0).min(image_height),
+            to_pixels(border.slice.right, image_width).max(0.0).min(image_width),
+            to_pixels(border.slice.bottom, image_height).max(0.0).min(image_height),
+            to_pixels(border.slice.left, image_width).max(0.0).min(image_width),
+        );
+
+        let resolve_length = |length: BorderImageLength, border_width: f32, slice: f32, percent_resolve: f32| {
+            match length {
+                BorderImageLength::Auto => slice,
+                BorderImageLength::Number(n) => n.get() * border_width,
+                BorderImageLength::Length(l) => to_pixels(l, percent_resolve),
+            }
+        };
+
+        let border_image_widths = WrLayoutSideOffsets::new(
+            resolve_length(border.width.top, border_top, slice_top, rect_size.height),
+            resolve_length(border.width.right, border_right, slice_right, rect_size.width),
+            resolve_length(border.width.bottom, border_bottom, slice_bottom, rect_size.height),
+            resolve_length(border.width.left, border_left, slice_left, rect_size.width),
+        );
+
+        let outset = WrLayoutSideOffsets::new(
+            resolve_length(border.outset.top, border_top, 0.0, rect_size.height),
+            resolve_length(border.outset.right, border_right, 0.0, rect_size.width),
+            resolve_length(border.outset.bottom, border_bottom, 0.0, rect_size.height),
+            resolve_length(border.outset.left, border_left, 0.0, rect_size.width),
+        );
+
+        // The outset moves the border outside of the rectangle, so the clip has to grow with it
+        let border_info = WrLayoutPrimitiveInfo {
+            clip_rect: WrLayoutRect::new(
+                WrLayoutPoint::new(info.clip_rect.origin.x - outset.left, info.clip_rect.origin.y - outset.top),
+                WrLayoutSize::new(
+                    info.clip_rect.size.width + outset.left + outset.right,
+                    info.clip_rect.size.height + outset.top + outset.bottom,
+                ),
+            ),
+            .. *info
+        };
+
+        let border_details = WrBorderDetails::NinePatch(WrNinePatchBorder {
+            source: WrNinePatchBorderSource::Image(wr_translate_image_key(image.key)),
+            width: image_width as u32,
+            height: image_height as u32,
+            slice: SideOffsets2D::new(slice_top as u32, slice_right as u32, slice_bottom as u32, slice_left as u32),
+            fill: border.slice.fill,
+            repeat_horizontal: wr_translate_border_image_repeat(border.repeat.horizontal),
+            repeat_vertical: wr_translate_border_image_repeat(border.repeat.vertical),
+            outset,
+        });
+
+        builder.push_border(&border_info, border_image_widths, border_details);
+    }
+
     /// Returns the merged offsets and details for the top, left,
     /// right and bottom styles - necessary, so we can combine `border-top`,
     /// `border-left`, etc. into one border
@@ -1437,6 +1854,7 @@ mod border {
         widths: StyleBorderWidths,
         colors: StyleBorderColors,
         styles: StyleBorderStyles,
+        viewport_size: LayoutSize,
     ) -> Option<(WrLayoutSideOffsets, WrBorderDetails)> {
 
         use super::{wr_translate_color_u, wr_translate_border_radius};
@@ -1489,10 +1907,10 @@ mod border {
         );
 
         let border_widths = WrLayoutSideOffsets::new(
-            width_top.map(|v| v.to_pixels(rect_size.height)).unwrap_or(0.0),
-            width_right.map(|v| v.to_pixels(rect_size.width)).unwrap_or(0.0),
-            width_bottom.map(|v| v.to_pixels(rect_size.height)).unwrap_or(0.0),
-            width_left.map(|v| v.to_pixels(rect_size.width)).unwrap_or(0.0),
+            width_top.map(|v| v.to_pixels_in_viewport(rect_size.height, viewport_size.width, viewport_size.height)).unwrap_or(0.0),
+            width_right.map(|v| v.to_pixels_in_viewport(rect_size.width, viewport_size.width, viewport_size.height)).unwrap_or(0.0),
+            width_bottom.map(|v| v.to_pixels_in_viewport(rect_size.height, viewport_size.width, viewport_size.height)).unwrap_or(0.0),
+            width_left.map(|v| v.to_pixels_in_viewport(rect_size.width, viewport_size.width, viewport_size.height)).unwrap_or(0.0),
         );
 
         let border_details = WrBorderDetails::Normal(WrNormalBorder {
@@ -1500,7 +1918,7 @@ mod border {
             left:   WrBorderSide { color: wr_translate_color_u(color_left.0).into(), style: translate_wr_border(style_left, width_left) },
             right:  WrBorderSide { color: wr_translate_color_u(color_right.0).into(), style: translate_wr_border(style_right, width_right) },
             bottom: WrBorderSide { color: wr_translate_color_u(color_bottom.0).into(), style: translate_wr_border(style_bottom, width_bottom) },
-            radius: if has_no_border_radius { WrBorderRadius::zero() } else { wr_translate_border_radius(radii, rect_size) },
+            radius: if has_no_border_radius { WrBorderRadius::zero() } else { wr_translate_border_radius(radii, rect_size, viewport_size) },
             do_aa: !has_no_border_radius,
         });
 